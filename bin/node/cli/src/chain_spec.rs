@@ -58,6 +58,8 @@ pub struct Extensions {
 	pub bad_blocks: sc_client_api::BadBlocks<Block>,
 	/// The light sync state extension used by the sync-state rpc.
 	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// The trusted finalized block to start warp sync from.
+	pub warp_sync_checkpoint: grandpa::warp_proof::WarpSyncCheckpointExtension,
 }
 
 /// Specialized `ChainSpec`.
@@ -100,6 +100,16 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Write a checkpoint of the database.
+	BackupDb(sc_cli::BackupDbCmd),
+
+	/// Restore a checkpoint of the database written by `backup-db`.
+	RestoreDb(sc_cli::RestoreDbCmd),
+
+	/// Database management cli utilities.
+	#[command(subcommand)]
+	Db(sc_cli::DbSubcommand),
+
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
 }
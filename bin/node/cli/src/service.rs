@@ -345,11 +345,19 @@ pub fn new_full_base(
 		grandpa_protocol_name.clone(),
 	));
 
-	let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
-		backend.clone(),
-		import_setup.1.shared_authority_set().clone(),
-		Vec::default(),
-	));
+	let warp_sync_checkpoint = grandpa::warp_proof::load_checkpoint::<Block>(
+		&*config.chain_spec,
+		config.network.warp_sync_checkpoint.as_deref(),
+	)
+	.map_err(|e| ServiceError::Application(e.into()))?;
+	let warp_sync = Arc::new(
+		grandpa::warp_proof::NetworkProvider::new(
+			backend.clone(),
+			import_setup.1.shared_authority_set().clone(),
+			Vec::default(),
+		)
+		.with_checkpoint(warp_sync_checkpoint),
+	);
 
 	let (network, system_rpc_tx, tx_handler_controller, network_starter, sync_service) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
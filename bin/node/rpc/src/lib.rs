@@ -36,6 +36,7 @@ use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
 use node_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Nonce};
+use pallet_asset_conversion::NativeOrAssetId;
 use sc_client_api::AuxStore;
 use sc_consensus_babe::BabeWorkerHandle;
 use sc_consensus_grandpa::{
@@ -116,18 +117,36 @@ where
 		+ Send
 		+ 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+	C::Api: pallet_staking_rpc::StakerInfoRuntimeApi<Block, Balance, AccountId>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_transaction_payment_rpc::AssetTransactionPaymentRuntimeApi<Block, u32, u128>,
+	C::Api: pallet_asset_conversion_rpc::AssetConversionRuntimeApi<
+		Block,
+		Balance,
+		u128,
+		NativeOrAssetId<u32>,
+	>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: sp_dry_run::DryRunApi<Block>
+		+ sp_api::ApiExt<Block, StateBackend = sc_client_api::StateBackendFor<B, Block>>,
 	P: TransactionPool + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
-	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use pallet_asset_conversion_rpc::{AssetConversion, AssetConversionApiServer};
+	use pallet_staking_rpc::{Staking, StakingApiServer};
+	use pallet_transaction_payment_rpc::{
+		AssetTransactionPayment, AssetTransactionPaymentApiServer, TransactionPayment,
+		TransactionPaymentApiServer,
+	};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
-	use sc_rpc::dev::{Dev, DevApiServer};
+	use sc_rpc::{
+		dev::{Dev, DevApiServer},
+		dry_run::{DryRun, DryRunApiServer},
+	};
 	use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
 	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -151,6 +170,9 @@ where
 
 	io.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(AssetTransactionPayment::<_, (Block, u128)>::new(client.clone()).into_rpc())?;
+	io.merge(AssetConversion::<_, (Block, Balance, u128)>::new(client.clone()).into_rpc())?;
+	io.merge(Staking::<_, (Block, Balance)>::new(client.clone()).into_rpc())?;
 	io.merge(
 		Babe::new(client.clone(), babe_worker_handle.clone(), keystore, select_chain, deny_unsafe)
 			.into_rpc(),
@@ -171,7 +193,8 @@ where
 			.into_rpc(),
 	)?;
 
-	io.merge(StateMigration::new(client.clone(), backend, deny_unsafe).into_rpc())?;
+	io.merge(StateMigration::new(client.clone(), backend.clone(), deny_unsafe).into_rpc())?;
+	io.merge(DryRun::new(client.clone(), backend, deny_unsafe).into_rpc())?;
 	io.merge(Dev::new(client, deny_unsafe).into_rpc())?;
 
 	Ok(io)
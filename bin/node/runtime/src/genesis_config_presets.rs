@@ -0,0 +1,345 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The genesis config presets of the runtime.
+//!
+//! Each preset is a JSON patch, applied on top of the default `RuntimeGenesisConfig`. The code of
+//! the runtime isn't part of the presets, as it's set by whoever builds the chain spec.
+
+use crate::{
+	constants::currency::*, AccountId, AssetsConfig, BabeConfig, Balance, BalancesConfig,
+	SessionConfig, SessionKeys, StakerStatus, StakingConfig, SudoConfig, BABE_GENESIS_EPOCH_CONFIG,
+};
+use hex_literal::hex;
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_core::crypto::UncheckedInto;
+use sp_genesis_builder::{PresetId, DEV_RUNTIME_PRESET, LOCAL_TESTNET_RUNTIME_PRESET};
+use sp_runtime::Perbill;
+use sp_std::prelude::*;
+
+/// The name of the preset for the staging testnet.
+pub const STAGING_RUNTIME_PRESET: &str = "staging_testnet";
+
+/// The keys of a genesis authority: its stash and controller accounts, and its session keys.
+type AuthorityKeys = (AccountId, AccountId, GrandpaId, BabeId, ImOnlineId, AuthorityDiscoveryId);
+
+// The runtime can't derive keys from seeds, so the well-known development keys are hardcoded:
+// the sr25519 public keys of `//Name` and `//Name//stash`, and the ed25519 public key of `//Name`.
+const ALICE: [u8; 32] = hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+const ALICE_STASH: [u8; 32] =
+	hex!("be5ddb1579b72e84524fc29e78609e3caf42e85aa118ebfe0b0ad404b5bdd25f");
+const ALICE_ED25519: [u8; 32] =
+	hex!("88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee");
+const BOB: [u8; 32] = hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48");
+const BOB_STASH: [u8; 32] =
+	hex!("fe65717dad0447d715f660a0a58411de509b42e6efb8375f562f58a554d5860e");
+const BOB_ED25519: [u8; 32] =
+	hex!("d17c2d7823ebf260fd138f2d7e27d114c0145d968b5ff5006125f2414fadae69");
+const CHARLIE: [u8; 32] = hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22");
+const CHARLIE_STASH: [u8; 32] =
+	hex!("1e07379407fecc4b89eb7dbd287c2c781cfb1907a96947a3eb18e4f8e7198625");
+const DAVE: [u8; 32] = hex!("306721211d5404bd9da88e0204360a1a9ab8b87c66c1bc2fcdd37f3c2222cc20");
+const DAVE_STASH: [u8; 32] =
+	hex!("e860f1b1c7227f7c22602f53f15af80747814dffd839719731ee3bba6edc126c");
+const EVE: [u8; 32] = hex!("e659a7a1628cdd93febc04a4e0646ea20e9f5f0ce097d9a05290d4a9e054df4e");
+const EVE_STASH: [u8; 32] =
+	hex!("8ac59e11963af19174d0b94d5d78041c233f55d2e19324665bafdfb62925af2d");
+const FERDIE: [u8; 32] = hex!("1cbd2d43530a44705ad088af313e18f80b53ef16b36177cd4b77b846f2a5f07c");
+const FERDIE_STASH: [u8; 32] =
+	hex!("101191192fc877c24d725b337120fa3edc63d227bbc92705db1e2cb65f56981a");
+
+/// The keys of a development authority, from its sr25519 and ed25519 public keys.
+fn dev_authority_keys(sr25519: [u8; 32], stash: [u8; 32], ed25519: [u8; 32]) -> AuthorityKeys {
+	(
+		stash.into(),
+		sr25519.into(),
+		ed25519.unchecked_into(),
+		sr25519.unchecked_into(),
+		sr25519.unchecked_into(),
+		sr25519.unchecked_into(),
+	)
+}
+
+/// The accounts endowed on development chains.
+fn dev_endowed_accounts() -> Vec<AccountId> {
+	[
+		ALICE,
+		BOB,
+		CHARLIE,
+		DAVE,
+		EVE,
+		FERDIE,
+		ALICE_STASH,
+		BOB_STASH,
+		CHARLIE_STASH,
+		DAVE_STASH,
+		EVE_STASH,
+		FERDIE_STASH,
+	]
+	.into_iter()
+	.map(Into::into)
+	.collect()
+}
+
+/// The genesis config patch of a testnet, mirroring the `testnet_genesis` of the node's chain
+/// specs, without nominators.
+fn testnet_genesis(
+	initial_authorities: Vec<AuthorityKeys>,
+	root_key: AccountId,
+	mut endowed_accounts: Vec<AccountId>,
+) -> serde_json::Value {
+	// endow all authorities.
+	for (stash, ..) in &initial_authorities {
+		if !endowed_accounts.contains(stash) {
+			endowed_accounts.push(stash.clone());
+		}
+	}
+
+	const ENDOWMENT: Balance = 10_000_000 * DOLLARS;
+	const STASH: Balance = ENDOWMENT / 1000;
+
+	serde_json::json!({
+		"balances": BalancesConfig {
+			balances: endowed_accounts.iter().cloned().map(|x| (x, ENDOWMENT)).collect(),
+		},
+		"session": SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|x| {
+					(
+						x.0.clone(),
+						x.0.clone(),
+						SessionKeys {
+							grandpa: x.2.clone(),
+							babe: x.3.clone(),
+							im_online: x.4.clone(),
+							authority_discovery: x.5.clone(),
+						},
+					)
+				})
+				.collect(),
+		},
+		"staking": StakingConfig {
+			validator_count: initial_authorities.len() as u32,
+			minimum_validator_count: initial_authorities.len() as u32,
+			invulnerables: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+			slash_reward_fraction: Perbill::from_percent(10),
+			stakers: initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.0.clone(), STASH, StakerStatus::Validator))
+				.collect(),
+			..Default::default()
+		},
+		"sudo": SudoConfig { key: Some(root_key) },
+		"babe": BabeConfig { epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG), ..Default::default() },
+		"assets": AssetsConfig {
+			// This asset is used by the NIS pallet as counterpart currency.
+			assets: vec![(9, ALICE.into(), true, 1)],
+			..Default::default()
+		},
+	})
+}
+
+/// The genesis config patch of a development chain, with Alice as the single validator.
+fn development_config_genesis() -> serde_json::Value {
+	testnet_genesis(
+		vec![dev_authority_keys(ALICE, ALICE_STASH, ALICE_ED25519)],
+		ALICE.into(),
+		dev_endowed_accounts(),
+	)
+}
+
+/// The genesis config patch of a local testnet, with Alice and Bob as validators.
+fn local_testnet_genesis() -> serde_json::Value {
+	testnet_genesis(
+		vec![
+			dev_authority_keys(ALICE, ALICE_STASH, ALICE_ED25519),
+			dev_authority_keys(BOB, BOB_STASH, BOB_ED25519),
+		],
+		ALICE.into(),
+		dev_endowed_accounts(),
+	)
+}
+
+/// The genesis config patch of the staging testnet.
+fn staging_testnet_config_genesis() -> serde_json::Value {
+	#[rustfmt::skip]
+	// stash, controller, session-key
+	// generated with secret:
+	// for i in 1 2 3 4 ; do for j in stash controller; do subkey inspect "$secret"/fir/$j/$i; done; done
+	//
+	// and
+	//
+	// for i in 1 2 3 4 ; do for j in session; do subkey --ed25519 inspect "$secret"//fir//$j//$i; done; done
+
+	let initial_authorities: Vec<AuthorityKeys> = vec![
+		(
+			// 5Fbsd6WXDGiLTxunqeK5BATNiocfCqu9bS1yArVjCgeBLkVy
+			hex!("9c7a2ee14e565db0c69f78c7b4cd839fbf52b607d867e9e9c5a79042898a0d12").into(),
+			// 5EnCiV7wSHeNhjW3FSUwiJNkcc2SBkPLn5Nj93FmbLtBjQUq
+			hex!("781ead1e2fa9ccb74b44c19d29cb2a7a4b5be3972927ae98cd3877523976a276").into(),
+			// 5Fb9ayurnxnaXj56CjmyQLBiadfRCqUbL2VWNbbe1nZU6wiC
+			hex!("9becad03e6dcac03cee07edebca5475314861492cdfc96a2144a67bbe9699332")
+				.unchecked_into(),
+			// 5EZaeQ8djPcq9pheJUhgerXQZt9YaHnMJpiHMRhwQeinqUW8
+			hex!("6e7e4eb42cbd2e0ab4cae8708ce5509580b8c04d11f6758dbf686d50fe9f9106")
+				.unchecked_into(),
+			// 5EZaeQ8djPcq9pheJUhgerXQZt9YaHnMJpiHMRhwQeinqUW8
+			hex!("6e7e4eb42cbd2e0ab4cae8708ce5509580b8c04d11f6758dbf686d50fe9f9106")
+				.unchecked_into(),
+			// 5EZaeQ8djPcq9pheJUhgerXQZt9YaHnMJpiHMRhwQeinqUW8
+			hex!("6e7e4eb42cbd2e0ab4cae8708ce5509580b8c04d11f6758dbf686d50fe9f9106")
+				.unchecked_into(),
+		),
+		(
+			// 5ERawXCzCWkjVq3xz1W5KGNtVx2VdefvZ62Bw1FEuZW4Vny2
+			hex!("68655684472b743e456907b398d3a44c113f189e56d1bbfd55e889e295dfde78").into(),
+			// 5Gc4vr42hH1uDZc93Nayk5G7i687bAQdHHc9unLuyeawHipF
+			hex!("c8dc79e36b29395413399edaec3e20fcca7205fb19776ed8ddb25d6f427ec40e").into(),
+			// 5EockCXN6YkiNCDjpqqnbcqd4ad35nU4RmA1ikM4YeRN4WcE
+			hex!("7932cff431e748892fa48e10c63c17d30f80ca42e4de3921e641249cd7fa3c2f")
+				.unchecked_into(),
+			// 5DhLtiaQd1L1LU9jaNeeu9HJkP6eyg3BwXA7iNMzKm7qqruQ
+			hex!("482dbd7297a39fa145c570552249c2ca9dd47e281f0c500c971b59c9dcdcd82e")
+				.unchecked_into(),
+			// 5DhLtiaQd1L1LU9jaNeeu9HJkP6eyg3BwXA7iNMzKm7qqruQ
+			hex!("482dbd7297a39fa145c570552249c2ca9dd47e281f0c500c971b59c9dcdcd82e")
+				.unchecked_into(),
+			// 5DhLtiaQd1L1LU9jaNeeu9HJkP6eyg3BwXA7iNMzKm7qqruQ
+			hex!("482dbd7297a39fa145c570552249c2ca9dd47e281f0c500c971b59c9dcdcd82e")
+				.unchecked_into(),
+		),
+		(
+			// 5DyVtKWPidondEu8iHZgi6Ffv9yrJJ1NDNLom3X9cTDi98qp
+			hex!("547ff0ab649283a7ae01dbc2eb73932eba2fb09075e9485ff369082a2ff38d65").into(),
+			// 5FeD54vGVNpFX3PndHPXJ2MDakc462vBCD5mgtWRnWYCpZU9
+			hex!("9e42241d7cd91d001773b0b616d523dd80e13c6c2cab860b1234ef1b9ffc1526").into(),
+			// 5E1jLYfLdUQKrFrtqoKgFrRvxM3oQPMbf6DfcsrugZZ5Bn8d
+			hex!("5633b70b80a6c8bb16270f82cca6d56b27ed7b76c8fd5af2986a25a4788ce440")
+				.unchecked_into(),
+			// 5DhKqkHRkndJu8vq7pi2Q5S3DfftWJHGxbEUNH43b46qNspH
+			hex!("482a3389a6cf42d8ed83888cfd920fec738ea30f97e44699ada7323f08c3380a")
+				.unchecked_into(),
+			// 5DhKqkHRkndJu8vq7pi2Q5S3DfftWJHGxbEUNH43b46qNspH
+			hex!("482a3389a6cf42d8ed83888cfd920fec738ea30f97e44699ada7323f08c3380a")
+				.unchecked_into(),
+			// 5DhKqkHRkndJu8vq7pi2Q5S3DfftWJHGxbEUNH43b46qNspH
+			hex!("482a3389a6cf42d8ed83888cfd920fec738ea30f97e44699ada7323f08c3380a")
+				.unchecked_into(),
+		),
+		(
+			// 5HYZnKWe5FVZQ33ZRJK1rG3WaLMztxWrrNDb1JRwaHHVWyP9
+			hex!("f26cdb14b5aec7b2789fd5ca80f979cef3761897ae1f37ffb3e154cbcc1c2663").into(),
+			// 5EPQdAQ39WQNLCRjWsCk5jErsCitHiY5ZmjfWzzbXDoAoYbn
+			hex!("66bc1e5d275da50b72b15de072a2468a5ad414919ca9054d2695767cf650012f").into(),
+			// 5DMa31Hd5u1dwoRKgC4uvqyrdK45RHv3CpwvpUC1EzuwDit4
+			hex!("3919132b851ef0fd2dae42a7e734fe547af5a6b809006100f48944d7fae8e8ef")
+				.unchecked_into(),
+			// 5C4vDQxA8LTck2xJEy4Yg1hM9qjDt4LvTQaMo4Y8ne43aU6x
+			hex!("00299981a2b92f878baaf5dbeba5c18d4e70f2a1fcd9c61b32ea18daf38f4378")
+				.unchecked_into(),
+			// 5C4vDQxA8LTck2xJEy4Yg1hM9qjDt4LvTQaMo4Y8ne43aU6x
+			hex!("00299981a2b92f878baaf5dbeba5c18d4e70f2a1fcd9c61b32ea18daf38f4378")
+				.unchecked_into(),
+			// 5C4vDQxA8LTck2xJEy4Yg1hM9qjDt4LvTQaMo4Y8ne43aU6x
+			hex!("00299981a2b92f878baaf5dbeba5c18d4e70f2a1fcd9c61b32ea18daf38f4378")
+				.unchecked_into(),
+		),
+	];
+
+	// generated with secret: subkey inspect "$secret"/fir
+	// 5Ff3iXP75ruzroPWRP2FYBHWnmGGBSb63857BgnzCoXNxfPo
+	let root_key: AccountId =
+		hex!("9ee5e5bdc0ec239eb164f865ecc345ce4c88e76ee002e0f7e318097347471809").into();
+
+	testnet_genesis(initial_authorities, root_key.clone(), vec![root_key])
+}
+
+/// The names of the presets of the runtime.
+pub fn preset_names() -> Vec<PresetId> {
+	vec![
+		PresetId::from(DEV_RUNTIME_PRESET),
+		PresetId::from(LOCAL_TESTNET_RUNTIME_PRESET),
+		PresetId::from(STAGING_RUNTIME_PRESET),
+	]
+}
+
+/// The genesis config patch of the preset named `name`, if there's one.
+pub fn preset_for_name(name: &str) -> Option<serde_json::Value> {
+	match name {
+		DEV_RUNTIME_PRESET => Some(development_config_genesis()),
+		LOCAL_TESTNET_RUNTIME_PRESET => Some(local_testnet_genesis()),
+		STAGING_RUNTIME_PRESET => Some(staging_testnet_config_genesis()),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::RuntimeGenesisConfig;
+	use sp_core::{ed25519, sr25519, Pair};
+	use sp_runtime::BuildStorage;
+
+	fn sr25519_public(uri: &str) -> [u8; 32] {
+		sr25519::Pair::from_string(uri, None).unwrap().public().0
+	}
+
+	#[test]
+	fn dev_keys_are_derived_from_the_dev_seeds() {
+		for (name, key, stash) in [
+			("Alice", ALICE, ALICE_STASH),
+			("Bob", BOB, BOB_STASH),
+			("Charlie", CHARLIE, CHARLIE_STASH),
+			("Dave", DAVE, DAVE_STASH),
+			("Eve", EVE, EVE_STASH),
+			("Ferdie", FERDIE, FERDIE_STASH),
+		] {
+			assert_eq!(sr25519_public(&format!("//{}", name)), key);
+			assert_eq!(sr25519_public(&format!("//{}//stash", name)), stash);
+		}
+		for (name, key) in [("Alice", ALICE_ED25519), ("Bob", BOB_ED25519)] {
+			assert_eq!(
+				ed25519::Pair::from_string(&format!("//{}", name), None).unwrap().public().0,
+				key
+			);
+		}
+	}
+
+	#[test]
+	fn presets_build_the_genesis_state() {
+		for id in preset_names() {
+			let patch = preset_for_name(id.as_str().unwrap()).unwrap();
+
+			// Every preset sets whole pallet configs, so the patch is applied key by key.
+			let mut config = serde_json::to_value(RuntimeGenesisConfig::default()).unwrap();
+			for (pallet, pallet_config) in patch.as_object().unwrap() {
+				assert!(config.get(pallet).is_some(), "unknown pallet {}", pallet);
+				config[pallet] = pallet_config.clone();
+			}
+
+			let config: RuntimeGenesisConfig = serde_json::from_value(config).unwrap();
+			config.build_storage().unwrap();
+		}
+		assert!(preset_for_name("unknown").is_none());
+	}
+}
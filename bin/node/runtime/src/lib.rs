@@ -30,6 +30,7 @@ use frame_election_provider_support::{
 use frame_support::{
 	construct_runtime,
 	dispatch::DispatchClass,
+	genesis_builder_helper::{build_config, create_default_config, get_preset},
 	instances::{Instance1, Instance2},
 	ord_parameter_types,
 	pallet_prelude::Get,
@@ -52,7 +53,7 @@ use frame_system::{
 };
 pub use node_primitives::{AccountId, Signature};
 use node_primitives::{Balance, BlockNumber, Hash, Moment, Nonce};
-use pallet_asset_conversion::{NativeOrAssetId, NativeOrAssetIdConverter};
+use pallet_asset_conversion::{NativeOrAssetId, NativeOrAssetIdConverter, SwapRoute};
 use pallet_election_provider_multi_phase::SolutionAccuracyOf;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use pallet_session::historical as pallet_session_historical;
@@ -72,8 +73,10 @@ use sp_runtime::{
 		self, AccountIdConversion, BlakeTwo256, Block as BlockT, Bounded, ConvertInto,
 		IdentityLookup, NumberFor, OpaqueKeys, SaturatedConversion,
 	},
-	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, FixedPointNumber, Perbill, Permill, Perquintill,
+	transaction_validity::{
+		TransactionPriority, TransactionSource, TransactionValidity, TransactionValidityError,
+	},
+	ApplyExtrinsicResult, FixedPointNumber, FixedU128, Perbill, Permill, Perquintill,
 };
 use sp_std::prelude::*;
 #[cfg(any(feature = "std", test))]
@@ -107,6 +110,9 @@ mod voter_bags;
 /// Runtime API definition for assets.
 pub mod assets_api;
 
+/// The genesis config presets.
+pub mod genesis_config_presets;
+
 // Make the WASM binary available.
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
@@ -247,6 +253,8 @@ impl pallet_utility::Config for Runtime {
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
 		RuntimeBlockWeights::get().max_block;
+	pub MaximumSchedulerTaskWeight: Weight = Perbill::from_percent(50) *
+		RuntimeBlockWeights::get().max_block;
 }
 
 impl pallet_scheduler::Config for Runtime {
@@ -263,6 +271,7 @@ impl pallet_scheduler::Config for Runtime {
 	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type Preimages = Preimage;
+	type MaxTaskWeight = MaximumSchedulerTaskWeight;
 }
 
 impl pallet_glutton::Config for Runtime {
@@ -430,8 +439,10 @@ parameter_types! {
 	pub const SessionsPerEra: sp_staking::SessionIndex = 6;
 	pub const BondingDuration: sp_staking::EraIndex = 24 * 28;
 	pub const SlashDeferDuration: sp_staking::EraIndex = 24 * 7; // 1/4 the bonding duration.
+	pub const MinUnbondingDuration: sp_staking::EraIndex = 24 * 14; // 1/2 the bonding duration.
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MaxNominatorRewardedPerValidator: u32 = 256;
+	pub const MaxAutoPayoutsPerBlock: u32 = 4;
 	pub const OffendingValidatorsThreshold: Perbill = Perbill::from_percent(17);
 	pub OffchainRepeat: BlockNumber = 5;
 	pub HistoryDepth: u32 = 84;
@@ -456,11 +467,13 @@ impl pallet_staking::Config for Runtime {
 	type SessionsPerEra = SessionsPerEra;
 	type BondingDuration = BondingDuration;
 	type SlashDeferDuration = SlashDeferDuration;
+	type MinUnbondingDuration = MinUnbondingDuration;
 	type AdminOrigin = EnsureRoot<AccountId>;
 	type SessionInterface = Self;
 	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
+	type MaxAutoPayoutsPerBlock = MaxAutoPayoutsPerBlock;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
 	type ElectionProvider = ElectionProviderMultiPhase;
 	type GenesisElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
@@ -808,6 +821,28 @@ impl pallet_message_queue::Config for Runtime {
 	type HeapSize = ConstU32<{ 64 * 1024 }>;
 	type MaxStale = ConstU32<128>;
 	type ServiceWeight = MessageQueueServiceWeight;
+	type QueueAdminOrigin = EnsureRoot<AccountId>;
+	type MaxPrioritizedQueues = ConstU32<16>;
+}
+
+parameter_types! {
+	/// Allocate at most 50% of each block to multi-block migrations.
+	pub MigrationsMaxServiceWeight: Weight = Perbill::from_percent(50) * RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_migrations::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Migrations = (
+		pallet_assets::migration::v2::MigrateToV2<Runtime, Instance1>,
+		pallet_assets::migration::v2::MigrateToV2<Runtime, Instance2>,
+		pallet_staking::migrations::v14::MigrateToV14<Runtime>,
+	);
+	type CursorMaxLen = ConstU32<65_536>;
+	type IdentifierMaxLen = ConstU32<256>;
+	// The asset migrations rewrite `Account` in place, so resuming after one of them failed would
+	// leave accounts which can't be decoded anymore.
+	type FailedMigrationHandler = frame_support::migrations::FreezeChainOnFailedMigration;
+	type MaxServiceWeight = MigrationsMaxServiceWeight;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -938,6 +973,10 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
 	type Freezer = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type MaxHolds = ConstU32<2>;
+	type MaxFreezes = ConstU32<2>;
 	type Extra = ();
 	type CallbackHandle = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
@@ -965,6 +1004,10 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
 	type Freezer = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type MaxHolds = ConstU32<2>;
+	type MaxFreezes = ConstU32<2>;
 	type Extra = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
@@ -1000,6 +1043,7 @@ impl pallet_asset_conversion::Config for Runtime {
 	type WeightInfo = pallet_asset_conversion::weights::SubstrateWeight<Runtime>;
 	type AllowMultiAssetPools = AllowMultiAssetPools;
 	type MaxSwapPathLength = ConstU32<4>;
+	type MaxPriceObservations = ConstU32<256>;
 	type MintMinLiquidity = MintMinLiquidity;
 	type MultiAssetIdConverter = NativeOrAssetIdConverter<u32>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1101,6 +1145,7 @@ construct_runtime!(
 		AssetConversion: pallet_asset_conversion,
 		MessageQueue: pallet_message_queue,
 		Pov: frame_benchmarking_pallet_pov,
+		MultiBlockMigrations: pallet_migrations,
 	}
 );
 
@@ -1145,6 +1190,7 @@ pub type Executive = frame_executive::Executive<
 	Runtime,
 	AllPalletsWithSystem,
 	Migrations,
+	MultiBlockMigrations,
 >;
 
 // All migrations executed on runtime upgrade as a nested tuple of types implementing
@@ -1240,6 +1286,42 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_dry_run::DryRunApi<Block> for Runtime {
+		fn dry_run_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+			block_hash: <Block as BlockT>::Hash,
+		) -> Result<sp_dry_run::DryRunEffects, sp_dry_run::DryRunError> {
+			Executive::dry_run_extrinsic(extrinsic, block_hash)
+		}
+
+		fn dry_run_call(
+			origin: Vec<u8>,
+			call: Vec<u8>,
+			block_hash: <Block as BlockT>::Hash,
+		) -> Result<sp_dry_run::DryRunEffects, sp_dry_run::DryRunError> {
+			Executive::dry_run_call::<OriginCaller>(&origin, &call, block_hash)
+		}
+	}
+
+	#[api_version(2)]
+	impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
+		fn create_default_config() -> Vec<u8> {
+			create_default_config::<RuntimeGenesisConfig>()
+		}
+
+		fn build_config(config: Vec<u8>) -> sp_genesis_builder::Result {
+			build_config::<RuntimeGenesisConfig>(config)
+		}
+
+		fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
+			genesis_config_presets::preset_names()
+		}
+
+		fn get_preset(id: &sp_genesis_builder::PresetId) -> Option<Vec<u8>> {
+			get_preset(id, genesis_config_presets::preset_for_name)
+		}
+	}
+
 	impl sp_offchain::OffchainWorkerApi<Block> for Runtime {
 		fn offchain_worker(header: &<Block as BlockT>::Header) {
 			Executive::offchain_worker(header)
@@ -1282,10 +1364,49 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl pallet_staking_runtime_api::StakingApi<Block, Balance> for Runtime {
 		fn nominations_quota(balance: Balance) -> u32 {
 			Staking::api_nominations_quota(balance)
 		}
+
+		fn unbonding_duration(value: Balance) -> sp_staking::EraIndex {
+			Staking::unbonding_duration(value)
+		}
+
+		fn unbonding_queue() -> Vec<(sp_staking::EraIndex, Balance)> {
+			Staking::api_unbonding_queue()
+		}
+	}
+
+	impl pallet_staking_runtime_api::StakerInfoApi<Block, Balance, AccountId> for Runtime {
+		fn pending_rewards(
+			stash: AccountId,
+		) -> Vec<pallet_staking_runtime_api::PendingReward<AccountId, Balance>> {
+			Staking::api_pending_rewards(stash)
+		}
+
+		fn exposure(
+			stash: AccountId,
+		) -> Option<pallet_staking_runtime_api::StakerExposure<AccountId, Balance>> {
+			Staking::api_exposure(stash)
+		}
+
+		fn unbonding(stash: AccountId) -> Vec<pallet_staking_runtime_api::UnbondingChunk<Balance>> {
+			Staking::api_unbonding(stash)
+		}
+
+		fn estimated_apy() -> Perbill {
+			Staking::api_estimated_apy(
+				SessionsPerEra::get() as u64 * EpochDuration::get() * MILLISECS_PER_BLOCK,
+			)
+		}
+
+		fn era_validators(
+			era: Option<sp_staking::EraIndex>,
+		) -> Vec<pallet_staking_runtime_api::EraValidator<AccountId, Balance>> {
+			Staking::api_era_validators(era)
+		}
 	}
 
 	impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -1379,6 +1500,76 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_transaction_payment_rpc_runtime_api::AssetTransactionPaymentApi<
+		Block,
+		u32,
+		u128,
+	> for Runtime {
+		fn query_fee_in_asset(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset_id: u32,
+			actual_weight: Option<Weight>,
+		) -> Result<u128, TransactionValidityError> {
+			match uxt.signature {
+				Some((who, _, _)) => AssetConversionTxPayment::query_fee_in_asset(
+					&who,
+					&uxt.function,
+					len,
+					asset_id,
+					actual_weight,
+				),
+				None => Ok(0),
+			}
+		}
+	}
+
+	impl pallet_transaction_payment_rpc_runtime_api::AssetTransactionPaymentCallApi<
+		Block,
+		AccountId,
+		RuntimeCall,
+		u32,
+		u128,
+	> for Runtime {
+		fn query_call_fee_in_asset(
+			who: AccountId,
+			call: RuntimeCall,
+			len: u32,
+			asset_id: u32,
+			actual_weight: Option<Weight>,
+		) -> Result<u128, TransactionValidityError> {
+			AssetConversionTxPayment::query_fee_in_asset(&who, &call, len, asset_id, actual_weight)
+		}
+	}
+
+	impl pallet_message_queue::MessageQueueApi<Block, u32, u32> for Runtime {
+		fn footprint(origin: u32) -> frame_support::traits::Footprint {
+			<MessageQueue as frame_support::traits::EnqueueMessage<u32>>::footprint(origin)
+		}
+
+		fn overweight_messages(origin: u32) -> Vec<pallet_message_queue::OverweightMessage<u32>> {
+			MessageQueue::overweight_messages(&origin)
+		}
+
+		fn next_serviced_page() -> Option<(u32, u32)> {
+			MessageQueue::next_serviced_page()
+		}
+	}
+
+	impl pallet_scheduler::SchedulerApi<Block, BlockNumber> for Runtime {
+		fn agenda(
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<pallet_scheduler::AgendaEntry<BlockNumber>> {
+			Scheduler::agenda(from, to)
+		}
+
+		fn next_run(id: [u8; 32]) -> Option<BlockNumber> {
+			Scheduler::next_run(id)
+		}
+	}
+
+	#[api_version(3)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -1397,6 +1588,18 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: NativeOrAssetId<u32>, asset2: NativeOrAssetId<u32>) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(&asset1, &asset2).ok()
 		}
+
+		fn quote_best_path_exact_tokens_for_tokens(asset1: NativeOrAssetId<u32>, asset2: NativeOrAssetId<u32>, amount: u128) -> Option<SwapRoute<NativeOrAssetId<u32>, u128>> {
+			AssetConversion::quote_best_path_exact_tokens_for_tokens(asset1, asset2, amount)
+		}
+
+		fn quote_best_path_tokens_for_exact_tokens(asset1: NativeOrAssetId<u32>, asset2: NativeOrAssetId<u32>, amount: u128) -> Option<SwapRoute<NativeOrAssetId<u32>, u128>> {
+			AssetConversion::quote_best_path_tokens_for_exact_tokens(asset1, asset2, amount)
+		}
+
+		fn get_twap(asset1: NativeOrAssetId<u32>, asset2: NativeOrAssetId<u32>, window: u32) -> Option<FixedU128> {
+			AssetConversion::get_twap(&asset1, &asset2, window)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
@@ -1429,6 +1632,7 @@ impl_runtime_apis! {
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
@@ -1448,6 +1652,10 @@ impl_runtime_apis! {
 			// have a backtrace here.
 			Executive::try_execute_block(block, state_root_check, signature_check, select).unwrap()
 		}
+
+		fn multi_block_migrations_ongoing() -> bool {
+			<MultiBlockMigrations as frame_support::migrations::MultiStepMigrator>::ongoing()
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1470,6 +1678,13 @@ impl_runtime_apis! {
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
+			#[cfg(feature = "bls-experimental")]
+			{
+				use baseline::bls::Pallet as BlsBench;
+				frame_benchmarking::list_benchmark!(
+					list, extra, frame_benchmarking_bls, BlsBench::<Runtime>
+				);
+			}
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -1502,6 +1717,14 @@ impl_runtime_apis! {
 			let mut batches = Vec::<BenchmarkBatch>::new();
 			let params = (&config, &whitelist);
 			add_benchmarks!(params, batches);
+			#[cfg(feature = "bls-experimental")]
+			{
+				use baseline::bls::Pallet as BlsBench;
+				impl baseline::bls::Config for Runtime {}
+				frame_benchmarking::add_benchmark!(
+					params, batches, frame_benchmarking_bls, BlsBench::<Runtime>
+				);
+			}
 			Ok(batches)
 		}
 	}
@@ -27,6 +27,9 @@ use clap::Parser;
 use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
 
 use node_cli::chain_spec::{self, AccountId};
+use sc_chain_spec::{
+	ChainType, GenericChainSpec, GenesisConfigBuilderRuntimeCaller, NoGenesisConfig,
+};
 use sc_keystore::LocalKeystore;
 use sp_core::{
 	crypto::{ByteArray, Ss58Codec},
@@ -35,7 +38,8 @@ use sp_core::{
 use sp_keystore::KeystorePtr;
 
 /// A utility to easily create a testnet chain spec definition with a given set
-/// of authorities and endowed accounts and/or generate random accounts.
+/// of authorities and endowed accounts and/or generate random accounts, or a chain
+/// spec of any runtime, from its WASM code and genesis config presets.
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
 enum ChainSpecBuilder {
@@ -84,18 +88,76 @@ enum ChainSpecBuilder {
 		#[arg(long, short)]
 		keystore_path: Option<PathBuf>,
 	},
+	/// Create a new chain spec from the WASM code of a runtime, with its genesis
+	/// config built by the runtime itself.
+	///
+	/// The genesis config is the default one of the runtime, patched with one of its
+	/// presets or with a JSON patch.
+	Create {
+		/// The spec name.
+		#[arg(long, default_value = "Custom")]
+		chain_name: String,
+		/// The spec id.
+		#[arg(long, default_value = "custom")]
+		chain_id: String,
+		/// The type of the chain: `development`, `local`, `live`, or a custom one.
+		#[arg(long, default_value = "live", value_parser = parse_chain_type)]
+		chain_type: ChainType,
+		/// The path of the runtime WASM code.
+		#[arg(long, short)]
+		runtime_wasm_path: PathBuf,
+		/// The name of the runtime preset to patch the default genesis config with.
+		#[arg(long, short, conflicts_with = "patch_path")]
+		preset: Option<String>,
+		/// The path of a JSON patch to patch the default genesis config with.
+		#[arg(long)]
+		patch_path: Option<PathBuf>,
+		/// Output the raw genesis storage, instead of the genesis config.
+		#[arg(long)]
+		raw: bool,
+		/// The path where the chain spec should be saved.
+		#[arg(long, short, default_value = "./chain_spec.json")]
+		chain_spec_path: PathBuf,
+	},
+	/// List the names of the genesis config presets of a runtime.
+	ListPresets {
+		/// The path of the runtime WASM code.
+		#[arg(long, short)]
+		runtime_wasm_path: PathBuf,
+	},
+	/// Print the genesis config patch of a runtime preset, or the default genesis
+	/// config of the runtime if no preset is given.
+	DisplayPreset {
+		/// The path of the runtime WASM code.
+		#[arg(long, short)]
+		runtime_wasm_path: PathBuf,
+		/// The name of the preset.
+		#[arg(long, short)]
+		preset: Option<String>,
+	},
 }
 
 impl ChainSpecBuilder {
-	/// Returns the path where the chain spec should be saved.
-	fn chain_spec_path(&self) -> &Path {
+	/// Returns the path where the chain spec should be saved, if the command creates one.
+	fn chain_spec_path(&self) -> Option<&Path> {
 		match self {
-			ChainSpecBuilder::New { chain_spec_path, .. } => chain_spec_path.as_path(),
-			ChainSpecBuilder::Generate { chain_spec_path, .. } => chain_spec_path.as_path(),
+			ChainSpecBuilder::New { chain_spec_path, .. } => Some(chain_spec_path.as_path()),
+			ChainSpecBuilder::Generate { chain_spec_path, .. } => Some(chain_spec_path.as_path()),
+			ChainSpecBuilder::Create { chain_spec_path, .. } => Some(chain_spec_path.as_path()),
+			ChainSpecBuilder::ListPresets { .. } | ChainSpecBuilder::DisplayPreset { .. } => None,
 		}
 	}
 }
 
+fn parse_chain_type(chain_type: &str) -> Result<ChainType, String> {
+	Ok(match chain_type {
+		"development" => ChainType::Development,
+		"local" => ChainType::Local,
+		"live" => ChainType::Live,
+		custom => ChainType::Custom(custom.to_string()),
+	})
+}
+
 fn genesis_constructor(
 	authority_seeds: &[String],
 	nominator_accounts: &[AccountId],
@@ -142,7 +204,7 @@ fn generate_chain_spec(
 	let chain_spec = chain_spec::ChainSpec::from_genesis(
 		"Custom",
 		"custom",
-		sc_chain_spec::ChainType::Live,
+		ChainType::Live,
 		move || {
 			genesis_constructor(
 				&authority_seeds,
@@ -162,6 +224,65 @@ fn generate_chain_spec(
 	chain_spec.as_json(false)
 }
 
+fn read_runtime_wasm(runtime_wasm_path: &Path) -> Result<Vec<u8>, String> {
+	fs::read(runtime_wasm_path).map_err(|err| {
+		format!("Failed to read the runtime at `{}`: {}", runtime_wasm_path.display(), err)
+	})
+}
+
+fn generate_chain_spec_for_runtime(
+	chain_name: &str,
+	chain_id: &str,
+	chain_type: ChainType,
+	runtime_wasm_path: &Path,
+	preset: Option<String>,
+	patch_path: Option<PathBuf>,
+	raw: bool,
+) -> Result<String, String> {
+	let code = read_runtime_wasm(runtime_wasm_path)?;
+
+	let builder = GenericChainSpec::<NoGenesisConfig>::builder(&code, Default::default())
+		.with_name(chain_name)
+		.with_id(chain_id)
+		.with_chain_type(chain_type);
+	let builder = match (preset, patch_path) {
+		(Some(preset), _) => builder.with_genesis_config_preset_name(&preset),
+		(None, Some(patch_path)) => {
+			let patch = fs::read(&patch_path).map_err(|err| {
+				format!("Failed to read the patch at `{}`: {}", patch_path.display(), err)
+			})?;
+			let patch = serde_json::from_slice(&patch)
+				.map_err(|err| format!("Failed to parse the patch: {}", err))?;
+			builder.with_genesis_config_patch(patch)
+		},
+		(None, None) => builder,
+	};
+
+	builder.build().as_json(raw)
+}
+
+fn print_presets(runtime_wasm_path: &Path) -> Result<(), String> {
+	let code = read_runtime_wasm(runtime_wasm_path)?;
+	for name in GenesisConfigBuilderRuntimeCaller::<()>::new(&code).preset_names()? {
+		println!("{}", name);
+	}
+	Ok(())
+}
+
+fn print_preset(runtime_wasm_path: &Path, preset: Option<String>) -> Result<(), String> {
+	let code = read_runtime_wasm(runtime_wasm_path)?;
+	let caller = GenesisConfigBuilderRuntimeCaller::<()>::new(&code);
+	let config = match preset {
+		Some(preset) => caller.get_named_preset(&preset)?,
+		None => caller.get_default_config()?,
+	};
+
+	let json = serde_json::to_string_pretty(&config)
+		.map_err(|err| format!("Failed to print the genesis config: {}", err))?;
+	println!("{}", json);
+	Ok(())
+}
+
 fn generate_authority_keys_and_store(seeds: &[String], keystore_path: &Path) -> Result<(), String> {
 	for (n, seed) in seeds.iter().enumerate() {
 		let keystore: KeystorePtr =
@@ -250,7 +371,7 @@ fn main() -> Result<(), String> {
 	);
 
 	let builder = ChainSpecBuilder::parse();
-	let chain_spec_path = builder.chain_spec_path().to_path_buf();
+	let chain_spec_path = builder.chain_spec_path().map(Path::to_path_buf);
 
 	let (authority_seeds, nominator_accounts, endowed_accounts, sudo_account) = match builder {
 		ChainSpecBuilder::Generate { authorities, nominators, endowed, keystore_path, .. } => {
@@ -296,10 +417,36 @@ fn main() -> Result<(), String> {
 			sudo_account,
 			..
 		} => (authority_seeds, nominator_accounts, endowed_accounts, sudo_account),
+		ChainSpecBuilder::Create {
+			chain_name,
+			chain_id,
+			chain_type,
+			runtime_wasm_path,
+			preset,
+			patch_path,
+			raw,
+			chain_spec_path,
+		} => {
+			let json = generate_chain_spec_for_runtime(
+				&chain_name,
+				&chain_id,
+				chain_type,
+				&runtime_wasm_path,
+				preset,
+				patch_path,
+				raw,
+			)?;
+			return fs::write(chain_spec_path, json).map_err(|err| err.to_string())
+		},
+		ChainSpecBuilder::ListPresets { runtime_wasm_path } =>
+			return print_presets(&runtime_wasm_path),
+		ChainSpecBuilder::DisplayPreset { runtime_wasm_path, preset } =>
+			return print_preset(&runtime_wasm_path, preset),
 	};
 
 	let json =
 		generate_chain_spec(authority_seeds, nominator_accounts, endowed_accounts, sudo_account)?;
 
+	let chain_spec_path = chain_spec_path.expect("`New` and `Generate` create a chain spec; qed");
 	fs::write(chain_spec_path, json).map_err(|err| err.to_string())
 }
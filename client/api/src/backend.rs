@@ -19,8 +19,9 @@
 
 //! Substrate Client data backend
 
-use std::collections::HashSet;
+use std::{collections::HashSet, path::Path};
 
+use codec::{Decode, Encode};
 use parking_lot::RwLock;
 
 use sp_consensus::BlockOrigin;
@@ -84,6 +85,21 @@ pub struct ImportSummary<Block: BlockT> {
 	pub import_notification_action: ImportNotificationAction,
 }
 
+/// Description of a database checkpoint, as taken by [`Backend::checkpoint`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CheckpointInfo<Block: BlockT> {
+	/// Genesis hash of the chain the checkpoint belongs to.
+	pub genesis_hash: Block::Hash,
+	/// Hash of the best block in the checkpoint.
+	pub best_hash: Block::Hash,
+	/// Number of the best block in the checkpoint.
+	pub best_number: NumberFor<Block>,
+	/// Hash of the last finalized block in the checkpoint.
+	pub finalized_hash: Block::Hash,
+	/// Number of the last finalized block in the checkpoint.
+	pub finalized_number: NumberFor<Block>,
+}
+
 /// Finalization operation summary.
 ///
 /// Contains information about the block that just got finalized,
@@ -622,6 +638,14 @@ pub trait Backend<Block: BlockT>: AuxStore + Send + Sync {
 
 	/// Tells whether the backend requires full-sync mode.
 	fn requires_full_sync(&self) -> bool;
+
+	/// Write a consistent snapshot of the backend to `path`, which must not exist yet, while the
+	/// backend stays open.
+	///
+	/// Returns an error by default, for backends which don't support checkpoints.
+	fn checkpoint(&self, _path: &Path) -> sp_blockchain::Result<CheckpointInfo<Block>> {
+		Err(sp_blockchain::Error::Backend("Backend does not support checkpoints".into()))
+	}
 }
 
 /// Mark for all Backend implementations, that are making use of state data, stored locally.
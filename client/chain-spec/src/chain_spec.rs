@@ -20,23 +20,40 @@
 //! Substrate chain configurations.
 #![warn(missing_docs)]
 
-use crate::{extension::GetExtension, ChainType, Properties, RuntimeGenesis};
+use crate::{
+	extension::GetExtension, genesis_config_builder::GenesisConfigBuilderRuntimeCaller, ChainType,
+	Properties, RuntimeGenesis,
+};
 use sc_network::config::MultiaddrWithPeerId;
 use sc_telemetry::TelemetryEndpoints;
 use serde::{Deserialize, Serialize};
 use serde_json as json;
 use sp_core::{
-	storage::{ChildInfo, Storage, StorageChild, StorageData, StorageKey},
+	storage::{well_known_keys, ChildInfo, Storage, StorageChild, StorageData, StorageKey},
 	Bytes,
 };
 use sp_runtime::BuildStorage;
-use std::{borrow::Cow, collections::BTreeMap, fs::File, path::PathBuf, sync::Arc};
+use std::{
+	borrow::Cow, collections::BTreeMap, fs::File, marker::PhantomData, path::PathBuf, sync::Arc,
+};
+
+/// How the genesis config of a chain spec built from the runtime code is obtained.
+#[derive(Clone)]
+enum GenesisBuildAction {
+	/// A patch, applied on top of the default genesis config of the runtime.
+	Patch(json::Value),
+	/// The full genesis config.
+	Full(json::Value),
+	/// The patch of a preset provided by the runtime.
+	NamedPreset(String),
+}
 
 enum GenesisSource<G> {
 	File(PathBuf),
 	Binary(Cow<'static, [u8]>),
 	Factory(Arc<dyn Fn() -> G + Send + Sync>),
 	Storage(Storage),
+	GenesisBuilderApi(GenesisBuildAction, Vec<u8>),
 }
 
 impl<G> Clone for GenesisSource<G> {
@@ -46,6 +63,8 @@ impl<G> Clone for GenesisSource<G> {
 			Self::Binary(ref d) => Self::Binary(d.clone()),
 			Self::Factory(ref f) => Self::Factory(f.clone()),
 			Self::Storage(ref s) => Self::Storage(s.clone()),
+			Self::GenesisBuilderApi(ref action, ref code) =>
+				Self::GenesisBuilderApi(action.clone(), code.clone()),
 		}
 	}
 }
@@ -105,32 +124,49 @@ impl<G: RuntimeGenesis> GenesisSource<G> {
 
 				Ok(Genesis::Raw(RawGenesis { top, children_default }))
 			},
+			Self::GenesisBuilderApi(action, code) => {
+				let json_blob = match action {
+					GenesisBuildAction::Patch(patch) =>
+						RuntimeGenesisConfigJson::Patch(patch.clone()),
+					GenesisBuildAction::Full(config) =>
+						RuntimeGenesisConfigJson::Config(config.clone()),
+					GenesisBuildAction::NamedPreset(name) => RuntimeGenesisConfigJson::Patch(
+						GenesisConfigBuilderRuntimeCaller::<()>::new(code)
+							.get_named_preset(name)?,
+					),
+				};
+
+				Ok(Genesis::RuntimeGenesis(RuntimeGenesisInner { code: code.clone(), json_blob }))
+			},
 		}
 	}
 }
 
 impl<G: RuntimeGenesis, E> BuildStorage for ChainSpec<G, E> {
 	fn assimilate_storage(&self, storage: &mut Storage) -> Result<(), String> {
-		match self.genesis.resolve()? {
-			Genesis::Runtime(gc) => gc.assimilate_storage(storage),
-			Genesis::Raw(RawGenesis { top: map, children_default: children_map }) => {
-				storage.top.extend(map.into_iter().map(|(k, v)| (k.0, v.0)));
-				children_map.into_iter().for_each(|(k, v)| {
-					let child_info = ChildInfo::new_default(k.0.as_slice());
-					storage
-						.children_default
-						.entry(k.0)
-						.or_insert_with(|| StorageChild { data: Default::default(), child_info })
-						.data
-						.extend(v.into_iter().map(|(k, v)| (k.0, v.0)));
-				});
-				Ok(())
-			},
-			// The `StateRootHash` variant exists as a way to keep note that other clients support
-			// it, but Substrate itself isn't capable of loading chain specs with just a hash at the
-			// moment.
-			Genesis::StateRootHash(_) => Err("Genesis storage in hash format not supported".into()),
-		}
+		let RawGenesis { top: map, children_default: children_map } =
+			match self.genesis.resolve()? {
+				Genesis::Runtime(gc) => return gc.assimilate_storage(storage),
+				Genesis::Raw(raw) => raw,
+				Genesis::RuntimeGenesis(inner) => inner.build_storage()?.into(),
+				// The `StateRootHash` variant exists as a way to keep note that other clients
+				// support it, but Substrate itself isn't capable of loading chain specs with
+				// just a hash at the moment.
+				Genesis::StateRootHash(_) =>
+					return Err("Genesis storage in hash format not supported".into()),
+			};
+
+		storage.top.extend(map.into_iter().map(|(k, v)| (k.0, v.0)));
+		children_map.into_iter().for_each(|(k, v)| {
+			let child_info = ChildInfo::new_default(k.0.as_slice());
+			storage
+				.children_default
+				.entry(k.0)
+				.or_insert_with(|| StorageChild { data: Default::default(), child_info })
+				.data
+				.extend(v.into_iter().map(|(k, v)| (k.0, v.0)));
+		});
+		Ok(())
 	}
 }
 
@@ -145,6 +181,58 @@ pub struct RawGenesis {
 	pub children_default: BTreeMap<StorageKey, GenesisStorage>,
 }
 
+impl From<Storage> for RawGenesis {
+	fn from(storage: Storage) -> Self {
+		let top = storage.top.into_iter().map(|(k, v)| (StorageKey(k), StorageData(v))).collect();
+		let children_default = storage
+			.children_default
+			.into_iter()
+			.map(|(sk, child)| {
+				(
+					StorageKey(sk),
+					child.data.into_iter().map(|(k, v)| (StorageKey(k), StorageData(v))).collect(),
+				)
+			})
+			.collect();
+
+		RawGenesis { top, children_default }
+	}
+}
+
+/// The genesis config of a chain spec, as JSON, given to the `GenesisBuilder` API of its runtime.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+enum RuntimeGenesisConfigJson {
+	/// The full genesis config.
+	Config(json::Value),
+	/// A patch, applied on top of the default genesis config of the runtime.
+	Patch(json::Value),
+}
+
+/// A genesis built by the runtime, from its code and a JSON genesis config.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeGenesisInner {
+	/// The runtime code, set as the `:code` of the genesis storage.
+	#[serde(with = "sp_core::bytes")]
+	code: Vec<u8>,
+	#[serde(flatten)]
+	json_blob: RuntimeGenesisConfigJson,
+}
+
+impl RuntimeGenesisInner {
+	/// Build the genesis storage, by calling the `GenesisBuilder` API of the runtime.
+	fn build_storage(self) -> Result<Storage, String> {
+		let caller = GenesisConfigBuilderRuntimeCaller::<()>::new(&self.code);
+		let mut storage = match self.json_blob {
+			RuntimeGenesisConfigJson::Config(config) => caller.get_storage_for_config(config)?,
+			RuntimeGenesisConfigJson::Patch(patch) => caller.get_storage_for_patch(patch)?,
+		};
+		storage.top.insert(well_known_keys::CODE.to_vec(), self.code);
+		Ok(storage)
+	}
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
@@ -153,6 +241,8 @@ enum Genesis<G> {
 	Raw(RawGenesis),
 	/// State root hash of the genesis storage.
 	StateRootHash(StorageData),
+	/// The genesis config, built by the `GenesisBuilder` API of the runtime.
+	RuntimeGenesis(RuntimeGenesisInner),
 }
 
 /// A configuration of a client. Does not include runtime storage initialization.
@@ -195,6 +285,19 @@ struct ClientSpec<E> {
 /// We use `Option` here since `()` is not flattenable by serde.
 pub type NoExtension = Option<()>;
 
+/// A type denoting the absence of a native genesis config.
+///
+/// It's meant for specs whose genesis is built by the `GenesisBuilder` API of their runtime, see
+/// [`ChainSpec::builder`].
+#[derive(Serialize, Deserialize)]
+pub struct NoGenesisConfig;
+
+impl BuildStorage for NoGenesisConfig {
+	fn assimilate_storage(&self, _: &mut Storage) -> Result<(), String> {
+		Err("No native genesis config, the genesis must be built by the runtime".into())
+	}
+}
+
 /// A configuration of a chain. Can be used to build a genesis block.
 pub struct ChainSpec<G, E = NoExtension> {
 	client_spec: ClientSpec<E>,
@@ -291,6 +394,12 @@ impl<G, E> ChainSpec<G, E> {
 		ChainSpec { client_spec, genesis: GenesisSource::Factory(Arc::new(constructor)) }
 	}
 
+	/// Create a builder of a spec whose genesis is built by the `GenesisBuilder` API of the
+	/// runtime `code`.
+	pub fn builder(code: &[u8], extensions: E) -> ChainSpecBuilder<G, E> {
+		ChainSpecBuilder::new(code, extensions)
+	}
+
 	/// Type of the chain.
 	fn chain_type(&self) -> ChainType {
 		self.client_spec.chain_type.clone()
@@ -357,6 +466,7 @@ impl<G: RuntimeGenesis, E: serde::Serialize + Clone + 'static> ChainSpec<G, E> {
 
 				Genesis::Raw(RawGenesis { top, children_default })
 			},
+			(true, Genesis::RuntimeGenesis(inner)) => Genesis::Raw(inner.build_storage()?.into()),
 			(_, genesis) => genesis,
 		};
 		Ok(JsonContainer { client_spec: self.client_spec.clone(), genesis })
@@ -443,6 +553,137 @@ where
 	}
 }
 
+/// A builder of [`ChainSpec`]s whose genesis is built by the `GenesisBuilder` API of their
+/// runtime.
+///
+/// By default, the genesis is the default genesis config of the runtime.
+pub struct ChainSpecBuilder<G, E = NoExtension> {
+	code: Vec<u8>,
+	extensions: E,
+	name: String,
+	id: String,
+	chain_type: ChainType,
+	genesis_build_action: GenesisBuildAction,
+	boot_nodes: Option<Vec<MultiaddrWithPeerId>>,
+	telemetry_endpoints: Option<TelemetryEndpoints>,
+	protocol_id: Option<String>,
+	fork_id: Option<String>,
+	properties: Option<Properties>,
+	_genesis: PhantomData<G>,
+}
+
+impl<G, E> ChainSpecBuilder<G, E> {
+	/// Create a new builder, for the runtime `code`.
+	pub fn new(code: &[u8], extensions: E) -> Self {
+		Self {
+			code: code.into(),
+			extensions,
+			name: "Development".to_string(),
+			id: "dev".to_string(),
+			chain_type: ChainType::Local,
+			genesis_build_action: GenesisBuildAction::Patch(json::json!({})),
+			boot_nodes: None,
+			telemetry_endpoints: None,
+			protocol_id: None,
+			fork_id: None,
+			properties: None,
+			_genesis: PhantomData,
+		}
+	}
+
+	/// Set the spec name.
+	pub fn with_name(mut self, name: &str) -> Self {
+		self.name = name.into();
+		self
+	}
+
+	/// Set the spec id.
+	pub fn with_id(mut self, id: &str) -> Self {
+		self.id = id.into();
+		self
+	}
+
+	/// Set the type of the chain.
+	pub fn with_chain_type(mut self, chain_type: ChainType) -> Self {
+		self.chain_type = chain_type;
+		self
+	}
+
+	/// Set the list of bootnode addresses.
+	pub fn with_boot_nodes(mut self, boot_nodes: Vec<MultiaddrWithPeerId>) -> Self {
+		self.boot_nodes = Some(boot_nodes);
+		self
+	}
+
+	/// Set the telemetry endpoints.
+	pub fn with_telemetry_endpoints(mut self, telemetry_endpoints: TelemetryEndpoints) -> Self {
+		self.telemetry_endpoints = Some(telemetry_endpoints);
+		self
+	}
+
+	/// Set the network protocol id.
+	pub fn with_protocol_id(mut self, protocol_id: &str) -> Self {
+		self.protocol_id = Some(protocol_id.into());
+		self
+	}
+
+	/// Set the optional network fork identifier.
+	pub fn with_fork_id(mut self, fork_id: &str) -> Self {
+		self.fork_id = Some(fork_id.into());
+		self
+	}
+
+	/// Set the additional loosly-typed properties of the chain.
+	pub fn with_properties(mut self, properties: Properties) -> Self {
+		self.properties = Some(properties);
+		self
+	}
+
+	/// Set the genesis config to the default one of the runtime, patched with `patch`.
+	pub fn with_genesis_config_patch(mut self, patch: json::Value) -> Self {
+		self.genesis_build_action = GenesisBuildAction::Patch(patch);
+		self
+	}
+
+	/// Set the full genesis config, no defaults are used.
+	pub fn with_genesis_config(mut self, config: json::Value) -> Self {
+		self.genesis_build_action = GenesisBuildAction::Full(config);
+		self
+	}
+
+	/// Set the genesis config to the default one of the runtime, patched with its preset named
+	/// `name`.
+	///
+	/// The preset is fetched from the runtime when the spec is exported or its genesis is built.
+	pub fn with_genesis_config_preset_name(mut self, name: &str) -> Self {
+		self.genesis_build_action = GenesisBuildAction::NamedPreset(name.into());
+		self
+	}
+
+	/// Build the [`ChainSpec`].
+	pub fn build(self) -> ChainSpec<G, E> {
+		let client_spec = ClientSpec {
+			name: self.name,
+			id: self.id,
+			chain_type: self.chain_type,
+			boot_nodes: self.boot_nodes.unwrap_or_default(),
+			telemetry_endpoints: self.telemetry_endpoints,
+			protocol_id: self.protocol_id,
+			fork_id: self.fork_id,
+			properties: self.properties,
+			extensions: self.extensions,
+			consensus_engine: (),
+			genesis: Default::default(),
+			code_substitutes: BTreeMap::new(),
+		};
+
+		ChainSpec {
+			client_spec,
+			genesis: GenesisSource::GenesisBuilderApi(self.genesis_build_action, self.code),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -507,6 +748,48 @@ mod tests {
 		assert_eq!(spec.extensions().my_property, "Test Extension");
 	}
 
+	#[test]
+	fn builder_should_output_runtime_genesis() {
+		let patch = json::json!({ "balances": { "balances": [["5GrwvaEF", 1000]] } });
+		let spec = TestSpec::builder(&[1, 2, 3], None)
+			.with_name("Test")
+			.with_id("test")
+			.with_chain_type(ChainType::Development)
+			.with_genesis_config_patch(patch.clone())
+			.build();
+
+		let json: json::Value = json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		assert_eq!(json["name"], "Test");
+		assert_eq!(json["chainType"], "Development");
+		assert_eq!(
+			json["genesis"],
+			json::json!({ "runtimeGenesis": { "code": "0x010203", "patch": patch } })
+		);
+	}
+
+	#[test]
+	fn should_deserialize_runtime_genesis_chain_spec() {
+		let json = json::json!({
+			"name": "Test",
+			"id": "test",
+			"chainType": "Local",
+			"bootNodes": [],
+			"telemetryEndpoints": null,
+			"protocolId": null,
+			"properties": null,
+			"codeSubstitutes": {},
+			"genesis": {
+				"runtimeGenesis": {
+					"code": "0x010203",
+					"config": { "sudo": { "key": null } },
+				},
+			},
+		});
+		let spec = TestSpec::from_json_bytes(json.to_string().into_bytes()).unwrap();
+
+		assert_eq!(json::from_str::<json::Value>(&spec.as_json(false).unwrap()).unwrap(), json);
+	}
+
 	#[test]
 	fn chain_spec_raw_output_should_be_deterministic() {
 		let mut spec = TestSpec2::from_json_bytes(Cow::Owned(
@@ -0,0 +1,143 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A helper module for calling the `GenesisBuilder` API of a runtime from its WASM code.
+
+use codec::{Decode, Encode};
+use sc_executor::WasmExecutor;
+use serde_json::Value;
+use sp_core::{
+	storage::Storage,
+	traits::{CallContext, CodeExecutor, Externalities, FetchRuntimeCode, RuntimeCode},
+};
+use sp_genesis_builder::{PresetId, Result as BuildResult};
+use sp_state_machine::BasicExternalities;
+use sp_wasm_interface::HostFunctions;
+use std::borrow::Cow;
+
+/// A utility that facilitates calling the `GenesisBuilder` API of the runtime, given its WASM
+/// code.
+///
+/// The runtime can provide `EHF` as extra host functions, on top of the Substrate ones.
+pub struct GenesisConfigBuilderRuntimeCaller<'a, EHF = ()>
+where
+	EHF: HostFunctions,
+{
+	code: Cow<'a, [u8]>,
+	code_hash: Vec<u8>,
+	executor: WasmExecutor<(sp_io::SubstrateHostFunctions, EHF)>,
+}
+
+impl<'a, EHF> FetchRuntimeCode for GenesisConfigBuilderRuntimeCaller<'a, EHF>
+where
+	EHF: HostFunctions,
+{
+	fn fetch_runtime_code(&self) -> Option<Cow<[u8]>> {
+		Some(self.code.as_ref().into())
+	}
+}
+
+impl<'a, EHF> GenesisConfigBuilderRuntimeCaller<'a, EHF>
+where
+	EHF: HostFunctions,
+{
+	/// Creates a new instance of the caller, for the given runtime `code`.
+	pub fn new(code: &'a [u8]) -> Self {
+		GenesisConfigBuilderRuntimeCaller {
+			code: code.into(),
+			code_hash: sp_core::blake2_256(code).to_vec(),
+			executor: WasmExecutor::<(sp_io::SubstrateHostFunctions, EHF)>::builder()
+				.with_allow_missing_host_functions(true)
+				.build(),
+		}
+	}
+
+	fn call(
+		&self,
+		ext: &mut dyn Externalities,
+		method: &str,
+		data: &[u8],
+	) -> Result<Vec<u8>, String> {
+		self.executor
+			.call(
+				ext,
+				&RuntimeCode { heap_pages: None, code_fetcher: self, hash: self.code_hash.clone() },
+				method,
+				data,
+				false,
+				CallContext::Offchain,
+			)
+			.0
+			.map_err(|e| format!("wasm call error {}", e))
+	}
+
+	fn call_and_decode<T: Decode>(&self, method: &str, data: &[u8]) -> Result<T, String> {
+		let mut ext = BasicExternalities::new_empty();
+		let result = self.call(&mut ext, method, data)?;
+		T::decode(&mut &result[..]).map_err(|e| format!("scale codec error: {}", e))
+	}
+
+	/// Returns the default `GenesisConfig` of the runtime.
+	pub fn get_default_config(&self) -> Result<Value, String> {
+		let config: Vec<u8> = self.call_and_decode("GenesisBuilder_create_default_config", &[])?;
+		serde_json::from_slice(&config).map_err(|e| format!("Invalid default config: {}", e))
+	}
+
+	/// Returns the names of the presets provided by the runtime.
+	pub fn preset_names(&self) -> Result<Vec<String>, String> {
+		let names: Vec<PresetId> = self.call_and_decode("GenesisBuilder_preset_names", &[])?;
+		names
+			.into_iter()
+			.map(|id| {
+				id.as_str()
+					.map(ToOwned::to_owned)
+					.ok_or_else(|| format!("Invalid preset name: {:?}", id))
+			})
+			.collect()
+	}
+
+	/// Returns the JSON patch of the preset named `name`.
+	pub fn get_named_preset(&self, name: &str) -> Result<Value, String> {
+		let patch: Option<Vec<u8>> =
+			self.call_and_decode("GenesisBuilder_get_preset", &PresetId::from(name).encode())?;
+		let patch = patch.ok_or_else(|| format!("The runtime has no preset named `{}`", name))?;
+		serde_json::from_slice(&patch).map_err(|e| format!("Invalid preset `{}`: {}", name, e))
+	}
+
+	/// Builds the genesis storage from the full `GenesisConfig`, without using any defaults.
+	pub fn get_storage_for_config(&self, config: Value) -> Result<Storage, String> {
+		let mut ext = BasicExternalities::new_empty();
+		let result = self.call(
+			&mut ext,
+			"GenesisBuilder_build_config",
+			&config.to_string().into_bytes().encode(),
+		)?;
+		BuildResult::decode(&mut &result[..])
+			.map_err(|e| format!("scale codec error: {}", e))?
+			.map_err(|e| format!("Failed to build the genesis storage: {}", e))?;
+		Ok(ext.into_storages())
+	}
+
+	/// Builds the genesis storage from the `patch`, applied on top of the default `GenesisConfig`.
+	pub fn get_storage_for_patch(&self, patch: Value) -> Result<Storage, String> {
+		let mut config = self.get_default_config()?;
+		crate::json_patch::merge(&mut config, patch);
+		self.get_storage_for_config(config)
+	}
+}
@@ -0,0 +1,79 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A helper module providing the merge of JSON patches into genesis configs.
+
+use serde_json::Value;
+
+/// Recursively merges the JSON `patch` into `config`, as described by RFC 7386.
+///
+/// Objects are merged key by key, and a `null` value in the patch removes the key. Every other
+/// value in the patch replaces the value in `config`.
+pub fn merge(config: &mut Value, patch: Value) {
+	match (config, patch) {
+		(Value::Object(config), Value::Object(patch)) =>
+			for (key, value) in patch {
+				if value.is_null() {
+					config.remove(&key);
+				} else {
+					merge(config.entry(key).or_insert(Value::Null), value);
+				}
+			},
+		(config, patch) => *config = patch,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn merge_replaces_values() {
+		let mut config = json!({ "balances": { "balances": [["a", 1]] }, "sudo": { "key": "a" } });
+		merge(&mut config, json!({ "balances": { "balances": [["b", 2]] } }));
+
+		assert_eq!(
+			config,
+			json!({ "balances": { "balances": [["b", 2]] }, "sudo": { "key": "a" } })
+		);
+	}
+
+	#[test]
+	fn merge_recurses_into_objects() {
+		let mut config = json!({ "staking": { "validatorCount": 0, "minimumValidatorCount": 0 } });
+		merge(&mut config, json!({ "staking": { "validatorCount": 2 }, "sudo": { "key": "a" } }));
+
+		assert_eq!(
+			config,
+			json!({
+				"staking": { "validatorCount": 2, "minimumValidatorCount": 0 },
+				"sudo": { "key": "a" },
+			})
+		);
+	}
+
+	#[test]
+	fn merge_removes_null_values() {
+		let mut config = json!({ "sudo": { "key": "a" }, "babe": { "epochConfig": null } });
+		merge(&mut config, json!({ "sudo": null, "babe": { "epochConfig": { "c": [1, 4] } } }));
+
+		assert_eq!(config, json!({ "babe": { "epochConfig": { "c": [1, 4] } } }));
+	}
+}
@@ -149,11 +149,16 @@
 //! "consensusEngine": null,
 //! // The genesis declaration of the chain.
 //! //
-//! // `runtime`, `raw`, `stateRootHash` denote the type of the genesis declaration.
+//! // `runtime`, `runtimeGenesis`, `raw`, `stateRootHash` denote the type of the genesis
+//! // declaration.
 //! //
 //! // These declarations are in the following formats:
 //! // - `runtime` is a `json` object that can be parsed by a compatible `GenesisConfig`. This
 //! //  `GenesisConfig` is declared by a runtime and opaque to the node.
+//! // - `runtimeGenesis` is a `json` object with the hex encoded runtime `code`, and either a full
+//! //   `config` or a `patch`, applied on top of the default `GenesisConfig` of the runtime. The
+//! //   genesis storage is built by the `GenesisBuilder` API of the runtime, so the node doesn't
+//! //   need to know its `GenesisConfig`.
 //! // - `raw` is a `json` object with two fields `top` and `children_default`. Each of these
 //! //   fields is a map of `key => value`. These key/value pairs represent the genesis storage.
 //! // - `stateRootHash` is a single hex encoded hash that represents the genesis hash. The hash
@@ -179,14 +184,18 @@
 mod chain_spec;
 mod extension;
 mod genesis;
+mod genesis_config_builder;
+mod json_patch;
 
 pub use self::{
-	chain_spec::{ChainSpec as GenericChainSpec, NoExtension},
+	chain_spec::{ChainSpec as GenericChainSpec, ChainSpecBuilder, NoExtension, NoGenesisConfig},
 	extension::{get_extension, get_extension_mut, Extension, Fork, Forks, GetExtension, Group},
 	genesis::{
 		construct_genesis_block, resolve_state_version_from_wasm, BuildGenesisBlock,
 		GenesisBlockBuilder,
 	},
+	genesis_config_builder::GenesisConfigBuilderRuntimeCaller,
+	json_patch::merge as json_merge,
 };
 pub use sc_chain_spec_derive::{ChainSpecExtension, ChainSpecGroup};
 
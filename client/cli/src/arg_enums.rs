@@ -134,6 +134,8 @@ pub enum CryptoScheme {
 	Ed25519,
 	/// Use sr25519.
 	Sr25519,
+	/// Use ecdsa.
+	Ecdsa,
 }
 
 /// The type of the output format.
@@ -193,6 +195,10 @@ pub enum Database {
 	RocksDb,
 	/// ParityDb. <https://github.com/paritytech/parity-db/>
 	ParityDb,
+	/// Embedded database keeping all data in memory and persisting it to a log file. Not meant
+	/// for production, only for tests and small development chains.
+	#[value(name = "filedb-experimental")]
+	FileDbExperimental,
 	/// Detect whether there is an existing database. Use it, if there is, if not, create new
 	/// instance of ParityDb
 	Auto,
@@ -209,6 +215,7 @@ impl Database {
 			"rocksdb",
 			"paritydb",
 			"paritydb-experimental",
+			"filedb-experimental",
 			"auto",
 		]
 	}
@@ -0,0 +1,78 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use clap::Parser;
+use sc_client_api::Backend;
+use sp_runtime::traits::Block as BlockT;
+use std::path::PathBuf;
+
+/// The `backup-db` command used to write a checkpoint of the database.
+///
+/// The checkpoint can be restored with the `restore-db` command. A checkpoint of a running node
+/// can be taken with the unsafe `backup_createCheckpoint` RPC instead.
+#[derive(Debug, Clone, Parser)]
+pub struct BackupDbCmd {
+	/// Directory to write the checkpoint to. It must not exist yet.
+	#[arg(value_name = "PATH")]
+	pub output: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl BackupDbCmd {
+	/// Run the `backup-db` command
+	pub fn run<B: BlockT>(&self, config: &sc_service::Configuration) -> error::Result<()> {
+		let backend = sc_service::new_db_backend::<B>(config.db_config())?;
+		let info = backend.checkpoint(&self.output)?;
+		println!(
+			"Checkpoint written to {:?}: best #{} ({:?}), finalized #{} ({:?})",
+			self.output,
+			info.best_number,
+			info.best_hash,
+			info.finalized_number,
+			info.finalized_hash,
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for BackupDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,41 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Database related CLI utilities
+
+use super::migrate_db_cmd::MigrateDbCmd;
+use crate::error;
+use sc_service::Configuration;
+use sp_runtime::traits::Block as BlockT;
+
+/// Database utilities for the cli.
+#[derive(Debug, clap::Subcommand)]
+pub enum DbSubcommand {
+	/// Convert the database of a node to a different database backend.
+	Migrate(MigrateDbCmd),
+}
+
+impl DbSubcommand {
+	/// run the db subcommands
+	pub fn run<B: BlockT>(&self, config: &Configuration) -> error::Result<()> {
+		match self {
+			DbSubcommand::Migrate(cmd) => cmd.run::<B>(config),
+		}
+	}
+}
@@ -72,6 +72,12 @@ impl InsertKeyCmd {
 				let keystore: KeystorePtr = LocalKeystore::open(path, password)?.into();
 				(keystore, public)
 			},
+			KeystoreConfig::EncryptedPath { path, password, encryption_password } => {
+				let public = with_crypto_scheme!(self.scheme, to_vec(&suri, password.clone()))?;
+				let keystore: KeystorePtr =
+					LocalKeystore::open_encrypted(path, password, encryption_password)?.into();
+				(keystore, public)
+			},
 			_ => unreachable!("keystore_config always returns path and password; qed"),
 		};
 
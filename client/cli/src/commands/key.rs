@@ -21,6 +21,7 @@
 use super::{
 	generate::GenerateCmd, generate_node_key::GenerateNodeKeyCmd, insert_key::InsertKeyCmd,
 	inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
+	reencrypt_keystore::ReencryptKeystoreCmd,
 };
 use crate::{Error, SubstrateCli};
 
@@ -42,6 +43,9 @@ pub enum KeySubcommand {
 
 	/// Insert a key to the keystore of a node.
 	Insert(InsertKeyCmd),
+
+	/// Re-encrypt the key files of a node's keystore with a new password.
+	ReencryptKeystore(ReencryptKeystoreCmd),
 }
 
 impl KeySubcommand {
@@ -53,6 +57,7 @@ impl KeySubcommand {
 			KeySubcommand::Inspect(cmd) => cmd.run(),
 			KeySubcommand::Insert(cmd) => cmd.run(cli),
 			KeySubcommand::InspectNodeKey(cmd) => cmd.run(),
+			KeySubcommand::ReencryptKeystore(cmd) => cmd.run(cli),
 		}
 	}
 }
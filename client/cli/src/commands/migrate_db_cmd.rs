@@ -0,0 +1,73 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{arg_enums::Database, error, CliConfiguration, SharedParams};
+use clap::Parser;
+use sc_service::Configuration;
+use sp_runtime::traits::Block as BlockT;
+
+/// The `db migrate` command used to convert the database of a node to another backend.
+///
+/// Every column is copied into the new database, which must not exist yet, and checked once
+/// written. The state of the last finalized block is then traversed in the new database. The
+/// original database is kept and can be removed once the node runs on the new one.
+#[derive(Debug, Clone, Parser)]
+pub struct MigrateDbCmd {
+	/// Database backend to migrate from.
+	#[arg(long, value_name = "DB", ignore_case = true, value_enum)]
+	pub from: Database,
+
+	/// Database backend to migrate to.
+	#[arg(long, value_name = "DB", ignore_case = true, value_enum)]
+	pub to: Database,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl MigrateDbCmd {
+	/// Run the `db migrate` command
+	pub fn run<B: BlockT>(&self, config: &Configuration) -> error::Result<()> {
+		let cache_size = self.database_cache_size()?.unwrap_or(1024);
+		let from = self.database_config(&config.data_path, cache_size, self.from)?;
+		let to = self.database_config(&config.data_path, cache_size, self.to)?;
+		let summary = sc_client_db::migrate_database::<B>(&from, &to)?;
+		println!(
+			"Migrated {} database entries from {} to {}",
+			summary.entries.iter().sum::<u64>(),
+			from,
+			to,
+		);
+		match summary.verified_block {
+			Some((hash, number)) => println!(
+				"Verified the state of finalized block #{} ({:?}): {} keys",
+				number, hash, summary.verified_keys,
+			),
+			None => println!("No finalized state to verify"),
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for MigrateDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
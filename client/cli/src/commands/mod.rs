@@ -16,9 +16,11 @@
 
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
+mod backup_db_cmd;
 mod build_spec_cmd;
 mod chain_info_cmd;
 mod check_block_cmd;
+mod db;
 mod export_blocks_cmd;
 mod export_state_cmd;
 mod generate;
@@ -28,7 +30,10 @@ mod insert_key;
 mod inspect_key;
 mod inspect_node_key;
 mod key;
+mod migrate_db_cmd;
 mod purge_chain_cmd;
+mod reencrypt_keystore;
+mod restore_db_cmd;
 mod revert_cmd;
 mod run_cmd;
 mod sign;
@@ -38,10 +43,12 @@ mod vanity;
 mod verify;
 
 pub use self::{
-	build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd,
-	export_blocks_cmd::ExportBlocksCmd, export_state_cmd::ExportStateCmd, generate::GenerateCmd,
-	generate_node_key::GenerateNodeKeyCmd, import_blocks_cmd::ImportBlocksCmd,
-	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	backup_db_cmd::BackupDbCmd, build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd,
+	check_block_cmd::CheckBlockCmd, db::DbSubcommand, export_blocks_cmd::ExportBlocksCmd,
+	export_state_cmd::ExportStateCmd, generate::GenerateCmd, generate_node_key::GenerateNodeKeyCmd,
+	import_blocks_cmd::ImportBlocksCmd, insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd,
+	inspect_node_key::InspectNodeKeyCmd, key::KeySubcommand, migrate_db_cmd::MigrateDbCmd,
+	purge_chain_cmd::PurgeChainCmd, reencrypt_keystore::ReencryptKeystoreCmd,
+	restore_db_cmd::RestoreDbCmd, revert_cmd::RevertCmd, run_cmd::RunCmd, sign::SignCmd,
+	vanity::VanityCmd, verify::VerifyCmd,
 };
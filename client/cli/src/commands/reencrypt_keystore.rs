@@ -0,0 +1,168 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `reencrypt-keystore` subcommand
+
+use crate::{read_secret_file, Error, KeystoreParams, SharedParams, SubstrateCli};
+use clap::Parser;
+use sc_keystore::LocalKeystore;
+use sc_service::config::{BasePath, KeystoreConfig};
+use std::path::PathBuf;
+
+/// The `reencrypt-keystore` command
+#[derive(Debug, Clone, Parser)]
+#[command(
+	name = "reencrypt-keystore",
+	about = "Re-encrypt the key files of a node's keystore with a new password."
+)]
+pub struct ReencryptKeystoreCmd {
+	/// File that contains the new password to encrypt the key files with.
+	#[arg(long, value_name = "PATH", required_unless_present = "decrypt")]
+	new_encryption_password_filename: Option<PathBuf>,
+
+	/// Write the key files back as plaintext instead of encrypting them.
+	#[arg(long, conflicts_with = "new_encryption_password_filename")]
+	decrypt: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl ReencryptKeystoreCmd {
+	/// Run the command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let base_path = self
+			.shared_params
+			.base_path()?
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		let config_dir = base_path.config_dir(chain_spec.id());
+
+		let new_encryption_password = self
+			.new_encryption_password_filename
+			.as_ref()
+			.map(|file| read_secret_file(file))
+			.transpose()?;
+
+		let written = match self.keystore_params.keystore_config(&config_dir)? {
+			KeystoreConfig::Path { path, .. } =>
+				LocalKeystore::reencrypt(path, None, new_encryption_password)?,
+			KeystoreConfig::EncryptedPath { path, encryption_password, .. } =>
+				LocalKeystore::reencrypt(path, Some(encryption_password), new_encryption_password)?,
+			_ => unreachable!("keystore_config always returns path and password; qed"),
+		};
+
+		println!("Rewrote {} key files", written);
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_service::{ChainSpec, ChainType, GenericChainSpec, NoExtension};
+	use sp_core::{
+		crypto::{KeyTypeId, SecretString},
+		ByteArray,
+	};
+	use sp_keystore::Keystore;
+	use std::{fs, str::FromStr};
+	use tempfile::TempDir;
+
+	struct Cli;
+
+	impl SubstrateCli for Cli {
+		fn impl_name() -> String {
+			"test".into()
+		}
+
+		fn impl_version() -> String {
+			"2.0".into()
+		}
+
+		fn description() -> String {
+			"test".into()
+		}
+
+		fn support_url() -> String {
+			"test.test".into()
+		}
+
+		fn copyright_start_year() -> i32 {
+			2021
+		}
+
+		fn author() -> String {
+			"test".into()
+		}
+
+		fn load_spec(&self, _: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
+			Ok(Box::new(GenericChainSpec::from_genesis(
+				"test",
+				"test_id",
+				ChainType::Development,
+				|| unimplemented!("Not required in tests"),
+				Vec::new(),
+				None,
+				None,
+				None,
+				None,
+				NoExtension::None,
+			)))
+		}
+	}
+
+	#[test]
+	fn reencrypt_with_custom_base_path() {
+		let path = TempDir::new().unwrap();
+		let path_str = format!("{}", path.path().display());
+		let keystore_path = path.path().join("chains").join("test_id").join("keystore");
+		let key_type = KeyTypeId(*b"test");
+
+		let keystore = LocalKeystore::open(&keystore_path, None).unwrap();
+		let public = keystore.sr25519_generate_new(key_type, None).unwrap();
+		drop(keystore);
+
+		let password_file = path.path().join("password");
+		fs::write(&password_file, "password").unwrap();
+		let password_file_str = format!("{}", password_file.display());
+
+		let reencrypt = ReencryptKeystoreCmd::parse_from(&[
+			"reencrypt-keystore",
+			"-d",
+			&path_str,
+			"--new-encryption-password-filename",
+			&password_file_str,
+		]);
+		assert!(reencrypt.run(&Cli).is_ok());
+
+		let keys = [(public.to_raw_vec(), key_type)];
+		let keystore = LocalKeystore::open(&keystore_path, None).unwrap();
+		assert!(!keystore.has_keys(&keys));
+		let password = SecretString::from_str("password").unwrap();
+		let keystore = LocalKeystore::open_encrypted(&keystore_path, None, password).unwrap();
+		assert!(keystore.has_keys(&keys));
+	}
+}
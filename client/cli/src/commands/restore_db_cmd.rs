@@ -0,0 +1,68 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error, CliConfiguration, DatabaseParams, SharedParams};
+use clap::Parser;
+use sc_service::DatabaseSource;
+use sp_runtime::traits::Block as BlockT;
+use std::path::PathBuf;
+
+/// The `restore-db` command used to restore a checkpoint written by `backup-db`.
+///
+/// The checkpoint is validated before it is restored, and the node's database must not exist yet.
+#[derive(Debug, Clone, Parser)]
+pub struct RestoreDbCmd {
+	/// Directory of the checkpoint to restore.
+	#[arg(value_name = "PATH")]
+	pub checkpoint: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl RestoreDbCmd {
+	/// Run the `restore-db` command
+	pub fn run<B: BlockT>(&self, database_config: DatabaseSource) -> error::Result<()> {
+		let info = sc_client_db::restore_checkpoint::<B>(&self.checkpoint, &database_config)?;
+		println!(
+			"Restored checkpoint {:?}: best #{} ({:?}), finalized #{} ({:?})",
+			self.checkpoint,
+			info.best_number,
+			info.best_hash,
+			info.finalized_number,
+			info.finalized_hash,
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for RestoreDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
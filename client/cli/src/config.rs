@@ -34,7 +34,10 @@ use sc_service::{
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
 use sc_tracing::logging::LoggerBuilder;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+	net::SocketAddr,
+	path::{Path, PathBuf},
+};
 
 /// The maximum number of characters for a node name.
 pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
@@ -153,6 +156,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(Default::default())
 	}
 
+	/// Get the path the transaction pool is persisted to, within `config_dir`.
+	///
+	/// By default this is `None`, leaving the transaction pool options as is.
+	fn transaction_pool_persist_path(&self, _config_dir: &Path) -> Result<Option<PathBuf>> {
+		Ok(None)
+	}
+
 	/// Get the network configuration
 	///
 	/// By default this is retrieved from `NetworkParams` if it is available otherwise it creates
@@ -219,6 +229,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let role_dir = "full";
 		let rocksdb_path = base_path.join("db").join(role_dir);
 		let paritydb_path = base_path.join("paritydb").join(role_dir);
+		let filedb_path = base_path.join("filedb").join(role_dir);
 		Ok(match database {
 			#[cfg(feature = "rocksdb")]
 			Database::RocksDb => DatabaseSource::RocksDb { path: rocksdb_path, cache_size },
@@ -230,6 +241,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 				);
 				DatabaseSource::ParityDb { path: paritydb_path }
 			},
+			Database::FileDbExperimental => {
+				eprintln!(
+					"WARNING: \"filedb-experimental\" keeps the whole database in memory and is not meant for production. \
+				Please use \"paritydb\" or \"rocksdb\" for anything but tests and small development chains."
+				);
+				DatabaseSource::FileDb { path: filedb_path }
+			},
 			Database::Auto => DatabaseSource::Auto { paritydb_path, rocksdb_path, cache_size },
 		})
 	}
@@ -467,6 +485,10 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let max_runtime_instances = self.max_runtime_instances()?.unwrap_or(8);
 		let is_validator = role.is_authority();
 		let keystore = self.keystore_config(&config_dir)?;
+		let mut transaction_pool = self.transaction_pool(is_dev)?;
+		if let Some(path) = self.transaction_pool_persist_path(&config_dir)? {
+			transaction_pool.persist_path = Some(path);
+		}
 		let telemetry_endpoints = self.telemetry_endpoints(&chain_spec)?;
 		let runtime_cache_size = self.runtime_cache_size()?;
 
@@ -474,7 +496,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			impl_name: C::impl_name(),
 			impl_version: C::impl_version(),
 			tokio_handle,
-			transaction_pool: self.transaction_pool(is_dev)?,
+			transaction_pool,
 			network: self.network_config(
 				&chain_spec,
 				is_dev,
@@ -60,6 +60,13 @@ pub struct KeystoreParams {
 		conflicts_with_all = &["password_interactive", "password"]
 	)]
 	pub password_filename: Option<PathBuf>,
+
+	/// File that contains the password used to encrypt the keystore's key files.
+	///
+	/// Key files are stored as plaintext unless this is provided. Existing plaintext key files are
+	/// encrypted when the keystore is opened.
+	#[arg(long, value_name = "PATH")]
+	pub keystore_encryption_password_filename: Option<PathBuf>,
 }
 
 /// Parse a secret string, returning a displayable error.
@@ -79,12 +86,22 @@ impl KeystoreParams {
 			self.password.clone()
 		};
 
+		let encryption_password = self
+			.keystore_encryption_password_filename
+			.as_ref()
+			.map(|file| read_secret_file(file))
+			.transpose()?;
+
 		let path = self
 			.keystore_path
 			.clone()
 			.unwrap_or_else(|| config_dir.join(DEFAULT_KEYSTORE_CONFIG_PATH));
 
-		Ok(KeystoreConfig::Path { path, password })
+		Ok(match encryption_password {
+			Some(encryption_password) =>
+				KeystoreConfig::EncryptedPath { path, password, encryption_password },
+			None => KeystoreConfig::Path { path, password },
+		})
 	}
 
 	/// helper method to fetch password from `KeyParams` or read from stdin
@@ -102,6 +119,12 @@ impl KeystoreParams {
 	}
 }
 
+/// Read a secret from `file`.
+pub fn read_secret_file(file: &Path) -> Result<SecretString> {
+	let secret = fs::read_to_string(file).map_err(|e| format!("{}", e))?;
+	Ok(SecretString::new(secret))
+}
+
 fn input_keystore_password() -> Result<String> {
 	rpassword::prompt_password("Keystore password: ").map_err(|e| format!("{:?}", e).into())
 }
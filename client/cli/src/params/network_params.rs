@@ -150,6 +150,20 @@ pub struct NetworkParams {
 	/// and observe block requests timing out.
 	#[arg(long, value_name = "COUNT", default_value_t = 64)]
 	pub max_blocks_per_request: u32,
+
+	/// Maximum number of blocks below the last finalized block to download the history of.
+	///
+	/// After warp sync, the headers, bodies and justifications of the skipped blocks are
+	/// downloaded in the background, down to genesis unless limited by this option.
+	#[arg(long, value_name = "COUNT")]
+	pub max_block_history: Option<u32>,
+
+	/// Path to a JSON file with a trusted finalized block to start warp sync from.
+	///
+	/// Overrides the checkpoint provided by the chain spec, if any. Proofs of the authority
+	/// set changes are only downloaded and verified from that block onwards.
+	#[arg(long, value_name = "PATH")]
+	pub warp_sync_checkpoint: Option<PathBuf>,
 }
 
 impl NetworkParams {
@@ -245,6 +259,8 @@ impl NetworkParams {
 			yamux_window_size: None,
 			ipfs_server: self.ipfs_server,
 			sync_mode: self.sync.into(),
+			max_block_history: self.max_block_history,
+			warp_sync_checkpoint: self.warp_sync_checkpoint.clone(),
 		}
 	}
 }
@@ -19,7 +19,8 @@
 
 use crate::error;
 use clap::Args;
-use sc_service::{BlocksPruning, PruningMode};
+use sc_service::{BlocksPruning, Constraints, PruningMode};
+use std::time::Duration;
 
 /// Parameters to define the pruning mode
 #[derive(Debug, Clone, Args)]
@@ -27,9 +28,11 @@ pub struct PruningParams {
 	/// Specify the state pruning mode.
 	/// This mode specifies when the block's state (ie, storage)
 	/// should be pruned (ie, removed) from the database.
-	/// This setting can only be set on the first creation of the database. Every subsequent run
-	/// will load the pruning mode from the database and will error if the stored mode doesn't
-	/// match this CLI value. It is fine to drop this CLI flag for subsequent runs.
+	/// This setting is stored in the database, along with `--state-pruning-max-age` and
+	/// `--state-pruning-max-size`, on its first creation. Subsequent runs without any of these
+	/// flags load the stored pruning mode. Passing any of them replaces the stored constraints,
+	/// and errors if the stored mode can't be switched to the requested one, which is only
+	/// possible from an archive to a pruned mode.
 	/// Possible values:
 	///  - archive: Keep the state of all blocks.
 	///  - 'archive-canonical' Keep only the state of finalized blocks.
@@ -38,6 +41,21 @@ pub struct PruningParams {
 	#[arg(alias = "pruning", long, value_name = "PRUNING_MODE")]
 	pub state_pruning: Option<DatabasePruningMode>,
 
+	/// Prune the state of finalized blocks produced this many hours before the last finalized
+	/// block, going by the block timestamps stored by `pallet-timestamp`.
+	///
+	/// If `--state-pruning` isn't set to a number of blocks, the number of blocks kept is
+	/// only limited by this and `--state-pruning-max-size`.
+	#[arg(long, value_name = "HOURS")]
+	pub state_pruning_max_age: Option<u64>,
+
+	/// Prune the state of finalized blocks while the database is larger than this many GiB.
+	///
+	/// If `--state-pruning` isn't set to a number of blocks, the number of blocks kept is
+	/// only limited by this and `--state-pruning-max-age`.
+	#[arg(long, value_name = "GiB")]
+	pub state_pruning_max_size: Option<u64>,
+
 	/// Specify the blocks pruning mode.
 	/// This mode specifies when the block's body (including justifications)
 	/// should be pruned (ie, removed) from the database.
@@ -58,7 +76,38 @@ pub struct PruningParams {
 impl PruningParams {
 	/// Get the pruning value from the parameters
 	pub fn state_pruning(&self) -> error::Result<Option<PruningMode>> {
-		Ok(self.state_pruning.map(|v| v.into()))
+		let mode = self.state_pruning.map(|v| v.into());
+		if self.state_pruning_max_age.is_none() && self.state_pruning_max_size.is_none() {
+			return Ok(mode)
+		}
+
+		let mut constraints = match mode {
+			Some(PruningMode::Constrained(constraints)) => constraints,
+			Some(_) =>
+				return Err(error::Error::Input(
+					"`--state-pruning-max-age` and `--state-pruning-max-size` can't be used with \
+					 an archive `--state-pruning` mode"
+						.into(),
+				)),
+			None => Constraints { max_blocks: None, max_age: None, max_size: None },
+		};
+		constraints.max_age = self
+			.state_pruning_max_age
+			.map(|hours| {
+				hours.checked_mul(60 * 60 * 1000).map(Duration::from_millis).ok_or_else(|| {
+					error::Error::Input("`--state-pruning-max-age` is too large".into())
+				})
+			})
+			.transpose()?;
+		constraints.max_size = self
+			.state_pruning_max_size
+			.map(|gib| {
+				gib.checked_mul(1024 * 1024 * 1024).ok_or_else(|| {
+					error::Error::Input("`--state-pruning-max-size` is too large".into())
+				})
+			})
+			.transpose()?;
+		Ok(Some(PruningMode::Constrained(constraints)))
 	}
 
 	/// Get the block pruning value from the parameters
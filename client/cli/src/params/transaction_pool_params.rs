@@ -18,7 +18,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::Args;
-use sc_service::config::TransactionPoolOptions;
+use sc_service::config::{
+	PoolLimit, PriorityReserve, SenderKey, SenderLimit, TransactionPoolOptions,
+};
+use std::path::{Path, PathBuf};
+
+/// Name of the file, within the chain's config directory, the transaction pool is persisted to.
+pub const DEFAULT_TRANSACTION_POOL_PERSIST_FILE: &str = "txpool";
 
 /// Parameters used to create the pool configuration.
 #[derive(Debug, Clone, Args)]
@@ -34,6 +40,38 @@ pub struct TransactionPoolParams {
 	/// How long a transaction is banned for, if it is considered invalid. Defaults to 1800s.
 	#[arg(long, value_name = "SECONDS")]
 	pub tx_ban_seconds: Option<u64>,
+
+	/// Persist the transaction pool to disk across restarts.
+	///
+	/// Ready and future transactions are periodically written to the chain's config directory,
+	/// and once more on shutdown. On startup they are resubmitted to the pool and revalidated
+	/// against the best block.
+	#[arg(long)]
+	pub pool_persist: bool,
+
+	/// Maximum number of transactions a single sender may have in the transaction pool.
+	///
+	/// Senders are identified by the leading bytes of the first tag their transactions provide,
+	/// see `--pool-sender-tag-prefix`.
+	#[arg(long, value_name = "COUNT")]
+	pub pool_sender_limit: Option<usize>,
+
+	/// Number of leading bytes of a transaction's first provided tag identifying its sender.
+	///
+	/// Defaults to 32, the length of an encoded `AccountId32`, which FRAME's `CheckNonce`
+	/// extension puts first in the tag.
+	#[arg(long, value_name = "BYTES", default_value_t = 32)]
+	pub pool_sender_tag_prefix: usize,
+
+	/// Number of ready transactions reserved for transactions of at least
+	/// `--pool-priority-threshold` priority, such as operational ones.
+	#[arg(long, value_name = "COUNT", requires = "pool_priority_threshold")]
+	pub pool_priority_reserve: Option<usize>,
+
+	/// Minimal priority of the transactions allowed into the slice reserved by
+	/// `--pool-priority-reserve`.
+	#[arg(long, value_name = "PRIORITY", requires = "pool_priority_reserve")]
+	pub pool_priority_threshold: Option<u64>,
 }
 
 impl TransactionPoolParams {
@@ -58,6 +96,27 @@ impl TransactionPoolParams {
 			std::time::Duration::from_secs(30 * 60)
 		};
 
+		opts.sender = self.pool_sender_limit.map(|count| SenderLimit {
+			key: SenderKey::TagPrefix(self.pool_sender_tag_prefix),
+			limit: PoolLimit { count, total_bytes: opts.ready.total_bytes },
+		});
+
+		if let (Some(count), Some(min_priority)) =
+			(self.pool_priority_reserve, self.pool_priority_threshold)
+		{
+			// reserve the same share of the ready queue's bytes as of its count
+			let total_bytes =
+				opts.ready.total_bytes.saturating_mul(count) / opts.ready.count.max(1);
+			opts.priority_reserve =
+				Some(PriorityReserve { min_priority, limit: PoolLimit { count, total_bytes } });
+		}
+
 		opts
 	}
+
+	/// The path within `config_dir` to persist the transaction pool to, if it should be.
+	pub fn persist_path(&self, config_dir: &Path) -> Option<PathBuf> {
+		self.pool_persist
+			.then(|| config_dir.join(DEFAULT_TRANSACTION_POOL_PERSIST_FILE))
+	}
 }
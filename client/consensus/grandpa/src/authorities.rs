@@ -715,6 +715,12 @@ impl<N: Ord + Clone> AuthoritySetChanges<N> {
 		Self(Default::default())
 	}
 
+	/// The latest authority set change, i.e. the id of the set which was replaced and the number
+	/// of its last block.
+	pub(crate) fn latest(&self) -> Option<&(u64, N)> {
+		self.0.last()
+	}
+
 	pub(crate) fn append(&mut self, set_id: u64, block_number: N) {
 		self.0.push((set_id, block_number));
 	}
@@ -748,13 +754,21 @@ impl<N: Ord + Clone> AuthoritySetChanges<N> {
 		}
 	}
 
-	pub(crate) fn insert(&mut self, block_number: N) {
+	/// Insert the change of set at `block_number`, found while importing the block history.
+	///
+	/// The block history is imported from the top down, so a change below every known one ended
+	/// the set before the next known one, or before `current_set_id` if there is none.
+	pub(crate) fn insert(&mut self, block_number: N, current_set_id: u64) {
 		let idx = self
 			.0
 			.binary_search_by_key(&block_number, |(_, n)| n.clone())
 			.unwrap_or_else(|b| b);
 
-		let set_id = if idx == 0 { 0 } else { self.0[idx - 1].0 + 1 };
+		let set_id = match (idx, self.0.first()) {
+			(0, Some((next_set_id, _))) => next_set_id.saturating_sub(1),
+			(0, None) => current_set_id.saturating_sub(1),
+			_ => self.0[idx - 1].0 + 1,
+		};
 		assert!(idx == self.0.len() || self.0[idx].0 != set_id);
 		self.0.insert(idx, (set_id, block_number));
 	}
@@ -1687,11 +1701,26 @@ mod tests {
 		authority_set_changes.append(1, 81);
 		authority_set_changes.append(4, 121);
 
-		authority_set_changes.insert(101);
+		authority_set_changes.insert(101, 5);
 		assert_eq!(authority_set_changes.get_set_id(100), AuthoritySetChangeId::Set(2, 101));
 		assert_eq!(authority_set_changes.get_set_id(101), AuthoritySetChangeId::Set(2, 101));
 	}
 
+	#[test]
+	fn authority_set_changes_insert_descending() {
+		let mut authority_set_changes = AuthoritySetChanges::empty();
+
+		authority_set_changes.insert(121, 3);
+		assert_eq!(authority_set_changes, AuthoritySetChanges(vec![(2, 121)]));
+		assert_eq!(authority_set_changes.get_set_id(100), AuthoritySetChangeId::Unknown);
+
+		authority_set_changes.insert(81, 3);
+		authority_set_changes.insert(41, 3);
+		assert_eq!(authority_set_changes, AuthoritySetChanges(vec![(0, 41), (1, 81), (2, 121)]));
+		assert_eq!(authority_set_changes.get_set_id(50), AuthoritySetChangeId::Set(1, 81));
+		assert_eq!(authority_set_changes.get_set_id(122), AuthoritySetChangeId::Latest);
+	}
+
 	#[test]
 	fn authority_set_changes_for_complete_data() {
 		let mut authority_set_changes = AuthoritySetChanges::empty();
@@ -558,7 +558,8 @@ where
 				}
 				assert!(block.justifications.is_some());
 				let mut authority_set = self.authority_set.inner_locked();
-				authority_set.authority_set_changes.insert(number);
+				let current_set_id = authority_set.set_id;
+				authority_set.authority_set_changes.insert(number, current_set_id);
 				crate::aux_schema::update_authority_set::<Block, _, _>(
 					&authority_set,
 					None,
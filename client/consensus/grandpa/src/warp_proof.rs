@@ -22,16 +22,20 @@ use crate::{
 	best_justification, find_scheduled_change, AuthoritySetChanges, AuthoritySetHardFork,
 	BlockNumberOps, GrandpaJustification, SharedAuthoritySet,
 };
+use sc_chain_spec::ChainSpec;
 use sc_client_api::Backend as ClientBackend;
-use sc_network_common::sync::warp::{EncodedProof, VerificationResult, WarpSyncProvider};
+use sc_network_common::sync::warp::{
+	EncodedProof, VerificationResult, WarpSyncCheckpoint, WarpSyncProvider,
+};
 use sp_blockchain::{Backend as BlockchainBackend, HeaderBackend};
 use sp_consensus_grandpa::{AuthorityList, SetId, GRANDPA_ENGINE_ID};
+use sp_core::Bytes;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
 };
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 /// Warp proof processing error.
 #[derive(Debug, thiserror::Error)]
@@ -51,6 +55,9 @@ pub enum Error {
 	/// Missing header or authority set change data.
 	#[error("Missing required data to be able to answer request.")]
 	MissingData,
+	/// Invalid warp sync checkpoint.
+	#[error("Invalid warp sync checkpoint: {0}")]
+	InvalidCheckpoint(String),
 }
 
 /// The maximum size in bytes of the `WarpSyncProof`.
@@ -235,6 +242,88 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 	}
 }
 
+/// The warp sync checkpoint chain spec extension.
+///
+/// This represents a JSON serialized [`WarpSyncCheckpointSpec`], see [`load_checkpoint`].
+pub type WarpSyncCheckpointExtension = Option<serde_json::Value>;
+
+/// JSON representation of a trusted [`WarpSyncCheckpoint`], as provided through the
+/// [`WarpSyncCheckpointExtension`] or a checkpoint file.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+#[serde(bound = "")]
+pub struct WarpSyncCheckpointSpec<Block: BlockT> {
+	/// Hash of the checkpoint block.
+	pub hash: Block::Hash,
+	/// The SCALE encoded header of the checkpoint block.
+	pub header: Bytes,
+	/// Id of the authority set finalizing the blocks following the checkpoint.
+	pub set_id: SetId,
+	/// The SCALE encoded authorities of that set.
+	pub authorities: Bytes,
+}
+
+impl<Block: BlockT> From<&WarpSyncCheckpoint<Block>> for WarpSyncCheckpointSpec<Block> {
+	fn from(checkpoint: &WarpSyncCheckpoint<Block>) -> Self {
+		WarpSyncCheckpointSpec {
+			hash: checkpoint.header.hash(),
+			header: checkpoint.header.encode().into(),
+			set_id: checkpoint.set_id,
+			authorities: checkpoint.authorities.encode().into(),
+		}
+	}
+}
+
+impl<Block: BlockT> TryFrom<WarpSyncCheckpointSpec<Block>> for WarpSyncCheckpoint<Block> {
+	type Error = Error;
+
+	fn try_from(spec: WarpSyncCheckpointSpec<Block>) -> Result<Self, Error> {
+		let header = Block::Header::decode(&mut &spec.header[..])?;
+		if header.hash() != spec.hash {
+			return Err(Error::InvalidCheckpoint(format!(
+				"header hash {} does not match the checkpoint hash {}",
+				header.hash(),
+				spec.hash,
+			)))
+		}
+
+		let authorities = AuthorityList::decode(&mut &spec.authorities[..])?;
+		if authorities.is_empty() {
+			return Err(Error::InvalidCheckpoint("empty authority set".to_string()))
+		}
+
+		Ok(WarpSyncCheckpoint { header, set_id: spec.set_id, authorities })
+	}
+}
+
+/// Load the trusted warp sync checkpoint from the JSON file at `path` if given, or otherwise
+/// from the [`WarpSyncCheckpointExtension`] of the chain spec.
+pub fn load_checkpoint<Block: BlockT>(
+	chain_spec: &dyn ChainSpec,
+	path: Option<&Path>,
+) -> Result<Option<WarpSyncCheckpoint<Block>>, Error> {
+	let value = match path {
+		Some(path) => {
+			let file = std::fs::File::open(path).map_err(|e| {
+				Error::InvalidCheckpoint(format!("failed to open {}: {}", path.display(), e))
+			})?;
+			serde_json::from_reader(std::io::BufReader::new(file))
+				.map_err(|e| Error::InvalidCheckpoint(e.to_string()))?
+		},
+		None => match sc_chain_spec::get_extension::<WarpSyncCheckpointExtension>(
+			chain_spec.extensions(),
+		) {
+			Some(Some(value)) => value.clone(),
+			_ => return Ok(None),
+		},
+	};
+
+	let spec: WarpSyncCheckpointSpec<Block> =
+		serde_json::from_value(value).map_err(|e| Error::InvalidCheckpoint(e.to_string()))?;
+	spec.try_into().map(Some)
+}
+
 /// Implements network API for warp sync.
 pub struct NetworkProvider<Block: BlockT, Backend: ClientBackend<Block>>
 where
@@ -243,6 +332,7 @@ where
 	backend: Arc<Backend>,
 	authority_set: SharedAuthoritySet<Block::Hash, NumberFor<Block>>,
 	hard_forks: HashMap<(Block::Hash, NumberFor<Block>), (SetId, AuthorityList)>,
+	checkpoint: Option<WarpSyncCheckpoint<Block>>,
 }
 
 impl<Block: BlockT, Backend: ClientBackend<Block>> NetworkProvider<Block, Backend>
@@ -262,8 +352,45 @@ where
 				.into_iter()
 				.map(|fork| (fork.block, (fork.set_id, fork.authorities)))
 				.collect(),
+			checkpoint: None,
 		}
 	}
+
+	/// Start warp sync from the given trusted checkpoint instead of genesis.
+	pub fn with_checkpoint(mut self, checkpoint: Option<WarpSyncCheckpoint<Block>>) -> Self {
+		self.checkpoint = checkpoint;
+		self
+	}
+
+	/// Build a checkpoint at the last block of the previous authority set, from which warp sync
+	/// can start with the current authority set.
+	///
+	/// Returns `None` if the authority set never changed.
+	pub fn latest_checkpoint(&self) -> Result<Option<WarpSyncCheckpoint<Block>>, Error> {
+		let (set_id, authorities, change) = {
+			let authority_set = self.authority_set.inner();
+			(
+				authority_set.set_id,
+				authority_set.current_authorities.clone(),
+				authority_set.authority_set_changes.latest().cloned(),
+			)
+		};
+
+		let Some((previous_set_id, number)) = change else { return Ok(None) };
+		if previous_set_id + 1 != set_id {
+			return Err(Error::MissingData)
+		}
+
+		let blockchain = self.backend.blockchain();
+		let header = blockchain
+			.hash(number)?
+			.map(|hash| blockchain.header(hash))
+			.transpose()?
+			.flatten()
+			.ok_or(Error::MissingData)?;
+
+		Ok(Some(WarpSyncCheckpoint { header, set_id, authorities }))
+	}
 }
 
 impl<Block: BlockT, Backend: ClientBackend<Block>> WarpSyncProvider<Block>
@@ -314,35 +441,49 @@ where
 	fn current_authorities(&self) -> AuthorityList {
 		self.authority_set.inner().current_authorities.clone()
 	}
+
+	fn checkpoint(&self) -> Option<WarpSyncCheckpoint<Block>> {
+		self.checkpoint.clone()
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{codec::Encode, WarpSyncProof};
-	use crate::{AuthoritySetChanges, GrandpaJustification};
+	use super::{
+		codec::{Decode, Encode},
+		Error, NetworkProvider, WarpSyncCheckpoint, WarpSyncCheckpointSpec, WarpSyncProof,
+	};
+	use crate::{AuthoritySet, AuthoritySetChanges, GrandpaJustification};
 	use rand::prelude::*;
 	use sc_block_builder::BlockBuilderProvider;
+	use sc_network_common::sync::warp::{EncodedProof, VerificationResult, WarpSyncProvider};
 	use sp_blockchain::HeaderBackend;
 	use sp_consensus::BlockOrigin;
-	use sp_consensus_grandpa::GRANDPA_ENGINE_ID;
+	use sp_consensus_grandpa::{AuthorityList, GRANDPA_ENGINE_ID};
 	use sp_keyring::Sr25519Keyring;
+	use sp_runtime::traits::Header as _;
 	use std::sync::Arc;
 	use substrate_test_runtime_client::{
-		BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt,
-		TestClientBuilder, TestClientBuilderExt,
+		runtime::Block, Backend, BlockBuilderExt, ClientBlockImportExt, ClientExt,
+		DefaultTestClientBuilderExt, TestClient, TestClientBuilder, TestClientBuilderExt,
 	};
 
-	#[test]
-	fn warp_sync_proof_generate_verify() {
+	fn authority_list(authorities: &[Sr25519Keyring]) -> AuthorityList {
+		authorities.iter().map(|keyring| (keyring.public().into(), 1)).collect()
+	}
+
+	/// Build a chain of 100 blocks with an authority set change every 10 blocks, returning the
+	/// authority set changes and the authorities of each set.
+	fn build_chain() -> (Arc<Backend>, Arc<TestClient>, Vec<(u64, u64)>, Vec<Vec<Sr25519Keyring>>) {
 		let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
 		let builder = TestClientBuilder::new();
 		let backend = builder.backend();
 		let mut client = Arc::new(builder.build());
 
 		let available_authorities = Sr25519Keyring::iter().collect::<Vec<_>>();
-		let genesis_authorities = vec![(Sr25519Keyring::Alice.public().into(), 1)];
 
 		let mut current_authorities = vec![Sr25519Keyring::Alice];
+		let mut set_authorities = vec![current_authorities.clone()];
 		let mut current_set_id = 0;
 		let mut authority_set_changes = Vec::new();
 
@@ -418,9 +559,20 @@ mod tests {
 
 				current_set_id += 1;
 				current_authorities = new_authorities;
+				set_authorities.push(current_authorities.clone());
 			}
 		}
 
+		(backend, client, authority_set_changes, set_authorities)
+	}
+
+	#[test]
+	fn warp_sync_proof_generate_verify() {
+		let (backend, client, authority_set_changes, set_authorities) = build_chain();
+		let genesis_authorities = vec![(Sr25519Keyring::Alice.public().into(), 1)];
+		let current_set_id = authority_set_changes.len() as u64;
+		let current_authorities = set_authorities.last().unwrap();
+
 		let authority_set_changes = AuthoritySetChanges::from(authority_set_changes);
 
 		// generate a warp sync proof
@@ -441,4 +593,69 @@ mod tests {
 		assert_eq!(new_set_id, current_set_id);
 		assert_eq!(new_authorities, expected_authorities);
 	}
+
+	#[test]
+	fn warp_sync_from_checkpoint() {
+		let (backend, client, authority_set_changes, set_authorities) = build_chain();
+
+		// the authority set as it was after the fifth set change, at block 50
+		let authority_set = AuthoritySet::new(
+			authority_list(&set_authorities[5]),
+			5,
+			fork_tree::ForkTree::new(),
+			Vec::new(),
+			AuthoritySetChanges::from(authority_set_changes[..5].to_vec()),
+		)
+		.unwrap();
+		let provider = NetworkProvider::new(backend.clone(), authority_set.into(), Vec::new());
+
+		let checkpoint = provider.latest_checkpoint().unwrap().unwrap();
+		assert_eq!(checkpoint.header.number(), &50);
+		assert_eq!(checkpoint.header.hash(), client.hash(50).unwrap().unwrap());
+		assert_eq!(checkpoint.set_id, 5);
+		assert_eq!(checkpoint.authorities, authority_list(&set_authorities[5]));
+
+		// the checkpoint survives a round trip through its chain spec representation
+		let json = serde_json::to_value(WarpSyncCheckpointSpec::from(&checkpoint)).unwrap();
+		let spec: WarpSyncCheckpointSpec<Block> = serde_json::from_value(json).unwrap();
+		let decoded = WarpSyncCheckpoint::try_from(spec.clone()).unwrap();
+		assert_eq!(decoded, checkpoint);
+
+		// a header not matching the checkpoint hash is rejected
+		let mismatched = WarpSyncCheckpointSpec { hash: Default::default(), ..spec };
+		assert!(matches!(
+			WarpSyncCheckpoint::try_from(mismatched),
+			Err(Error::InvalidCheckpoint(_))
+		));
+
+		// proofs are verified from the checkpoint onwards, without the earlier set changes
+		let authority_set = AuthoritySet::new(
+			authority_list(set_authorities.last().unwrap()),
+			authority_set_changes.len() as u64,
+			fork_tree::ForkTree::new(),
+			Vec::new(),
+			AuthoritySetChanges::from(authority_set_changes.clone()),
+		)
+		.unwrap();
+		let provider = NetworkProvider::new(backend, authority_set.into(), Vec::new())
+			.with_checkpoint(Some(checkpoint.clone()));
+		assert_eq!(provider.checkpoint(), Some(checkpoint.clone()));
+
+		let EncodedProof(proof) = provider.generate(checkpoint.header.hash()).unwrap();
+		let proof = WarpSyncProof::<Block>::decode(&mut proof.as_slice()).unwrap();
+		assert_eq!(proof.proofs.len(), 5);
+		assert_eq!(proof.proofs[0].header.number(), &60);
+
+		match provider
+			.verify(&EncodedProof(proof.encode()), checkpoint.set_id, checkpoint.authorities)
+			.unwrap()
+		{
+			VerificationResult::Complete(set_id, authorities, header) => {
+				assert_eq!(set_id, 10);
+				assert_eq!(authorities, authority_list(set_authorities.last().unwrap()));
+				assert_eq!(header.hash(), client.hash(100).unwrap().unwrap());
+			},
+			VerificationResult::Partial(..) => panic!("proof is within the size limit"),
+		}
+	}
 }
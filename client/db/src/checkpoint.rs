@@ -0,0 +1,180 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Online database checkpoints.
+//!
+//! A checkpoint is a directory holding a copy of the database, taken through
+//! [`Database::checkpoint`](sp_database::Database::checkpoint), next to a SCALE encoded
+//! [`CheckpointInfo`] describing the chain it contains. Restoring a checkpoint validates the
+//! database copy against that description before it is moved into place.
+
+use std::{fs, io, path::Path};
+
+use codec::{Decode, Encode};
+use sc_client_api::backend::CheckpointInfo;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{
+	columns, file_db,
+	utils::{open_database, read_meta, DatabaseType},
+	Database, DatabaseSource, DbHash,
+};
+
+/// Directory of a checkpoint holding the copy of the database.
+const DB_DIR: &str = "db";
+/// File of a checkpoint holding its encoded `CheckpointInfo`.
+const INFO_FILE: &str = "checkpoint_info";
+
+fn io_err(context: &str, path: &Path, e: io::Error) -> ClientError {
+	ClientError::Backend(format!("{} {:?}: {}", context, path, e))
+}
+
+/// Write a checkpoint of `db` to `path`.
+pub(crate) fn create<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	path: &Path,
+) -> ClientResult<CheckpointInfo<Block>> {
+	if path.exists() {
+		return Err(ClientError::Backend(format!("Checkpoint path {:?} already exists", path)))
+	}
+
+	let result = (|| -> ClientResult<CheckpointInfo<Block>> {
+		fs::create_dir_all(path).map_err(|e| io_err("Failed to create", path, e))?;
+		let db_path = path.join(DB_DIR);
+		db.checkpoint(&db_path)?;
+
+		let info = read_info::<Block>(&source_at(&db_path)?)?;
+		let info_path = path.join(INFO_FILE);
+		fs::write(&info_path, info.encode())
+			.map_err(|e| io_err("Failed to write", &info_path, e))?;
+		Ok(info)
+	})();
+
+	if result.is_err() {
+		// Don't leave a partial checkpoint around which could later be mistaken for a valid one.
+		let _ = fs::remove_dir_all(path);
+	}
+	result
+}
+
+/// Restore the checkpoint at `checkpoint` as the database described by `target`.
+///
+/// The database in the checkpoint is opened first and its best and finalized blocks are compared
+/// against the ones recorded when the checkpoint was taken. The database at `target` must not
+/// exist yet.
+pub fn restore_checkpoint<Block: BlockT>(
+	checkpoint: &Path,
+	target: &DatabaseSource,
+) -> ClientResult<CheckpointInfo<Block>> {
+	let info_path = checkpoint.join(INFO_FILE);
+	let encoded = fs::read(&info_path).map_err(|e| io_err("Failed to read", &info_path, e))?;
+	let expected = CheckpointInfo::<Block>::decode(&mut &encoded[..]).map_err(|e| {
+		ClientError::Backend(format!("Failed to decode checkpoint info {:?}: {}", info_path, e))
+	})?;
+
+	let db_path = checkpoint.join(DB_DIR);
+	let source = source_at(&db_path)?;
+	let found = read_info::<Block>(&source)?;
+	if found != expected {
+		return Err(ClientError::Backend(format!(
+			"Checkpoint database does not match its description: expected {:?}, found {:?}",
+			expected, found,
+		)))
+	}
+
+	let target_path = match (&source, target) {
+		#[cfg(feature = "rocksdb")]
+		(DatabaseSource::RocksDb { .. }, DatabaseSource::Auto { rocksdb_path, .. }) =>
+			rocksdb_path.as_path(),
+		#[cfg(feature = "rocksdb")]
+		(DatabaseSource::RocksDb { .. }, DatabaseSource::RocksDb { path, .. }) => path.as_path(),
+		(DatabaseSource::ParityDb { .. }, DatabaseSource::Auto { paritydb_path, .. }) =>
+			paritydb_path.as_path(),
+		(DatabaseSource::ParityDb { .. }, DatabaseSource::ParityDb { path }) => path.as_path(),
+		(DatabaseSource::FileDb { .. }, DatabaseSource::FileDb { path }) => path.as_path(),
+		(source, target) =>
+			return Err(ClientError::Backend(format!(
+				"Can't restore a {} checkpoint into a {} database",
+				source, target,
+			))),
+	};
+
+	let occupied = fs::read_dir(target_path)
+		.map(|mut entries| entries.next().is_some())
+		.unwrap_or(false);
+	if occupied {
+		return Err(ClientError::Backend(format!(
+			"Refusing to restore a checkpoint over the existing database at {:?}",
+			target_path
+		)))
+	}
+
+	copy_dir(&db_path, target_path).map_err(|e| io_err("Failed to restore", target_path, e))?;
+	Ok(expected)
+}
+
+/// Source to open the database copy at `db_path` with, depending on the kind of database.
+fn source_at(db_path: &Path) -> ClientResult<DatabaseSource> {
+	// Written next to every RocksDB database, see `upgrade::update_version`.
+	if db_path.join("db_version").exists() {
+		rocksdb_source(db_path)
+	} else if db_path.join(file_db::LOG_FILE).exists() {
+		Ok(DatabaseSource::FileDb { path: db_path.to_path_buf() })
+	} else {
+		Ok(DatabaseSource::ParityDb { path: db_path.to_path_buf() })
+	}
+}
+
+/// Open the database copy at `source` and describe the chain it contains.
+fn read_info<Block: BlockT>(source: &DatabaseSource) -> ClientResult<CheckpointInfo<Block>> {
+	let db = open_database::<Block>(source, DatabaseType::Full, false)?;
+	let meta = read_meta::<Block>(&*db, columns::HEADER)?;
+	Ok(CheckpointInfo {
+		genesis_hash: meta.genesis_hash,
+		best_hash: meta.best_hash,
+		best_number: meta.best_number,
+		finalized_hash: meta.finalized_hash,
+		finalized_number: meta.finalized_number,
+	})
+}
+
+#[cfg(feature = "rocksdb")]
+fn rocksdb_source(db_path: &Path) -> ClientResult<DatabaseSource> {
+	Ok(DatabaseSource::RocksDb { path: db_path.to_path_buf(), cache_size: 128 })
+}
+
+#[cfg(not(feature = "rocksdb"))]
+fn rocksdb_source(_db_path: &Path) -> ClientResult<DatabaseSource> {
+	Err(ClientError::Backend("`rocksdb` feature not enabled, checkpoint can not be opened".into()))
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+	fs::create_dir_all(to)?;
+	for entry in fs::read_dir(from)? {
+		let entry = entry?;
+		let target = to.join(entry.file_name());
+		if entry.file_type()?.is_dir() {
+			copy_dir(&entry.path(), &target)?;
+		} else {
+			fs::copy(entry.path(), target)?;
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,130 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! A `Database` adapter for the embedded `sp_database::FileDb`.
+
+use crate::{
+	columns,
+	utils::{DatabaseType, NUM_COLUMNS},
+};
+use sp_database::{error, ColumnId, Database, FileDb, FileDbOptions, Transaction};
+use std::{io, path::Path, sync::Arc};
+
+/// Name of the file identifying a `FileDb` database directory.
+pub(crate) const LOG_FILE: &str = "data.log";
+
+struct DbAdapter(FileDb);
+
+/// Open the `FileDb` database at `path` as a trait object implementing `sp_database::Database`.
+pub fn open<H: Clone + AsRef<[u8]>>(
+	path: &Path,
+	db_type: DatabaseType,
+	create: bool,
+) -> io::Result<Arc<dyn Database<H>>> {
+	let ref_counted = match db_type {
+		DatabaseType::Full => vec![columns::STATE, columns::TRANSACTION],
+	};
+	let options = FileDbOptions { columns: NUM_COLUMNS, ref_counted };
+	Ok(Arc::new(DbAdapter(FileDb::open(path, options, create)?)))
+}
+
+impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
+	fn commit(&self, transaction: Transaction<H>) -> error::Result<()> {
+		Database::<H>::commit(&self.0, transaction)
+	}
+
+	fn get(&self, col: ColumnId, key: &[u8]) -> Option<Vec<u8>> {
+		Database::<H>::get(&self.0, col, key)
+	}
+
+	fn contains(&self, col: ColumnId, key: &[u8]) -> bool {
+		Database::<H>::contains(&self.0, col, key)
+	}
+
+	fn value_size(&self, col: ColumnId, key: &[u8]) -> Option<usize> {
+		Database::<H>::value_size(&self.0, col, key)
+	}
+
+	fn supports_ref_counting(&self) -> bool {
+		Database::<H>::supports_ref_counting(&self.0)
+	}
+
+	// As with parity-db, reference counted state is keyed by hash alone.
+	fn sanitize_key(&self, key: &mut Vec<u8>) {
+		let _prefix = key.drain(0..key.len() - crate::DB_HASH_LEN);
+	}
+
+	fn checkpoint(&self, path: &Path) -> error::Result<()> {
+		Database::<H>::checkpoint(&self.0, path)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::DbHash;
+	use sp_database::conformance::{self, TestColumns};
+
+	const TEST_COLUMNS: TestColumns =
+		TestColumns { plain: columns::META, other: columns::HEADER, ref_counted: columns::STATE };
+
+	#[test]
+	fn conformance() {
+		let dir = tempfile::tempdir().unwrap();
+		let db = open::<DbHash>(dir.path(), DatabaseType::Full, true).unwrap();
+		conformance::check_all(&*db, TEST_COLUMNS);
+		conformance::check_persistence(
+			|| open::<DbHash>(&dir.path().join("persistence"), DatabaseType::Full, true).unwrap(),
+			TEST_COLUMNS,
+		);
+	}
+
+	#[test]
+	fn open_requires_create_flag() {
+		let dir = tempfile::tempdir().unwrap();
+		assert_eq!(
+			open::<DbHash>(dir.path(), DatabaseType::Full, false).err().map(|e| e.kind()),
+			Some(io::ErrorKind::NotFound),
+		);
+		assert!(open::<DbHash>(dir.path(), DatabaseType::Full, true).is_ok());
+		assert!(open::<DbHash>(dir.path(), DatabaseType::Full, false).is_ok());
+	}
+
+	#[test]
+	fn torn_record_is_discarded() {
+		use std::io::Write;
+
+		let dir = tempfile::tempdir().unwrap();
+		let db = open::<DbHash>(dir.path(), DatabaseType::Full, true).unwrap();
+		let mut tx = Transaction::new();
+		tx.set(columns::META, b"key", b"value");
+		db.commit(tx).unwrap();
+		drop(db);
+
+		let log = dir.path().join(LOG_FILE);
+		let len = std::fs::metadata(&log).unwrap().len();
+		// A record header announcing more payload than was written.
+		let mut file = std::fs::OpenOptions::new().append(true).open(&log).unwrap();
+		file.write_all(&[64, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+		drop(file);
+
+		let db = open::<DbHash>(dir.path(), DatabaseType::Full, false).unwrap();
+		assert_eq!(db.get(columns::META, b"key"), Some(b"value".to_vec()));
+		assert_eq!(std::fs::metadata(&log).unwrap().len(), len);
+	}
+}
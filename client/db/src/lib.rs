@@ -33,7 +33,10 @@ pub mod offchain;
 
 pub mod bench;
 
+mod checkpoint;
 mod children;
+mod file_db;
+mod migrate;
 mod parity_db;
 mod pinned_blocks_cache;
 mod record_stats_state;
@@ -66,13 +69,14 @@ use sc_client_api::{
 	utils::is_descendent_of,
 	IoInfo, MemoryInfo, MemorySize, UsageInfo,
 };
-use sc_state_db::{IsPruned, LastCanonicalized, StateDb};
+use sc_state_db::{IsPruned, LastCanonicalized, PruningContext, StateDb};
 use sp_arithmetic::traits::Saturating;
 use sp_blockchain::{
 	Backend as _, CachedHeaderMetadata, Error as ClientError, HeaderBackend, HeaderMetadata,
 	HeaderMetadataCache, Result as ClientResult,
 };
 use sp_core::{
+	hashing::twox_128,
 	offchain::OffchainOverlayedChange,
 	storage::{well_known_keys, ChildInfo},
 };
@@ -94,7 +98,9 @@ use sp_state_machine::{
 use sp_trie::{cache::SharedTrieCache, prefixed_key, MemoryDB, PrefixedMemoryDB};
 
 // Re-export the Database trait so that one can pass an implementation of it.
-pub use sc_state_db::PruningMode;
+pub use checkpoint::restore_checkpoint;
+pub use migrate::{migrate_database, MigrationSummary};
+pub use sc_state_db::{Constraints, PruningMode};
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
@@ -335,6 +341,13 @@ pub enum DatabaseSource {
 		path: PathBuf,
 	},
 
+	/// Load an embedded `FileDb` database from a given path. It keeps all data in memory, so it
+	/// is not meant for production, only for tests and small development chains.
+	FileDb {
+		/// Path to the database.
+		path: PathBuf,
+	},
+
 	/// Use a custom already-open database.
 	Custom {
 		/// the handle to the custom storage
@@ -357,6 +370,7 @@ impl DatabaseSource {
 			#[cfg(feature = "rocksdb")]
 			DatabaseSource::RocksDb { path, .. } => Some(path),
 			DatabaseSource::ParityDb { path } => Some(path),
+			DatabaseSource::FileDb { path } => Some(path),
 			DatabaseSource::Custom { .. } => None,
 		}
 	}
@@ -373,7 +387,7 @@ impl DatabaseSource {
 				*path = p.into();
 				true
 			},
-			DatabaseSource::ParityDb { ref mut path } => {
+			DatabaseSource::ParityDb { ref mut path } | DatabaseSource::FileDb { ref mut path } => {
 				*path = p.into();
 				true
 			},
@@ -389,6 +403,7 @@ impl std::fmt::Display for DatabaseSource {
 			#[cfg(feature = "rocksdb")]
 			DatabaseSource::RocksDb { .. } => "RocksDb",
 			DatabaseSource::ParityDb { .. } => "ParityDb",
+			DatabaseSource::FileDb { .. } => "FileDb",
 			DatabaseSource::Custom { .. } => "Custom",
 		};
 		write!(f, "{}", name)
@@ -1098,6 +1113,10 @@ pub struct Backend<Block: BlockT> {
 	blocks_pruning: BlocksPruning,
 	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo)>,
 	state_usage: Arc<StateUsageStats>,
+	/// Size of the database, if the state is pruned by size.
+	db_size: Option<utils::DatabaseSize>,
+	/// Whether the state is pruned by age, which needs the timestamps of the blocks.
+	prune_by_age: bool,
 	genesis_state: RwLock<Option<Arc<DbGenesisStorage<Block>>>>,
 	shared_trie_cache: Option<sp_trie::cache::SharedTrieCache<HashFor<Block>>>,
 }
@@ -1195,6 +1214,26 @@ impl<Block: BlockT> Backend<Block> {
 
 		let state_pruning_used = state_db.pruning_mode();
 		let is_archive_pruning = state_pruning_used.is_archive();
+		let prune_by_age = matches!(
+			&state_pruning_used,
+			PruningMode::Constrained(constraints) if constraints.max_age.is_some()
+		);
+		let db_size = match &state_pruning_used {
+			PruningMode::Constrained(constraints) if constraints.max_size.is_some() =>
+				match utils::database_dir(&config.source) {
+					Some(path) => Some(utils::DatabaseSize::spawn(
+						path.to_path_buf(),
+						std::time::Duration::from_secs(10),
+					)),
+					None => {
+						warn!(
+							"State pruning by size is unsupported for databases not stored on disk"
+						);
+						None
+					},
+				},
+			_ => None,
+		};
 		let blockchain = BlockchainDb::new(db.clone())?;
 
 		let storage_db =
@@ -1211,6 +1250,8 @@ impl<Block: BlockT> Backend<Block> {
 			is_archive: is_archive_pruning,
 			io_stats: FrozenForDuration::new(std::time::Duration::from_secs(1)),
 			state_usage: Arc::new(StateUsageStats::new()),
+			db_size,
+			prune_by_age,
 			blocks_pruning: config.blocks_pruning,
 			genesis_state: RwLock::new(None),
 			shared_trie_cache: config.trie_cache_maximum_size.map(|maximum_size| {
@@ -1366,6 +1407,31 @@ impl<Block: BlockT> Backend<Block> {
 		Ok(MetaUpdate { hash, number, is_best: false, is_finalized: true, with_state })
 	}
 
+	// canonicalizes the state of the block, pruning the states which no longer satisfy the
+	// pruning constraints.
+	fn canonicalize_state(
+		&self,
+		hash: &Block::Hash,
+	) -> ClientResult<sc_state_db::CommitSet<Vec<u8>>> {
+		let context = PruningContext {
+			timestamp: if self.prune_by_age { self.block_timestamp(*hash) } else { None },
+			db_size: self.db_size.as_ref().and_then(|size| size.get()),
+		};
+		self.storage.state_db.canonicalize_block_with(hash, context).map_err(
+			sp_blockchain::Error::from_state_db::<
+				sc_state_db::Error<sp_database::error::DatabaseError>,
+			>,
+		)
+	}
+
+	// the timestamp of the block, as set by `pallet-timestamp` in its state, if it is available.
+	fn block_timestamp(&self, hash: Block::Hash) -> Option<u64> {
+		let key = [twox_128(b"Timestamp"), twox_128(b"Now")].concat();
+		let state = sc_client_api::Backend::state_at(self, hash).ok()?;
+		let timestamp = state.storage(&key).ok()??;
+		u64::decode(&mut &timestamp[..]).ok()
+	}
+
 	// performs forced canonicalization with a delay after importing a non-finalized block.
 	fn force_delayed_canonicalize(
 		&self,
@@ -1405,11 +1471,7 @@ impl<Block: BlockT> Backend<Block> {
 			}
 
 			trace!(target: "db", "Canonicalize block #{} ({:?})", to_canonicalize, hash_to_canonicalize);
-			let commit = self.storage.state_db.canonicalize_block(&hash_to_canonicalize).map_err(
-				sp_blockchain::Error::from_state_db::<
-					sc_state_db::Error<sp_database::error::DatabaseError>,
-				>,
-			)?;
+			let commit = self.canonicalize_state(&hash_to_canonicalize)?;
 			apply_state_commit(transaction, commit);
 		}
 
@@ -1566,11 +1628,7 @@ impl<Block: BlockT> Backend<Block> {
 				apply_state_commit(&mut transaction, commit);
 				if number <= last_finalized_num {
 					// Canonicalize in the db when re-importing existing blocks with state.
-					let commit = self.storage.state_db.canonicalize_block(&hash).map_err(
-						sp_blockchain::Error::from_state_db::<
-							sc_state_db::Error<sp_database::error::DatabaseError>,
-						>,
-					)?;
+					let commit = self.canonicalize_state(&hash)?;
 					apply_state_commit(&mut transaction, commit);
 					meta_updates.push(MetaUpdate {
 						hash,
@@ -1653,9 +1711,10 @@ impl<Block: BlockT> Backend<Block> {
 					);
 				}
 
-				if let Some((mut start, end)) = block_gap {
-					if number == start {
-						start += One::one();
+				if let Some((start, mut end)) = block_gap {
+					// The gap is filled from the top, each block being imported after its child.
+					if number == end {
+						end -= One::one();
 						utils::insert_number_to_key_mapping(
 							&mut transaction,
 							columns::KEY_LOOKUP,
@@ -1677,7 +1736,8 @@ impl<Block: BlockT> Backend<Block> {
 						);
 					}
 				} else if number > best_num + One::one() &&
-					number > One::one() && self.blockchain.header(parent_hash)?.is_none()
+					number > One::one() &&
+					self.blockchain.header(parent_hash)?.is_none()
 				{
 					let gap = (best_num + One::one(), number - One::one());
 					transaction.set(columns::META, meta_keys::BLOCK_GAP, &gap.encode());
@@ -1768,11 +1828,7 @@ impl<Block: BlockT> Backend<Block> {
 		};
 
 		if requires_canonicalization && sc_client_api::Backend::have_state_at(self, f_hash, f_num) {
-			let commit = self.storage.state_db.canonicalize_block(&f_hash).map_err(
-				sp_blockchain::Error::from_state_db::<
-					sc_state_db::Error<sp_database::error::DatabaseError>,
-				>,
-			)?;
+			let commit = self.canonicalize_state(&f_hash)?;
 			apply_state_commit(transaction, commit);
 		}
 
@@ -2516,6 +2572,13 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			self.blockchain.unpin(hash);
 		}
 	}
+
+	fn checkpoint(
+		&self,
+		path: &Path,
+	) -> ClientResult<sc_client_api::backend::CheckpointInfo<Block>> {
+		checkpoint::create::<Block>(&*self.storage.db, path)
+	}
 }
 
 impl<Block: BlockT> sc_client_api::backend::LocalBackend<Block> for Backend<Block> {}
@@ -4398,4 +4461,167 @@ pub(crate) mod tests {
 		backend.unpin_block(fork_hash_3);
 		assert!(bc.body(fork_hash_3).unwrap().is_none());
 	}
+
+	#[test]
+	fn checkpoint_is_unsupported_in_memory() {
+		let backend = Backend::<Block>::new_test(10, 0);
+		let dir = tempfile::tempdir().unwrap();
+		assert!(backend.checkpoint(&dir.path().join("checkpoint")).is_err());
+		assert!(!dir.path().join("checkpoint").exists());
+	}
+
+	#[test]
+	fn checkpoint_and_restore_parity_db() {
+		let dir = tempfile::tempdir().unwrap();
+		let settings = |path: PathBuf| DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::ArchiveAll),
+			source: DatabaseSource::ParityDb { path },
+			blocks_pruning: BlocksPruning::KeepAll,
+		};
+
+		let backend = Backend::<Block>::new(settings(dir.path().join("live")), 0).unwrap();
+		let mut hash = insert_header(&backend, 0, Default::default(), None, Default::default());
+		let finalized = insert_header(&backend, 1, hash, None, Default::default());
+		backend.finalize_block(finalized, None).unwrap();
+		hash = finalized;
+		for number in 2..5 {
+			hash = insert_header(&backend, number, hash, None, Default::default());
+		}
+
+		let checkpoint = dir.path().join("checkpoint");
+		let info = backend.checkpoint(&checkpoint).unwrap();
+		assert_eq!(info.best_hash, hash);
+		assert_eq!(info.best_number, 4);
+		assert_eq!(info.finalized_hash, finalized);
+		assert_eq!(info.finalized_number, 1);
+		// Checkpoints are never overwritten.
+		assert!(backend.checkpoint(&checkpoint).is_err());
+
+		// The live database keeps working after the checkpoint was taken.
+		insert_header(&backend, 5, hash, None, Default::default());
+
+		let restored = DatabaseSource::ParityDb { path: dir.path().join("restored") };
+		assert_eq!(restore_checkpoint::<Block>(&checkpoint, &restored).unwrap(), info);
+		// Restoring over an existing database is refused.
+		assert!(restore_checkpoint::<Block>(&checkpoint, &restored).is_err());
+
+		let backend = Backend::<Block>::new(settings(dir.path().join("restored")), 0).unwrap();
+		let restored_info = backend.blockchain().info();
+		assert_eq!(restored_info.best_hash, hash);
+		assert_eq!(restored_info.finalized_hash, finalized);
+	}
+
+	#[cfg(feature = "rocksdb")]
+	#[test]
+	fn migrate_rocksdb_to_parity_db() {
+		use sp_core::storage::StorageChild;
+
+		fn insert_state_block(
+			backend: &Backend<Block>,
+			number: u64,
+			parent_hash: H256,
+			changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+		) -> H256 {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, parent_hash).unwrap();
+			let (root, overlay) = op.old_state.storage_root(
+				changes.iter().map(|(k, v)| (k.as_slice(), v.as_deref())),
+				StateVersion::V1,
+			);
+			op.update_db_storage(overlay).unwrap();
+			op.update_storage(changes, Vec::new()).unwrap();
+			let header = Header {
+				number,
+				parent_hash,
+				state_root: root,
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+			let hash = header.hash();
+			op.set_block_data(header, Some(vec![]), None, None, NewBlockState::Final)
+				.unwrap();
+			backend.commit_operation(op).unwrap();
+			hash
+		}
+
+		let dir = tempfile::tempdir().unwrap();
+		let from = DatabaseSource::RocksDb { path: dir.path().join("rocksdb"), cache_size: 16 };
+		let to = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+		let settings = |source: DatabaseSource| DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::blocks_pruning(1)),
+			source,
+			blocks_pruning: BlocksPruning::KeepFinalized,
+		};
+
+		let (genesis, block1) = {
+			let backend = Backend::<Block>::new(settings(from.clone()), 0).unwrap();
+			let mut op = backend.begin_operation().unwrap();
+			let child_info = ChildInfo::new_default(b"child");
+			let storage = Storage {
+				top: vec![(vec![1, 3, 5], vec![2, 4, 6]), (vec![1, 2, 3], vec![9, 9, 9])]
+					.into_iter()
+					.collect(),
+				children_default: vec![(
+					child_info.storage_key().to_vec(),
+					StorageChild {
+						data: vec![(vec![7], vec![8]), (vec![7, 7], vec![9])].into_iter().collect(),
+						child_info,
+					},
+				)]
+				.into_iter()
+				.collect(),
+			};
+			let header = Header {
+				number: 0,
+				parent_hash: Default::default(),
+				state_root: op.reset_storage(storage, StateVersion::V1).unwrap(),
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+			let genesis = header.hash();
+			op.set_block_data(header, Some(vec![]), None, None, NewBlockState::Final)
+				.unwrap();
+			backend.commit_operation(op).unwrap();
+
+			let block1 = insert_state_block(
+				&backend,
+				1,
+				genesis,
+				vec![(vec![1, 3, 5], None), (vec![5, 5, 5], Some(vec![4, 5, 6]))],
+			);
+			(genesis, block1)
+		};
+
+		let summary = migrate_database::<Block>(&from, &to).unwrap();
+		assert_eq!(summary.verified_block, Some((block1, 1)));
+		// Two top keys and the child trie root, next to the two keys of the child trie.
+		assert_eq!(summary.verified_keys, 5);
+		assert_eq!(summary.entries[columns::HEADER as usize], 2);
+		// The new database is never overwritten.
+		assert!(migrate_database::<Block>(&from, &to).is_err());
+
+		let backend = Backend::<Block>::new(settings(to), 0).unwrap();
+		let info = backend.blockchain().info();
+		assert_eq!(info.genesis_hash, genesis);
+		assert_eq!(info.finalized_hash, block1);
+		let state = backend.state_at(block1).unwrap();
+		assert_eq!(state.storage(&[1, 3, 5]).unwrap(), None);
+		assert_eq!(state.storage(&[5, 5, 5]).unwrap(), Some(vec![4, 5, 6]));
+		assert_eq!(
+			state.child_storage(&ChildInfo::new_default(b"child"), &[7, 7]).unwrap(),
+			Some(vec![9])
+		);
+
+		// The migrated pruning journal keeps working.
+		let block2 = insert_state_block(&backend, 2, block1, vec![(vec![1, 2, 3], Some(vec![1]))]);
+		let state = backend.state_at(block2).unwrap();
+		assert_eq!(state.storage(&[1, 2, 3]).unwrap(), Some(vec![1]));
+		assert_eq!(state.storage(&[5, 5, 5]).unwrap(), Some(vec![4, 5, 6]));
+		assert_eq!(
+			state.child_storage(&ChildInfo::new_default(b"child"), &[7]).unwrap(),
+			Some(vec![8])
+		);
+	}
 }
@@ -0,0 +1,314 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Migration of RocksDB databases to databases with reference counting, such as ParityDb.
+//!
+//! RocksDB stores trie nodes under their key prefixed by their position in the trie and leaves
+//! pruning to the state database, while ParityDb counts the references to every node and indexed
+//! transaction. Every column is streamed through [`Transaction`]s into the new database, with
+//! node keys stripped of their prefix and reference counts rebuilt from the copied entries. The
+//! journals of the state database are converted accordingly.
+//!
+//! The migrated database is checked by reading back every copied value and by traversing the
+//! finalized state, including child tries, from its root.
+
+use std::{fs, io};
+
+use kvdb::KeyValueDB;
+use sc_client_api::backend::Backend as _;
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_core::storage::{well_known_keys, ChildInfo};
+use sp_database::Transaction;
+use sp_runtime::traits::{Block as BlockT, Hash, HashFor, NumberFor};
+use sp_state_machine::{backend::Backend as StateBackend, IterArgs};
+
+use crate::{
+	columns,
+	utils::{database_dir, open_database, DatabaseType, NUM_COLUMNS},
+	Backend, BlocksPruning, Database, DatabaseSettings, DatabaseSource, DbHash, DB_HASH_LEN,
+};
+
+/// Number of changes written to the new database per transaction.
+const BATCH_SIZE: usize = 10_000;
+
+/// Outcome of [`migrate_database`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationSummary<Block: BlockT> {
+	/// Number of entries copied from each column, indexed by column.
+	pub entries: Vec<u64>,
+	/// The finalized block whose state was traversed in the new database, if any.
+	pub verified_block: Option<(Block::Hash, NumberFor<Block>)>,
+	/// Number of keys found in the traversed state, including the keys of child tries.
+	pub verified_keys: u64,
+}
+
+/// Migrate the RocksDB database at `from` to the new database at `to`.
+///
+/// The new database must count references, as ParityDb does, and must not exist yet. The
+/// database at `from` is left untouched, apart from being upgraded to the latest version.
+pub fn migrate_database<Block: BlockT>(
+	from: &DatabaseSource,
+	to: &DatabaseSource,
+) -> ClientResult<MigrationSummary<Block>> {
+	if let Some(path) = database_dir(to) {
+		let occupied =
+			fs::read_dir(path).map(|mut entries| entries.next().is_some()).unwrap_or(false);
+		if occupied {
+			return Err(ClientError::Backend(format!(
+				"Refusing to migrate into the existing database at {:?}",
+				path
+			)))
+		}
+	}
+
+	let source = open_source::<Block>(from)?;
+	let target = open_database::<Block>(to, DatabaseType::Full, true)?;
+	if !target.supports_ref_counting() {
+		return Err(ClientError::Backend(format!(
+			"Can't migrate to a {} database, which doesn't count references",
+			to
+		)))
+	}
+
+	let entries = copy_columns::<Block>(&*source, &*target)?;
+	// Close the new database before it is opened again by the backend.
+	drop(target);
+
+	let (verified_block, verified_keys) = verify_state::<Block>(to)?;
+	Ok(MigrationSummary { entries, verified_block, verified_keys })
+}
+
+#[cfg(feature = "rocksdb")]
+fn open_source<Block: BlockT>(from: &DatabaseSource) -> ClientResult<Box<dyn KeyValueDB>> {
+	match from {
+		DatabaseSource::RocksDb { path, cache_size } =>
+			Ok(Box::new(crate::utils::open_raw_kvdb_rocksdb::<Block>(
+				path,
+				DatabaseType::Full,
+				false,
+				*cache_size,
+			)?)),
+		from => Err(ClientError::Backend(format!("Can't migrate from a {} database", from))),
+	}
+}
+
+#[cfg(not(feature = "rocksdb"))]
+fn open_source<Block: BlockT>(_from: &DatabaseSource) -> ClientResult<Box<dyn KeyValueDB>> {
+	Err(ClientError::Backend("`rocksdb` feature not enabled, database can not be migrated".into()))
+}
+
+fn db_err(e: io::Error) -> ClientError {
+	ClientError::Backend(format!("Failed to read the source database: {}", e))
+}
+
+/// Key of a trie node in a database with reference counting, without the prefix of `key`.
+fn node_key(key: &[u8]) -> ClientResult<&[u8]> {
+	key.len()
+		.checked_sub(DB_HASH_LEN)
+		.map(|start| &key[start..])
+		.ok_or_else(|| ClientError::Backend(format!("Invalid trie node key {:?}", key)))
+}
+
+/// Key of the counter the RocksDB adapter keeps next to `key`, see `sp_database::as_database`.
+fn counter_key(key: &[u8]) -> Vec<u8> {
+	let mut counter_key = key.to_vec();
+	counter_key.push(0);
+	counter_key
+}
+
+/// Writes changes to the new database in batches.
+struct Writer<'a> {
+	db: &'a dyn Database<DbHash>,
+	transaction: Transaction<DbHash>,
+}
+
+impl<'a> Writer<'a> {
+	fn new(db: &'a dyn Database<DbHash>) -> Self {
+		Writer { db, transaction: Transaction::new() }
+	}
+
+	fn set(&mut self, col: u32, key: &[u8], value: Vec<u8>) -> ClientResult<()> {
+		self.transaction.set_from_vec(col, key, value);
+		self.maybe_flush()
+	}
+
+	fn store(&mut self, col: u32, key: DbHash, value: Vec<u8>) -> ClientResult<()> {
+		self.transaction.store(col, key, value);
+		self.maybe_flush()
+	}
+
+	fn maybe_flush(&mut self) -> ClientResult<()> {
+		if self.transaction.0.len() >= BATCH_SIZE {
+			self.flush()?;
+		}
+		Ok(())
+	}
+
+	fn flush(&mut self) -> ClientResult<()> {
+		self.db.commit(std::mem::take(&mut self.transaction)).map_err(Into::into)
+	}
+}
+
+/// Copy and check every column of `source` into `target`, returning the number of entries copied
+/// from each column.
+fn copy_columns<Block: BlockT>(
+	source: &dyn KeyValueDB,
+	target: &dyn Database<DbHash>,
+) -> ClientResult<Vec<u64>> {
+	let mut entries = vec![0; NUM_COLUMNS as usize];
+	let mut writer = Writer::new(target);
+	let mut state_meta = Vec::new();
+
+	for col in 0..NUM_COLUMNS {
+		for entry in source.iter(col) {
+			let (key, value) = entry.map_err(db_err)?;
+			match col {
+				columns::STATE => {
+					let node_key = node_key(&key)?;
+					if HashFor::<Block>::hash(&value).as_ref() != node_key {
+						return Err(ClientError::Backend(format!(
+							"Trie node {:?} doesn't match its hash",
+							key
+						)))
+					}
+					// Every copy of a node under a different prefix counts as one reference.
+					writer.set(col, node_key, value)?;
+				},
+				columns::TRANSACTION => {
+					if is_counter(source, col, &key)? {
+						continue
+					}
+					if key.len() != DB_HASH_LEN {
+						return Err(ClientError::Backend(format!(
+							"Invalid indexed transaction key {:?}",
+							key
+						)))
+					}
+					let hash = DbHash::from_slice(&key);
+					for _ in 0..read_counter(source, col, &key)? {
+						writer.store(col, hash, value.clone())?;
+					}
+				},
+				columns::STATE_META => state_meta.push((key.to_vec(), value)),
+				_ => writer.set(col, &key, value)?,
+			}
+			entries[col as usize] += 1;
+		}
+	}
+
+	let state_meta = sc_state_db::convert_meta_for_ref_counting::<Block::Hash, Vec<u8>>(
+		state_meta,
+		|mut key| key.split_off(key.len().saturating_sub(DB_HASH_LEN)),
+	)
+	.map_err(|e| ClientError::Backend(format!("Failed to convert the state journals: {}", e)))?;
+	for (key, value) in state_meta.iter() {
+		writer.set(columns::STATE_META, key, value.clone())?;
+	}
+	writer.flush()?;
+
+	// Read everything back.
+	let mismatch = |col, key: &[u8]| {
+		ClientError::Backend(format!("Value of {:?} in column {} was not migrated", key, col))
+	};
+	for col in 0..NUM_COLUMNS {
+		if col == columns::STATE_META {
+			continue
+		}
+		for entry in source.iter(col) {
+			let (key, value) = entry.map_err(db_err)?;
+			let key = match col {
+				columns::STATE => node_key(&key)?,
+				columns::TRANSACTION if is_counter(source, col, &key)? => continue,
+				_ => &key[..],
+			};
+			if target.get(col, key).as_ref() != Some(&value) {
+				return Err(mismatch(col, key))
+			}
+		}
+	}
+	for (key, value) in state_meta {
+		if target.get(columns::STATE_META, &key) != Some(value) {
+			return Err(mismatch(columns::STATE_META, &key))
+		}
+	}
+
+	Ok(entries)
+}
+
+/// Whether `key` is the counter of another entry of the reference counted column `col`.
+fn is_counter(source: &dyn KeyValueDB, col: u32, key: &[u8]) -> ClientResult<bool> {
+	Ok(key.len() == DB_HASH_LEN + 1 &&
+		key.last() == Some(&0) &&
+		source.has_key(col, &key[..DB_HASH_LEN]).map_err(db_err)?)
+}
+
+/// Number of references to `key` in the reference counted column `col`.
+fn read_counter(source: &dyn KeyValueDB, col: u32, key: &[u8]) -> ClientResult<u32> {
+	match source.get(col, &counter_key(key)).map_err(db_err)? {
+		Some(counter) => counter
+			.try_into()
+			.map(u32::from_le_bytes)
+			.map_err(|counter| ClientError::Backend(format!("Invalid counter {:?}", counter))),
+		None => Ok(1),
+	}
+}
+
+/// Traverse the finalized state of the database at `source`, returning the finalized block and
+/// the number of keys in its state.
+fn verify_state<Block: BlockT>(
+	source: &DatabaseSource,
+) -> ClientResult<(Option<(Block::Hash, NumberFor<Block>)>, u64)> {
+	let backend = Backend::<Block>::new(
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: None,
+			source: source.clone(),
+			blocks_pruning: BlocksPruning::KeepAll,
+		},
+		0,
+	)?;
+
+	let Some((hash, number)) = backend.blockchain().info().finalized_state else {
+		return Ok((None, 0))
+	};
+	let state = backend.state_at(hash)?;
+	let missing =
+		|e| ClientError::Backend(format!("State of #{} ({:?}) is incomplete: {}", number, hash, e));
+
+	let mut keys = 0;
+	let mut child_tries = Vec::new();
+	for pair in state.pairs(IterArgs::default()).map_err(missing)? {
+		let (key, _) = pair.map_err(missing)?;
+		if let Some(storage_key) =
+			key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+		{
+			child_tries.push(ChildInfo::new_default(storage_key));
+		}
+		keys += 1;
+	}
+	for child_info in child_tries {
+		let args = IterArgs { child_info: Some(child_info), ..Default::default() };
+		for pair in state.pairs(args).map_err(missing)? {
+			pair.map_err(missing)?;
+			keys += 1;
+		}
+	}
+
+	Ok((Some((hash, number)), keys))
+}
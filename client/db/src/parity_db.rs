@@ -23,7 +23,14 @@ use crate::{
 /// A `Database` adapter for parity-db.
 use sp_database::{error::DatabaseError, Change, ColumnId, Database, Transaction};
 
-struct DbAdapter(parity_db::Db);
+use parking_lot::{MappedRwLockReadGuard, RwLock, RwLockReadGuard};
+use std::{fs, io, path::Path};
+
+struct DbAdapter {
+	// Only taken out while a checkpoint is written, which holds the lock for writing.
+	db: RwLock<Option<parity_db::Db>>,
+	options: parity_db::Options,
+}
 
 fn handle_err<T>(result: parity_db::Result<T>) -> T {
 	match result {
@@ -84,17 +91,38 @@ pub fn open<H: Clone + AsRef<[u8]>>(
 		parity_db::Db::open(&config)?
 	};
 
-	Ok(std::sync::Arc::new(DbAdapter(db)))
+	Ok(std::sync::Arc::new(DbAdapter { db: RwLock::new(Some(db)), options: config }))
+}
+
+/// Copy the files of the closed database at `from` into `to`.
+fn copy_files(from: &Path, to: &Path) -> io::Result<()> {
+	for entry in fs::read_dir(from)? {
+		let path = entry?.path();
+		// The lock file belongs to the running instance and must not be carried over.
+		if path.is_file() && path.file_name() != Some(std::ffi::OsStr::new("lock")) {
+			fs::copy(&path, to.join(path.file_name().expect("files have a name; qed")))?;
+		}
+	}
+	Ok(())
 }
 
 fn ref_counted_column(col: u32) -> bool {
 	col == columns::TRANSACTION || col == columns::STATE
 }
 
+impl DbAdapter {
+	fn db(&self) -> MappedRwLockReadGuard<parity_db::Db> {
+		RwLockReadGuard::map(self.db.read(), |db| {
+			db.as_ref().expect("the database is put back before a checkpoint returns; qed")
+		})
+	}
+}
+
 impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 	fn commit(&self, transaction: Transaction<H>) -> Result<(), DatabaseError> {
 		let mut not_ref_counted_column = Vec::new();
-		let result = self.0.commit(transaction.0.into_iter().filter_map(|change| {
+		let db = self.db();
+		let result = db.commit(transaction.0.into_iter().filter_map(|change| {
 			Some(match change {
 				Change::Set(col, key, value) => (col as u8, key, Some(value)),
 				Change::Remove(col, key) => (col as u8, key, None),
@@ -110,7 +138,7 @@ impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 				Change::Reference(col, key) => {
 					if ref_counted_column(col) {
 						// FIXME accessing value is not strictly needed, optimize this in parity-db.
-						let value = <Self as Database<H>>::get(self, col, key.as_ref());
+						let value = handle_err(db.get(col as u8, key.as_ref()));
 						(col as u8, key.as_ref().to_vec(), value)
 					} else {
 						if !not_ref_counted_column.contains(&col) {
@@ -142,15 +170,15 @@ impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 	}
 
 	fn get(&self, col: ColumnId, key: &[u8]) -> Option<Vec<u8>> {
-		handle_err(self.0.get(col as u8, key))
+		handle_err(self.db().get(col as u8, key))
 	}
 
 	fn contains(&self, col: ColumnId, key: &[u8]) -> bool {
-		handle_err(self.0.get_size(col as u8, key)).is_some()
+		handle_err(self.db().get_size(col as u8, key)).is_some()
 	}
 
 	fn value_size(&self, col: ColumnId, key: &[u8]) -> Option<usize> {
-		handle_err(self.0.get_size(col as u8, key)).map(|s| s as usize)
+		handle_err(self.db().get_size(col as u8, key)).map(|s| s as usize)
 	}
 
 	fn supports_ref_counting(&self) -> bool {
@@ -160,4 +188,24 @@ impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 	fn sanitize_key(&self, key: &mut Vec<u8>) {
 		let _prefix = key.drain(0..key.len() - crate::DB_HASH_LEN);
 	}
+
+	// parity-db has no way to iterate over the original keys of hashed columns, so the column
+	// files are copied instead. The background workers keep writing the commits from the
+	// write-ahead logs into the tables, so the database is closed, which stops them, for the
+	// files to be copied at rest. It is reopened afterwards, and reads and commits wait until
+	// then.
+	fn checkpoint(&self, path: &Path) -> Result<(), DatabaseError> {
+		if path.exists() {
+			return Err(DatabaseError(Box::new(io::Error::new(
+				io::ErrorKind::AlreadyExists,
+				format!("Checkpoint path {:?} already exists", path),
+			))))
+		}
+
+		let mut db = self.db.write();
+		drop(db.take());
+		let copied = fs::create_dir_all(path).and_then(|_| copy_files(&self.options.path, path));
+		*db = Some(handle_err(parity_db::Db::open(&self.options)));
+		copied.map_err(|e| DatabaseError(Box::new(e)))
+	}
 }
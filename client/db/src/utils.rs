@@ -20,9 +20,17 @@
 //! Db-based backend utility structures and functions, used by both
 //! full and light storages.
 
-use std::{fmt, fs, io, path::Path, sync::Arc};
+use std::{
+	fmt, fs, io,
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
 
-use log::{debug, info};
+use log::{debug, info, warn};
 
 use crate::{Database, DatabaseSource, DbHash};
 use codec::Decode;
@@ -193,6 +201,7 @@ fn open_database_at<Block: BlockT>(
 ) -> OpenDbResult {
 	let db: Arc<dyn Database<DbHash>> = match &db_source {
 		DatabaseSource::ParityDb { path } => open_parity_db::<Block>(path, db_type, create)?,
+		DatabaseSource::FileDb { path } => open_file_db::<Block>(path, db_type, create)?,
 		#[cfg(feature = "rocksdb")]
 		DatabaseSource::RocksDb { path, cache_size } =>
 			open_kvdb_rocksdb::<Block>(path, db_type, create, *cache_size)?,
@@ -294,6 +303,13 @@ fn open_parity_db<Block: BlockT>(path: &Path, db_type: DatabaseType, create: boo
 	}
 }
 
+fn open_file_db<Block: BlockT>(path: &Path, db_type: DatabaseType, create: bool) -> OpenDbResult {
+	crate::file_db::open(path, db_type, create).map_err(|e| match e.kind() {
+		io::ErrorKind::NotFound => OpenDbError::DoesNotExist,
+		_ => OpenDbError::Internal(e.to_string()),
+	})
+}
+
 #[cfg(any(feature = "rocksdb", test))]
 fn open_kvdb_rocksdb<Block: BlockT>(
 	path: &Path,
@@ -301,6 +317,19 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	create: bool,
 	cache_size: usize,
 ) -> OpenDbResult {
+	let db = open_raw_kvdb_rocksdb::<Block>(path, db_type, create, cache_size)?;
+	Ok(sp_database::as_checkpointable_database(db, checkpoint_kvdb_rocksdb))
+}
+
+/// Open the RocksDB database at `path` without wrapping it, upgrading it to the latest version
+/// first.
+#[cfg(any(feature = "rocksdb", test))]
+pub(crate) fn open_raw_kvdb_rocksdb<Block: BlockT>(
+	path: &Path,
+	db_type: DatabaseType,
+	create: bool,
+	cache_size: usize,
+) -> Result<kvdb_rocksdb::Database, OpenDbError> {
 	// first upgrade database to required version
 	match crate::upgrade::upgrade_db::<Block>(path, db_type) {
 		// in case of missing version file, assume that database simply does not exist at given
@@ -341,7 +370,43 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
 	// write database version only after the database is succesfully opened
 	crate::upgrade::update_version(path)?;
-	Ok(sp_database::as_database(db))
+	Ok(db)
+}
+
+/// Copy every column of `db` into a new RocksDB database at `path`.
+///
+/// RocksDB iterators read from an implicit snapshot taken when they are created, so creating one
+/// per column while commits are paused gives a consistent view of the whole database. Commits
+/// resume before the entries are copied.
+#[cfg(any(feature = "rocksdb", test))]
+fn checkpoint_kvdb_rocksdb(
+	db: &kvdb_rocksdb::Database,
+	path: &Path,
+	paused: sp_database::CommitsPaused,
+) -> io::Result<()> {
+	/// Number of entries written to the checkpoint per transaction.
+	const BATCH_SIZE: usize = 10_000;
+
+	let columns = (0..NUM_COLUMNS).map(|col| (col, db.iter(col))).collect::<Vec<_>>();
+	drop(paused);
+
+	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
+	db_config.create_if_missing = true;
+	let checkpoint = kvdb_rocksdb::Database::open(&db_config, path)?;
+
+	for (col, entries) in columns {
+		let mut transaction = kvdb::DBTransaction::new();
+		for entry in entries {
+			let (key, value) = entry?;
+			transaction.put_vec(col, &key, value);
+			if transaction.ops.len() >= BATCH_SIZE {
+				checkpoint.write(std::mem::take(&mut transaction))?;
+			}
+		}
+		checkpoint.write(transaction)?;
+	}
+
+	crate::upgrade::update_version(path)
 }
 
 #[cfg(not(any(feature = "rocksdb", test)))]
@@ -555,6 +620,63 @@ impl DatabaseType {
 	}
 }
 
+/// Directory the database of `source` is stored in on disk, if any.
+pub(crate) fn database_dir(source: &DatabaseSource) -> Option<&Path> {
+	match source {
+		DatabaseSource::Auto { paritydb_path, rocksdb_path, .. } =>
+			Some(if rocksdb_path.exists() { rocksdb_path } else { paritydb_path }),
+		_ => source.path(),
+	}
+}
+
+/// Total size in bytes of the files within `path`, recursively.
+fn dir_size(path: &Path) -> io::Result<u64> {
+	let mut size = 0;
+	for entry in fs::read_dir(path)? {
+		let entry = entry?;
+		let metadata = entry.metadata()?;
+		size += if metadata.is_dir() { dir_size(&entry.path())? } else { metadata.len() };
+	}
+	Ok(size)
+}
+
+/// Size of a database on disk, sampled by a background thread.
+///
+/// Walking the database directory is costly, so it is kept off the import path. The thread stops
+/// once this is dropped.
+pub(crate) struct DatabaseSize {
+	// `u64::MAX` while unknown.
+	size: Arc<AtomicU64>,
+}
+
+impl DatabaseSize {
+	/// Start sampling the size of the database in `path` every `interval`.
+	pub(crate) fn spawn(path: PathBuf, interval: Duration) -> Self {
+		let size = Arc::new(AtomicU64::new(u64::MAX));
+		let sampled = Arc::downgrade(&size);
+		let spawned = std::thread::Builder::new().name("db-size".into()).spawn(move || {
+			while let Some(size) = sampled.upgrade() {
+				match dir_size(&path) {
+					Ok(measured) => size.store(measured.min(u64::MAX - 1), Ordering::Relaxed),
+					Err(e) =>
+						warn!("Failed to measure the size of the database at {:?}: {}", path, e),
+				}
+				drop(size);
+				std::thread::sleep(interval);
+			}
+		});
+		if let Err(e) = spawned {
+			warn!("Failed to spawn the thread measuring the size of the database: {}", e);
+		}
+		DatabaseSize { size }
+	}
+
+	/// The last sampled size in bytes, if any.
+	pub(crate) fn get(&self) -> Option<u64> {
+		Some(self.size.load(Ordering::Relaxed)).filter(|size| *size != u64::MAX)
+	}
+}
+
 pub(crate) struct JoinInput<'a, 'b>(&'a [u8], &'b [u8]);
 
 pub(crate) fn join_input<'a, 'b>(i1: &'a [u8], i2: &'b [u8]) -> JoinInput<'a, 'b> {
@@ -660,6 +782,58 @@ mod tests {
 		};
 	}
 
+	#[test]
+	fn database_conformance() {
+		use crate::{columns, DbHash};
+		use sp_database::conformance::{self, TestColumns};
+
+		const TEST_COLUMNS: TestColumns = TestColumns {
+			plain: columns::META,
+			other: columns::HEADER,
+			ref_counted: columns::STATE,
+		};
+
+		let db = sp_database::as_database::<_, DbHash>(kvdb_memorydb::create(NUM_COLUMNS));
+		conformance::check_all(&*db, TEST_COLUMNS);
+
+		let dir = tempfile::tempdir().unwrap();
+		let paritydb_path = dir.path().join("paritydb");
+		let rocksdb_path = dir.path().join("rocksdb");
+		conformance::check_all(
+			&*crate::parity_db::open::<DbHash>(&paritydb_path, DatabaseType::Full, true, false)
+				.unwrap(),
+			TEST_COLUMNS,
+		);
+		conformance::check_persistence(
+			|| {
+				crate::parity_db::open(
+					&dir.path().join("paritydb_persistence"),
+					DatabaseType::Full,
+					true,
+					false,
+				)
+				.unwrap()
+			},
+			TEST_COLUMNS,
+		);
+		conformance::check_all(
+			&*open_kvdb_rocksdb::<Block>(&rocksdb_path, DatabaseType::Full, true, 128).unwrap(),
+			TEST_COLUMNS,
+		);
+		conformance::check_persistence(
+			|| {
+				open_kvdb_rocksdb::<Block>(
+					&dir.path().join("rocksdb_persistence"),
+					DatabaseType::Full,
+					true,
+					128,
+				)
+				.unwrap()
+			},
+			TEST_COLUMNS,
+		);
+	}
+
 	#[test]
 	fn database_type_as_str_works() {
 		assert_eq!(DatabaseType::Full.as_str(), "full");
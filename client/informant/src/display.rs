@@ -24,7 +24,7 @@ use sc_client_api::ClientInfo;
 use sc_network::NetworkStatus;
 use sc_network_common::sync::{
 	warp::{WarpSyncPhase, WarpSyncProgress},
-	SyncState, SyncStatus,
+	BlockHistoryProgress, SyncState, SyncStatus,
 };
 use sp_runtime::traits::{Block as BlockT, CheckedDiv, NumberFor, Saturating, Zero};
 use std::{fmt, time::Instant};
@@ -97,53 +97,52 @@ impl<B: BlockT> InformantDisplay<B> {
 			(diff_bytes_inbound, diff_bytes_outbound)
 		};
 
-		let (level, status, target) =
-			match (sync_status.state, sync_status.state_sync, sync_status.warp_sync) {
-				// Do not set status to "Block history" when we are doing a major sync.
-				//
-				// A node could for example have been warp synced to the tip of the chain and
-				// shutdown. At the next start we still need to download the block history, but
-				// first will sync to the tip of the chain.
+		let (level, status, target) = match (
+			sync_status.state,
+			sync_status.state_sync,
+			sync_status.warp_sync,
+			sync_status.block_history,
+		) {
+			// Do not set status to "Block history" when we are doing a major sync.
+			//
+			// A node could for example have been warp synced to the tip of the chain and
+			// shutdown. At the next start we still need to download the block history, but
+			// first will sync to the tip of the chain.
+			(sync_status, _, _, Some(BlockHistoryProgress { current, target }))
+				if !sync_status.is_major_syncing() =>
+				("⏩", "Block history".into(), format!(", #{current}, target=#{target}")),
+			(_, _, Some(WarpSyncProgress { phase: WarpSyncPhase::AwaitingTargetBlock, .. }), _) =>
+				("⏩", "Waiting for pending target block".into(), "".into()),
+			// Handle all phases besides the two phases we already handle above.
+			(_, _, Some(warp), _)
+				if !matches!(
+					warp.phase,
+					WarpSyncPhase::AwaitingTargetBlock | WarpSyncPhase::DownloadingBlocks(_)
+				) =>
 				(
-					sync_status,
-					_,
-					Some(WarpSyncProgress { phase: WarpSyncPhase::DownloadingBlocks(n), .. }),
-				) if !sync_status.is_major_syncing() => ("⏩", "Block history".into(), format!(", #{}", n)),
-				(
-					_,
-					_,
-					Some(WarpSyncProgress { phase: WarpSyncPhase::AwaitingTargetBlock, .. }),
-				) => ("⏩", "Waiting for pending target block".into(), "".into()),
-				// Handle all phases besides the two phases we already handle above.
-				(_, _, Some(warp))
-					if !matches!(
-						warp.phase,
-						WarpSyncPhase::AwaitingTargetBlock | WarpSyncPhase::DownloadingBlocks(_)
-					) =>
-					(
-						"⏩",
-						"Warping".into(),
-						format!(
-							", {}, {:.2} Mib",
-							warp.phase,
-							(warp.total_bytes as f32) / (1024f32 * 1024f32)
-						),
-					),
-				(_, Some(state), _) => (
-					"⚙️ ",
-					"Downloading state".into(),
+					"⏩",
+					"Warping".into(),
 					format!(
-						", {}%, {:.2} Mib",
-						state.percentage,
-						(state.size as f32) / (1024f32 * 1024f32)
+						", {}, {:.2} Mib",
+						warp.phase,
+						(warp.total_bytes as f32) / (1024f32 * 1024f32)
 					),
 				),
-				(SyncState::Idle, _, _) => ("💤", "Idle".into(), "".into()),
-				(SyncState::Downloading { target }, _, _) =>
-					("⚙️ ", format!("Syncing{}", speed), format!(", target=#{target}")),
-				(SyncState::Importing { target }, _, _) =>
-					("⚙️ ", format!("Preparing{}", speed), format!(", target=#{target}")),
-			};
+			(_, Some(state), _, _) => (
+				"⚙️ ",
+				"Downloading state".into(),
+				format!(
+					", {}%, {:.2} Mib",
+					state.percentage,
+					(state.size as f32) / (1024f32 * 1024f32)
+				),
+			),
+			(SyncState::Idle, _, _, _) => ("💤", "Idle".into(), "".into()),
+			(SyncState::Downloading { target }, _, _, _) =>
+				("⚙️ ", format!("Syncing{}", speed), format!(", target=#{target}")),
+			(SyncState::Importing { target }, _, _, _) =>
+				("⚙️ ", format!("Preparing{}", speed), format!(", target=#{target}")),
+		};
 
 		if self.format.enable_color {
 			info!(
@@ -0,0 +1,243 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key file format, optionally encrypting the key phrase at rest.
+//!
+//! Encrypted key files use XChaCha20-Poly1305, under a key derived from the keystore's
+//! encryption password with scrypt.
+
+use chacha20poly1305::{
+	aead::{Aead, NewAead},
+	Key, XChaCha20Poly1305, XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{ExposeSecret, SecretString};
+use zeroize::Zeroizing;
+
+use crate::{Error, Result};
+
+/// The version of the encrypted key file format.
+const VERSION: u32 = 1;
+
+/// The log2 of the scrypt CPU/memory cost used for newly encrypted key files.
+#[cfg(not(test))]
+const SCRYPT_LOG_N: u8 = 15;
+/// Lowered for tests, as the parameters used are stored within each key file.
+#[cfg(test)]
+const SCRYPT_LOG_N: u8 = 10;
+/// The scrypt block size used for newly encrypted key files.
+const SCRYPT_R: u32 = 8;
+/// The scrypt parallelization used for newly encrypted key files.
+const SCRYPT_P: u32 = 1;
+
+/// The highest log2 of the scrypt CPU/memory cost accepted from a key file.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+/// The highest scrypt block size accepted from a key file.
+const MAX_SCRYPT_R: u32 = 32;
+/// The highest scrypt parallelization accepted from a key file.
+const MAX_SCRYPT_P: u32 = 16;
+
+/// The length of the salt for the key derivation.
+const SALT_LEN: usize = 32;
+/// The length of an XChaCha20-Poly1305 nonce.
+const NONCE_LEN: usize = 24;
+/// The length of an XChaCha20-Poly1305 key.
+const KEY_LEN: usize = 32;
+
+/// The contents of a key file.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum KeyFile {
+	/// The key phrase, stored as is.
+	Plain(String),
+	/// The key phrase, encrypted with the keystore's encryption password.
+	Encrypted(EncryptedPhrase),
+}
+
+/// A key phrase encrypted with a key derived from a password.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EncryptedPhrase {
+	/// The version of the format.
+	version: u32,
+	/// The parameters used to derive the key from the password.
+	kdf: ScryptParams,
+	/// The hex-encoded nonce.
+	nonce: String,
+	/// The hex-encoded key phrase, encrypted and authenticated.
+	ciphertext: String,
+}
+
+/// The scrypt parameters a key was derived with.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScryptParams {
+	/// The log2 of the CPU/memory cost.
+	log_n: u8,
+	/// The block size.
+	r: u32,
+	/// The parallelization.
+	p: u32,
+	/// The hex-encoded salt.
+	salt: String,
+}
+
+impl ScryptParams {
+	/// Derive the encryption key for `password`.
+	///
+	/// The parameters are read from the key file, so they are bounded to keep a corrupted or
+	/// crafted key file from making the derivation take an unbounded amount of memory and time.
+	fn derive_key(&self, password: &SecretString) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+		if self.log_n > MAX_SCRYPT_LOG_N || self.r > MAX_SCRYPT_R || self.p > MAX_SCRYPT_P {
+			return Err(Error::InvalidKeyFile)
+		}
+		let salt = array_bytes::hex2bytes(&self.salt).map_err(|_| Error::InvalidKeyFile)?;
+		let params = scrypt::Params::new(self.log_n, self.r, self.p, KEY_LEN)
+			.map_err(|_| Error::InvalidKeyFile)?;
+
+		let mut key = Zeroizing::new([0; KEY_LEN]);
+		scrypt::scrypt(password.expose_secret().as_bytes(), &salt, &params, &mut key[..])
+			.map_err(|_| Error::InvalidKeyFile)?;
+		Ok(key)
+	}
+}
+
+impl KeyFile {
+	/// Create the key file for `phrase`, encrypting it if an encryption password is given.
+	pub(crate) fn new(phrase: &str, encryption_password: Option<&SecretString>) -> Result<Self> {
+		let Some(password) = encryption_password else { return Ok(Self::Plain(phrase.into())) };
+
+		let mut salt = [0; SALT_LEN];
+		OsRng.fill_bytes(&mut salt);
+		let mut nonce = [0; NONCE_LEN];
+		OsRng.fill_bytes(&mut nonce);
+
+		let kdf = ScryptParams {
+			log_n: SCRYPT_LOG_N,
+			r: SCRYPT_R,
+			p: SCRYPT_P,
+			salt: array_bytes::bytes2hex("0x", salt),
+		};
+		let key = kdf.derive_key(password)?;
+		let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key[..]))
+			.encrypt(XNonce::from_slice(&nonce), phrase.as_bytes())
+			.map_err(|_| Error::InvalidKeyFile)?;
+
+		Ok(Self::Encrypted(EncryptedPhrase {
+			version: VERSION,
+			kdf,
+			nonce: array_bytes::bytes2hex("0x", nonce),
+			ciphertext: array_bytes::bytes2hex("0x", ciphertext),
+		}))
+	}
+
+	/// Returns `true` if the key phrase is encrypted.
+	pub(crate) fn is_encrypted(&self) -> bool {
+		matches!(self, Self::Encrypted(_))
+	}
+
+	/// Get the key phrase, decrypting it with `encryption_password` if needed.
+	pub(crate) fn into_phrase(
+		self,
+		encryption_password: Option<&SecretString>,
+	) -> Result<Zeroizing<String>> {
+		let encrypted = match self {
+			Self::Plain(phrase) => return Ok(Zeroizing::new(phrase)),
+			Self::Encrypted(encrypted) => encrypted,
+		};
+		if encrypted.version != VERSION {
+			return Err(Error::InvalidKeyFile)
+		}
+		let password = encryption_password.ok_or(Error::EncryptionPasswordRequired)?;
+
+		let nonce = array_bytes::hex2bytes(&encrypted.nonce).map_err(|_| Error::InvalidKeyFile)?;
+		if nonce.len() != NONCE_LEN {
+			return Err(Error::InvalidKeyFile)
+		}
+		let ciphertext =
+			array_bytes::hex2bytes(&encrypted.ciphertext).map_err(|_| Error::InvalidKeyFile)?;
+
+		let key = encrypted.kdf.derive_key(password)?;
+		let phrase = Zeroizing::new(
+			XChaCha20Poly1305::new(Key::from_slice(&key[..]))
+				.decrypt(XNonce::from_slice(&nonce), &ciphertext[..])
+				.map_err(|_| Error::InvalidEncryptionPassword)?,
+		);
+
+		let phrase = std::str::from_utf8(&phrase).map_err(|_| Error::InvalidKeyFile)?;
+		Ok(Zeroizing::new(phrase.to_owned()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	#[test]
+	fn plain_key_file_is_a_json_string() {
+		let file = KeyFile::new("//Alice", None).unwrap();
+		assert!(!file.is_encrypted());
+		assert_eq!(serde_json::to_string(&file).unwrap(), r#""//Alice""#);
+
+		let file: KeyFile = serde_json::from_str(r#""//Alice""#).unwrap();
+		assert_eq!(*file.into_phrase(None).unwrap(), "//Alice");
+	}
+
+	#[test]
+	fn encrypted_key_file_roundtrips() {
+		let password = SecretString::from_str("password").unwrap();
+		let file = KeyFile::new("//Alice", Some(&password)).unwrap();
+		assert!(file.is_encrypted());
+
+		let encoded = serde_json::to_string(&file).unwrap();
+		assert!(!encoded.contains("Alice"));
+
+		let decode = || serde_json::from_str::<KeyFile>(&encoded).unwrap();
+		assert_eq!(*decode().into_phrase(Some(&password)).unwrap(), "//Alice");
+		assert!(matches!(decode().into_phrase(None), Err(Error::EncryptionPasswordRequired)));
+
+		let wrong_password = SecretString::from_str("wrong").unwrap();
+		assert!(matches!(
+			decode().into_phrase(Some(&wrong_password)),
+			Err(Error::InvalidEncryptionPassword)
+		));
+	}
+
+	#[test]
+	fn excessive_scrypt_params_are_rejected() {
+		let password = SecretString::from_str("password").unwrap();
+		let file = KeyFile::new("//Alice", Some(&password)).unwrap();
+		let encoded = serde_json::to_value(&file).unwrap();
+
+		for (param, value) in [("logN", 63), ("logN", 21), ("r", 33), ("p", 17)] {
+			let mut encoded = encoded.clone();
+			encoded["kdf"][param] = value.into();
+			let file = serde_json::from_value::<KeyFile>(encoded).unwrap();
+			assert!(matches!(file.into_phrase(Some(&password)), Err(Error::InvalidKeyFile)));
+		}
+
+		// The limits themselves are accepted.
+		let mut encoded = encoded;
+		encoded["kdf"]["r"] = MAX_SCRYPT_R.into();
+		let file = serde_json::from_value::<KeyFile>(encoded).unwrap();
+		// The key was derived with other parameters, so the password no longer matches.
+		assert!(matches!(file.into_phrase(Some(&password)), Err(Error::InvalidEncryptionPassword)));
+	}
+}
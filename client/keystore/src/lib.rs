@@ -24,6 +24,8 @@ use sp_core::crypto::KeyTypeId;
 use sp_keystore::Error as TraitError;
 use std::io;
 
+/// Key file format
+mod encryption;
 /// Local keystore implementation
 mod local;
 pub use local::LocalKeystore;
@@ -55,6 +57,15 @@ pub enum Error {
 	/// Keystore unavailable
 	#[error("Keystore unavailable")]
 	Unavailable,
+	/// Key file is encrypted, but no encryption password was provided
+	#[error("Key file is encrypted, but no keystore encryption password was provided")]
+	EncryptionPasswordRequired,
+	/// Invalid encryption password
+	#[error("Could not decrypt the key file, the keystore encryption password is incorrect")]
+	InvalidEncryptionPassword,
+	/// Invalid key file
+	#[error("Invalid key file")]
+	InvalidKeyFile,
 }
 
 /// Keystore Result
@@ -64,8 +75,12 @@ impl From<Error> for TraitError {
 	fn from(error: Error) -> Self {
 		match error {
 			Error::KeyNotSupported(id) => TraitError::KeyNotSupported(id),
-			Error::InvalidSeed | Error::InvalidPhrase | Error::PublicKeyMismatch =>
-				TraitError::ValidationError(error.to_string()),
+			Error::InvalidSeed |
+			Error::InvalidPhrase |
+			Error::PublicKeyMismatch |
+			Error::EncryptionPasswordRequired |
+			Error::InvalidEncryptionPassword |
+			Error::InvalidKeyFile => TraitError::ValidationError(error.to_string()),
 			Error::Unavailable => TraitError::Unavailable,
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
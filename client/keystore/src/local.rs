@@ -24,18 +24,19 @@ use sp_application_crypto::{AppCrypto, AppPair, IsWrappedBy};
 use sp_core::{bls377, bls381};
 use sp_core::{
 	crypto::{ByteArray, ExposeSecret, KeyTypeId, Pair as CorePair, SecretString, VrfSecret},
-	ed25519, sr25519,
+	ecdsa, ed25519, sr25519,
 };
 use sp_keystore::{Error as TraitError, Keystore, KeystorePtr};
 use std::{
 	collections::HashMap,
 	fs::{self, File},
 	io::Write,
-	path::PathBuf,
+	path::{Path, PathBuf},
 	sync::Arc,
 };
+use zeroize::Zeroizing;
 
-use crate::{Error, Result};
+use crate::{encryption::KeyFile, Error, Result};
 
 /// A local based keystore that is either memory-based or filesystem-based.
 pub struct LocalKeystore(RwLock<KeystoreInner>);
@@ -47,6 +48,38 @@ impl LocalKeystore {
 		Ok(Self(RwLock::new(inner)))
 	}
 
+	/// Create a local keystore from filesystem, encrypting its key files with
+	/// `encryption_password`.
+	///
+	/// Existing plaintext key files are encrypted when opening the keystore.
+	pub fn open_encrypted<T: Into<PathBuf>>(
+		path: T,
+		password: Option<SecretString>,
+		encryption_password: SecretString,
+	) -> Result<Self> {
+		let mut inner = KeystoreInner::open(path, password)?;
+		inner.encryption_password = Some(encryption_password);
+		inner.encrypt_plaintext_files()?;
+		Ok(Self(RwLock::new(inner)))
+	}
+
+	/// Re-encrypt all the key files of the keystore at `path`.
+	///
+	/// The key files are decrypted with `encryption_password`, which is only required if any of
+	/// them is encrypted, and written back encrypted with `new_encryption_password`. If
+	/// `new_encryption_password` is `None`, they are written back as plaintext.
+	///
+	/// Returns the number of key files written.
+	pub fn reencrypt<T: Into<PathBuf>>(
+		path: T,
+		encryption_password: Option<SecretString>,
+		new_encryption_password: Option<SecretString>,
+	) -> Result<usize> {
+		let mut inner = KeystoreInner::open(path, None)?;
+		inner.encryption_password = encryption_password;
+		inner.reencrypt_files(new_encryption_password)
+	}
+
 	/// Create a local keystore in memory.
 	pub fn in_memory() -> Self {
 		let inner = KeystoreInner::new_in_memory();
@@ -197,6 +230,44 @@ impl Keystore for LocalKeystore {
 		self.sign::<ed25519::Pair>(key_type, public, msg)
 	}
 
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys::<ecdsa::Pair>(key_type)
+	}
+
+	/// Generate a new pair compatible with the 'ecdsa' signature scheme.
+	///
+	/// If `[seed]` is `Some` then the key will be ephemeral and stored in memory.
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.generate_new::<ecdsa::Pair>(key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.sign::<ecdsa::Pair>(key_type, public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		let sig = self
+			.0
+			.read()
+			.key_pair_by_type::<ecdsa::Pair>(public, key_type)?
+			.map(|pair| pair.sign_prehashed(msg));
+		Ok(sig)
+	}
+
 	#[cfg(feature = "bls-experimental")]
 	fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
 		self.public_keys::<bls381::Pair>(key_type)
@@ -287,6 +358,12 @@ struct KeystoreInner {
 	/// Map over `(KeyTypeId, Raw public key)` -> `Key phrase/seed`
 	additional: HashMap<(KeyTypeId, Vec<u8>), String>,
 	password: Option<SecretString>,
+	/// The password key files are encrypted with, if any.
+	encryption_password: Option<SecretString>,
+	/// The key phrases of the encrypted key files decrypted so far, by key file path.
+	///
+	/// Decrypting a key file is deliberately expensive, so it's only done once per key file.
+	unlocked: RwLock<HashMap<PathBuf, Zeroizing<String>>>,
 }
 
 impl KeystoreInner {
@@ -297,7 +374,13 @@ impl KeystoreInner {
 		let path = path.into();
 		fs::create_dir_all(&path)?;
 
-		Ok(Self { path: Some(path), additional: HashMap::new(), password })
+		Ok(Self {
+			path: Some(path),
+			additional: HashMap::new(),
+			password,
+			encryption_password: None,
+			unlocked: Default::default(),
+		})
 	}
 
 	/// Get the password for this store.
@@ -307,7 +390,13 @@ impl KeystoreInner {
 
 	/// Create a new in-memory store.
 	fn new_in_memory() -> Self {
-		Self { path: None, additional: HashMap::new(), password: None }
+		Self {
+			path: None,
+			additional: HashMap::new(),
+			password: None,
+			encryption_password: None,
+			unlocked: Default::default(),
+		}
 	}
 
 	/// Get the key phrase for the given public key and key type from the in-memory store.
@@ -334,7 +423,7 @@ impl KeystoreInner {
 	/// Places it into the file system store, if a path is configured.
 	fn insert(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<()> {
 		if let Some(path) = self.key_file_path(public, key_type) {
-			Self::write_to_file(path, suri)?;
+			self.write_to_file(path, suri)?;
 		}
 
 		Ok(())
@@ -347,7 +436,7 @@ impl KeystoreInner {
 	fn generate_by_type<Pair: CorePair>(&mut self, key_type: KeyTypeId) -> Result<Pair> {
 		let (pair, phrase, _) = Pair::generate_with_phrase(self.password());
 		if let Some(path) = self.key_file_path(pair.public().as_slice(), key_type) {
-			Self::write_to_file(path, &phrase)?;
+			self.write_to_file(path, &phrase)?;
 		} else {
 			self.insert_ephemeral_pair(&pair, &phrase, key_type);
 		}
@@ -355,21 +444,95 @@ impl KeystoreInner {
 		Ok(pair)
 	}
 
-	/// Write the given `data` to `file`.
-	fn write_to_file(file: PathBuf, data: &str) -> Result<()> {
-		let mut file = File::create(file)?;
+	/// Write the given key `phrase` to `file`, encrypted if an encryption password is set.
+	///
+	/// The key file is written to a temporary file first, replacing `file` only once complete.
+	/// The temporary file is removed if the write fails.
+	fn write_to_file(&self, file: PathBuf, phrase: &str) -> Result<()> {
+		let key_file = KeyFile::new(phrase, self.encryption_password.as_ref())?;
+
+		// the extension makes the name invalid hex, so it's never listed as a key
+		let tmp_path = file.with_extension("tmp");
+		let write_tmp_file = || -> Result<()> {
+			let mut tmp_file = File::create(&tmp_path)?;
+
+			#[cfg(target_family = "unix")]
+			{
+				use std::os::unix::fs::PermissionsExt;
+				tmp_file.set_permissions(fs::Permissions::from_mode(0o600))?;
+			}
+
+			serde_json::to_writer(&tmp_file, &key_file)?;
+			tmp_file.flush()?;
+			tmp_file.sync_all()?;
+			Ok(())
+		};
+		let result =
+			write_tmp_file().and_then(|()| fs::rename(&tmp_path, &file).map_err(Into::into));
+		if let Err(e) = result {
+			let _ = fs::remove_file(&tmp_path);
+			return Err(e)
+		}
+
+		let mut unlocked = self.unlocked.write();
+		if key_file.is_encrypted() {
+			unlocked.insert(file, Zeroizing::new(phrase.to_owned()));
+		} else {
+			unlocked.remove(&file);
+		}
+		Ok(())
+	}
+
+	/// Read the key phrase stored in `file`, decrypting it if needed.
+	///
+	/// Decrypted key phrases are cached, so each key file is only decrypted once.
+	fn read_from_file(&self, file: &Path) -> Result<Zeroizing<String>> {
+		if let Some(phrase) = self.unlocked.read().get(file) {
+			return Ok(phrase.clone())
+		}
+
+		let key_file: KeyFile = serde_json::from_reader(&File::open(file)?)?;
+		let encrypted = key_file.is_encrypted();
+		let phrase = key_file.into_phrase(self.encryption_password.as_ref())?;
+		if encrypted {
+			self.unlocked.write().insert(file.to_path_buf(), phrase.clone());
+		}
+		Ok(phrase)
+	}
 
-		#[cfg(target_family = "unix")]
-		{
-			use std::os::unix::fs::PermissionsExt;
-			file.set_permissions(fs::Permissions::from_mode(0o600))?;
+	/// Encrypt all the key files stored as plaintext.
+	fn encrypt_plaintext_files(&self) -> Result<()> {
+		for (file, _) in self.key_files()? {
+			let key_file: KeyFile = serde_json::from_reader(&File::open(&file)?)?;
+			if !key_file.is_encrypted() {
+				let phrase = key_file.into_phrase(None)?;
+				self.write_to_file(file, &phrase)?;
+			}
 		}
 
-		serde_json::to_writer(&file, data)?;
-		file.flush()?;
 		Ok(())
 	}
 
+	/// Write all the key files back with `new_encryption_password`, returning how many there
+	/// are.
+	///
+	/// All the key files are read before any is written, so that no key file is changed if any
+	/// of them can't be decrypted.
+	fn reencrypt_files(&mut self, new_encryption_password: Option<SecretString>) -> Result<usize> {
+		let phrases = self
+			.key_files()?
+			.into_iter()
+			.map(|(file, _)| self.read_from_file(&file).map(|phrase| (file, phrase)))
+			.collect::<Result<Vec<_>>>()?;
+
+		self.encryption_password = new_encryption_password;
+		for (file, phrase) in &phrases {
+			self.write_to_file(file.clone(), phrase)?;
+		}
+
+		Ok(phrases.len())
+	}
+
 	/// Create a new key from seed.
 	///
 	/// Does not place it into the file system store.
@@ -384,9 +547,13 @@ impl KeystoreInner {
 	}
 
 	/// Get the key phrase for a given public key and key type.
-	fn key_phrase_by_type(&self, public: &[u8], key_type: KeyTypeId) -> Result<Option<String>> {
+	fn key_phrase_by_type(
+		&self,
+		public: &[u8],
+		key_type: KeyTypeId,
+	) -> Result<Option<Zeroizing<String>>> {
 		if let Some(phrase) = self.get_additional_pair(public, key_type) {
-			return Ok(Some(phrase.clone()))
+			return Ok(Some(Zeroizing::new(phrase.clone())))
 		}
 
 		let path = if let Some(path) = self.key_file_path(public, key_type) {
@@ -396,9 +563,7 @@ impl KeystoreInner {
 		};
 
 		if path.exists() {
-			let file = File::open(path)?;
-
-			serde_json::from_reader(&file).map_err(Into::into).map(Some)
+			self.read_from_file(&path).map(Some)
 		} else {
 			Ok(None)
 		}
@@ -445,6 +610,22 @@ impl KeystoreInner {
 			.filter_map(|k| if k.0 == key_type { Some(k.1.clone()) } else { None })
 			.collect();
 
+		for (_, hex) in self.key_files()? {
+			if hex[0..4] != key_type.0 {
+				continue
+			}
+			let public = hex[4..].to_vec();
+			public_keys.push(public);
+		}
+
+		Ok(public_keys)
+	}
+
+	/// Returns the path of every key file in the file system store, along with its decoded
+	/// name (the key type followed by the raw public key).
+	fn key_files(&self) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+		let mut key_files = Vec::new();
+
 		if let Some(path) = &self.path {
 			for entry in fs::read_dir(&path)? {
 				let entry = entry?;
@@ -453,20 +634,14 @@ impl KeystoreInner {
 				// skip directories and non-unicode file names (hex is unicode)
 				if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
 					match array_bytes::hex2bytes(name) {
-						Ok(ref hex) if hex.len() > 4 => {
-							if hex[0..4] != key_type.0 {
-								continue
-							}
-							let public = hex[4..].to_vec();
-							public_keys.push(public);
-						},
+						Ok(hex) if hex.len() > 4 => key_files.push((path, hex)),
 						_ => continue,
 					}
 				}
 			}
 		}
 
-		Ok(public_keys)
+		Ok(key_files)
 	}
 
 	/// Get a key pair for the given public key.
@@ -677,6 +852,148 @@ mod tests {
 		assert_eq!(store.sr25519_public_keys(TEST_KEY_TYPE).len(), 2);
 	}
 
+	#[test]
+	fn encrypted_keys_require_the_encryption_password() {
+		let temp_dir = TempDir::new().unwrap();
+		let encryption_password = || SecretString::from_str("encryption").unwrap();
+		let store =
+			LocalKeystore::open_encrypted(temp_dir.path(), None, encryption_password()).unwrap();
+
+		let public = store.sr25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		let path = store.0.read().key_file_path(public.as_ref(), TEST_KEY_TYPE).unwrap();
+		let key_file: KeyFile = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+		assert!(key_file.is_encrypted());
+		assert!(store.has_keys(&[(public.to_raw_vec(), TEST_KEY_TYPE)]));
+
+		drop(store);
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+		// The public keys are still listed, but the key can't be used
+		assert_eq!(store.sr25519_public_keys(TEST_KEY_TYPE), vec![public]);
+		assert!(matches!(
+			store.0.read().key_pair_by_type::<sr25519::Pair>(&public, TEST_KEY_TYPE),
+			Err(Error::EncryptionPasswordRequired)
+		));
+
+		let wrong_password = SecretString::from_str("wrong").unwrap();
+		let store = LocalKeystore::open_encrypted(temp_dir.path(), None, wrong_password).unwrap();
+		assert!(matches!(
+			store.0.read().key_pair_by_type::<sr25519::Pair>(&public, TEST_KEY_TYPE),
+			Err(Error::InvalidEncryptionPassword)
+		));
+	}
+
+	#[test]
+	fn plaintext_keys_are_encrypted_on_open() {
+		let temp_dir = TempDir::new().unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+
+		let secret_uri = "//Alice";
+		let key_pair = sr25519::AppPair::from_string(secret_uri, None).unwrap();
+		store.insert(SR25519, secret_uri, key_pair.public().as_ref()).unwrap();
+		let path = store.0.read().key_file_path(key_pair.public().as_ref(), SR25519).unwrap();
+		assert_eq!(fs::read_to_string(&path).unwrap(), "\"//Alice\"");
+
+		drop(store);
+		let encryption_password = SecretString::from_str("encryption").unwrap();
+		let store =
+			LocalKeystore::open_encrypted(temp_dir.path(), None, encryption_password).unwrap();
+		assert!(!fs::read_to_string(&path).unwrap().contains("Alice"));
+
+		let store_key_pair = store.key_pair::<sr25519::AppPair>(&key_pair.public()).unwrap();
+		assert_eq!(Some(key_pair.public()), store_key_pair.map(|pair| pair.public()));
+	}
+
+	#[test]
+	fn decrypted_keys_are_cached() {
+		let temp_dir = TempDir::new().unwrap();
+		let encryption_password = || SecretString::from_str("encryption").unwrap();
+		let store =
+			LocalKeystore::open_encrypted(temp_dir.path(), None, encryption_password()).unwrap();
+		let public = store.sr25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		let path = store.0.read().key_file_path(public.as_ref(), TEST_KEY_TYPE).unwrap();
+		drop(store);
+
+		let store =
+			LocalKeystore::open_encrypted(temp_dir.path(), None, encryption_password()).unwrap();
+		assert!(store.has_keys(&[(public.to_raw_vec(), TEST_KEY_TYPE)]));
+
+		// Once decrypted, the key file isn't read again
+		fs::write(&path, "{}").unwrap();
+		assert!(store.sr25519_sign(TEST_KEY_TYPE, &public, b"msg").unwrap().is_some());
+
+		// But removing it still removes the key
+		fs::remove_file(&path).unwrap();
+		assert!(!store.has_keys(&[(public.to_raw_vec(), TEST_KEY_TYPE)]));
+	}
+
+	#[test]
+	fn failed_writes_leave_no_temporary_file() {
+		let temp_dir = TempDir::new().unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+
+		let secret_uri = "//Alice";
+		let key_pair = sr25519::AppPair::from_string(secret_uri, None).unwrap();
+		let path = store.0.read().key_file_path(key_pair.public().as_ref(), SR25519).unwrap();
+		// A non-empty directory can't be replaced by the key file
+		fs::create_dir(&path).unwrap();
+		fs::write(path.join("file"), "").unwrap();
+
+		assert!(store.insert(SR25519, secret_uri, key_pair.public().as_ref()).is_err());
+		assert!(!path.with_extension("tmp").exists());
+	}
+
+	#[test]
+	fn reencrypt_changes_the_encryption_password() {
+		let temp_dir = TempDir::new().unwrap();
+		let old_password = || SecretString::from_str("old").unwrap();
+		let new_password = || SecretString::from_str("new").unwrap();
+
+		let store = LocalKeystore::open_encrypted(temp_dir.path(), None, old_password()).unwrap();
+		let public = store.ed25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		drop(store);
+
+		// Nothing is written without the right password
+		assert!(LocalKeystore::reencrypt(temp_dir.path(), None, Some(new_password())).is_err());
+		assert_eq!(
+			LocalKeystore::reencrypt(temp_dir.path(), Some(old_password()), Some(new_password()))
+				.unwrap(),
+			1
+		);
+
+		let store = LocalKeystore::open_encrypted(temp_dir.path(), None, old_password()).unwrap();
+		assert!(!store.has_keys(&[(public.to_raw_vec(), TEST_KEY_TYPE)]));
+		let store = LocalKeystore::open_encrypted(temp_dir.path(), None, new_password()).unwrap();
+		assert!(store.has_keys(&[(public.to_raw_vec(), TEST_KEY_TYPE)]));
+		drop(store);
+
+		// Decrypting writes the key files back as plaintext
+		LocalKeystore::reencrypt(temp_dir.path(), Some(new_password()), None).unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+		assert!(store.has_keys(&[(public.to_raw_vec(), TEST_KEY_TYPE)]));
+	}
+
+	#[test]
+	fn ecdsa_keys_are_persisted_and_sign() {
+		let temp_dir = TempDir::new().unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+
+		let public = store.ecdsa_generate_new(TEST_KEY_TYPE, None).unwrap();
+		// Ephemeral keys are available without being persisted
+		let ephemeral = store.ecdsa_generate_new(TEST_KEY_TYPE, Some("//Alice")).unwrap();
+		drop(store);
+
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+		assert_eq!(store.ecdsa_public_keys(TEST_KEY_TYPE), vec![public]);
+		assert!(store.ecdsa_sign(TEST_KEY_TYPE, &ephemeral, b"msg").unwrap().is_none());
+
+		let sig = store.ecdsa_sign(TEST_KEY_TYPE, &public, b"msg").unwrap().unwrap();
+		assert!(ecdsa::Pair::verify(&sig, b"msg", &public));
+
+		let hash = sp_core::blake2_256(b"msg");
+		let sig = store.ecdsa_sign_prehashed(TEST_KEY_TYPE, &public, &hash).unwrap().unwrap();
+		assert!(ecdsa::Pair::verify_prehashed(&sig, &hash, &public));
+	}
+
 	#[test]
 	#[cfg(target_family = "unix")]
 	fn uses_correct_file_permissions_on_unix() {
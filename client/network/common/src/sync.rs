@@ -86,6 +86,15 @@ pub struct StateDownloadProgress {
 	pub size: u64,
 }
 
+/// Reported progress of the download of the block history missing after warp sync.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BlockHistoryProgress<Block: BlockT> {
+	/// Lowest block downloaded so far.
+	pub current: NumberFor<Block>,
+	/// Lowest block to download.
+	pub target: NumberFor<Block>,
+}
+
 /// Syncing status and statistics.
 #[derive(Debug, Clone)]
 pub struct SyncStatus<Block: BlockT> {
@@ -103,6 +112,8 @@ pub struct SyncStatus<Block: BlockT> {
 	pub state_sync: Option<StateDownloadProgress>,
 	/// Warp sync in progress, if any.
 	pub warp_sync: Option<WarpSyncProgress<Block>>,
+	/// Block history download in progress, if any.
+	pub block_history: Option<BlockHistoryProgress<Block>>,
 }
 
 /// A peer did not behave as expected and should be reported.
@@ -40,6 +40,17 @@ pub enum WarpSyncParams<Block: BlockT> {
 	WaitForTarget(oneshot::Receiver<<Block as BlockT>::Header>),
 }
 
+/// A trusted finalized block to start warp sync from instead of genesis.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct WarpSyncCheckpoint<Block: BlockT> {
+	/// Header of the checkpoint block.
+	pub header: Block::Header,
+	/// Id of the authority set finalizing the blocks following the checkpoint.
+	pub set_id: SetId,
+	/// Authorities of that set.
+	pub authorities: AuthorityList,
+}
+
 /// Proof verification result.
 pub enum VerificationResult<Block: BlockT> {
 	/// Proof is valid, but the target was not reached.
@@ -66,6 +77,11 @@ pub trait WarpSyncProvider<Block: BlockT>: Send + Sync {
 	/// Get current list of authorities. This is supposed to be genesis authorities when starting
 	/// sync.
 	fn current_authorities(&self) -> AuthorityList;
+	/// Trusted checkpoint to start proof verification from, instead of genesis and
+	/// [`Self::current_authorities`].
+	fn checkpoint(&self) -> Option<WarpSyncCheckpoint<Block>> {
+		None
+	}
 }
 
 /// Reported warp sync phase.
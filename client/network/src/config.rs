@@ -557,6 +557,16 @@ pub struct NetworkConfiguration {
 	/// Initial syncing mode.
 	pub sync_mode: SyncMode,
 
+	/// Maximum number of blocks below the last finalized block to download the history of, when
+	/// it is missing after warp sync.
+	///
+	/// The history is downloaded down to genesis if `None`.
+	pub max_block_history: Option<u32>,
+
+	/// Path to a JSON file with a trusted finalized block to start warp sync from, overriding
+	/// the checkpoint of the chain spec.
+	pub warp_sync_checkpoint: Option<PathBuf>,
+
 	/// True if Kademlia random discovery should be enabled.
 	///
 	/// If true, the node will automatically randomly walk the DHT in order to find new peers.
@@ -623,6 +633,8 @@ impl NetworkConfiguration {
 			max_parallel_downloads: 5,
 			max_blocks_per_request: 64,
 			sync_mode: SyncMode::Full,
+			max_block_history: None,
+			warp_sync_checkpoint: None,
 			enable_dht_random_walk: true,
 			allow_non_globals_in_dht: false,
 			kademlia_disjoint_query_paths: false,
@@ -359,6 +359,7 @@ where
 			block_announce_validator,
 			max_parallel_downloads,
 			max_blocks_per_request,
+			net_config.network_config.max_block_history,
 			warp_sync_params,
 			metrics_registry,
 			network_service.clone(),
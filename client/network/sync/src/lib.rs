@@ -65,10 +65,10 @@ use sc_network_common::{
 			BlockResponse, Direction, FromBlock,
 		},
 		warp::{EncodedProof, WarpProofRequest, WarpSyncParams, WarpSyncPhase, WarpSyncProgress},
-		BadPeer, ChainSync as ChainSyncT, ImportResult, Metrics, OnBlockData, OnBlockJustification,
-		OnStateData, OpaqueBlockRequest, OpaqueBlockResponse, OpaqueStateRequest,
-		OpaqueStateResponse, PeerInfo, PeerRequest, PollBlockAnnounceValidation, SyncMode,
-		SyncState, SyncStatus,
+		BadPeer, BlockHistoryProgress, ChainSync as ChainSyncT, ImportResult, Metrics, OnBlockData,
+		OnBlockJustification, OnStateData, OpaqueBlockRequest, OpaqueBlockResponse,
+		OpaqueStateRequest, OpaqueStateResponse, PeerInfo, PeerRequest,
+		PollBlockAnnounceValidation, SyncMode, SyncState, SyncStatus,
 	},
 };
 use sp_arithmetic::traits::Saturating;
@@ -86,7 +86,7 @@ use sp_runtime::{
 };
 
 use std::{
-	collections::{hash_map::Entry, HashMap, HashSet},
+	collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
 	iter,
 	ops::Range,
 	pin::Pin,
@@ -266,10 +266,90 @@ impl SyncingMetrics {
 	}
 }
 
+/// Download of the block history below a block gap, from the top of the gap downwards.
+///
+/// Ranges of blocks are requested by number from every available peer in parallel. A downloaded
+/// range is only imported once it links to the blocks above it, starting from the parent of the
+/// lowest block above the gap, so every block is checked against its child before it is imported.
 struct GapSync<B: BlockT> {
-	blocks: BlockCollection<B>,
-	best_queued_number: NumberFor<B>,
+	/// Hash of the highest block yet to be imported.
+	next_hash: B::Hash,
+	/// Number of the highest block yet to be imported.
+	next_number: NumberFor<B>,
+	/// Lowest block to download.
 	target: NumberFor<B>,
+	/// Ranges of blocks being downloaded or waiting for the blocks above them to be downloaded,
+	/// by the number of their highest block.
+	ranges: BTreeMap<NumberFor<B>, GapRange<B>>,
+	/// Peers which didn't have the requested blocks.
+	unavailable: HashSet<PeerId>,
+}
+
+/// A range of blocks of a [`GapSync`].
+enum GapRange<B: BlockT> {
+	/// Requested from a peer, with the number of blocks requested.
+	Downloading(PeerId, u32),
+	/// Downloaded from a peer, from the highest block down.
+	Downloaded(PeerId, Vec<BlockData<B>>),
+}
+
+impl<B: BlockT> GapRange<B> {
+	fn len(&self) -> u32 {
+		match self {
+			GapRange::Downloading(_, len) => *len,
+			GapRange::Downloaded(_, blocks) => blocks.len() as u32,
+		}
+	}
+}
+
+impl<B: BlockT> GapSync<B> {
+	fn progress(&self) -> BlockHistoryProgress<B> {
+		BlockHistoryProgress { current: self.next_number + One::one(), target: self.target }
+	}
+
+	/// The highest range of at most `max` blocks which is neither downloaded nor being
+	/// downloaded, as the number of its highest block and its length.
+	fn next_range(&self, max: u32) -> Option<(NumberFor<B>, u32)> {
+		let mut top = self.next_number;
+		let mut bottom = self.target;
+		for (&range_top, range) in self.ranges.range(..=self.next_number).rev() {
+			if range_top < top {
+				bottom = range_top + One::one();
+				break
+			}
+			top = range_top.saturating_sub(range.len().into());
+		}
+		if top < bottom {
+			return None
+		}
+		let len = (top - bottom + One::one()).saturated_into::<u32>();
+		Some((top, std::cmp::min(len, max)))
+	}
+
+	/// Take the downloaded ranges which link to the blocks above them, with the peers they were
+	/// downloaded from.
+	///
+	/// A range which doesn't link is dropped and the peer it was downloaded from is returned
+	/// separately.
+	fn drain_linked(&mut self) -> (Vec<(PeerId, BlockData<B>)>, Option<PeerId>) {
+		let mut linked = Vec::new();
+		while let Some(GapRange::Downloaded(..)) = self.ranges.get(&self.next_number) {
+			let Some(GapRange::Downloaded(who, blocks)) = self.ranges.remove(&self.next_number)
+			else {
+				unreachable!("matched as downloaded right above; qed")
+			};
+			if blocks.first().map(|b| b.hash) != Some(self.next_hash) {
+				self.unavailable.insert(who);
+				return (linked, Some(who))
+			}
+			if let Some(header) = blocks.last().and_then(|b| b.header.as_ref()) {
+				self.next_hash = *header.parent_hash();
+				self.next_number = header.number().saturating_sub(One::one());
+			}
+			linked.extend(blocks.into_iter().map(|b| (who, b)));
+		}
+		(linked, None)
+	}
 }
 
 type PendingResponse<B> = Pin<
@@ -334,6 +414,8 @@ pub struct ChainSync<B: BlockT, Client> {
 	import_existing: bool,
 	/// Gap download process.
 	gap_sync: Option<GapSync<B>>,
+	/// Maximum number of blocks below the last finalized block to download the history of.
+	max_block_history: Option<u32>,
 	/// Handle for communicating with `NetworkService`
 	network_service: service::network::NetworkServiceHandle,
 	/// Protocol name used for block announcements
@@ -513,7 +595,7 @@ where
 
 		let warp_sync_progress = match (&self.warp_sync, &self.mode, &self.gap_sync) {
 			(_, _, Some(gap_sync)) => Some(WarpSyncProgress {
-				phase: WarpSyncPhase::DownloadingBlocks(gap_sync.best_queued_number),
+				phase: WarpSyncPhase::DownloadingBlocks(gap_sync.progress().current),
 				total_bytes: 0,
 			}),
 			(None, SyncMode::Warp, _) => Some(WarpSyncProgress {
@@ -534,6 +616,7 @@ where
 			queued_blocks: self.queue_blocks.len() as u32,
 			state_sync: self.state_sync.as_ref().map(|s| s.progress()),
 			warp_sync: warp_sync_progress,
+			block_history: self.gap_sync.as_ref().map(|s| s.progress()),
 		}
 	}
 
@@ -773,43 +856,86 @@ where
 						}
 						self.ready_blocks()
 					},
-					PeerSyncState::DownloadingGap(_) => {
+					PeerSyncState::DownloadingGap(top) => {
+						let top = *top;
 						peer.state = PeerSyncState::Available;
 						if let Some(gap_sync) = &mut self.gap_sync {
-							gap_sync.blocks.clear_peer_download(who);
-							if let Some(start_block) =
-								validate_blocks::<B>(&blocks, who, Some(request))?
-							{
-								gap_sync.blocks.insert(start_block, blocks, *who);
+							// The range may have been dropped by a restart of the gap sync.
+							match gap_sync.ranges.get(&top) {
+								Some(GapRange::Downloading(peer, _)) if peer == who => {
+									gap_sync.ranges.remove(&top);
+								},
+								_ => return Ok(OnBlockData::Continue),
+							}
+							if blocks.is_empty() {
+								// The peer may be missing the history itself.
+								debug!(
+									target: "sync",
+									"Block history #{} is unavailable from {}",
+									top,
+									who,
+								);
+								gap_sync.unavailable.insert(*who);
+								return Ok(OnBlockData::Continue)
+							}
+							validate_blocks::<B>(&blocks, who, Some(request))?;
+							// The last block is the requested one, each block must be the parent
+							// of the next.
+							let linked = blocks.windows(2).all(|pair| {
+								pair[1].header.as_ref().map(|header| *header.parent_hash()) ==
+									Some(pair[0].hash)
+							});
+							if !linked {
+								debug!(target: "sync", "Unlinked block history received from {}", who);
+								return Err(BadPeer(*who, rep::BAD_BLOCK))
+							}
+							// Blocks are imported from the top down, each one after its child.
+							blocks.reverse();
+							blocks.retain(|b| {
+								b.header.as_ref().map_or(false, |h| *h.number() >= gap_sync.target)
+							});
+							if !blocks.is_empty() {
+								gap_sync.ranges.insert(top, GapRange::Downloaded(*who, blocks));
+							}
+							let (blocks, unlinked) = gap_sync.drain_linked();
+							if let Some(unlinked) = unlinked {
+								debug!(
+									target: "sync",
+									"Block history from {} doesn't link to the chain above it",
+									unlinked,
+								);
+								if blocks.is_empty() && unlinked == *who {
+									return Err(BadPeer(*who, rep::BAD_BLOCK))
+								}
 							}
 							gap = true;
-							let blocks: Vec<_> = gap_sync
-								.blocks
-								.ready_blocks(gap_sync.best_queued_number + One::one())
+							debug!(
+								target: "sync",
+								"Downloaded {} history blocks from {}, next #{}",
+								blocks.len(),
+								who,
+								gap_sync.next_number,
+							);
+							blocks
 								.into_iter()
-								.map(|block_data| {
-									let justifications =
-										block_data.block.justifications.or_else(|| {
-											legacy_justification_mapping(
-												block_data.block.justification,
-											)
-										});
+								.map(|(origin, b)| {
+									let justifications = b
+										.justifications
+										.or_else(|| legacy_justification_mapping(b.justification));
 									IncomingBlock {
-										hash: block_data.block.hash,
-										header: block_data.block.header,
-										body: block_data.block.body,
-										indexed_body: block_data.block.indexed_body,
+										hash: b.hash,
+										header: b.header,
+										body: b.body,
+										indexed_body: b.indexed_body,
 										justifications,
-										origin: block_data.origin,
+										origin: Some(origin),
 										allow_missing_state: true,
 										import_existing: self.import_existing,
 										skip_execution: true,
 										state: None,
 									}
 								})
-								.collect();
-							debug!(target: "sync", "Drained {} gap blocks from {}", blocks.len(), gap_sync.best_queued_number);
-							blocks
+								.collect()
 						} else {
 							debug!(target: "sync", "Unexpected gap block response from {}", who);
 							return Err(BadPeer(*who, rep::NO_BLOCK))
@@ -1227,7 +1353,10 @@ where
 	fn peer_disconnected(&mut self, who: &PeerId) {
 		self.blocks.clear_peer_download(who);
 		if let Some(gap_sync) = &mut self.gap_sync {
-			gap_sync.blocks.clear_peer_download(who)
+			gap_sync.unavailable.remove(who);
+			gap_sync
+				.ranges
+				.retain(|_, range| !matches!(range, GapRange::Downloading(peer, _) if peer == who));
 		}
 		self.peers.remove(who);
 		self.pending_responses.remove(who);
@@ -1399,6 +1528,7 @@ where
 		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
 		max_parallel_downloads: u32,
 		max_blocks_per_request: u32,
+		max_block_history: Option<u32>,
 		warp_sync_params: Option<WarpSyncParams<B>>,
 		metrics_registry: Option<&Registry>,
 		network_service: service::network::NetworkServiceHandle,
@@ -1441,6 +1571,7 @@ where
 			warp_sync: None,
 			import_existing: false,
 			gap_sync: None,
+			max_block_history,
 			network_service,
 			block_request_protocol_name,
 			state_request_protocol_name,
@@ -1556,11 +1687,6 @@ where
 		if self.fork_targets.remove(hash).is_some() {
 			trace!(target: "sync", "Completed fork sync {:?}", hash);
 		}
-		if let Some(gap_sync) = &mut self.gap_sync {
-			if number > gap_sync.best_queued_number && number <= gap_sync.target {
-				gap_sync.best_queued_number = number;
-			}
-		}
 		if number > self.best_queued_number {
 			self.best_queued_number = number;
 			self.best_queued_hash = *hash;
@@ -1839,13 +1965,32 @@ where
 			}
 		}
 
+		self.gap_sync = None;
 		if let Some((start, end)) = info.block_gap {
-			debug!(target: "sync", "Starting gap sync #{} - #{}", start, end);
-			self.gap_sync = Some(GapSync {
-				best_queued_number: start - One::one(),
-				target: end,
-				blocks: BlockCollection::new(),
-			});
+			let target = match self.max_block_history {
+				Some(max) => std::cmp::max(start, info.finalized_number.saturating_sub(max.into())),
+				None => start,
+			};
+			// The highest block of the gap is the parent of the lowest block above it.
+			let above = match self.client.hash(end + One::one())? {
+				Some(hash) => self.client.header(hash)?,
+				None => None,
+			};
+			match above {
+				Some(header) if target <= end => {
+					debug!(target: "sync", "Starting gap sync #{} - #{}", target, end);
+					self.gap_sync = Some(GapSync {
+						next_hash: *header.parent_hash(),
+						next_number: end,
+						target,
+						ranges: BTreeMap::new(),
+						unavailable: HashSet::new(),
+					});
+				},
+				Some(_) => debug!(target: "sync", "Block history is limited to #{}", target),
+				None =>
+					warn!(target: "sync", "Block #{} above the block gap is missing", end + One::one()),
+			}
 		}
 		trace!(target: "sync", "Restarted sync at #{} ({:?})", self.best_queued_number, self.best_queued_hash);
 		Ok(())
@@ -2434,18 +2579,10 @@ where
 					trace!(target: "sync", "Downloading fork {:?} from {}", hash, id);
 					peer.state = PeerSyncState::DownloadingStale(hash);
 					Some((id, req))
-				} else if let Some((range, req)) = gap_sync.as_mut().and_then(|sync| {
-					peer_gap_block_request(
-						&id,
-						peer,
-						&mut sync.blocks,
-						attrs,
-						sync.target,
-						sync.best_queued_number,
-						max_blocks_per_request,
-					)
+				} else if let Some((top, req)) = gap_sync.as_mut().and_then(|sync| {
+					peer_gap_block_request(&id, peer, sync, attrs, max_blocks_per_request)
 				}) {
-					peer.state = PeerSyncState::DownloadingGap(range.start);
+					peer.state = PeerSyncState::DownloadingGap(top);
 					trace!(
 						target: "sync",
 						"New gap block request for {}, (best:{}, common:{}) {:?}",
@@ -2677,9 +2814,6 @@ where
 		for (_, hash) in &results {
 			self.queue_blocks.remove(hash);
 			self.blocks.clear_queued(hash);
-			if let Some(gap_sync) = &mut self.gap_sync {
-				gap_sync.blocks.clear_queued(hash);
-			}
 		}
 		for (result, hash) in results {
 			if has_error {
@@ -2949,37 +3083,32 @@ fn peer_block_request<B: BlockT>(
 	Some((range, request))
 }
 
-/// Get a new block request for the peer if any.
+/// Get a new block history request for the peer if any, along with the number of the highest
+/// block requested.
 fn peer_gap_block_request<B: BlockT>(
 	id: &PeerId,
 	peer: &PeerSync<B>,
-	blocks: &mut BlockCollection<B>,
+	gap_sync: &mut GapSync<B>,
 	attrs: BlockAttributes,
-	target: NumberFor<B>,
-	common_number: NumberFor<B>,
 	max_blocks_per_request: u32,
-) -> Option<(Range<NumberFor<B>>, BlockRequest<B>)> {
-	let range = blocks.needed_blocks(
-		*id,
-		max_blocks_per_request,
-		std::cmp::min(peer.best_number, target),
-		common_number,
-		1,
-		MAX_DOWNLOAD_AHEAD,
-	)?;
-
-	// The end is not part of the range.
-	let last = range.end.saturating_sub(One::one());
-	let from = FromBlock::Number(last);
+) -> Option<(NumberFor<B>, BlockRequest<B>)> {
+	if gap_sync.unavailable.contains(id) {
+		return None
+	}
+	let (top, count) = gap_sync.next_range(max_blocks_per_request)?;
+	if peer.best_number < top {
+		return None
+	}
+	gap_sync.ranges.insert(top, GapRange::Downloading(*id, count));
 
 	let request = BlockRequest::<B> {
 		id: 0,
 		fields: attrs,
-		from,
+		from: FromBlock::Number(top),
 		direction: Direction::Descending,
-		max: Some((range.end - range.start).saturated_into::<u32>()),
+		max: Some(count),
 	};
-	Some((range, request))
+	Some((top, request))
 }
 
 /// Get pending fork sync targets for a peer.
@@ -3201,6 +3330,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -3268,6 +3398,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -3450,6 +3581,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -3577,6 +3709,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -3735,6 +3868,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -3878,6 +4012,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -4023,6 +4158,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -4069,6 +4205,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -4123,6 +4260,7 @@ mod test {
 			64,
 			None,
 			None,
+			None,
 			chain_sync_network_handle,
 			import_queue,
 			ProtocolName::from("block-request"),
@@ -4189,4 +4327,67 @@ mod test {
 		sync.peer_disconnected(&peers[1]);
 		assert_eq!(sync.pending_responses.len(), 0);
 	}
+
+	#[test]
+	fn gap_sync_downloads_ranges_in_parallel_and_imports_them_once_linked() {
+		let mut headers: Vec<Header> = Vec::new();
+		for number in 0..=10u64 {
+			let parent_hash = headers.last().map_or_else(Default::default, |h| h.hash());
+			headers.push(Header::new(
+				number,
+				Default::default(),
+				Default::default(),
+				parent_hash,
+				Default::default(),
+			));
+		}
+		let range = |top: usize, bottom: usize| {
+			(bottom..=top)
+				.rev()
+				.map(|number| BlockData {
+					hash: headers[number].hash(),
+					header: Some(headers[number].clone()),
+					body: None,
+					indexed_body: None,
+					receipt: None,
+					message_queue: None,
+					justification: None,
+					justifications: None,
+				})
+				.collect::<Vec<_>>()
+		};
+		let peers: Vec<_> = (0..3).map(|_| PeerId::random()).collect();
+		let mut gap_sync = GapSync::<Block> {
+			next_hash: headers[10].hash(),
+			next_number: 10,
+			target: 1,
+			ranges: BTreeMap::new(),
+			unavailable: HashSet::new(),
+		};
+
+		// Every peer gets its own range.
+		assert_eq!(gap_sync.next_range(4), Some((10, 4)));
+		gap_sync.ranges.insert(10, GapRange::Downloading(peers[0], 4));
+		assert_eq!(gap_sync.next_range(4), Some((6, 4)));
+		gap_sync.ranges.insert(6, GapRange::Downloading(peers[1], 4));
+		assert_eq!(gap_sync.next_range(4), Some((2, 2)));
+
+		// A lower range waits for the range above it.
+		gap_sync.ranges.insert(6, GapRange::Downloaded(peers[1], range(6, 3)));
+		assert_eq!(gap_sync.drain_linked().0.len(), 0);
+		gap_sync.ranges.insert(10, GapRange::Downloaded(peers[0], range(10, 7)));
+		let (linked, unlinked) = gap_sync.drain_linked();
+		assert_eq!(
+			linked.iter().map(|(_, b)| b.hash).collect::<Vec<_>>(),
+			range(10, 3).into_iter().map(|b| b.hash).collect::<Vec<_>>(),
+		);
+		assert_eq!(unlinked, None);
+		assert_eq!((gap_sync.next_number, gap_sync.next_hash), (2, headers[2].hash()));
+
+		// A range which doesn't link to the blocks above it is dropped.
+		gap_sync.ranges.insert(2, GapRange::Downloaded(peers[2], range(3, 2)));
+		assert_eq!(gap_sync.drain_linked(), (vec![], Some(peers[2])));
+		assert!(gap_sync.unavailable.contains(&peers[2]));
+		assert_eq!(gap_sync.next_range(4), Some((2, 2)));
+	}
 }
@@ -85,12 +85,27 @@ where
 	/// authorities. Alternatively we can pass a target block when we want to skip downloading
 	/// proofs, in this case we will continue polling until the target block is known.
 	pub fn new(client: Arc<Client>, warp_sync_params: WarpSyncParams<B>) -> Self {
-		let last_hash = client.hash(Zero::zero()).unwrap().expect("Genesis header always exists");
 		match warp_sync_params {
 			WarpSyncParams::WithProvider(warp_sync_provider) => {
+				let (set_id, authorities, last_hash) = match warp_sync_provider.checkpoint() {
+					Some(checkpoint) => {
+						log::debug!(
+							target: "sync",
+							"Starting warp sync from checkpoint #{} ({})",
+							checkpoint.header.number(),
+							checkpoint.header.hash(),
+						);
+						(checkpoint.set_id, checkpoint.authorities, checkpoint.header.hash())
+					},
+					None => (
+						0,
+						warp_sync_provider.current_authorities(),
+						client.hash(Zero::zero()).unwrap().expect("Genesis header always exists"),
+					),
+				};
 				let phase = Phase::WarpProof {
-					set_id: 0,
-					authorities: warp_sync_provider.current_authorities(),
+					set_id,
+					authorities,
 					last_hash,
 					warp_sync_provider: warp_sync_provider.clone(),
 				};
@@ -235,7 +250,8 @@ where
 				let request = BlockRequest::<B> {
 					id: 0,
 					fields: BlockAttributes::HEADER |
-						BlockAttributes::BODY | BlockAttributes::JUSTIFICATION,
+						BlockAttributes::BODY |
+						BlockAttributes::JUSTIFICATION,
 					from: FromBlock::Hash(header.hash()),
 					direction: Direction::Ascending,
 					max: Some(1),
@@ -709,6 +709,8 @@ pub struct FullPeerConfig {
 	pub storage_chain: bool,
 	/// Optional target block header to sync to
 	pub target_block: Option<<Block as BlockT>::Header>,
+	/// Maximum number of blocks below the last finalized block to download the history of.
+	pub max_block_history: Option<u32>,
 }
 
 #[async_trait::async_trait]
@@ -798,6 +800,7 @@ where
 		let mut network_config =
 			NetworkConfiguration::new("test-node", "test-client", Default::default(), None);
 		network_config.sync_mode = config.sync_mode;
+		network_config.max_block_history = config.max_block_history;
 		network_config.transport = TransportConfig::MemoryOnly;
 		network_config.listen_addresses = vec![listen_addr.clone()];
 		network_config.allow_non_globals_in_dht = true;
@@ -1232,7 +1232,8 @@ async fn warp_sync() {
 		sync_mode: SyncMode::Warp,
 		..Default::default()
 	});
-	let gap_end = net.peer(0).push_blocks(63, false).pop().unwrap();
+	let gap = net.peer(0).push_blocks(63, false);
+	let (gap_start, gap_end) = (gap[0], gap[62]);
 	let target = net.peer(0).push_blocks(1, false).pop().unwrap();
 	net.peer(1).push_blocks(64, false);
 	net.peer(2).push_blocks(64, false);
@@ -1244,13 +1245,50 @@ async fn warp_sync() {
 	// Wait for peer 1 download block history
 	futures::future::poll_fn::<(), _>(|cx| {
 		net.poll(cx);
-		if net.peer(3).has_body(gap_end) && net.peer(3).has_body(target) {
+		if net.peer(3).has_body(gap_start) &&
+			net.peer(3).has_body(gap_end) &&
+			net.peer(3).has_body(target)
+		{
+			Poll::Ready(())
+		} else {
+			Poll::Pending
+		}
+	})
+	.await;
+	assert_eq!(net.peer(3).client().info().block_gap, None);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn warp_sync_with_limited_block_history() {
+	sp_tracing::try_init_simple();
+	let mut net = TestNet::new(0);
+	// Create 3 synced peers and 1 peer trying to warp sync.
+	net.add_full_peer_with_config(Default::default());
+	net.add_full_peer_with_config(Default::default());
+	net.add_full_peer_with_config(Default::default());
+	net.add_full_peer_with_config(FullPeerConfig {
+		sync_mode: SyncMode::Warp,
+		max_block_history: Some(16),
+		..Default::default()
+	});
+	let blocks = net.peer(0).push_blocks(64, false);
+	net.peer(1).push_blocks(64, false);
+	net.peer(2).push_blocks(64, false);
+	net.run_until_sync().await;
+	assert!(net.peer(3).client().has_state_at(&BlockId::Number(64)));
+
+	// Wait for peer 3 to download the last 16 blocks of the history.
+	futures::future::poll_fn::<(), _>(|cx| {
+		net.poll(cx);
+		if net.peer(3).client().info().block_gap == Some((1, 47)) {
 			Poll::Ready(())
 		} else {
 			Poll::Pending
 		}
 	})
 	.await;
+	assert!(blocks[47..].iter().all(|b| net.peer(3).has_body(*b)));
+	assert!(!net.peer(3).has_body(blocks[46]));
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
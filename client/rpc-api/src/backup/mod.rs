@@ -0,0 +1,54 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate backup API.
+//!
+//! Allows taking a checkpoint of the database of a running node. The checkpoint is written to the
+//! node's filesystem, so the endpoints in this RPC module are all marked `unsafe`.
+
+pub mod error;
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+
+/// Description of a checkpoint returned by the `backup_createCheckpoint` RPC.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint<Hash> {
+	/// Genesis hash of the chain the checkpoint belongs to.
+	pub genesis_hash: Hash,
+	/// Hash of the best block in the checkpoint.
+	pub best_hash: Hash,
+	/// Number of the best block in the checkpoint.
+	pub best_number: u64,
+	/// Hash of the last finalized block in the checkpoint.
+	pub finalized_hash: Hash,
+	/// Number of the last finalized block in the checkpoint.
+	pub finalized_number: u64,
+}
+
+/// Substrate backup API.
+#[rpc(client, server)]
+pub trait BackupApi<Hash> {
+	/// Write a consistent checkpoint of the node's database to `path`, which must not exist yet.
+	///
+	/// The checkpoint can be restored with the `restore-db` command while the node is stopped.
+	#[method(name = "backup_createCheckpoint", blocking)]
+	fn create_checkpoint(&self, path: String) -> RpcResult<Checkpoint<Hash>>;
+}
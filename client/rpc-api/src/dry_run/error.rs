@@ -0,0 +1,67 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Error helpers for Dry-run RPC module.
+
+use jsonrpsee::{
+	core::Error as JsonRpseeError,
+	types::error::{CallError, ErrorObject},
+};
+
+/// Dry-run RPC errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Failed to query the state of the specified block: Probably an invalid hash.
+	#[error("Error while querying the state: {0}")]
+	StateQueryError(Box<dyn std::error::Error + Send>),
+	/// The extrinsic could not be decoded.
+	#[error("Failed to decode the extrinsic: {0}")]
+	DecodeError(String),
+	/// The runtime could not dry-run the extrinsic or call.
+	#[error("Failed to dry-run: {0}")]
+	DryRunFailed(String),
+	/// Calling the runtime failed.
+	#[error("Runtime error: {0}")]
+	RuntimeError(String),
+	/// The method is marked as unsafe but unsafe flag wasn't supplied on the CLI.
+	#[error(transparent)]
+	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
+}
+
+/// Base error code for all dry-run errors.
+const BASE_ERROR: i32 = crate::error::base::DRY_RUN;
+
+impl From<Error> for JsonRpseeError {
+	fn from(e: Error) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			Error::StateQueryError(_) =>
+				CallError::Custom(ErrorObject::owned(BASE_ERROR + 1, msg, None::<()>)),
+			Error::DecodeError(_) =>
+				CallError::Custom(ErrorObject::owned(BASE_ERROR + 2, msg, None::<()>)),
+			Error::DryRunFailed(_) =>
+				CallError::Custom(ErrorObject::owned(BASE_ERROR + 3, msg, None::<()>)),
+			Error::RuntimeError(_) =>
+				CallError::Custom(ErrorObject::owned(BASE_ERROR + 4, msg, None::<()>)),
+			Error::UnsafeRpcCalled(e) => e.into(),
+		}
+		.into()
+	}
+}
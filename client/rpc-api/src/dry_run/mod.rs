@@ -0,0 +1,64 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate dry-run API, to preview what extrinsics and calls would do before submitting them.
+//! The endpoints in this RPC module execute arbitrary code, and are all marked `unsafe`.
+
+pub mod error;
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use sp_core::{
+	storage::{PrefixedStorageKey, StorageKey},
+	Bytes,
+};
+
+/// The effects of a dry-run, returned by the `dryRun_*` RPCs.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunResult {
+	/// The SCALE-encoded `DispatchResultWithPostInfo` of the dispatch.
+	pub execution_result: Bytes,
+	/// The SCALE-encoded events emitted, in order.
+	pub emitted_events: Vec<Bytes>,
+	/// The storage keys changed, in order.
+	///
+	/// This includes the keys the runtime changes to simulate the next block.
+	pub changed_keys: Vec<StorageKey>,
+	/// The keys changed in child tries, in order, along with the prefixed storage key of their
+	/// child trie.
+	pub changed_child_keys: Vec<(PrefixedStorageKey, Vec<StorageKey>)>,
+}
+
+/// Substrate dry-run API.
+///
+/// The extrinsics and calls are executed on top of the state of a block, as if they were included
+/// in the next block, and none of their effects are committed.
+#[rpc(client, server)]
+pub trait DryRunApi<Hash> {
+	/// Dry-run the SCALE-encoded `extrinsic` at the block `at`, or the best block.
+	#[method(name = "dryRun_extrinsic")]
+	fn dry_run_extrinsic(&self, extrinsic: Bytes, at: Option<Hash>) -> RpcResult<DryRunResult>;
+
+	/// Dry-run the SCALE-encoded runtime `call`, dispatched from the SCALE-encoded runtime
+	/// `origin`, at the block `at`, or the best block.
+	#[method(name = "dryRun_call")]
+	fn dry_run_call(&self, origin: Bytes, call: Bytes, at: Option<Hash>)
+		-> RpcResult<DryRunResult>;
+}
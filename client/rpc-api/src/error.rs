@@ -25,4 +25,6 @@ pub mod base {
 	pub const STATE: i32 = 4000;
 	pub const OFFCHAIN: i32 = 5000;
 	pub const DEV: i32 = 6000;
+	pub const BACKUP: i32 = 7000;
+	pub const DRY_RUN: i32 = 8000;
 }
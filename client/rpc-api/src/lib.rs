@@ -29,9 +29,11 @@ mod policy;
 pub use policy::DenyUnsafe;
 
 pub mod author;
+pub mod backup;
 pub mod chain;
 pub mod child_state;
 pub mod dev;
+pub mod dry_run;
 pub mod offchain;
 pub mod state;
 pub mod system;
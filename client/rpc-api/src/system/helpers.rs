@@ -91,6 +91,19 @@ pub struct SyncState<Number> {
 	pub current_block: Number,
 	/// Height of the highest block in the network.
 	pub highest_block: Number,
+	/// Download of the block history missing after warp sync, if in progress.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub block_history: Option<BlockHistory<Number>>,
+}
+
+/// Progress of the download of the block history.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHistory<Number> {
+	/// Height of the lowest block downloaded so far.
+	pub current_block: Number,
+	/// Height of the lowest block to download.
+	pub target_block: Number,
 }
 
 #[cfg(test)]
@@ -131,6 +144,7 @@ mod tests {
 				starting_block: 12u32,
 				current_block: 50u32,
 				highest_block: 128u32,
+				block_history: None,
 			})
 			.unwrap(),
 			r#"{"startingBlock":12,"currentBlock":50,"highestBlock":128}"#,
@@ -141,9 +155,21 @@ mod tests {
 				starting_block: 12u32,
 				current_block: 50u32,
 				highest_block: 50u32,
+				block_history: None,
 			})
 			.unwrap(),
 			r#"{"startingBlock":12,"currentBlock":50,"highestBlock":50}"#,
 		);
+
+		assert_eq!(
+			::serde_json::to_string(&SyncState {
+				starting_block: 12u32,
+				current_block: 50u32,
+				highest_block: 50u32,
+				block_history: Some(BlockHistory { current_block: 30u32, target_block: 1u32 }),
+			})
+			.unwrap(),
+			r#"{"startingBlock":12,"currentBlock":50,"highestBlock":50,"blockHistory":{"currentBlock":30,"targetBlock":1}}"#,
+		);
 	}
 }
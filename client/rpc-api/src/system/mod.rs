@@ -24,7 +24,7 @@ use jsonrpsee::{
 	proc_macros::rpc,
 };
 
-pub use self::helpers::{Health, NodeRole, PeerInfo, SyncState, SystemInfo};
+pub use self::helpers::{BlockHistory, Health, NodeRole, PeerInfo, SyncState, SystemInfo};
 
 pub mod error;
 pub mod helpers;
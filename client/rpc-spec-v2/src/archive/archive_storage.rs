@@ -0,0 +1,211 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `archive_storage` method.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
+use sp_blockchain::Error as BlockChainError;
+use sp_core::{hexdisplay::HexDisplay, storage::well_known_keys};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::archive::event::{
+	ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType, StorageResult, StorageResultType,
+};
+
+/// Generates the results of the `archive_unstable_storage` method.
+pub struct ArchiveStorage<Client, Block, BE> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// The maximum number of responses the API can return for a descendant query at a time.
+	storage_max_descendant_responses: usize,
+	/// The maximum number of queried items allowed for the `archive_storage` at a time.
+	storage_max_queried_items: usize,
+	/// Phantom member to pin the block type.
+	_phantom: PhantomData<(BE, Block)>,
+}
+
+impl<Client, Block, BE> ArchiveStorage<Client, Block, BE> {
+	/// Constructs a new [`ArchiveStorage`].
+	pub fn new(
+		client: Arc<Client>,
+		storage_max_descendant_responses: usize,
+		storage_max_queried_items: usize,
+	) -> Self {
+		Self {
+			client,
+			storage_max_descendant_responses,
+			storage_max_queried_items,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+/// Checks if the provided key (main or child key) is valid
+/// for queries.
+///
+/// Keys that are identical to `:child_storage:` or `:child_storage:default:`
+/// are not queryable.
+fn is_key_queryable(key: &[u8]) -> bool {
+	!well_known_keys::is_default_child_storage_key(key) &&
+		!well_known_keys::is_child_storage_key(key)
+}
+
+impl<Client, Block, BE> ArchiveStorage<Client, Block, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: StorageProvider<Block, BE> + 'static,
+{
+	/// Fetch the value from storage.
+	fn query_storage_value(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<StorageResult>, BlockChainError> {
+		let result = if let Some(child_key) = child_key {
+			self.client.child_storage(hash, child_key, key)
+		} else {
+			self.client.storage(hash, key)
+		}?;
+
+		Ok(result.map(|storage_data| StorageResult {
+			key: format!("0x{:?}", HexDisplay::from(&key.0)),
+			result: StorageResultType::Value(format!("0x{:?}", HexDisplay::from(&storage_data.0))),
+			child_trie_key: child_key
+				.map(|child_key| format!("0x{:?}", HexDisplay::from(&child_key.storage_key()))),
+		}))
+	}
+
+	/// Fetch the hash of a value from storage.
+	fn query_storage_hash(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<StorageResult>, BlockChainError> {
+		let result = if let Some(child_key) = child_key {
+			self.client.child_storage_hash(hash, child_key, key)
+		} else {
+			self.client.storage_hash(hash, key)
+		}?;
+
+		Ok(result.map(|storage_hash| StorageResult {
+			key: format!("0x{:?}", HexDisplay::from(&key.0)),
+			result: StorageResultType::Hash(format!(
+				"0x{:?}",
+				HexDisplay::from(&storage_hash.as_ref())
+			)),
+			child_trie_key: child_key
+				.map(|child_key| format!("0x{:?}", HexDisplay::from(&child_key.storage_key()))),
+		}))
+	}
+
+	/// Iterate over the descendants of the provided key, starting after the pagination key.
+	///
+	/// At most `storage_max_descendant_responses` results are returned.
+	fn query_storage_descendants(
+		&self,
+		hash: Block::Hash,
+		query: &PaginatedStorageQuery<StorageKey>,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Vec<StorageResult>, BlockChainError> {
+		let keys_iter = if let Some(child_key) = child_key {
+			self.client.child_storage_keys(
+				hash,
+				child_key.clone(),
+				Some(&query.key),
+				query.pagination_start_key.as_ref(),
+			)
+		} else {
+			self.client
+				.storage_keys(hash, Some(&query.key), query.pagination_start_key.as_ref())
+		}?;
+
+		let mut results = Vec::new();
+		for key in keys_iter {
+			if results.len() >= self.storage_max_descendant_responses {
+				break
+			}
+
+			let result = match query.query_type {
+				StorageQueryType::DescendantsValues =>
+					self.query_storage_value(hash, &key, child_key)?,
+				StorageQueryType::DescendantsHashes =>
+					self.query_storage_hash(hash, &key, child_key)?,
+				StorageQueryType::Value | StorageQueryType::Hash => None,
+			};
+			results.extend(result);
+		}
+
+		Ok(results)
+	}
+
+	/// Generate the results of the provided storage queries.
+	///
+	/// Queries exceeding `storage_max_queried_items` are discarded and reported back
+	/// in [`ArchiveStorageResult::discarded_items`].
+	pub fn handle_query(
+		&self,
+		hash: Block::Hash,
+		mut items: Vec<PaginatedStorageQuery<StorageKey>>,
+		child_key: Option<ChildInfo>,
+	) -> Result<ArchiveStorageResult, BlockChainError> {
+		let discarded_items = items.len().saturating_sub(self.storage_max_queried_items);
+		items.truncate(self.storage_max_queried_items);
+
+		// The child key must not be prefixed with ":child_storage:" nor
+		// ":child_storage:default:".
+		if let Some(child_key) = child_key.as_ref() {
+			if !is_key_queryable(child_key.storage_key()) {
+				return Ok(ArchiveStorageResult { items: Vec::new(), discarded_items })
+			}
+		}
+
+		let mut storage_results = Vec::with_capacity(items.len());
+		for item in items {
+			if !is_key_queryable(&item.key.0) {
+				continue
+			}
+
+			match item.query_type {
+				StorageQueryType::Value => storage_results.extend(self.query_storage_value(
+					hash,
+					&item.key,
+					child_key.as_ref(),
+				)?),
+				StorageQueryType::Hash => storage_results.extend(self.query_storage_hash(
+					hash,
+					&item.key,
+					child_key.as_ref(),
+				)?),
+				StorageQueryType::DescendantsValues | StorageQueryType::DescendantsHashes =>
+					storage_results.extend(self.query_storage_descendants(
+						hash,
+						&item,
+						child_key.as_ref(),
+					)?),
+			}
+		}
+
+		Ok(ArchiveStorageResult { items: storage_results, discarded_items })
+	}
+}
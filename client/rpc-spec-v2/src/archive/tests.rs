@@ -0,0 +1,448 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use assert_matches::assert_matches;
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::{error::Error, params::ArrayParams},
+	rpc_params,
+	types::{error::CallError, EmptyServerParams as EmptyParams},
+	RpcModule,
+};
+use sc_block_builder::BlockBuilderProvider;
+use sc_client_api::ChildInfo;
+use sc_rpc_api::DenyUnsafe;
+use sp_api::BlockT;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_core::hexdisplay::HexDisplay;
+use std::sync::Arc;
+use substrate_test_runtime::Transfer;
+use substrate_test_runtime_client::{
+	prelude::*, runtime, Backend, BlockBuilderExt, Client, ClientBlockImportExt,
+};
+
+type Header = substrate_test_runtime_client::runtime::Header;
+type Block = substrate_test_runtime_client::runtime::Block;
+const CHAIN_GENESIS: [u8; 32] = [0; 32];
+const INVALID_HASH: [u8; 32] = [1; 32];
+const KEY: &[u8] = b":mock";
+const CHILD_STORAGE_KEY: &[u8] = b"child";
+const CHILD_VALUE: &[u8] = b"child value";
+const MAX_DESCENDANT_RESPONSES: usize = 3;
+const MAX_QUERIED_ITEMS: usize = 4;
+
+type ArchiveRpcModule = RpcModule<Archive<Backend, Block, Client<Backend>>>;
+
+fn setup_api() -> (Arc<Client<Backend>>, ArchiveRpcModule) {
+	setup_api_with_deny_unsafe(DenyUnsafe::No)
+}
+
+fn setup_api_with_deny_unsafe(deny_unsafe: DenyUnsafe) -> (Arc<Client<Backend>>, ArchiveRpcModule) {
+	let child_info = ChildInfo::new_default(CHILD_STORAGE_KEY);
+	let builder = TestClientBuilder::new().add_extra_child_storage(
+		&child_info,
+		KEY.to_vec(),
+		CHILD_VALUE.to_vec(),
+	);
+	let backend = builder.backend();
+	let client = Arc::new(builder.build());
+
+	let api = Archive::new(
+		client.clone(),
+		backend,
+		CHAIN_GENESIS,
+		ArchiveConfig {
+			max_descendant_responses: MAX_DESCENDANT_RESPONSES,
+			max_queried_items: MAX_QUERIED_ITEMS,
+		},
+		deny_unsafe,
+	)
+	.into_rpc();
+
+	(client, api)
+}
+
+#[tokio::test]
+async fn archive_genesis() {
+	let (_client, api) = setup_api();
+
+	let genesis: String =
+		api.call("archive_unstable_genesisHash", EmptyParams::new()).await.unwrap();
+	assert_eq!(genesis, format!("0x{}", HexDisplay::from(&CHAIN_GENESIS)));
+}
+
+#[tokio::test]
+async fn archive_body() {
+	let (mut client, api) = setup_api();
+
+	// Invalid block hash.
+	let invalid_hash = format!("0x{:?}", HexDisplay::from(&INVALID_HASH));
+	let extrinsics: Option<Vec<String>> =
+		api.call("archive_unstable_body", [invalid_hash]).await.unwrap();
+	assert!(extrinsics.is_none());
+
+	// Import a new block with an extrinsic.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder
+		.push_transfer(runtime::Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Ferdie.into(),
+			amount: 42,
+			nonce: 0,
+		})
+		.unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let expected_tx = hex_string(&block.extrinsics[0].encode());
+
+	let body: Vec<String> = api.call("archive_unstable_body", [block_hash]).await.unwrap();
+	assert_eq!(vec![expected_tx], body);
+}
+
+#[tokio::test]
+async fn archive_header() {
+	let (mut client, api) = setup_api();
+
+	// Invalid block hash.
+	let invalid_hash = format!("0x{:?}", HexDisplay::from(&INVALID_HASH));
+	let header: Option<String> = api.call("archive_unstable_header", [invalid_hash]).await.unwrap();
+	assert!(header.is_none());
+
+	// Import a new block.
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let header: String = api.call("archive_unstable_header", [block_hash]).await.unwrap();
+	let bytes = array_bytes::hex2bytes(&header).unwrap();
+	let header: Header = Decode::decode(&mut &bytes[..]).unwrap();
+	assert_eq!(header, block.header);
+}
+
+#[tokio::test]
+async fn archive_finalized_height() {
+	let (client, api) = setup_api();
+
+	let client_height: u32 = client.info().finalized_number;
+
+	let height: u32 =
+		api.call("archive_unstable_finalizedHeight", EmptyParams::new()).await.unwrap();
+
+	assert_eq!(client_height, height);
+}
+
+#[tokio::test]
+async fn archive_hash_by_height() {
+	let (mut client, api) = setup_api();
+
+	// Genesis height.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [0]).await.unwrap();
+	assert_eq!(hashes, vec![format!("{:?}", client.info().genesis_hash)]);
+
+	// Block tree:
+	// finalized  -> block 1 -> block 2 -> block 3
+	//                       -> block 2 -> block 4
+	let block_1 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_1_hash = block_1.header.hash();
+	client.import(BlockOrigin::Own, block_1.clone()).await.unwrap();
+
+	let block_2 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_2_hash = block_2.header.hash();
+	client.import(BlockOrigin::Own, block_2.clone()).await.unwrap();
+
+	let block_3 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_3_hash = block_3.header.hash();
+	client.import(BlockOrigin::Own, block_3.clone()).await.unwrap();
+
+	// Import block 4 fork.
+	let mut block_builder = client.new_block_at(block_2_hash, Default::default(), false).unwrap();
+	// This push is required as otherwise block 4 has the same hash as block 3 and won't get
+	// imported
+	block_builder
+		.push_transfer(Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Ferdie.into(),
+			amount: 41,
+			nonce: 0,
+		})
+		.unwrap();
+	let block_4 = block_builder.build().unwrap().block;
+	let block_4_hash = block_4.header.hash();
+	client.import(BlockOrigin::Own, block_4.clone()).await.unwrap();
+
+	// Finalize the block 1.
+	client.finalize_block(block_1_hash, None).unwrap();
+
+	// Expect the block 1 hash, which is at or below the finalized height.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [1]).await.unwrap();
+	assert_eq!(hashes, vec![format!("{:?}", block_1_hash)]);
+
+	// Only one non-finalized block at height 2.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [2]).await.unwrap();
+	assert_eq!(hashes, vec![format!("{:?}", block_2_hash)]);
+
+	// Two forks at height 3.
+	let mut hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [3]).await.unwrap();
+	hashes.sort();
+	let mut expected = vec![format!("{:?}", block_3_hash), format!("{:?}", block_4_hash)];
+	expected.sort();
+	assert_eq!(hashes, expected);
+
+	// Height above the best block.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [4]).await.unwrap();
+	assert!(hashes.is_empty());
+}
+
+#[tokio::test]
+async fn archive_call() {
+	let (mut client, api) = setup_api();
+	let invalid_hash = format!("0x{:?}", HexDisplay::from(&INVALID_HASH));
+
+	// Invalid parameter (non-hex).
+	let err = api
+		.call::<_, serde_json::Value>(
+			"archive_unstable_call",
+			[&invalid_hash, "BabeApi_current_epoch", "0x0"],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::Call(CallError::Custom(ref err)) if err.code() == 3005 && err.message().contains("Invalid parameter")
+	);
+
+	// Pass an invalid block hash.
+	let result: MethodResult = api
+		.call("archive_unstable_call", [&invalid_hash, "BabeApi_current_epoch", "0x00"])
+		.await
+		.unwrap();
+	assert_matches!(result, MethodResult::Err(_));
+
+	let block_1 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_1_hash = block_1.header.hash();
+	client.import(BlockOrigin::Own, block_1.clone()).await.unwrap();
+
+	// Valid call.
+	let alice_id = AccountKeyring::Alice.to_account_id();
+	// Hex encoded scale encoded bytes representing the call parameters.
+	let call_parameters = hex_string(&alice_id.encode());
+	let result: MethodResult = api
+		.call(
+			"archive_unstable_call",
+			[&format!("{:?}", block_1_hash), "AccountNonceApi_account_nonce", &call_parameters],
+		)
+		.await
+		.unwrap();
+	let expected = MethodResult::ok("0x0000000000000000");
+	assert_eq!(result, expected);
+}
+
+#[tokio::test]
+async fn archive_call_denied_if_unsafe() {
+	let (client, api) = setup_api_with_deny_unsafe(DenyUnsafe::Yes);
+	let genesis_hash = format!("{:?}", client.info().genesis_hash);
+
+	let alice_id = AccountKeyring::Alice.to_account_id();
+	let call_parameters = hex_string(&alice_id.encode());
+	let err = api
+		.call::<_, serde_json::Value>(
+			"archive_unstable_call",
+			[&genesis_hash, "AccountNonceApi_account_nonce", &call_parameters],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::Call(CallError::Custom(ref err)) if err.message().contains("unsafe")
+	);
+}
+
+#[tokio::test]
+async fn archive_storage_value_and_hash() {
+	let (client, api) = setup_api();
+	let genesis_hash = format!("{:?}", client.info().genesis_hash);
+	let key = hex_string(&KEY);
+
+	// Key not present in the main trie.
+	let result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params(
+				&genesis_hash,
+				vec![PaginatedStorageQuery {
+					key: key.clone(),
+					query_type: StorageQueryType::Value,
+					pagination_start_key: None,
+				}],
+				None,
+			),
+		)
+		.await
+		.unwrap();
+	assert!(result.items.is_empty());
+	assert_eq!(result.discarded_items, 0);
+
+	// Query the value and the hash from the child trie.
+	let child_key = hex_string(&CHILD_STORAGE_KEY);
+	let result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params(
+				&genesis_hash,
+				vec![
+					PaginatedStorageQuery {
+						key: key.clone(),
+						query_type: StorageQueryType::Value,
+						pagination_start_key: None,
+					},
+					PaginatedStorageQuery {
+						key: key.clone(),
+						query_type: StorageQueryType::Hash,
+						pagination_start_key: None,
+					},
+				],
+				Some(child_key.clone()),
+			),
+		)
+		.await
+		.unwrap();
+
+	let expected_hash = hex_string(&sp_core::blake2_256(CHILD_VALUE));
+	assert_eq!(
+		result.items,
+		vec![
+			StorageResult {
+				key: key.clone(),
+				result: StorageResultType::Value(hex_string(&CHILD_VALUE)),
+				child_trie_key: Some(child_key.clone()),
+			},
+			StorageResult {
+				key: key.clone(),
+				result: StorageResultType::Hash(expected_hash),
+				child_trie_key: Some(child_key),
+			},
+		]
+	);
+	assert_eq!(result.discarded_items, 0);
+}
+
+#[tokio::test]
+async fn archive_storage_paginate_descendants() {
+	let (mut client, api) = setup_api();
+
+	// Import a block that inserts 5 keys with the same prefix.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	for i in 0..5u8 {
+		builder.push_storage_change(vec![b':', b'A', i], Some(vec![i])).unwrap();
+	}
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let query = |pagination_start_key: Option<String>| PaginatedStorageQuery {
+		key: hex_string(b":A"),
+		query_type: StorageQueryType::DescendantsValues,
+		pagination_start_key,
+	};
+
+	// First page is capped by `MAX_DESCENDANT_RESPONSES`.
+	let result: ArchiveStorageResult = api
+		.call("archive_unstable_storage", rpc_params(&block_hash, vec![query(None)], None))
+		.await
+		.unwrap();
+	assert_eq!(result.items.len(), MAX_DESCENDANT_RESPONSES);
+	for (i, item) in result.items.iter().enumerate() {
+		assert_eq!(item.key, hex_string(&[b':', b'A', i as u8]));
+		assert_eq!(item.result, StorageResultType::Value(hex_string(&[i as u8])));
+	}
+
+	// Resume after the last returned key.
+	let last_key = result.items.last().unwrap().key.clone();
+	let result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params(&block_hash, vec![query(Some(last_key))], None),
+		)
+		.await
+		.unwrap();
+	assert_eq!(result.items.len(), 5 - MAX_DESCENDANT_RESPONSES);
+	for (i, item) in result.items.iter().enumerate() {
+		let i = (i + MAX_DESCENDANT_RESPONSES) as u8;
+		assert_eq!(item.key, hex_string(&[b':', b'A', i]));
+	}
+}
+
+#[tokio::test]
+async fn archive_storage_discarded_items() {
+	let (client, api) = setup_api();
+	let genesis_hash = format!("{:?}", client.info().genesis_hash);
+
+	let items = (0..MAX_QUERIED_ITEMS + 2)
+		.map(|_| PaginatedStorageQuery {
+			key: hex_string(&KEY),
+			query_type: StorageQueryType::Value,
+			pagination_start_key: None,
+		})
+		.collect();
+
+	let result: ArchiveStorageResult = api
+		.call("archive_unstable_storage", rpc_params(&genesis_hash, items, None))
+		.await
+		.unwrap();
+	assert_eq!(result.discarded_items, 2);
+}
+
+#[tokio::test]
+async fn archive_storage_invalid_key() {
+	let (client, api) = setup_api();
+	let genesis_hash = format!("{:?}", client.info().genesis_hash);
+
+	// Child trie prefixed keys are not queryable.
+	let key = hex_string(&sp_core::storage::well_known_keys::CHILD_STORAGE_KEY_PREFIX);
+	let result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params(
+				&genesis_hash,
+				vec![PaginatedStorageQuery {
+					key,
+					query_type: StorageQueryType::Value,
+					pagination_start_key: None,
+				}],
+				None,
+			),
+		)
+		.await
+		.unwrap();
+	assert!(result.items.is_empty());
+}
+
+fn hex_string<Data: AsRef<[u8]>>(data: &Data) -> String {
+	format!("0x{}", hex::encode(data))
+}
+
+fn rpc_params(
+	hash: &str,
+	items: Vec<PaginatedStorageQuery<String>>,
+	child_trie: Option<String>,
+) -> ArrayParams {
+	rpc_params![hash, items, child_trie]
+}
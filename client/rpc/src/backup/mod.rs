@@ -0,0 +1,68 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the [`BackupApiServer`] trait for taking checkpoints of a running node's
+//! database.
+
+#[cfg(test)]
+mod tests;
+
+use jsonrpsee::core::RpcResult;
+use sc_client_api::Backend;
+use sc_rpc_api::{backup::error::Error, DenyUnsafe};
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+use std::{marker::PhantomData, path::Path, sync::Arc};
+
+pub use sc_rpc_api::backup::{BackupApiServer, Checkpoint};
+
+/// The Backup API. All methods are unsafe.
+pub struct Backup<Block: BlockT, BE> {
+	backend: Arc<BE>,
+	deny_unsafe: DenyUnsafe,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block: BlockT, BE> Backup<Block, BE> {
+	/// Create a new Backup API.
+	pub fn new(backend: Arc<BE>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { backend, deny_unsafe, _phantom: PhantomData }
+	}
+}
+
+impl<Block, BE> BackupApiServer<Block::Hash> for Backup<Block, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+{
+	fn create_checkpoint(&self, path: String) -> RpcResult<Checkpoint<Block::Hash>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let info = self
+			.backend
+			.checkpoint(Path::new(&path))
+			.map_err(|e| Error::CheckpointFailed(e.to_string()))?;
+		Ok(Checkpoint {
+			genesis_hash: info.genesis_hash,
+			best_hash: info.best_hash,
+			best_number: info.best_number.unique_saturated_into(),
+			finalized_hash: info.finalized_hash,
+			finalized_number: info.finalized_number.unique_saturated_into(),
+		})
+	}
+}
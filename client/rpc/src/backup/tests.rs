@@ -0,0 +1,52 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use substrate_test_runtime_client::{prelude::*, runtime::Block};
+
+#[tokio::test]
+async fn unsupported_backend_fails() {
+	let backend = TestClientBuilder::new().backend();
+	let api = <Backup<Block, _>>::new(backend, DenyUnsafe::No).into_rpc();
+
+	let dir = std::env::temp_dir().join("backup_rpc_unsupported_backend_fails");
+	let err = api
+		.call::<_, Checkpoint<<Block as BlockT>::Hash>>(
+			"backup_createCheckpoint",
+			[dir.to_string_lossy()],
+		)
+		.await
+		.unwrap_err();
+	assert!(err.to_string().contains("Failed to create checkpoint"));
+	assert!(!dir.exists());
+}
+
+#[tokio::test]
+async fn deny_unsafe_works() {
+	let backend = TestClientBuilder::new().backend();
+	let api = <Backup<Block, _>>::new(backend, DenyUnsafe::Yes).into_rpc();
+
+	let request = r#"{"jsonrpc":"2.0","method":"backup_createCheckpoint","params":["/tmp/checkpoint"],"id":1}"#;
+	let (resp, _) = api.raw_json_request(request).await.expect("Raw calls should succeed");
+
+	assert_eq!(
+		resp.result,
+		r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"RPC call is unsafe to be called externally"},"id":1}"#
+	);
+}
@@ -0,0 +1,136 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the [`DryRunApiServer`] trait, previewing what extrinsics and calls would do
+//! before submitting them.
+
+#[cfg(test)]
+mod tests;
+
+use codec::Decode;
+use jsonrpsee::core::RpcResult;
+use sc_client_api::{Backend, HeaderBackend, StateBackendFor};
+use sc_rpc_api::{dry_run::error::Error, DenyUnsafe};
+use sp_api::{ApiError, ApiExt, ProvideRuntimeApi};
+use sp_core::{
+	storage::{ChildInfo, StorageKey},
+	Bytes,
+};
+use sp_dry_run::{DryRunApi as DryRunRuntimeApi, DryRunEffects, DryRunError};
+use sp_runtime::traits::Block as BlockT;
+use std::{
+	marker::{PhantomData, Send, Sync},
+	sync::Arc,
+};
+
+pub use sc_rpc_api::dry_run::{DryRunApiServer, DryRunResult};
+
+/// The Dry-run API. All methods are unsafe.
+pub struct DryRun<Block: BlockT, Client, BE> {
+	client: Arc<Client>,
+	backend: Arc<BE>,
+	deny_unsafe: DenyUnsafe,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block: BlockT, Client, BE> DryRun<Block, Client, BE> {
+	/// Create a new Dry-run API.
+	pub fn new(client: Arc<Client>, backend: Arc<BE>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, backend, deny_unsafe, _phantom: PhantomData }
+	}
+}
+
+impl<Block, Client, BE> DryRun<Block, Client, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: HeaderBackend<Block> + ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	Client::Api: DryRunRuntimeApi<Block> + ApiExt<Block, StateBackend = StateBackendFor<BE, Block>>,
+{
+	/// Dry-run with `f` at the block `at`, or the best block, and collect the storage keys it
+	/// changed, child tries included.
+	fn dry_run(
+		&self,
+		at: Option<Block::Hash>,
+		f: impl FnOnce(
+			&Client::Api,
+			Block::Hash,
+		) -> Result<Result<DryRunEffects, DryRunError>, ApiError>,
+	) -> RpcResult<DryRunResult> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let api = self.client.runtime_api();
+		let effects = f(&api, hash)
+			.map_err(|e| Error::RuntimeError(e.to_string()))?
+			.map_err(|e| Error::DryRunFailed(format!("{:?}", e)))?;
+
+		let state = self.backend.state_at(hash).map_err(|e| Error::StateQueryError(Box::new(e)))?;
+		let changes = api.into_storage_changes(&state, hash).map_err(Error::RuntimeError)?;
+
+		Ok(DryRunResult {
+			execution_result: effects.execution_result.into(),
+			emitted_events: effects.emitted_events.into_iter().map(Into::into).collect(),
+			changed_keys: changes
+				.main_storage_changes
+				.into_iter()
+				.map(|(key, _)| StorageKey(key))
+				.collect(),
+			changed_child_keys: changes
+				.child_storage_changes
+				.into_iter()
+				.map(|(storage_key, changes)| {
+					(
+						ChildInfo::new_default(&storage_key).prefixed_storage_key(),
+						changes.into_iter().map(|(key, _)| StorageKey(key)).collect(),
+					)
+				})
+				.collect(),
+		})
+	}
+}
+
+impl<Block, Client, BE> DryRunApiServer<Block::Hash> for DryRun<Block, Client, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: HeaderBackend<Block> + ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	Client::Api: DryRunRuntimeApi<Block> + ApiExt<Block, StateBackend = StateBackendFor<BE, Block>>,
+{
+	fn dry_run_extrinsic(
+		&self,
+		extrinsic: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<DryRunResult> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let extrinsic = Block::Extrinsic::decode(&mut &*extrinsic)
+			.map_err(|e| Error::DecodeError(e.to_string()))?;
+		self.dry_run(at, |api, hash| api.dry_run_extrinsic(hash, extrinsic, hash))
+	}
+
+	fn dry_run_call(
+		&self,
+		origin: Bytes,
+		call: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<DryRunResult> {
+		self.deny_unsafe.check_if_safe()?;
+
+		self.dry_run(at, |api, hash| api.dry_run_call(hash, origin.0, call.0, hash))
+	}
+}
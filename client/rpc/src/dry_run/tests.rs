@@ -0,0 +1,109 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use assert_matches::assert_matches;
+use codec::Encode;
+use jsonrpsee::{core::Error as RpcError, types::error::CallError};
+use sp_core::hashing::{blake2_128, twox_128};
+use substrate_test_runtime_client::{
+	prelude::*,
+	runtime::{Block, ExtrinsicBuilder, Transfer},
+};
+
+fn account_key(who: AccountKeyring) -> StorageKey {
+	let who = who.to_raw_public();
+	StorageKey([&twox_128(b"System")[..], &twox_128(b"Account"), &blake2_128(&who), &who].concat())
+}
+
+#[tokio::test]
+async fn dry_run_extrinsic_works() {
+	let (client, backend) = TestClientBuilder::new().build_with_backend();
+	let api = <DryRun<Block, _, _>>::new(Arc::new(client), backend, DenyUnsafe::No).into_rpc();
+
+	let xt = ExtrinsicBuilder::new_transfer(Transfer {
+		from: AccountKeyring::Alice.into(),
+		to: AccountKeyring::Bob.into(),
+		amount: 69,
+		nonce: 0,
+	})
+	.build();
+
+	let result: DryRunResult = api.call("dryRun_extrinsic", [Bytes(xt.encode())]).await.unwrap();
+
+	// The dispatch succeeded.
+	assert_eq!(result.execution_result.0[0], 0);
+	assert!(!result.emitted_events.is_empty());
+	assert!(result.changed_keys.contains(&account_key(AccountKeyring::Alice)));
+	assert!(result.changed_keys.contains(&account_key(AccountKeyring::Bob)));
+}
+
+#[tokio::test]
+async fn dry_run_reports_child_storage_changes() {
+	let (client, backend) = TestClientBuilder::new().build_with_backend();
+	let api = <DryRun<Block, _, _>>::new(Arc::new(client), backend, DenyUnsafe::No).into_rpc();
+
+	let xt = ExtrinsicBuilder::new_child_storage_change(
+		b"child".to_vec(),
+		b"key".to_vec(),
+		Some(b"value".to_vec()),
+	)
+	.build();
+
+	let result: DryRunResult = api.call("dryRun_extrinsic", [Bytes(xt.encode())]).await.unwrap();
+
+	// The dispatch succeeded.
+	assert_eq!(result.execution_result.0[0], 0);
+	assert_eq!(
+		result.changed_child_keys,
+		vec![(
+			ChildInfo::new_default(b"child").prefixed_storage_key(),
+			vec![StorageKey(b"key".to_vec())]
+		)]
+	);
+	// Child trie changes aren't reported as changes to the top trie.
+	assert!(result.changed_keys.iter().all(|key| !key.0.starts_with(b":child_storage:")));
+}
+
+#[tokio::test]
+async fn dry_run_reports_errors() {
+	let (client, backend) = TestClientBuilder::new().build_with_backend();
+	let api = <DryRun<Block, _, _>>::new(Arc::new(client), backend, DenyUnsafe::No).into_rpc();
+
+	assert_matches!(
+		api.call::<_, DryRunResult>("dryRun_extrinsic", [Bytes(vec![1, 2, 3])]).await,
+		Err(RpcError::Call(CallError::Custom(err))) if err.message().contains("Failed to decode")
+	);
+	assert_matches!(
+		api.call::<_, DryRunResult>("dryRun_call", [Bytes(vec![255]), Bytes(vec![])]).await,
+		Err(RpcError::Call(CallError::Custom(err))) if err.message().contains("InvalidOrigin")
+	);
+}
+
+#[tokio::test]
+async fn deny_unsafe_works() {
+	let (client, backend) = TestClientBuilder::new().build_with_backend();
+	let api = <DryRun<Block, _, _>>::new(Arc::new(client), backend, DenyUnsafe::Yes).into_rpc();
+
+	assert_matches!(
+		api.call::<_, DryRunResult>("dryRun_extrinsic", [Bytes(vec![])]).await,
+		Err(RpcError::Call(CallError::Custom(err)))
+			if err.message().contains("RPC call is unsafe to be called externally")
+	);
+}
@@ -33,8 +33,10 @@ pub use jsonrpsee::core::{
 pub use sc_rpc_api::DenyUnsafe;
 
 pub mod author;
+pub mod backup;
 pub mod chain;
 pub mod dev;
+pub mod dry_run;
 pub mod offchain;
 pub mod state;
 pub mod system;
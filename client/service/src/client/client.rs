@@ -606,9 +606,8 @@ where
 		}
 
 		let info = self.backend.blockchain().info();
-		let gap_block = info
-			.block_gap
-			.map_or(false, |(start, _)| *import_headers.post().number() == start);
+		let gap_block =
+			info.block_gap.map_or(false, |(_, end)| *import_headers.post().number() == end);
 
 		assert!(justifications.is_some() && finalized || justifications.is_none() || gap_block);
 
@@ -1152,6 +1151,26 @@ where
 		}
 	}
 
+	/// Is the given block the highest block of the block gap?
+	///
+	/// The gap is filled from the top, so its highest block is imported before its parent. It has
+	/// to be the parent of the lowest block above the gap.
+	fn is_block_gap_end(
+		&self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+	) -> sp_blockchain::Result<bool> {
+		let blockchain = self.backend.blockchain();
+		if !blockchain.info().block_gap.map_or(false, |(_, end)| number == end) {
+			return Ok(false)
+		}
+		let child = match blockchain.hash(number + One::one())? {
+			Some(child_hash) => blockchain.header(child_hash)?,
+			None => None,
+		};
+		Ok(child.map_or(false, |child| *child.parent_hash() == hash))
+	}
+
 	/// Get block header by id.
 	pub fn header(
 		&self,
@@ -1851,6 +1870,10 @@ where
 		{
 			BlockStatus::InChainWithState | BlockStatus::Queued => {},
 			BlockStatus::Unknown if allow_missing_parent => {},
+			BlockStatus::Unknown
+				if self
+					.is_block_gap_end(hash, number)
+					.map_err(|e| ConsensusError::ClientImport(e.to_string()))? => {},
 			BlockStatus::Unknown => return Ok(ImportResult::UnknownParent),
 			BlockStatus::InChainPruned if allow_missing_state => {},
 			BlockStatus::InChainPruned => return Ok(ImportResult::MissingState),
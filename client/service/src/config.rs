@@ -19,7 +19,7 @@
 
 //! Service configuration.
 
-pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
+pub use sc_client_db::{BlocksPruning, Constraints, Database, DatabaseSource, PruningMode};
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
 pub use sc_network::{
 	config::{
@@ -35,7 +35,9 @@ pub use sc_network::{
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
 pub use sc_telemetry::TelemetryEndpoints;
-pub use sc_transaction_pool::Options as TransactionPoolOptions;
+pub use sc_transaction_pool::{
+	Options as TransactionPoolOptions, PoolLimit, PriorityReserve, SenderKey, SenderLimit,
+};
 use sp_core::crypto::SecretString;
 use std::{
 	io, iter,
@@ -159,6 +161,15 @@ pub enum KeystoreConfig {
 		/// Node keystore's password.
 		password: Option<SecretString>,
 	},
+	/// Keystore at a path on-disk, with its key files encrypted at rest.
+	EncryptedPath {
+		/// The path of the keystore.
+		path: PathBuf,
+		/// Node keystore's password.
+		password: Option<SecretString>,
+		/// The password to encrypt the keystore's key files with.
+		encryption_password: SecretString,
+	},
 	/// In-memory keystore. Recommended for in-browser nodes.
 	InMemory,
 }
@@ -167,7 +178,7 @@ impl KeystoreConfig {
 	/// Returns the path for the keystore.
 	pub fn path(&self) -> Option<&Path> {
 		match self {
-			Self::Path { path, .. } => Some(path),
+			Self::Path { path, .. } | Self::EncryptedPath { path, .. } => Some(path),
 			Self::InMemory => None,
 		}
 	}
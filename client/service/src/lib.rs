@@ -71,7 +71,8 @@ pub use sc_chain_spec::{
 };
 
 pub use config::{
-	BasePath, BlocksPruning, Configuration, DatabaseSource, PruningMode, Role, RpcMethods, TaskType,
+	BasePath, BlocksPruning, Configuration, Constraints, DatabaseSource, PruningMode, Role,
+	RpcMethods, TaskType,
 };
 pub use sc_chain_spec::{
 	ChainSpec, ChainType, Extension as ChainSpecExtension, GenericChainSpec, NoExtension,
@@ -330,15 +331,19 @@ pub async fn build_system_rpc_future<
 				let _ = sender.send(vec![node_role]);
 			},
 			sc_rpc::system::Request::SyncState(sender) => {
-				use sc_rpc::system::SyncState;
+				use sc_rpc::system::{BlockHistory, SyncState};
 
-				match sync_service.best_seen_block().await {
-					Ok(best_seen_block) => {
+				match sync_service.status().await {
+					Ok(status) => {
 						let best_number = client.info().best_number;
 						let _ = sender.send(SyncState {
 							starting_block,
 							current_block: best_number,
-							highest_block: best_seen_block.unwrap_or(best_number),
+							highest_block: status.best_seen_block.unwrap_or(best_number),
+							block_history: status.block_history.map(|progress| BlockHistory {
+								current_block: progress.current,
+								target_block: progress.target,
+							}),
 						});
 					},
 					Err(_) => log::error!("`SyncingEngine` shut down"),
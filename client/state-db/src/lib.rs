@@ -40,14 +40,15 @@
 //!
 //! # Pruning.
 //! See `RefWindow` for pruning algorithm details. `StateDb` prunes on each canonicalization until
-//! pruning constraints are satisfied.
+//! pruning constraints are satisfied. Constraints may limit the number of blocks in the pruning
+//! window, the time since a block was canonicalized, and the size of the database.
 
 mod noncanonical;
 mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use log::trace;
 use noncanonical::NonCanonicalOverlay;
 use parking_lot::RwLock;
@@ -55,6 +56,7 @@ use pruning::{HaveBlock, RefWindow};
 use std::{
 	collections::{hash_map::Entry, HashMap},
 	fmt,
+	time::Duration,
 };
 
 const LOG_TARGET: &str = "state-db";
@@ -63,6 +65,7 @@ const PRUNING_MODE: &[u8] = b"mode";
 const PRUNING_MODE_ARCHIVE: &[u8] = b"archive";
 const PRUNING_MODE_ARCHIVE_CANON: &[u8] = b"archive_canonical";
 const PRUNING_MODE_CONSTRAINED: &[u8] = b"constrained";
+const PRUNING_CONSTRAINTS: &[u8] = b"constraints";
 pub(crate) const DEFAULT_MAX_BLOCK_CONSTRAINT: u32 = 256;
 
 /// Database value type.
@@ -219,12 +222,54 @@ pub struct CommitSet<H: Hash> {
 	pub meta: ChangeSet<Vec<u8>>,
 }
 
-/// Pruning constraints. If none are specified pruning is
+/// Pruning constraints. A canonicalized block is pruned as soon as any of the specified
+/// constraints is violated. If none are specified, only non-canonical states are kept.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Constraints {
-	/// Maximum blocks. Defaults to 0 when unspecified, effectively keeping only non-canonical
-	/// states.
+	/// Maximum blocks. Defaults to 0 when no constraint is specified, effectively keeping only
+	/// non-canonical states.
 	pub max_blocks: Option<u32>,
+	/// Maximum age of a block's state, from the timestamp of the block to the timestamp of the
+	/// last canonicalized block.
+	pub max_age: Option<Duration>,
+	/// Maximum size of the database in bytes. While the database is larger, the pruning window
+	/// shrinks by one block for every block canonicalized, until the database is small enough
+	/// again.
+	pub max_size: Option<u64>,
+}
+
+impl Constraints {
+	/// Encode the constraints to be stored in the meta-data.
+	fn encode_meta(&self) -> Vec<u8> {
+		(self.max_blocks, self.max_age.map(|max_age| max_age.as_millis() as u64), self.max_size)
+			.encode()
+	}
+
+	/// Decode constraints stored in the meta-data by [`Self::encode_meta`].
+	fn decode_meta(mut encoded: &[u8]) -> Option<Self> {
+		let (max_blocks, max_age, max_size) =
+			<(Option<u32>, Option<u64>, Option<u64>)>::decode(&mut encoded).ok()?;
+		Some(Constraints { max_blocks, max_age: max_age.map(Duration::from_millis), max_size })
+	}
+
+	/// The maximum number of blocks in the pruning window, if bounded.
+	fn max_blocks(&self) -> Option<u32> {
+		match self {
+			Constraints { max_blocks: None, max_age: None, max_size: None } => Some(0),
+			Constraints { max_blocks, .. } => *max_blocks,
+		}
+	}
+}
+
+/// Conditions at the time of a canonicalization, which the age and size based pruning
+/// constraints are evaluated against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruningContext {
+	/// Timestamp of the block being canonicalized, in milliseconds since the UNIX epoch, if
+	/// known. If unknown, the block is given the timestamp of the block before it.
+	pub timestamp: Option<u64>,
+	/// Current size of the database in bytes, if known.
+	pub db_size: Option<u64>,
 }
 
 /// Pruning mode.
@@ -241,7 +286,27 @@ pub enum PruningMode {
 impl PruningMode {
 	/// Create a mode that keeps given number of blocks.
 	pub fn blocks_pruning(n: u32) -> PruningMode {
-		PruningMode::Constrained(Constraints { max_blocks: Some(n) })
+		PruningMode::Constrained(Constraints { max_blocks: Some(n), max_age: None, max_size: None })
+	}
+
+	/// Create a mode that keeps the state of blocks whose timestamp is within the given duration
+	/// of the timestamp of the last canonicalized block.
+	pub fn age_pruning(max_age: Duration) -> PruningMode {
+		PruningMode::Constrained(Constraints {
+			max_blocks: None,
+			max_age: Some(max_age),
+			max_size: None,
+		})
+	}
+
+	/// Create a mode that keeps as many states as possible while the database is smaller than the
+	/// given number of bytes.
+	pub fn size_pruning(max_size: u64) -> PruningMode {
+		PruningMode::Constrained(Constraints {
+			max_blocks: None,
+			max_age: None,
+			max_size: Some(max_size),
+		})
 	}
 
 	/// Is this an archive (either ArchiveAll or ArchiveCanonical) pruning mode?
@@ -279,10 +344,37 @@ impl Default for PruningMode {
 
 impl Default for Constraints {
 	fn default() -> Self {
-		Self { max_blocks: Some(DEFAULT_MAX_BLOCK_CONSTRAINT) }
+		Self { max_blocks: Some(DEFAULT_MAX_BLOCK_CONSTRAINT), max_age: None, max_size: None }
 	}
 }
 
+/// Convert the meta values of a state database whose backing database doesn't count references
+/// for a backing database which does.
+///
+/// `meta` holds every meta value. The keys of the trie nodes referenced by the journals are
+/// converted with `convert_key`, which allows dropping a key prefix the new database doesn't use.
+pub fn convert_meta_for_ref_counting<BlockHash: Hash, Key: Hash>(
+	meta: impl IntoIterator<Item = (Vec<u8>, DBValue)>,
+	convert_key: impl Fn(Key) -> Key,
+) -> Result<Vec<(Vec<u8>, DBValue)>, codec::Error> {
+	let mut converted = Vec::new();
+	let mut pruning_journal = Vec::new();
+	for (key, value) in meta {
+		if let Some(record) =
+			noncanonical::convert_journal_record::<BlockHash, Key>(&key, &value, &convert_key)
+		{
+			converted.push((key, record?));
+		} else if let Some(block) = pruning::journal_record_block(&key) {
+			pruning_journal.push((block, value));
+		} else {
+			converted.push((key, value));
+		}
+	}
+	converted
+		.extend(pruning::convert_journal_records::<BlockHash, Key>(pruning_journal, convert_key)?);
+	Ok(converted)
+}
+
 fn to_meta_key<S: Codec>(suffix: &[u8], data: &S) -> Vec<u8> {
 	let mut buffer = data.encode();
 	buffer.extend(suffix);
@@ -317,9 +409,13 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		trace!(target: LOG_TARGET, "StateDb settings: {:?}. Ref-counting: {}", mode, ref_counting);
 
 		let non_canonical: NonCanonicalOverlay<BlockHash, Key> = NonCanonicalOverlay::new(&db)?;
-		let pruning: Option<RefWindow<BlockHash, Key, D>> = match mode {
-			PruningMode::Constrained(Constraints { max_blocks }) =>
-				Some(RefWindow::new(db, max_blocks.unwrap_or(0), ref_counting)?),
+		let pruning: Option<RefWindow<BlockHash, Key, D>> = match &mode {
+			PruningMode::Constrained(constraints) => {
+				// The window isn't bounded by a number of blocks when only the age or size are
+				// constrained, so size the caches for the default window.
+				let window_size = constraints.max_blocks().unwrap_or(DEFAULT_MAX_BLOCK_CONSTRAINT);
+				Some(RefWindow::new(db, window_size, ref_counting)?)
+			},
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
 		};
 
@@ -346,7 +442,11 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		}
 	}
 
-	fn canonicalize_block(&mut self, hash: &BlockHash) -> Result<CommitSet<Key>, Error<D::Error>> {
+	fn canonicalize_block(
+		&mut self,
+		hash: &BlockHash,
+		context: PruningContext,
+	) -> Result<CommitSet<Key>, Error<D::Error>> {
 		// NOTE: it is important that the change to `LAST_CANONICAL` (emit from
 		// `non_canonical.canonicalize`) and the insert of the new pruning journal (emit from
 		// `pruning.note_canonical`) are collected into the same `CommitSet` and are committed to
@@ -359,10 +459,12 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		if self.mode == PruningMode::ArchiveCanonical {
 			commit.data.deleted.clear();
 		}
+		let mut window_before = 0;
 		if let Some(ref mut pruning) = self.pruning {
-			pruning.note_canonical(hash, number, &mut commit)?;
+			window_before = pruning.window_size();
+			pruning.note_canonical(hash, number, context.timestamp, &mut commit)?;
 		}
-		self.prune(&mut commit)?;
+		self.prune(&mut commit, context, window_before)?;
 		Ok(commit)
 	}
 
@@ -408,12 +510,42 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		}
 	}
 
-	fn prune(&mut self, commit: &mut CommitSet<Key>) -> Result<(), Error<D::Error>> {
+	/// Prune until the constraints are satisfied. `window_before` is the size of the pruning
+	/// window before the block being canonicalized was added to it.
+	fn prune(
+		&mut self,
+		commit: &mut CommitSet<Key>,
+		context: PruningContext,
+		window_before: u64,
+	) -> Result<(), Error<D::Error>> {
 		if let (&mut Some(ref mut pruning), PruningMode::Constrained(constraints)) =
 			(&mut self.pruning, &self.mode)
 		{
+			let max_blocks = constraints.max_blocks();
+			// The size of the database only decreases after the pruned nodes are compacted away,
+			// so shrink the window gradually instead of pruning until the size is satisfied.
+			let size_target = match (constraints.max_size, context.db_size) {
+				(Some(max_size), Some(db_size)) if db_size > max_size =>
+					Some(window_before.saturating_sub(1)),
+				_ => None,
+			};
 			loop {
-				if pruning.window_size() <= constraints.max_blocks.unwrap_or(0) as u64 {
+				let window_size = pruning.window_size();
+				let exceeds_blocks = max_blocks.map_or(false, |max| window_size > max as u64);
+				let exceeds_size = size_target.map_or(false, |target| window_size > target);
+				// The age is measured against the last block rather than the current time, so that
+				// blocks canonicalized in quick succession while catching up are still pruned.
+				let exceeds_age = match (constraints.max_age, pruning.last_timestamp()) {
+					(Some(max_age), Some(last)) if window_size > 0 =>
+						match pruning.next_timestamp() {
+							Err(Error::StateDb(StateDbError::BlockUnavailable)) => break,
+							res => res?.map_or(false, |at| {
+								last.saturating_sub(at) > max_age.as_millis() as u64
+							}),
+						},
+					_ => false,
+				};
+				if !(exceeds_blocks || exceeds_size || exceeds_age) {
 					break
 				}
 
@@ -540,7 +672,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 	) -> Result<(CommitSet<Key>, StateDb<BlockHash, Key, D>), Error<D::Error>> {
 		let stored_mode = fetch_stored_pruning_mode(&db)?;
 
-		let selected_mode = match (should_init, stored_mode, requested_mode) {
+		let selected_mode = match (should_init, stored_mode.clone(), requested_mode) {
 			(true, stored_mode, requested_mode) => {
 				assert!(stored_mode.is_none(), "The storage has just been initialized. No meta-data is expected to be found in it.");
 				requested_mode.unwrap_or_default()
@@ -554,10 +686,16 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 
 			(false, Some(stored), None) => stored,
 
-			(false, Some(stored), Some(requested)) => choose_pruning_mode(stored, requested)?,
+			(false, Some(stored), Some(requested)) =>
+				choose_pruning_mode(stored.clone(), requested)?,
 		};
 
-		let db_init_commit_set = if should_init {
+		let switched_mode =
+			stored_mode.as_ref().map_or(false, |stored| stored.id() != selected_mode.id());
+		let changed_mode = stored_mode.as_ref() != Some(&selected_mode);
+		let mut state_db_sync = StateDbSync::new(selected_mode.clone(), ref_counting, db)?;
+
+		let db_init_commit_set = if should_init || changed_mode {
 			let mut cs: CommitSet<Key> = Default::default();
 
 			let key = to_meta_key(PRUNING_MODE, &());
@@ -565,13 +703,27 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 
 			cs.meta.inserted.push((key, value));
 
+			if let PruningMode::Constrained(constraints) = &selected_mode {
+				let key = to_meta_key(PRUNING_CONSTRAINTS, &());
+				cs.meta.inserted.push((key, constraints.encode_meta()));
+			}
+
+			// Blocks canonicalized while archiving have no pruning journal, so start the pruning
+			// window after them.
+			if let (true, Some(pruning), Some(last_canonical)) = (
+				switched_mode,
+				state_db_sync.pruning.as_mut(),
+				state_db_sync.non_canonical.last_canonicalized_block_number(),
+			) {
+				pruning.start_after(last_canonical, &mut cs);
+			}
+
 			cs
 		} else {
 			Default::default()
 		};
 
-		let state_db =
-			StateDb { db: RwLock::new(StateDbSync::new(selected_mode, ref_counting, db)?) };
+		let state_db = StateDb { db: RwLock::new(state_db_sync) };
 
 		Ok((db_init_commit_set, state_db))
 	}
@@ -593,7 +745,17 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 
 	/// Finalize a previously inserted block.
 	pub fn canonicalize_block(&self, hash: &BlockHash) -> Result<CommitSet<Key>, Error<D::Error>> {
-		self.db.write().canonicalize_block(hash)
+		self.canonicalize_block_with(hash, PruningContext::default())
+	}
+
+	/// Finalize a previously inserted block, evaluating the pruning constraints against the given
+	/// `context`.
+	pub fn canonicalize_block_with(
+		&self,
+		hash: &BlockHash,
+		context: PruningContext,
+	) -> Result<CommitSet<Key>, Error<D::Error>> {
+		self.db.write().canonicalize_block(hash, context)
 	}
 
 	/// Prevents pruning of specified block and its descendants.
@@ -676,7 +838,20 @@ fn fetch_stored_pruning_mode<D: MetaDb>(db: &D) -> Result<Option<PruningMode>, E
 	let meta_key_mode = to_meta_key(PRUNING_MODE, &());
 	if let Some(stored_mode) = db.get_meta(&meta_key_mode).map_err(Error::Db)? {
 		if let Some(mode) = PruningMode::from_id(&stored_mode) {
-			Ok(Some(mode))
+			// Databases created before the constraints were stored keep the default ones.
+			let meta_key_constraints = to_meta_key(PRUNING_CONSTRAINTS, &());
+			match (mode, db.get_meta(&meta_key_constraints).map_err(Error::Db)?) {
+				(PruningMode::Constrained(_), Some(stored_constraints)) =>
+					match Constraints::decode_meta(&stored_constraints) {
+						Some(constraints) => Ok(Some(PruningMode::Constrained(constraints))),
+						None => Err(StateDbError::Metadata(format!(
+							"Invalid value stored for PRUNING_CONSTRAINTS: {:02x?}",
+							stored_constraints
+						))
+						.into()),
+					},
+				(mode, _) => Ok(Some(mode)),
+			}
 		} else {
 			Err(StateDbError::Metadata(format!(
 				"Invalid value stored for PRUNING_MODE: {:02x?}",
@@ -689,6 +864,11 @@ fn fetch_stored_pruning_mode<D: MetaDb>(db: &D) -> Result<Option<PruningMode>, E
 	}
 }
 
+/// Choose the mode to open an existing database with.
+///
+/// An archive database may be switched to a constrained mode. This stops the state from growing,
+/// yet the states of the blocks canonicalized before the switch are never pruned and are reported
+/// as pruned from then on. The other way around is impossible, as pruned states can't be restored.
 fn choose_pruning_mode(
 	stored: PruningMode,
 	requested: PruningMode,
@@ -697,8 +877,10 @@ fn choose_pruning_mode(
 		(PruningMode::ArchiveAll, PruningMode::ArchiveAll) => Ok(PruningMode::ArchiveAll),
 		(PruningMode::ArchiveCanonical, PruningMode::ArchiveCanonical) =>
 			Ok(PruningMode::ArchiveCanonical),
-		(PruningMode::Constrained(_), PruningMode::Constrained(requested)) =>
-			Ok(PruningMode::Constrained(requested)),
+		(
+			PruningMode::Constrained(_) | PruningMode::ArchiveAll | PruningMode::ArchiveCanonical,
+			PruningMode::Constrained(requested),
+		) => Ok(PruningMode::Constrained(requested)),
 		(stored, requested) => Err(StateDbError::IncompatiblePruningModes { requested, stored }),
 	}
 }
@@ -707,9 +889,10 @@ fn choose_pruning_mode(
 mod tests {
 	use crate::{
 		test::{make_changeset, make_db, TestDb},
-		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
+		Constraints, Error, IsPruned, PruningContext, PruningMode, StateDb, StateDbError,
 	};
 	use sp_core::H256;
+	use std::time::Duration;
 
 	fn make_test_db(settings: PruningMode) -> (TestDb, StateDb<H256, H256, TestDb>) {
 		let mut db = make_db(&[91, 921, 922, 93, 94]);
@@ -789,8 +972,11 @@ mod tests {
 
 	#[test]
 	fn block_record_unavailable() {
-		let (mut db, state_db) =
-			make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(1) }));
+		let (mut db, state_db) = make_test_db(PruningMode::Constrained(Constraints {
+			max_blocks: Some(1),
+			max_age: None,
+			max_size: None,
+		}));
 		// import 2 blocks
 		for i in &[5, 6] {
 			db.commit(
@@ -824,13 +1010,21 @@ mod tests {
 
 	#[test]
 	fn prune_window_0() {
-		let (db, _) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(0) }));
+		let (db, _) = make_test_db(PruningMode::Constrained(Constraints {
+			max_blocks: Some(0),
+			max_age: None,
+			max_size: None,
+		}));
 		assert!(db.data_eq(&make_db(&[21, 3, 922, 94])));
 	}
 
 	#[test]
 	fn prune_window_1() {
-		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(1) }));
+		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints {
+			max_blocks: Some(1),
+			max_age: None,
+			max_size: None,
+		}));
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(0), 0), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::Pruned);
@@ -840,7 +1034,11 @@ mod tests {
 
 	#[test]
 	fn prune_window_2() {
-		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(2) }));
+		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints {
+			max_blocks: Some(2),
+			max_age: None,
+			max_size: None,
+		}));
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(0), 0), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::NotPruned);
@@ -848,11 +1046,178 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 21, 3, 921, 922, 93, 94])));
 	}
 
+	/// Insert a chain of blocks with the given numbers, each replacing the key of its parent.
+	fn insert_chain(
+		db: &mut TestDb,
+		state_db: &StateDb<H256, H256, TestDb>,
+		blocks: std::ops::RangeInclusive<u64>,
+	) {
+		for i in blocks {
+			db.commit(
+				&state_db
+					.insert_block(
+						&H256::from_low_u64_be(i),
+						i,
+						&H256::from_low_u64_be(i - 1),
+						make_changeset(&[i], &[i - 1]),
+					)
+					.unwrap(),
+			);
+		}
+	}
+
+	fn canonicalize_at(
+		db: &mut TestDb,
+		state_db: &StateDb<H256, H256, TestDb>,
+		block: u64,
+		timestamp: Option<u64>,
+		db_size: Option<u64>,
+	) {
+		db.commit(
+			&state_db
+				.canonicalize_block_with(
+					&H256::from_low_u64_be(block),
+					PruningContext { timestamp, db_size },
+				)
+				.unwrap(),
+		);
+	}
+
+	#[test]
+	fn prune_by_age() {
+		let mut db = make_db(&[0]);
+		let (state_db_init, state_db) = StateDb::open(
+			db.clone(),
+			Some(PruningMode::age_pruning(Duration::from_secs(10))),
+			false,
+			true,
+		)
+		.unwrap();
+		db.commit(&state_db_init);
+		insert_chain(&mut db, &state_db, 1..=4);
+
+		canonicalize_at(&mut db, &state_db, 1, Some(0), None);
+		canonicalize_at(&mut db, &state_db, 2, Some(5_000), None);
+		canonicalize_at(&mut db, &state_db, 3, Some(12_000), None);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[1, 2, 3])));
+
+		canonicalize_at(&mut db, &state_db, 4, Some(16_000), None);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[2, 3, 4])));
+	}
+
+	#[test]
+	fn prune_by_age_of_blocks_canonicalized_in_quick_succession() {
+		const DAY: u64 = 24 * 60 * 60 * 1000;
+		let mut db = make_db(&[0]);
+		let (state_db_init, state_db) = StateDb::open(
+			db.clone(),
+			Some(PruningMode::age_pruning(Duration::from_secs(60 * 60))),
+			false,
+			true,
+		)
+		.unwrap();
+		db.commit(&state_db_init);
+		insert_chain(&mut db, &state_db, 1..=5);
+
+		// Blocks produced a day apart are pruned, however fast they are canonicalized.
+		for i in 1..=3 {
+			canonicalize_at(&mut db, &state_db, i, Some(i * DAY), None);
+		}
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[2, 3])));
+
+		// A block of unknown timestamp is as old as the block before it.
+		canonicalize_at(&mut db, &state_db, 4, None, None);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[2, 3, 4])));
+
+		canonicalize_at(&mut db, &state_db, 5, Some(3 * DAY + 2 * 60 * 60 * 1000), None);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(4), 4), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(5), 5), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[4, 5])));
+	}
+
+	#[test]
+	fn prune_by_size() {
+		let mut db = make_db(&[0]);
+		let (state_db_init, state_db) =
+			StateDb::open(db.clone(), Some(PruningMode::size_pruning(100)), false, true).unwrap();
+		db.commit(&state_db_init);
+		insert_chain(&mut db, &state_db, 1..=5);
+
+		for i in 1..=3 {
+			canonicalize_at(&mut db, &state_db, i, None, Some(50));
+		}
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[0, 1, 2, 3])));
+
+		// Exceeding the size shrinks the window by one block per canonicalization.
+		canonicalize_at(&mut db, &state_db, 4, None, Some(150));
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[2, 3, 4])));
+
+		// The window is kept once the size is satisfied again.
+		canonicalize_at(&mut db, &state_db, 5, None, Some(80));
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+		assert!(db.data_eq(&make_db(&[2, 3, 4, 5])));
+	}
+
+	#[test]
+	fn switch_archive_to_constrained() {
+		for (archive, count_insertions) in [
+			(PruningMode::ArchiveAll, false),
+			(PruningMode::ArchiveCanonical, false),
+			(PruningMode::ArchiveAll, true),
+			(PruningMode::ArchiveCanonical, true),
+		] {
+			let mut db = make_db(&[0]);
+			let (state_db_init, state_db) =
+				StateDb::open(db.clone(), Some(archive), count_insertions, true).unwrap();
+			db.commit(&state_db_init);
+			insert_chain(&mut db, &state_db, 1..=2);
+			for i in 1..=2 {
+				db.commit(&state_db.canonicalize_block(&H256::from_low_u64_be(i)).unwrap());
+			}
+			std::mem::drop(state_db);
+
+			let (state_db_init, state_db) = StateDb::open(
+				db.clone(),
+				Some(PruningMode::blocks_pruning(1)),
+				count_insertions,
+				false,
+			)
+			.unwrap();
+			db.commit(&state_db_init);
+			insert_chain(&mut db, &state_db, 3..=5);
+			for i in 3..=5 {
+				db.commit(&state_db.canonicalize_block(&H256::from_low_u64_be(i)).unwrap());
+			}
+			assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(4), 4), IsPruned::Pruned);
+			// The window backed by the database may not have loaded the last block yet.
+			assert_ne!(state_db.is_pruned(&H256::from_low_u64_be(5), 5), IsPruned::Pruned);
+			// Only keys deleted after the switch are reclaimed.
+			assert!(db.data_eq(&make_db(&[0, 1, 4, 5])));
+			std::mem::drop(state_db);
+
+			// The new mode is stored along with its constraints.
+			let (_, state_db) =
+				StateDb::<H256, H256, TestDb>::open(db.clone(), None, count_insertions, false)
+					.unwrap();
+			assert_eq!(state_db.pruning_mode(), PruningMode::blocks_pruning(1));
+		}
+	}
+
 	#[test]
 	fn detects_incompatible_mode() {
 		let mut db = make_db(&[]);
 		let (state_db_init, state_db) =
-			StateDb::open(db.clone(), Some(PruningMode::ArchiveAll), false, true).unwrap();
+			StateDb::open(db.clone(), Some(PruningMode::blocks_pruning(2)), false, true).unwrap();
 		db.commit(&state_db_init);
 		db.commit(
 			&state_db
@@ -864,9 +1229,8 @@ mod tests {
 				)
 				.unwrap(),
 		);
-		let new_mode = PruningMode::Constrained(Constraints { max_blocks: Some(2) });
 		let state_db_open_result: Result<(_, StateDb<H256, H256, TestDb>), _> =
-			StateDb::open(db.clone(), Some(new_mode), false, false);
+			StateDb::open(db.clone(), Some(PruningMode::ArchiveAll), false, false);
 		assert!(state_db_open_result.is_err());
 	}
 
@@ -906,6 +1270,17 @@ mod tests {
 			(None, Some(PruningMode::ArchiveAll), Err(())),
 			(None, Some(PruningMode::ArchiveCanonical), Err(())),
 			(Some(PruningMode::blocks_pruning(256)), None, Ok(PruningMode::blocks_pruning(256))),
+			(Some(PruningMode::blocks_pruning(128)), None, Ok(PruningMode::blocks_pruning(128))),
+			(
+				Some(PruningMode::age_pruning(Duration::from_secs(3600))),
+				None,
+				Ok(PruningMode::age_pruning(Duration::from_secs(3600))),
+			),
+			(
+				Some(PruningMode::size_pruning(1 << 30)),
+				None,
+				Ok(PruningMode::size_pruning(1 << 30)),
+			),
 			(
 				Some(PruningMode::blocks_pruning(256)),
 				Some(PruningMode::blocks_pruning(256)),
@@ -924,9 +1299,21 @@ mod tests {
 			(Some(PruningMode::blocks_pruning(256)), Some(PruningMode::ArchiveAll), Err(())),
 			(Some(PruningMode::blocks_pruning(256)), Some(PruningMode::ArchiveCanonical), Err(())),
 			(Some(PruningMode::ArchiveAll), None, Ok(PruningMode::ArchiveAll)),
-			(Some(PruningMode::ArchiveAll), Some(PruningMode::blocks_pruning(256)), Err(())),
-			(Some(PruningMode::ArchiveAll), Some(PruningMode::blocks_pruning(128)), Err(())),
-			(Some(PruningMode::ArchiveAll), Some(PruningMode::blocks_pruning(512)), Err(())),
+			(
+				Some(PruningMode::ArchiveAll),
+				Some(PruningMode::blocks_pruning(256)),
+				Ok(PruningMode::blocks_pruning(256)),
+			),
+			(
+				Some(PruningMode::ArchiveAll),
+				Some(PruningMode::blocks_pruning(128)),
+				Ok(PruningMode::blocks_pruning(128)),
+			),
+			(
+				Some(PruningMode::ArchiveAll),
+				Some(PruningMode::blocks_pruning(512)),
+				Ok(PruningMode::blocks_pruning(512)),
+			),
 			(
 				Some(PruningMode::ArchiveAll),
 				Some(PruningMode::ArchiveAll),
@@ -934,9 +1321,21 @@ mod tests {
 			),
 			(Some(PruningMode::ArchiveAll), Some(PruningMode::ArchiveCanonical), Err(())),
 			(Some(PruningMode::ArchiveCanonical), None, Ok(PruningMode::ArchiveCanonical)),
-			(Some(PruningMode::ArchiveCanonical), Some(PruningMode::blocks_pruning(256)), Err(())),
-			(Some(PruningMode::ArchiveCanonical), Some(PruningMode::blocks_pruning(128)), Err(())),
-			(Some(PruningMode::ArchiveCanonical), Some(PruningMode::blocks_pruning(512)), Err(())),
+			(
+				Some(PruningMode::ArchiveCanonical),
+				Some(PruningMode::blocks_pruning(256)),
+				Ok(PruningMode::blocks_pruning(256)),
+			),
+			(
+				Some(PruningMode::ArchiveCanonical),
+				Some(PruningMode::blocks_pruning(128)),
+				Ok(PruningMode::blocks_pruning(128)),
+			),
+			(
+				Some(PruningMode::ArchiveCanonical),
+				Some(PruningMode::blocks_pruning(512)),
+				Ok(PruningMode::blocks_pruning(512)),
+			),
 			(Some(PruningMode::ArchiveCanonical), Some(PruningMode::ArchiveAll), Err(())),
 			(
 				Some(PruningMode::ArchiveCanonical),
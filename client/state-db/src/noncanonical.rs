@@ -82,6 +82,30 @@ fn to_journal_key(block: u64, index: u64) -> Vec<u8> {
 	to_meta_key(NON_CANONICAL_JOURNAL, &(block, index))
 }
 
+/// Convert the keys of the journal record stored under `key` with `convert_key`.
+///
+/// Returns `None` if `key` is not the key of a journal record.
+pub(crate) fn convert_journal_record<BlockHash: Hash, Key: Hash>(
+	key: &[u8],
+	value: &[u8],
+	convert_key: impl Fn(Key) -> Key,
+) -> Option<Result<Vec<u8>, codec::Error>> {
+	let is_journal_key =
+		key.len() == 16 + NON_CANONICAL_JOURNAL.len() && key.ends_with(NON_CANONICAL_JOURNAL);
+	if !is_journal_key {
+		return None
+	}
+	Some(JournalRecord::<BlockHash, Key>::decode(&mut &*value).map(|record| {
+		JournalRecord {
+			hash: record.hash,
+			parent_hash: record.parent_hash,
+			inserted: record.inserted.into_iter().map(|(k, v)| (convert_key(k), v)).collect(),
+			deleted: record.deleted.into_iter().map(&convert_key).collect(),
+		}
+		.encode()
+	}))
+}
+
 #[cfg_attr(test, derive(PartialEq, Debug))]
 struct BlockOverlay<BlockHash: Hash, Key: Hash> {
 	hash: BlockHash,
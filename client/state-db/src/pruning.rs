@@ -23,7 +23,8 @@
 //! There is also a global index of node key to block number.
 //! If a node is re-inserted into the window it gets removed from
 //! the death list.
-//! The changes are journaled in the DB.
+//! The changes are journaled in the DB, next to the time each block was canonicalized at, which
+//! time-based pruning constraints are evaluated against.
 
 use crate::{
 	noncanonical::LAST_CANONICAL, to_meta_key, CommitSet, Error, Hash, MetaDb, StateDbError,
@@ -35,6 +36,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 pub(crate) const LAST_PRUNED: &[u8] = b"last_pruned";
 const PRUNING_JOURNAL: &[u8] = b"pruning_journal";
+const PRUNING_JOURNAL_TIME: &[u8] = b"pruning_journal_time";
 
 /// See module documentation.
 pub struct RefWindow<BlockHash: Hash, Key: Hash, D: MetaDb> {
@@ -43,6 +45,8 @@ pub struct RefWindow<BlockHash: Hash, Key: Hash, D: MetaDb> {
 	queue: DeathRowQueue<BlockHash, Key, D>,
 	/// Block number that is next to be pruned.
 	base: u64,
+	/// Timestamp of the last block added to the window, if known.
+	last_timestamp: Option<u64>,
 }
 
 /// `DeathRowQueue` used to keep track of blocks in the pruning window, there are two flavors:
@@ -98,7 +102,8 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 						record.inserted.len(),
 						record.deleted.len(),
 					);
-					queue.import(base, block, record);
+					let timestamp = load_timestamp(db, block)?;
+					queue.import(base, block, record, timestamp);
 				},
 				None => break,
 			}
@@ -128,7 +133,13 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 	}
 
 	/// import a new block to the back of the queue
-	fn import(&mut self, base: u64, num: u64, journal_record: JournalRecord<BlockHash, Key>) {
+	fn import(
+		&mut self,
+		base: u64,
+		num: u64,
+		journal_record: JournalRecord<BlockHash, Key>,
+		timestamp: Option<u64>,
+	) {
 		let JournalRecord { hash, inserted, deleted } = journal_record;
 		trace!(target: LOG_TARGET, "Importing {}, base={}", num, base);
 		match self {
@@ -137,7 +148,11 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 				// cache.
 				if num == base + cache.len() as u64 && cache.len() < *cache_capacity {
 					trace!(target: LOG_TARGET, "Adding to DB backed cache {:?} (#{})", hash, num);
-					cache.push_back(DeathRow { hash, deleted: deleted.into_iter().collect() });
+					cache.push_back(DeathRow {
+						hash,
+						deleted: deleted.into_iter().collect(),
+						timestamp,
+					});
 				}
 				*last = Some(num);
 			},
@@ -153,7 +168,11 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 				for k in deleted.iter() {
 					death_index.insert(k.clone(), imported_block);
 				}
-				death_rows.push_back(DeathRow { hash, deleted: deleted.into_iter().collect() });
+				death_rows.push_back(DeathRow {
+					hash,
+					deleted: deleted.into_iter().collect(),
+					timestamp,
+				});
 			},
 		}
 	}
@@ -257,16 +276,28 @@ fn load_death_row_from_db<BlockHash: Hash, Key: Hash, D: MetaDb>(
 	match db.get_meta(&journal_key).map_err(Error::Db)? {
 		Some(record) => {
 			let JournalRecord { hash, deleted, .. } = Decode::decode(&mut record.as_slice())?;
-			Ok(Some(DeathRow { hash, deleted: deleted.into_iter().collect() }))
+			let timestamp = load_timestamp(db, block)?;
+			Ok(Some(DeathRow { hash, deleted: deleted.into_iter().collect(), timestamp }))
 		},
 		None => Ok(None),
 	}
 }
 
+/// Load the timestamp of the given block, in milliseconds since the UNIX epoch.
+///
+/// This is `None` if the timestamp of the block, and of all blocks before it, was unknown.
+fn load_timestamp<D: MetaDb>(db: &D, block: u64) -> Result<Option<u64>, Error<D::Error>> {
+	match db.get_meta(&to_journal_time_key(block)).map_err(Error::Db)? {
+		Some(time) => Ok(Some(u64::decode(&mut time.as_slice())?)),
+		None => Ok(None),
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct DeathRow<BlockHash: Hash, Key: Hash> {
 	hash: BlockHash,
 	deleted: HashSet<Key>,
+	timestamp: Option<u64>,
 }
 
 #[derive(Encode, Decode, Default)]
@@ -280,6 +311,58 @@ fn to_journal_key(block: u64) -> Vec<u8> {
 	to_meta_key(PRUNING_JOURNAL, &block)
 }
 
+fn to_journal_time_key(block: u64) -> Vec<u8> {
+	to_meta_key(PRUNING_JOURNAL_TIME, &block)
+}
+
+/// Number of the block whose journal record is stored under `key`, if it is one.
+pub(crate) fn journal_record_block(key: &[u8]) -> Option<u64> {
+	if key.len() != 8 + PRUNING_JOURNAL.len() || !key.ends_with(PRUNING_JOURNAL) {
+		return None
+	}
+	u64::decode(&mut &key[..8]).ok()
+}
+
+/// Convert the journal records of a window kept in memory to the records of a window backed by
+/// a database with reference counting, converting keys with `convert_key`.
+///
+/// A key inserted again by a later block must survive the pruning of the blocks which deleted it
+/// before. The memory window takes care of this when importing the records, while a database with
+/// reference counting relies on having counted the insertion. As every node is only counted once
+/// when copied, such deletions are dropped here, the same way the memory window does.
+pub(crate) fn convert_journal_records<BlockHash: Hash, Key: Hash>(
+	mut records: Vec<(u64, Vec<u8>)>,
+	convert_key: impl Fn(Key) -> Key,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, codec::Error> {
+	records.sort_by_key(|(block, _)| *block);
+	let mut rows: Vec<(u64, BlockHash, HashSet<Key>)> = Vec::with_capacity(records.len());
+	let mut death_index: HashMap<Key, usize> = HashMap::new();
+	for (block, value) in records {
+		let JournalRecord { hash, inserted, deleted } =
+			JournalRecord::<BlockHash, Key>::decode(&mut value.as_slice())?;
+		for k in inserted {
+			if let Some(row) = death_index.remove(&k) {
+				rows[row].2.remove(&k);
+			}
+		}
+		for k in deleted.iter() {
+			death_index.insert(k.clone(), rows.len());
+		}
+		rows.push((block, hash, deleted.into_iter().collect()));
+	}
+	Ok(rows
+		.into_iter()
+		.map(|(block, hash, deleted)| {
+			let record = JournalRecord::<BlockHash, Key> {
+				hash,
+				inserted: Vec::new(),
+				deleted: deleted.into_iter().map(&convert_key).collect(),
+			};
+			(to_journal_key(block), record.encode())
+		})
+		.collect())
+}
+
 /// The result return by `RefWindow::have_block`
 #[derive(Debug, PartialEq, Eq)]
 pub enum HaveBlock {
@@ -320,6 +403,12 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 				None => None,
 			};
 
+		// the timestamp of the last block in the queue, given to the next one if its own is unknown
+		let last_timestamp = match last_canonicalized_number {
+			Some(last) => load_timestamp(&db, last)?,
+			None => None,
+		};
+
 		let queue = if count_insertions {
 			// Highly scientific crafted number for deciding when to print the warning!
 			//
@@ -348,27 +437,43 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 			DeathRowQueue::new_db_backed(db, base, last, window_size)?
 		};
 
-		Ok(RefWindow { queue, base })
+		Ok(RefWindow { queue, base, last_timestamp })
 	}
 
 	pub fn window_size(&self) -> u64 {
 		self.queue.len(self.base) as u64
 	}
 
-	/// Get the hash of the next pruning block
-	pub fn next_hash(&mut self) -> Result<Option<BlockHash>, Error<D::Error>> {
+	fn front(&mut self) -> Result<Option<&DeathRow<BlockHash, Key>>, Error<D::Error>> {
 		let res = match &mut self.queue {
 			DeathRowQueue::DbBacked { db, cache, cache_capacity, .. } => {
 				if cache.is_empty() {
 					DeathRowQueue::load_batch_from_db(db, cache, self.base, *cache_capacity)?;
 				}
-				cache.front().map(|r| r.hash.clone())
+				cache.front()
 			},
-			DeathRowQueue::Mem { death_rows, .. } => death_rows.front().map(|r| r.hash.clone()),
+			DeathRowQueue::Mem { death_rows, .. } => death_rows.front(),
 		};
 		Ok(res)
 	}
 
+	/// Get the hash of the next pruning block
+	pub fn next_hash(&mut self) -> Result<Option<BlockHash>, Error<D::Error>> {
+		Ok(self.front()?.map(|r| r.hash.clone()))
+	}
+
+	/// Get the timestamp of the next pruning block, in milliseconds since the UNIX epoch, if
+	/// known.
+	pub fn next_timestamp(&mut self) -> Result<Option<u64>, Error<D::Error>> {
+		Ok(self.front()?.and_then(|r| r.timestamp))
+	}
+
+	/// Get the timestamp of the last block added to the window, in milliseconds since the UNIX
+	/// epoch, if known.
+	pub fn last_timestamp(&self) -> Option<u64> {
+		self.last_timestamp
+	}
+
 	fn is_empty(&self) -> bool {
 		self.window_size() == 0
 	}
@@ -383,6 +488,13 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		self.queue.have_block(hash, (number - self.base) as usize)
 	}
 
+	/// Start an empty pruning window right after the given block, as if all blocks up to and
+	/// including it had already been pruned. Adds changes to `commit`.
+	pub fn start_after(&mut self, number: u64, commit: &mut CommitSet<Key>) {
+		self.base = number + 1;
+		commit.meta.inserted.push((to_meta_key(LAST_PRUNED, &()), number.encode()));
+	}
+
 	/// Prune next block. Expects at least one block in the window. Adds changes to `commit`.
 	pub fn prune_one(&mut self, commit: &mut CommitSet<Key>) -> Result<(), Error<D::Error>> {
 		if let Some(pruned) = self.queue.pop_front(self.base)? {
@@ -391,6 +503,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 			commit.data.deleted.extend(pruned.deleted.into_iter());
 			commit.meta.inserted.push((to_meta_key(LAST_PRUNED, &()), index.encode()));
 			commit.meta.deleted.push(to_journal_key(self.base));
+			commit.meta.deleted.push(to_journal_time_key(self.base));
 			self.base += 1;
 			Ok(())
 		} else {
@@ -400,10 +513,14 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 	}
 
 	/// Add a change set to the window. Creates a journal record and pushes it to `commit`
+	///
+	/// `timestamp` is the timestamp of the block, in milliseconds since the UNIX epoch. If it is
+	/// unknown, the block is given the timestamp of the block before it.
 	pub fn note_canonical(
 		&mut self,
 		hash: &BlockHash,
 		number: u64,
+		timestamp: Option<u64>,
 		commit: &mut CommitSet<Key>,
 	) -> Result<(), Error<D::Error>> {
 		if self.base == 0 && self.is_empty() && number > 0 {
@@ -421,18 +538,26 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		let deleted = std::mem::take(&mut commit.data.deleted);
 		let journal_record = JournalRecord { hash: hash.clone(), inserted, deleted };
 		commit.meta.inserted.push((to_journal_key(number), journal_record.encode()));
-		self.queue.import(self.base, number, journal_record);
+		let timestamp = timestamp.or(self.last_timestamp);
+		if let Some(timestamp) = timestamp {
+			commit.meta.inserted.push((to_journal_time_key(number), timestamp.encode()));
+		}
+		self.queue.import(self.base, number, journal_record, timestamp);
+		self.last_timestamp = timestamp;
 		Ok(())
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{to_journal_key, DeathRowQueue, HaveBlock, JournalRecord, RefWindow, LAST_PRUNED};
+	use super::{
+		convert_journal_records, to_journal_key, DeathRowQueue, HaveBlock, JournalRecord,
+		RefWindow, LAST_PRUNED,
+	};
 	use crate::{
 		noncanonical::LAST_CANONICAL,
 		test::{make_commit, make_db, TestDb},
-		to_meta_key, CommitSet, Error, Hash, StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT,
+		to_meta_key, CommitSet, Error, Hash, MetaDb, StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT,
 	};
 	use codec::Encode;
 	use sp_core::H256;
@@ -479,7 +604,7 @@ mod tests {
 			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
 		let mut commit = make_commit(&[4, 5], &[1, 3]);
 		let hash = H256::random();
-		pruning.note_canonical(&hash, 0, &mut commit).unwrap();
+		pruning.note_canonical(&hash, 0, None, &mut commit).unwrap();
 		db.commit(&commit);
 		assert_eq!(pruning.have_block(&hash, 0), HaveBlock::Yes);
 		assert_eq!(pruning.have_block(&hash, 0), HaveBlock::Yes);
@@ -508,10 +633,10 @@ mod tests {
 		let mut pruning: RefWindow<H256, H256, TestDb> =
 			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
 		let mut commit = make_commit(&[4], &[1]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[5], &[2]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, None, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3, 4, 5])));
 
@@ -534,10 +659,10 @@ mod tests {
 		let mut pruning: RefWindow<H256, H256, TestDb> =
 			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
 		let mut commit = make_commit(&[4], &[1]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[5], &[2]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, None, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3, 4, 5])));
 		let mut commit = CommitSet::default();
@@ -557,13 +682,13 @@ mod tests {
 		let mut pruning: RefWindow<H256, H256, TestDb> =
 			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[2], &[]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 2, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 2, None, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3])));
 
@@ -589,13 +714,13 @@ mod tests {
 		let mut pruning: RefWindow<H256, H256, TestDb> =
 			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[2], &[]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 2, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 2, None, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3])));
 
@@ -619,13 +744,13 @@ mod tests {
 		let mut pruning: RefWindow<H256, H256, TestDb> =
 			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[2], &[]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, None, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 2, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 2, None, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3])));
 
@@ -637,6 +762,49 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 3])));
 	}
 
+	#[test]
+	fn converted_journal_keeps_reinserted() {
+		let mut db = make_db(&[1, 2, 3]);
+		let mut pruning: RefWindow<H256, H256, TestDb> =
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+		let mut commit = make_commit(&[], &[2]);
+		pruning.note_canonical(&H256::random(), 0, None, &mut commit).unwrap();
+		db.commit(&commit);
+		let mut commit = make_commit(&[2], &[]);
+		pruning.note_canonical(&H256::random(), 1, None, &mut commit).unwrap();
+		db.commit(&commit);
+		let mut commit = make_commit(&[], &[2]);
+		pruning.note_canonical(&H256::random(), 2, None, &mut commit).unwrap();
+		db.commit(&commit);
+
+		let records = (0..3)
+			.map(|block| (block, db.get_meta(&to_journal_key(block)).unwrap().unwrap()))
+			.collect();
+		let mut commit = CommitSet::default();
+		commit.meta.inserted = convert_journal_records::<H256, H256>(records, |k| k).unwrap();
+		commit
+			.meta
+			.inserted
+			.push((to_meta_key(LAST_CANONICAL, &()), (H256::random(), 2u64).encode()));
+		let mut db = make_db(&[1, 2, 3]);
+		db.commit(&commit);
+
+		// Only the deletion by the last block is left to be applied.
+		let mut pruning: RefWindow<H256, H256, TestDb> =
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+		for _ in 0..2 {
+			let mut commit = CommitSet::default();
+			pruning.prune_one(&mut commit).unwrap();
+			db.commit(&commit);
+			assert!(db.data_eq(&make_db(&[1, 2, 3])));
+		}
+		let mut commit = CommitSet::default();
+		pruning.prune_one(&mut commit).unwrap();
+		db.commit(&commit);
+		assert!(db.data_eq(&make_db(&[1, 3])));
+		assert_eq!(pruning.base, 3);
+	}
+
 	fn push_last_canonicalized<H: Hash>(block: u64, commit: &mut CommitSet<H>) {
 		commit
 			.meta
@@ -721,7 +889,7 @@ mod tests {
 		// queue size and content should match
 		for i in 0..(cache_capacity + 10) {
 			let mut commit = make_commit(&[], &[]);
-			pruning.note_canonical(&(i as u64), i as u64, &mut commit).unwrap();
+			pruning.note_canonical(&(i as u64), i as u64, None, &mut commit).unwrap();
 			push_last_canonicalized(i as u64, &mut commit);
 			db.commit(&commit);
 			// blocks will fill the cache first
@@ -745,7 +913,12 @@ mod tests {
 		// won't keep the new block in memory
 		let mut commit = CommitSet::default();
 		pruning
-			.note_canonical(&(cache_capacity as u64 + 10), cache_capacity as u64 + 10, &mut commit)
+			.note_canonical(
+				&(cache_capacity as u64 + 10),
+				cache_capacity as u64 + 10,
+				None,
+				&mut commit,
+			)
 			.unwrap();
 		assert_eq!(pruning.window_size(), cache_capacity as u64 + 11);
 		let (cache, _) = pruning.queue.get_db_backed_queue_state().unwrap();
@@ -793,7 +966,7 @@ mod tests {
 		// import blocks
 		for i in 0..(cache_capacity as u64 * 2 + 10) {
 			let mut commit = make_commit(&[], &[]);
-			pruning.note_canonical(&i, i, &mut commit).unwrap();
+			pruning.note_canonical(&i, i, None, &mut commit).unwrap();
 			push_last_canonicalized(i as u64, &mut commit);
 			db.commit(&commit);
 		}
@@ -843,14 +1016,14 @@ mod tests {
 		// import blocks and commit to db
 		let mut commit = make_commit(&[], &[]);
 		for i in 0..(cache_capacity + 10) {
-			pruning.note_canonical(&i, i, &mut commit).unwrap();
+			pruning.note_canonical(&i, i, None, &mut commit).unwrap();
 		}
 		db.commit(&commit);
 
 		// import a block but not commit to db yet
 		let mut pending_commit = make_commit(&[], &[]);
 		let index = cache_capacity + 10;
-		pruning.note_canonical(&index, index, &mut pending_commit).unwrap();
+		pruning.note_canonical(&index, index, None, &mut pending_commit).unwrap();
 
 		let mut commit = make_commit(&[], &[]);
 		// prune blocks that had committed to db
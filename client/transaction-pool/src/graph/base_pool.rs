@@ -21,7 +21,12 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc};
+use std::{
+	cmp::Ordering,
+	collections::{hash_map::Entry, HashMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+};
 
 use crate::LOG_TARGET;
 use log::{debug, trace, warn};
@@ -220,6 +225,12 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	/// transactions to future in case they were just stuck in verification.
 	recently_pruned: [HashSet<Tag>; RECENTLY_PRUNED_TAGS],
 	recently_pruned_index: usize,
+	/// Limits applied to the transactions of every individual sender.
+	sender_limit: Option<SenderLimit>,
+	/// Slice of the ready queue reserved for high-priority transactions.
+	priority_reserve: Option<PriorityReserve>,
+	/// Senders which had transactions imported since their limit was last checked.
+	pending_senders: HashSet<Vec<u8>>,
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
@@ -231,12 +242,32 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for Bas
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
 	/// Create new pool given reject_future_transactions flag.
 	pub fn new(reject_future_transactions: bool) -> Self {
+		Self::with_limits(reject_future_transactions, None, None)
+	}
+
+	/// Create new pool given reject_future_transactions flag, the limit applied to every
+	/// individual sender and the slice of the ready queue reserved for high-priority
+	/// transactions.
+	pub fn with_limits(
+		reject_future_transactions: bool,
+		sender_limit: Option<SenderLimit>,
+		priority_reserve: Option<PriorityReserve>,
+	) -> Self {
+		let usage = || {
+			UsageTracker::new(
+				sender_limit.as_ref().map(|sender| sender.key.clone()),
+				priority_reserve.as_ref().map(|reserve| reserve.min_priority),
+			)
+		};
 		Self {
 			reject_future_transactions,
-			future: Default::default(),
-			ready: Default::default(),
+			future: FutureTransactions::with_usage(usage()),
+			ready: ReadyTransactions::with_usage(usage()),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
+			sender_limit,
+			priority_reserve,
+			pending_senders: Default::default(),
 		}
 	}
 
@@ -273,6 +304,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			return Err(error::Error::AlreadyImported(Box::new(tx.hash)))
 		}
 
+		let sender = self.sender_limit.as_ref().and_then(|sender| sender.key.extract(&tx.provides));
 		let tx = WaitingTransaction::new(tx, self.ready.provided_tags(), &self.recently_pruned);
 		trace!(target: LOG_TARGET, "[{:?}] {:?}", tx.transaction.hash, tx);
 		debug!(
@@ -283,17 +315,20 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		);
 
 		// If all tags are not satisfied import to future.
-		if !tx.is_ready() {
+		let imported = if !tx.is_ready() {
 			if self.reject_future_transactions {
 				return Err(error::Error::RejectedFutureTransaction)
 			}
 
 			let hash = tx.transaction.hash.clone();
 			self.future.import(tx);
-			return Ok(Imported::Future { hash })
-		}
+			Imported::Future { hash }
+		} else {
+			self.import_to_ready(tx)?
+		};
 
-		self.import_to_ready(tx)
+		self.pending_senders.extend(sender);
+		Ok(imported)
 	}
 
 	/// Imports transaction to ready queue.
@@ -382,60 +417,101 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.ready.by_hash(hash)
 	}
 
+	/// Returns true if the per-sender limit or the priority reserve, within the given limit of
+	/// the ready queue, is exceeded.
+	///
+	/// Forgets about the senders which had transactions imported but are within their limit.
+	pub fn sender_or_reserve_limit_exceeded(&mut self, ready: &Limit) -> bool {
+		if let Some(sender) = &self.sender_limit {
+			let (ready_usage, future_usage) = (self.ready.usage(), self.future.usage());
+			self.pending_senders.retain(|key| {
+				ready_usage.sender(key).add_to(future_usage.sender(key)).exceeds(&sender.limit)
+			});
+		}
+
+		!self.pending_senders.is_empty() ||
+			self.priority_reserve
+				.as_ref()
+				.map_or(false, |reserve| self.reserve_exceeded(ready, reserve))
+	}
+
 	/// Makes sure that the transactions in the queues stay within provided limits.
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
-	/// them. Technically the worst transaction should be evaluated by computing the entire pending
-	/// set. We use a simplified approach to remove transactions with the lowest priority first or
-	/// those that occupy the pool for the longest time in case priority is the same.
+	/// them, along with the reason for their eviction. Technically the worst transaction should be
+	/// evaluated by computing the entire pending set. We use a simplified approach to remove
+	/// transactions with the lowest priority first or those that occupy the pool for the longest
+	/// time in case priority is the same.
+	///
+	/// The per-sender limit is enforced first, so a single sender exceeding it can't cause the
+	/// transactions of others to be evicted. Only the senders which had transactions imported since
+	/// their limit was last checked can exceed it. Then the transactions below the priority
+	/// reserve's threshold are kept out of the reserved slice of the ready queue, before the
+	/// overall queue limits are applied.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
 		future: &Limit,
-	) -> Vec<Arc<Transaction<Hash, Ex>>> {
+	) -> Vec<(Arc<Transaction<Hash, Ex>>, EvictionReason)> {
 		let mut removed = vec![];
 
-		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
-			// find the worst transaction
-			let worst = self.ready.fold::<TransactionRef<Hash, Ex>, _>(|worst, current| {
-				let transaction = &current.transaction;
-				worst
-					.map(|worst| {
-						// Here we don't use `TransactionRef`'s ordering implementation because
-						// while it prefers priority like need here, it also prefers older
-						// transactions for inclusion purposes and limit enforcement needs to prefer
-						// newer transactions instead and drop the older ones.
-						match worst.transaction.priority.cmp(&transaction.transaction.priority) {
-							Ordering::Less => worst,
-							Ordering::Equal =>
-								if worst.insertion_id > transaction.insertion_id {
-									transaction.clone()
-								} else {
-									worst
-								},
-							Ordering::Greater => transaction.clone(),
-						}
-					})
-					.or_else(|| Some(transaction.clone()))
-			});
+		if let Some(sender) = self.sender_limit.clone() {
+			for key in std::mem::take(&mut self.pending_senders) {
+				let of_sender = |tx: &Transaction<Hash, Ex>| {
+					sender.key.extract(&tx.provides).as_ref() == Some(&key)
+				};
+				loop {
+					let usage =
+						self.ready.usage().sender(&key).add_to(self.future.usage().sender(&key));
+					if !usage.exceeds(&sender.limit) {
+						break
+					}
 
-			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+					// transactions stuck in the future queue are the first to go
+					let worst = self
+						.oldest_future(&of_sender)
+						.or_else(|| self.worst_ready(&of_sender).map(|worst| worst.transaction));
+					let Some(worst) = worst else { break };
+					removed.extend(
+						self.remove_subtree(&[worst.hash.clone()])
+							.into_iter()
+							.map(|tx| (tx, EvictionReason::Sender)),
+					);
+				}
+			}
+		}
+
+		if let Some(reserve) = self.priority_reserve.clone() {
+			let below_threshold = |tx: &Transaction<Hash, Ex>| tx.priority < reserve.min_priority;
+			while self.reserve_exceeded(ready, &reserve) {
+				let Some(worst) = self.worst_ready(&below_threshold) else { break };
+				removed.extend(
+					self.remove_subtree(&[worst.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (tx, EvictionReason::PriorityReserve)),
+				);
+			}
+		}
+
+		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
+			if let Some(worst) = self.worst_ready(|_| true) {
+				removed.extend(
+					self.remove_subtree(&[worst.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (tx, EvictionReason::Ready)),
+				);
 			} else {
 				break
 			}
 		}
 
 		while future.is_exceeded(self.future.len(), self.future.bytes()) {
-			// find the worst transaction
-			let worst = self.future.fold(|worst, current| match worst {
-				None => Some(current.clone()),
-				Some(ref tx) if tx.imported_at > current.imported_at => Some(current.clone()),
-				other => other,
-			});
-
-			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+			if let Some(worst) = self.oldest_future(|_| true) {
+				removed.extend(
+					self.remove_subtree(&[worst.hash.clone()])
+						.into_iter()
+						.map(|tx| (tx, EvictionReason::Future)),
+				);
 			} else {
 				break
 			}
@@ -444,6 +520,68 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Returns true if the ready transactions below the priority reserve's threshold exceed the
+	/// unreserved slice of the ready queue.
+	fn reserve_exceeded(&self, ready: &Limit, reserve: &PriorityReserve) -> bool {
+		let unreserved = Limit {
+			count: ready.count.saturating_sub(reserve.limit.count),
+			total_bytes: ready.total_bytes.saturating_sub(reserve.limit.total_bytes),
+		};
+		self.ready.usage().below_min_priority().exceeds(&unreserved)
+	}
+
+	/// Finds the worst ready transaction matching `filter`.
+	fn worst_ready(
+		&mut self,
+		filter: impl Fn(&Transaction<Hash, Ex>) -> bool,
+	) -> Option<TransactionRef<Hash, Ex>> {
+		self.ready.fold::<TransactionRef<Hash, Ex>, _>(|worst, current| {
+			let transaction = &current.transaction;
+			if !filter(&transaction.transaction) {
+				return worst
+			}
+
+			worst
+				.map(|worst| {
+					// Here we don't use `TransactionRef`'s ordering implementation because
+					// while it prefers priority like need here, it also prefers older
+					// transactions for inclusion purposes and limit enforcement needs to prefer
+					// newer transactions instead and drop the older ones.
+					match worst.transaction.priority.cmp(&transaction.transaction.priority) {
+						Ordering::Less => worst,
+						Ordering::Equal =>
+							if worst.insertion_id > transaction.insertion_id {
+								transaction.clone()
+							} else {
+								worst
+							},
+						Ordering::Greater => transaction.clone(),
+					}
+				})
+				.or_else(|| Some(transaction.clone()))
+		})
+	}
+
+	/// Finds the future transaction matching `filter` which has been waiting the longest.
+	fn oldest_future(
+		&mut self,
+		filter: impl Fn(&Transaction<Hash, Ex>) -> bool,
+	) -> Option<Arc<Transaction<Hash, Ex>>> {
+		self.future
+			.fold(|worst, current| {
+				if !filter(&current.transaction) {
+					return worst
+				}
+
+				match worst {
+					None => Some(current.clone()),
+					Some(ref tx) if tx.imported_at > current.imported_at => Some(current.clone()),
+					other => other,
+				}
+			})
+			.map(|worst| worst.transaction)
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -531,6 +669,173 @@ impl Limit {
 	}
 }
 
+/// Identifies the sender of a transaction from the tags it provides.
+#[derive(Clone)]
+pub enum SenderKey {
+	/// The first `n` bytes of the first tag provided by the transaction.
+	///
+	/// FRAME's `CheckNonce` extension provides `(AccountId, Nonce).encode()`, so the encoded
+	/// length of the runtime's `AccountId` identifies the sender there.
+	TagPrefix(usize),
+	/// A custom extractor, e.g. one supplied by a node aware of the runtime's tag format.
+	Custom(Arc<dyn Fn(&[Tag]) -> Option<Vec<u8>> + Send + Sync>),
+}
+
+impl SenderKey {
+	/// Returns the key identifying the sender of a transaction providing `provides`, if any.
+	pub fn extract(&self, provides: &[Tag]) -> Option<Vec<u8>> {
+		match self {
+			Self::TagPrefix(len) =>
+				provides.first().filter(|tag| tag.len() >= *len).map(|tag| tag[..*len].to_vec()),
+			Self::Custom(extract) => extract(provides),
+		}
+	}
+}
+
+impl fmt::Debug for SenderKey {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::TagPrefix(len) => fmt.debug_tuple("TagPrefix").field(len).finish(),
+			Self::Custom(_) => fmt.write_str("Custom"),
+		}
+	}
+}
+
+/// Limits applied to the transactions of every individual sender, across both queues.
+#[derive(Debug, Clone)]
+pub struct SenderLimit {
+	/// How the sender of a transaction is identified.
+	pub key: SenderKey,
+	/// Limits of a single sender.
+	pub limit: Limit,
+}
+
+/// A slice of the ready queue reserved for high-priority transactions.
+#[derive(Debug, Clone)]
+pub struct PriorityReserve {
+	/// Minimal priority of the transactions allowed into the reserved slice.
+	pub min_priority: Priority,
+	/// Size of the reserved slice.
+	pub limit: Limit,
+}
+
+/// Reason a transaction was evicted from the pool while enforcing its limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+	/// The ready queue limit was exceeded.
+	Ready,
+	/// The future queue limit was exceeded.
+	Future,
+	/// The limit of the transaction's sender was exceeded.
+	Sender,
+	/// The transaction's priority doesn't allow it into the reserved slice of the ready queue.
+	PriorityReserve,
+}
+
+impl EvictionReason {
+	/// Returns the label used for this reason in metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Ready => "ready",
+			Self::Future => "future",
+			Self::Sender => "sender",
+			Self::PriorityReserve => "priority_reserve",
+		}
+	}
+}
+
+/// The number and total size of a set of transactions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+	/// Number of transactions.
+	pub count: usize,
+	/// Sum of the encoding lengths of the transactions.
+	pub bytes: usize,
+}
+
+impl Usage {
+	fn add(&mut self, bytes: usize) {
+		self.count += 1;
+		self.bytes += bytes;
+	}
+
+	fn remove(&mut self, bytes: usize) {
+		self.count = self.count.saturating_sub(1);
+		self.bytes = self.bytes.saturating_sub(bytes);
+	}
+
+	/// Returns the usage of both this and `other` set of transactions.
+	pub fn add_to(self, other: Usage) -> Usage {
+		Usage { count: self.count + other.count, bytes: self.bytes + other.bytes }
+	}
+
+	/// Returns true if `limit` is exceeded by this usage.
+	pub fn exceeds(&self, limit: &Limit) -> bool {
+		limit.is_exceeded(self.count, self.bytes)
+	}
+}
+
+/// Usage of a queue by each sender, and by transactions below a minimal priority.
+///
+/// Kept up to date by the queue as transactions enter and leave it, so the per-sender limit and
+/// the priority reserve can be checked without going over the whole queue.
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+	sender_key: Option<SenderKey>,
+	min_priority: Option<Priority>,
+	senders: HashMap<Vec<u8>, Usage>,
+	below_min_priority: Usage,
+}
+
+impl UsageTracker {
+	/// Create a tracker of the usage by each sender as identified by `sender_key`, and by the
+	/// transactions below `min_priority`.
+	pub fn new(sender_key: Option<SenderKey>, min_priority: Option<Priority>) -> Self {
+		Self { sender_key, min_priority, ..Default::default() }
+	}
+
+	/// Account for a transaction entering the queue.
+	pub fn add<Hash, Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		if let Some(sender) = self.sender_key.as_ref().and_then(|key| key.extract(&tx.provides)) {
+			self.senders.entry(sender).or_default().add(tx.bytes);
+		}
+		if self.min_priority.map_or(false, |min_priority| tx.priority < min_priority) {
+			self.below_min_priority.add(tx.bytes);
+		}
+	}
+
+	/// Account for a transaction leaving the queue.
+	pub fn remove<Hash, Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		if let Some(sender) = self.sender_key.as_ref().and_then(|key| key.extract(&tx.provides)) {
+			if let Entry::Occupied(mut usage) = self.senders.entry(sender) {
+				usage.get_mut().remove(tx.bytes);
+				if usage.get().count == 0 {
+					usage.remove();
+				}
+			}
+		}
+		if self.min_priority.map_or(false, |min_priority| tx.priority < min_priority) {
+			self.below_min_priority.remove(tx.bytes);
+		}
+	}
+
+	/// Account for all the transactions leaving the queue.
+	pub fn clear(&mut self) {
+		self.senders.clear();
+		self.below_min_priority = Default::default();
+	}
+
+	/// Returns the usage of the queue by `sender`.
+	pub fn sender(&self, sender: &[u8]) -> Usage {
+		self.senders.get(sender).copied().unwrap_or_default()
+	}
+
+	/// Returns the usage of the queue by transactions below the minimal priority.
+	pub fn below_min_priority(&self) -> Usage {
+		self.below_min_priority
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1044,4 +1349,132 @@ source: TransactionSource::External, requires: [03, 02], provides: [04], data: [
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn should_enforce_sender_limit_without_evicting_other_senders() {
+		// given
+		let sender = SenderLimit {
+			key: SenderKey::TagPrefix(1),
+			limit: Limit { count: 2, total_bytes: 100 },
+		};
+		let mut pool = BasePool::with_limits(false, Some(sender), None);
+		for nonce in 0..3u8 {
+			pool.import(Transaction {
+				data: vec![nonce],
+				hash: nonce as u64 + 1,
+				requires: if nonce == 0 { vec![] } else { vec![vec![1, nonce - 1]] },
+				provides: vec![vec![1, nonce]],
+				..DEFAULT_TX.clone()
+			})
+			.unwrap();
+		}
+		pool.import(Transaction {
+			data: vec![10u8],
+			hash: 10,
+			provides: vec![vec![2, 0]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		let limit = Limit { count: 10, total_bytes: 100 };
+
+		// when
+		assert!(pool.sender_or_reserve_limit_exceeded(&limit));
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		assert_eq!(
+			removed.iter().map(|(tx, reason)| (tx.hash, *reason)).collect::<Vec<_>>(),
+			vec![(3, EvictionReason::Sender)],
+		);
+		assert_eq!(pool.ready().map(|tx| tx.hash).collect::<HashSet<_>>(), [1, 2, 10].into());
+		assert!(!pool.sender_or_reserve_limit_exceeded(&limit));
+	}
+
+	#[test]
+	fn should_track_sender_usage_across_queues() {
+		// given
+		let sender = SenderLimit {
+			key: SenderKey::TagPrefix(1),
+			limit: Limit { count: 10, total_bytes: 100 },
+		};
+		let mut pool = BasePool::with_limits(false, Some(sender), None);
+		let usage = |count| Usage { count, bytes: count };
+
+		// when
+		pool.import(Transaction {
+			data: vec![2u8],
+			hash: 2,
+			requires: vec![vec![1, 0]],
+			provides: vec![vec![1, 1]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// then
+		assert_eq!(pool.future.usage().sender(&[1]), usage(1));
+		assert_eq!(pool.ready.usage().sender(&[1]), usage(0));
+
+		// when
+		pool.import(Transaction {
+			data: vec![1u8],
+			hash: 1,
+			provides: vec![vec![1, 0]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// then
+		assert_eq!(pool.future.usage().sender(&[1]), usage(0));
+		assert_eq!(pool.ready.usage().sender(&[1]), usage(2));
+
+		// when
+		pool.prune_tags(vec![vec![1, 0], vec![1, 1]]);
+
+		// then
+		assert_eq!(pool.ready.usage().sender(&[1]), usage(0));
+	}
+
+	#[test]
+	fn should_keep_reserved_capacity_for_high_priority_transactions() {
+		// given
+		let reserve =
+			PriorityReserve { min_priority: 10, limit: Limit { count: 1, total_bytes: 1 } };
+		let mut pool = BasePool::with_limits(false, None, Some(reserve));
+		for hash in 1..4u64 {
+			pool.import(Transaction {
+				data: vec![hash as u8],
+				hash,
+				provides: vec![vec![hash as u8]],
+				..DEFAULT_TX.clone()
+			})
+			.unwrap();
+		}
+		let limit = Limit { count: 3, total_bytes: 100 };
+
+		// when
+		assert!(pool.sender_or_reserve_limit_exceeded(&limit));
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		assert_eq!(
+			removed.iter().map(|(tx, reason)| (tx.hash, *reason)).collect::<Vec<_>>(),
+			vec![(3, EvictionReason::PriorityReserve)],
+		);
+
+		// when
+		pool.import(Transaction {
+			data: vec![4u8],
+			hash: 4,
+			priority: 20,
+			provides: vec![vec![4]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		assert!(!pool.sender_or_reserve_limit_exceeded(&limit));
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		assert!(removed.is_empty());
+		assert_eq!(pool.ready().map(|tx| tx.hash).collect::<HashSet<_>>(), [1, 2, 4].into());
+	}
 }
@@ -28,6 +28,7 @@ mod enactment_state;
 pub mod error;
 mod graph;
 mod metrics;
+mod persistence;
 mod revalidation;
 #[cfg(test)]
 mod tests;
@@ -41,11 +42,13 @@ use futures::{
 	prelude::*,
 };
 pub use graph::{
-	base_pool::Limit as PoolLimit, ChainApi, Options, Pool, Transaction, ValidatedTransaction,
+	base_pool::{EvictionReason, Limit as PoolLimit, PriorityReserve, SenderKey, SenderLimit},
+	ChainApi, Options, Pool, Transaction, ValidatedTransaction,
 };
 use parking_lot::Mutex;
 use std::{
 	collections::{HashMap, HashSet},
+	path::PathBuf,
 	pin::Pin,
 	sync::Arc,
 };
@@ -94,6 +97,7 @@ where
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
 	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	persist_path: Option<PathBuf>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -181,6 +185,7 @@ where
 					best_block_hash,
 					finalized_hash,
 				))),
+				persist_path: None,
 			},
 			background_task,
 		)
@@ -199,7 +204,14 @@ where
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
 	) -> Self {
-		let pool = Arc::new(graph::Pool::new(options, is_validator, pool_api.clone()));
+		let persist_path = options.persist_path.clone();
+		let metrics = PrometheusMetrics::new(prometheus);
+		let pool = Arc::new(graph::Pool::with_metrics(
+			options,
+			is_validator,
+			pool_api.clone(),
+			metrics.clone(),
+		));
 		let (revalidation_queue, background_task) = match revalidation_type {
 			RevalidationType::Light =>
 				(revalidation::RevalidationQueue::new(pool_api.clone(), pool.clone()), None),
@@ -214,6 +226,14 @@ where
 			spawner.spawn_essential("txpool-background", Some("transaction-pool"), background_task);
 		}
 
+		if let Some(path) = persist_path.clone() {
+			spawner.spawn_essential(
+				"txpool-persistence",
+				Some("transaction-pool"),
+				persistence::persistence_task(path, pool.clone(), best_block_hash).boxed(),
+			);
+		}
+
 		Self {
 			api: pool_api,
 			pool,
@@ -224,11 +244,12 @@ where
 				RevalidationType::Full => RevalidationStrategy::Always,
 			})),
 			ready_poll: Arc::new(Mutex::new(ReadyPoll::new(best_block_number))),
-			metrics: PrometheusMetrics::new(prometheus),
+			metrics,
 			enactment_state: Arc::new(Mutex::new(EnactmentState::new(
 				best_block_hash,
 				finalized_hash,
 			))),
+			persist_path,
 		}
 	}
 
@@ -243,6 +264,18 @@ where
 	}
 }
 
+impl<PoolApi, Block> Drop for BasicPool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: graph::ChainApi<Block = Block>,
+{
+	fn drop(&mut self) {
+		if let Some(path) = &self.persist_path {
+			persistence::snapshot(path, &self.pool);
+		}
+	}
+}
+
 impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 where
 	Block: BlockT,
@@ -0,0 +1,245 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistence of the pool's transactions across node restarts.
+//!
+//! When enabled, the ready and future transactions are periodically snapshot to a file, as well as
+//! once more when the pool is dropped. On startup the snapshot is read back and the transactions
+//! are resubmitted to the pool, which revalidates them against the best block.
+
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::Duration,
+};
+
+use crate::{
+	graph::{BlockHash, ChainApi, ExtrinsicFor, Pool},
+	LOG_TARGET,
+};
+use codec::{Decode, Encode};
+use futures_timer::Delay;
+use sp_runtime::{generic::BlockId, transaction_validity::TransactionSource};
+
+/// Interval at which the pool is snapshot to disk.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Version of the snapshot format, bumped on incompatible changes.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// The on-disk representation of the pool.
+#[derive(Encode, Decode)]
+struct Snapshot<Extrinsic> {
+	version: u32,
+	transactions: Vec<(TransactionSource, Extrinsic)>,
+}
+
+/// Write all transactions currently in the pool to `path`.
+///
+/// The snapshot is written to a temporary file first and then moved into place, so a crash
+/// mid-write never leaves a truncated snapshot behind.
+pub(crate) fn save<Api: ChainApi>(path: &Path, pool: &Pool<Api>) -> io::Result<usize> {
+	let transactions = pool.validated_pool().transactions();
+	let count = transactions.len();
+
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+
+	let mut tmp_path = path.as_os_str().to_owned();
+	tmp_path.push(".tmp");
+	let tmp_path = PathBuf::from(tmp_path);
+
+	fs::write(&tmp_path, Snapshot { version: SNAPSHOT_VERSION, transactions }.encode())?;
+	fs::rename(&tmp_path, path)?;
+
+	Ok(count)
+}
+
+/// Read the transactions stored at `path`.
+///
+/// Returns an empty list if no snapshot exists yet.
+pub(crate) fn load<Api: ChainApi>(
+	path: &Path,
+) -> io::Result<Vec<(TransactionSource, ExtrinsicFor<Api>)>> {
+	let bytes = match fs::read(path) {
+		Ok(bytes) => bytes,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(e) => return Err(e),
+	};
+
+	let snapshot = Snapshot::<ExtrinsicFor<Api>>::decode(&mut &bytes[..])
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+	if snapshot.version != SNAPSHOT_VERSION {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("unsupported snapshot version {}", snapshot.version),
+		))
+	}
+
+	Ok(snapshot.transactions)
+}
+
+/// Resubmit the transactions stored at `path` to the pool, validating them at block `at`.
+///
+/// Returns the number of transactions which were accepted back into the pool.
+pub(crate) async fn restore<Api: ChainApi>(
+	path: &Path,
+	pool: &Pool<Api>,
+	at: &BlockId<Api::Block>,
+) -> io::Result<usize> {
+	let transactions = load::<Api>(path)?;
+
+	let mut restored = 0;
+	for source in
+		[TransactionSource::InBlock, TransactionSource::Local, TransactionSource::External]
+	{
+		let xts = transactions
+			.iter()
+			.filter(|(tx_source, _)| *tx_source == source)
+			.map(|(_, xt)| xt.clone())
+			.collect::<Vec<_>>();
+		if xts.is_empty() {
+			continue
+		}
+
+		match pool.resubmit_at(at, source, xts).await {
+			Ok(results) => restored += results.iter().filter(|result| result.is_ok()).count(),
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"Failed to resubmit persisted transactions at {:?}: {}",
+				at,
+				e,
+			),
+		}
+	}
+
+	Ok(restored)
+}
+
+/// Snapshot the pool to `path`, logging the outcome.
+pub(crate) fn snapshot<Api: ChainApi>(path: &Path, pool: &Pool<Api>) {
+	match save(path, pool) {
+		Ok(count) => log::debug!(
+			target: LOG_TARGET,
+			"Persisted {} transactions to {}",
+			count,
+			path.display(),
+		),
+		Err(e) => log::warn!(
+			target: LOG_TARGET,
+			"Failed to persist transactions to {}: {}",
+			path.display(),
+			e,
+		),
+	}
+}
+
+/// Background task which restores the persisted transactions and then periodically snapshots the
+/// pool.
+pub(crate) async fn persistence_task<Api: ChainApi>(
+	path: PathBuf,
+	pool: Arc<Pool<Api>>,
+	best_block_hash: BlockHash<Api>,
+) {
+	match restore(&path, &pool, &BlockId::Hash(best_block_hash)).await {
+		Ok(restored) => log::info!(
+			target: LOG_TARGET,
+			"Restored {} persisted transactions from {}",
+			restored,
+			path.display(),
+		),
+		Err(e) => log::warn!(
+			target: LOG_TARGET,
+			"Failed to restore persisted transactions from {}: {}",
+			path.display(),
+			e,
+		),
+	}
+
+	loop {
+		Delay::new(SNAPSHOT_INTERVAL).await;
+		snapshot(&path, &pool);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{pool, uxt};
+	use futures::executor::block_on;
+	use substrate_test_runtime::{AccountId, Transfer, H256};
+	use substrate_test_runtime_client::AccountKeyring::Alice;
+
+	fn transfer(nonce: u64) -> Transfer {
+		Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce,
+		}
+	}
+
+	#[test]
+	fn snapshot_roundtrips_ready_and_future_transactions() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("txpool");
+
+		let pool = pool();
+		block_on(pool.submit_one(
+			&BlockId::Number(0),
+			TransactionSource::External,
+			uxt(transfer(0)),
+		))
+		.unwrap();
+		block_on(pool.submit_one(&BlockId::Number(0), TransactionSource::Local, uxt(transfer(2))))
+			.unwrap();
+		assert_eq!(pool.validated_pool().status().ready, 1);
+		assert_eq!(pool.validated_pool().status().future, 1);
+
+		assert_eq!(save(&path, &pool).unwrap(), 2);
+
+		let restored_pool = crate::tests::pool();
+		assert_eq!(block_on(restore(&path, &restored_pool, &BlockId::Number(0))).unwrap(), 2);
+		assert_eq!(restored_pool.validated_pool().status().ready, 1);
+		assert_eq!(restored_pool.validated_pool().status().future, 1);
+	}
+
+	#[test]
+	fn restore_without_snapshot_is_a_no_op() {
+		let dir = tempfile::tempdir().unwrap();
+		let pool = pool();
+
+		assert_eq!(
+			block_on(restore(&dir.path().join("txpool"), &pool, &BlockId::Number(0))).unwrap(),
+			0
+		);
+		assert_eq!(pool.validated_pool().status().ready, 0);
+	}
+
+	#[test]
+	fn restore_rejects_corrupted_snapshot() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("txpool");
+		fs::write(&path, b"garbage").unwrap();
+
+		assert!(block_on(restore(&path, &pool(), &BlockId::Number(0))).is_err());
+	}
+}
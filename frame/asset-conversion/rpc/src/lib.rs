@@ -0,0 +1,183 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the asset conversion pallet.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use pallet_asset_conversion::SwapRoute;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+
+pub use pallet_asset_conversion::AssetConversionApi as AssetConversionRuntimeApi;
+
+#[rpc(client, server)]
+pub trait AssetConversionApi<BlockHash, AssetId> {
+	#[method(name = "assetConversion_quoteBestPathExactTokensForTokens")]
+	fn quote_best_path_exact_tokens_for_tokens(
+		&self,
+		asset1: AssetId,
+		asset2: AssetId,
+		amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SwapRoute<AssetId, NumberOrHex>>>;
+
+	#[method(name = "assetConversion_quoteBestPathTokensForExactTokens")]
+	fn quote_best_path_tokens_for_exact_tokens(
+		&self,
+		asset1: AssetId,
+		asset2: AssetId,
+		amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SwapRoute<AssetId, NumberOrHex>>>;
+}
+
+/// Provides RPC methods to find the best route for a swap.
+pub struct AssetConversion<C, P> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> AssetConversion<C, P> {
+	/// Creates a new instance of the AssetConversion Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn invalid_params(message: String) -> JsonRpseeError {
+	JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+		ErrorCode::InvalidParams.code(),
+		message,
+		None::<()>,
+	)))
+}
+
+/// Converts the RPC representation of an amount into the runtime's `AssetBalance`.
+fn try_from_rpc_balance<AssetBalance>(amount: NumberOrHex) -> RpcResult<AssetBalance>
+where
+	AssetBalance: TryFrom<u128>,
+{
+	u128::try_from(amount)
+		.ok()
+		.and_then(|amount| amount.try_into().ok())
+		.ok_or_else(|| invalid_params(format!("{:?} doesn't fit in AssetBalance", amount)))
+}
+
+/// Converts the amounts of a route into their RPC representation.
+fn into_rpc_route<AssetId, AssetBalance>(
+	route: SwapRoute<AssetId, AssetBalance>,
+) -> RpcResult<SwapRoute<AssetId, NumberOrHex>>
+where
+	AssetBalance: MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	let amounts = route
+		.amounts
+		.into_iter()
+		.map(|value| {
+			value.try_into().map_err(|_| {
+				invalid_params(format!("{} doesn't fit in NumberOrHex representation", value))
+			})
+		})
+		.collect::<RpcResult<Vec<_>>>()?;
+
+	Ok(SwapRoute { path: route.path, amounts })
+}
+
+impl<C, Block, Balance, AssetBalance, AssetId>
+	AssetConversionApiServer<<Block as BlockT>::Hash, AssetId>
+	for AssetConversion<C, (Block, Balance, AssetBalance)>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AssetConversionRuntimeApi<Block, Balance, AssetBalance, AssetId>,
+	Balance: Codec + Send + Sync + 'static,
+	AssetBalance:
+		Codec + MaybeDisplay + Copy + TryFrom<u128> + TryInto<NumberOrHex> + Send + Sync + 'static,
+	AssetId: Codec + Clone + Send + Sync + 'static,
+{
+	fn quote_best_path_exact_tokens_for_tokens(
+		&self,
+		asset1: AssetId,
+		asset2: AssetId,
+		amount: NumberOrHex,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<SwapRoute<AssetId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let amount = try_from_rpc_balance(amount)?;
+		let route = api
+			.quote_best_path_exact_tokens_for_tokens(at_hash, asset1, asset2, amount)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to quote the best path.",
+					Some(e.to_string()),
+				))
+			})?;
+
+		route.map(into_rpc_route).transpose()
+	}
+
+	fn quote_best_path_tokens_for_exact_tokens(
+		&self,
+		asset1: AssetId,
+		asset2: AssetId,
+		amount: NumberOrHex,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<SwapRoute<AssetId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let amount = try_from_rpc_balance(amount)?;
+		let route = api
+			.quote_best_path_tokens_for_exact_tokens(at_hash, asset1, asset2, amount)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to quote the best path.",
+					Some(e.to_string()),
+				))
+			})?;
+
+		route.map(into_rpc_route).transpose()
+	}
+}
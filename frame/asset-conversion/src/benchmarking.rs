@@ -29,7 +29,7 @@ use frame_support::{
 	},
 };
 use frame_system::RawOrigin as SystemOrigin;
-use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_runtime::traits::{Bounded, One, StaticLookup};
 use sp_std::{ops::Div, prelude::*};
 
 use crate::Pallet as AssetConversion;
@@ -86,6 +86,13 @@ where
 	(lp_token, caller, caller_lookup)
 }
 
+/// Moves to the next block, so the price observations made so far get extended by the benchmarked
+/// call rather than left as they are.
+fn next_block<T: Config>() {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now + One::one());
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -121,6 +128,8 @@ benchmarks! {
 		let (lp_token, caller, _) = create_asset_and_pool::<T>(&asset1, &asset2);
 		let ed: u128 = T::Currency::minimum_balance().into();
 		let add_amount = 1000 + ed;
+		AssetConversion::<T>::update_price_observations(&(asset1.clone(), asset2.clone()));
+		next_block::<T>();
 	}: _(SystemOrigin::Signed(caller.clone()), asset1.clone(), asset2.clone(), add_amount.into(), 1000.into(), 0.into(), 0.into(), caller.clone())
 	verify {
 		let pool_id = (asset1.clone(), asset2.clone());
@@ -159,6 +168,7 @@ benchmarks! {
 			caller.clone(),
 		)?;
 		let total_supply = <T::PoolAssets as Inspect<T::AccountId>>::total_issuance(lp_token.clone());
+		next_block::<T>();
 	}: _(SystemOrigin::Signed(caller.clone()), asset1, asset2, remove_lp_amount.into(), 0.into(), 0.into(), caller.clone())
 	verify {
 		let new_total_supply = <T::PoolAssets as Inspect<T::AccountId>>::total_issuance(lp_token.clone());
@@ -216,6 +226,7 @@ benchmarks! {
 			caller.clone(),
 		)?;
 		let asset1_balance = T::Currency::balance(&caller);
+		next_block::<T>();
 	}: _(SystemOrigin::Signed(caller.clone()), path.clone(), ed.into(), 1.into(), caller.clone(), false)
 	verify {
 		let new_asset1_balance = T::Currency::balance(&caller);
@@ -273,6 +284,7 @@ benchmarks! {
 			caller.clone(),
 		)?;
 		let asset4_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(2), &caller);
+		next_block::<T>();
 	}: _(SystemOrigin::Signed(caller.clone()), path.clone(), 100.into(), add_amount1.into(), caller.clone(), false)
 	verify {
 		let new_asset4_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(2), &caller);
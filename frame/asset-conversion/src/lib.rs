@@ -34,6 +34,11 @@
 //!    a runtime call endpoint
 //!  - [query the size of a liquidity pool](`AssetConversionApi::get_reserves`) via a runtime api
 //!    endpoint.
+//!  - [find the best route for a
+//!    swap](`AssetConversionApi::quote_best_path_exact_tokens_for_tokens`) through the existing
+//!    pools via a runtime api endpoint.
+//!  - [query the time-weighted average price](`AssetConversionApi::get_twap`) of a pool via a
+//!    runtime api endpoint, derived from price accumulators updated on every reserves change.
 //!
 //! The `quote_price_exact_tokens_for_tokens` and `quote_price_tokens_for_exact_tokens` functions
 //! both take a path parameter of the route to take. If you want to swap from native asset to
@@ -108,10 +113,10 @@ pub mod pallet {
 	};
 	use sp_arithmetic::Permill;
 	use sp_runtime::{
-		traits::{IntegerSquareRoot, One, Zero},
-		Saturating,
+		traits::{IntegerSquareRoot, One, SaturatedConversion, Zero},
+		FixedPointNumber, FixedU128, Saturating,
 	};
-	use sp_std::prelude::*;
+	use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -192,6 +197,16 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSwapPathLength: Get<u32>;
 
+		/// The max number of price observations kept per pool for time-weighted average prices.
+		///
+		/// At most one observation is recorded per block, when the pool's reserves change. This
+		/// bounds the window [`Pallet::twap`] can be queried over.
+		///
+		/// Observations are kept in a ring buffer of this many slots, so changing this value
+		/// requires migrating [`PriceObservations`] and [`PriceObservationRings`].
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -219,6 +234,25 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type NextPoolAssetId<T: Config> = StorageValue<_, T::PoolAssetId, OptionQuery>;
 
+	/// The most recent cumulative prices of each pool, by slot of the pool's ring buffer. A new
+	/// observation is recorded on the first change of a pool's reserves within a block,
+	/// overwriting the oldest one once all `MaxPriceObservations` slots are used.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		PoolIdOf<T>,
+		Twox64Concat,
+		u32,
+		PriceCumulative<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The slots of [`PriceObservations`] in use by each pool.
+	#[pallet::storage]
+	pub type PriceObservationRings<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolIdOf<T>, PriceObservationRing, ValueQuery>;
+
 	// Pallet's events.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -368,6 +402,10 @@ pub mod pallet {
 				T::MaxSwapPathLength::get() > 1,
 				"the `MaxSwapPathLength` should be greater than 1",
 			);
+			assert!(
+				T::MaxPriceObservations::get() > 1,
+				"the `MaxPriceObservations` should be greater than 1",
+			);
 		}
 	}
 
@@ -477,6 +515,8 @@ pub mod pallet {
 			let maybe_pool = Pools::<T>::get(pool_id.clone());
 			let pool = maybe_pool.as_ref().ok_or(Error::<T>::PoolNotFound)?;
 
+			Self::update_price_observations(&pool_id);
+
 			let amount1: T::AssetBalance;
 			let amount2: T::AssetBalance;
 			let pool_account = Self::get_pool_account(&pool_id);
@@ -585,6 +625,8 @@ pub mod pallet {
 			let maybe_pool = Pools::<T>::get(pool_id.clone());
 			let pool = maybe_pool.as_ref().ok_or(Error::<T>::PoolNotFound)?;
 
+			Self::update_price_observations(&pool_id);
+
 			let pool_account = Self::get_pool_account(&pool_id);
 			let reserve1 = Self::get_balance(&pool_account, &asset1)?;
 			let reserve2 = Self::get_balance(&pool_account, &asset2)?;
@@ -762,6 +804,14 @@ pub mod pallet {
 			keep_alive: bool,
 		) -> Result<(), DispatchError> {
 			ensure!(amounts.len() > 1, Error::<T>::CorrespondenceError);
+			for assets_pair in path.windows(2) {
+				if let [asset1, asset2] = assets_pair {
+					Self::update_price_observations(&Self::get_pool_id(
+						asset1.clone(),
+						asset2.clone(),
+					));
+				}
+			}
 			if let Some([asset1, asset2]) = &path.get(0..2) {
 				let pool_id = Self::get_pool_id(asset1.clone(), asset2.clone());
 				let pool_account = Self::get_pool_account(&pool_id);
@@ -873,6 +923,138 @@ pub mod pallet {
 			Ok((balance1, balance2))
 		}
 
+		/// Returns the time-weighted average prices of the pool over at least the last `window`
+		/// blocks, as the price of the pool's first asset in its second asset and vice versa.
+		///
+		/// The average is taken from the most recent observation made at least `window` blocks
+		/// ago up to the current block. Returns `None` if the pool has no such observation, which
+		/// is the case for pools which haven't had liquidity for `window` blocks or whose oldest
+		/// kept observation is too recent.
+		pub fn twap(
+			pool_id: &PoolIdOf<T>,
+			window: BlockNumberFor<T>,
+		) -> Option<(FixedU128, FixedU128)> {
+			if window.is_zero() {
+				return None
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let start_block = now.checked_sub(&window)?;
+			let ring = PriceObservationRings::<T>::get(pool_id);
+			let latest = Self::price_observation(pool_id, &ring, ring.len.checked_sub(1)?)?;
+
+			// The observations are ordered by block, so binary search for the first one made
+			// after `start_block`. The one before it is where the window starts.
+			let (mut low, mut high) = (0, ring.len);
+			while low < high {
+				let mid = low + (high - low) / 2;
+				if Self::price_observation(pool_id, &ring, mid)?.block <= start_block {
+					low = mid + 1;
+				} else {
+					high = mid;
+				}
+			}
+			let start = Self::price_observation(pool_id, &ring, low.checked_sub(1)?)?;
+			let end = Self::accumulate_prices(pool_id, &latest, now);
+
+			let elapsed = now.saturating_sub(start.block).saturated_into::<u128>();
+			let average = |end: FixedU128, start: FixedU128| {
+				FixedU128::from_inner(end.into_inner().wrapping_sub(start.into_inner()) / elapsed)
+			};
+
+			Some((
+				average(end.price1_cumulative, start.price1_cumulative),
+				average(end.price2_cumulative, start.price2_cumulative),
+			))
+		}
+
+		/// Returns the time-weighted average price of `asset1` in `asset2` over at least the last
+		/// `window` blocks.
+		///
+		/// See [`Pallet::twap`].
+		pub fn get_twap(
+			asset1: &T::MultiAssetId,
+			asset2: &T::MultiAssetId,
+			window: BlockNumberFor<T>,
+		) -> Option<FixedU128> {
+			let pool_id = Self::get_pool_id(asset1.clone(), asset2.clone());
+			let (price1, price2) = Self::twap(&pool_id, window)?;
+			Some(if &pool_id.0 == asset1 { price1 } else { price2 })
+		}
+
+		/// Records the prices of the pool up to the current block, as given by its reserves since
+		/// the last observation.
+		///
+		/// This must be called before the reserves of the pool change.
+		pub(crate) fn update_price_observations(pool_id: &PoolIdOf<T>) {
+			if !Pools::<T>::contains_key(pool_id) {
+				return
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut ring = PriceObservationRings::<T>::get(pool_id);
+			let latest = ring
+				.len
+				.checked_sub(1)
+				.and_then(|index| Self::price_observation(pool_id, &ring, index));
+			let observation = match latest {
+				// the reserves the prices were accumulated over haven't changed yet
+				Some(latest) if latest.block == now => return,
+				Some(latest) => Self::accumulate_prices(pool_id, &latest, now),
+				None => PriceCumulative { block: now, ..Default::default() },
+			};
+
+			let max_observations = T::MaxPriceObservations::get();
+			PriceObservations::<T>::insert(pool_id, ring.next, observation);
+			ring.next = (ring.next + 1) % max_observations;
+			ring.len = (ring.len + 1).min(max_observations);
+			PriceObservationRings::<T>::insert(pool_id, ring);
+		}
+
+		/// Returns the `index`th oldest price observation kept for the pool.
+		pub(crate) fn price_observation(
+			pool_id: &PoolIdOf<T>,
+			ring: &PriceObservationRing,
+			index: u32,
+		) -> Option<PriceCumulative<BlockNumberFor<T>>> {
+			let max_observations = T::MaxPriceObservations::get();
+			let slot = (ring.next + max_observations - ring.len + index) % max_observations;
+			PriceObservations::<T>::get(pool_id, slot)
+		}
+
+		/// Extends the cumulative prices of `latest` up to the block `now`, assuming the current
+		/// reserves of the pool were held since.
+		fn accumulate_prices(
+			pool_id: &PoolIdOf<T>,
+			latest: &PriceCumulative<BlockNumberFor<T>>,
+			now: BlockNumberFor<T>,
+		) -> PriceCumulative<BlockNumberFor<T>> {
+			let elapsed = now.saturating_sub(latest.block).saturated_into::<u128>();
+			let Ok((reserve1, reserve2)) = Self::get_reserves(&pool_id.0, &pool_id.1) else {
+				return PriceCumulative { block: now, ..*latest }
+			};
+			let reserve1 = reserve1.saturated_into::<u128>();
+			let reserve2 = reserve2.saturated_into::<u128>();
+
+			let accumulate = |cumulative: FixedU128, price: FixedU128| {
+				FixedU128::from_inner(
+					cumulative.into_inner().wrapping_add(price.into_inner().wrapping_mul(elapsed)),
+				)
+			};
+
+			PriceCumulative {
+				block: now,
+				price1_cumulative: accumulate(
+					latest.price1_cumulative,
+					FixedU128::saturating_from_rational(reserve2, reserve1),
+				),
+				price2_cumulative: accumulate(
+					latest.price2_cumulative,
+					FixedU128::saturating_from_rational(reserve1, reserve2),
+				),
+			}
+		}
+
 		/// Leading to an amount at the end of a `path`, get the required amounts in.
 		pub(crate) fn get_amounts_in(
 			amount_out: &T::AssetBalance,
@@ -958,6 +1140,92 @@ pub mod pallet {
 			}
 		}
 
+		/// Used by the RPC service to find the route yielding the most of `asset2` for an exact
+		/// `amount` of `asset1`.
+		///
+		/// Every path of up to `MaxSwapPathLength` assets through the existing pools is
+		/// considered. On equal output, the shortest path is preferred.
+		pub fn quote_best_path_exact_tokens_for_tokens(
+			asset1: T::MultiAssetId,
+			asset2: T::MultiAssetId,
+			amount: T::AssetBalance,
+		) -> Option<SwapRoute<T::MultiAssetId, T::AssetBalance>> {
+			let mut best: Option<SwapRoute<T::MultiAssetId, T::AssetBalance>> = None;
+			for path in Self::find_swap_paths(&asset1, &asset2) {
+				let Ok(amounts) = Self::get_amounts_out(&amount, &path) else { continue };
+				let Some(&amount_out) = amounts.last() else { continue };
+				if best.as_ref().map_or(true, |best| best.amounts.last() < Some(&amount_out)) {
+					best = Some(SwapRoute { path: path.into_inner(), amounts });
+				}
+			}
+			best
+		}
+
+		/// Used by the RPC service to find the route requiring the least of `asset1` to receive an
+		/// exact `amount` of `asset2`.
+		///
+		/// Every path of up to `MaxSwapPathLength` assets through the existing pools is
+		/// considered. On equal input, the shortest path is preferred.
+		pub fn quote_best_path_tokens_for_exact_tokens(
+			asset1: T::MultiAssetId,
+			asset2: T::MultiAssetId,
+			amount: T::AssetBalance,
+		) -> Option<SwapRoute<T::MultiAssetId, T::AssetBalance>> {
+			let mut best: Option<SwapRoute<T::MultiAssetId, T::AssetBalance>> = None;
+			for path in Self::find_swap_paths(&asset1, &asset2) {
+				let Ok(amounts) = Self::get_amounts_in(&amount, &path) else { continue };
+				let Some(&amount_in) = amounts.first() else { continue };
+				if best.as_ref().map_or(true, |best| best.amounts.first() > Some(&amount_in)) {
+					best = Some(SwapRoute { path: path.into_inner(), amounts });
+				}
+			}
+			best
+		}
+
+		/// Returns every path of unique assets going from `asset1` to `asset2` through the
+		/// existing pools, with at most `MaxSwapPathLength` assets, ordered by length.
+		///
+		/// This iterates over all of `Pools`, and is meant to be used off-chain only.
+		pub(crate) fn find_swap_paths(
+			asset1: &T::MultiAssetId,
+			asset2: &T::MultiAssetId,
+		) -> Vec<BoundedVec<T::MultiAssetId, T::MaxSwapPathLength>> {
+			if asset1 == asset2 {
+				return Vec::new()
+			}
+
+			let mut pairs = BTreeMap::<T::MultiAssetId, Vec<T::MultiAssetId>>::new();
+			for (pool_asset1, pool_asset2) in Pools::<T>::iter_keys() {
+				pairs.entry(pool_asset1.clone()).or_default().push(pool_asset2.clone());
+				pairs.entry(pool_asset2).or_default().push(pool_asset1);
+			}
+
+			let max_path_len = T::MaxSwapPathLength::get() as usize;
+			let mut paths = Vec::new();
+			let mut pending = vec![vec![asset1.clone()]];
+			while let Some(path) = pending.pop() {
+				let last = path.last().expect("paths are never empty; qed");
+				if last == asset2 {
+					paths.extend(BoundedVec::try_from(path).ok());
+					continue
+				}
+				if path.len() >= max_path_len {
+					continue
+				}
+				for next in pairs.get(last).into_iter().flatten() {
+					// an asset appearing twice would mean a pool is used twice
+					if !path.contains(next) {
+						let mut next_path = path.clone();
+						next_path.push(next.clone());
+						pending.push(next_path);
+					}
+				}
+			}
+
+			paths.sort_by_key(|path| path.len());
+			paths
+		}
+
 		/// Calculates the optimal amount from the reserves.
 		pub fn quote(
 			amount: &T::AssetBalance,
@@ -1221,6 +1489,7 @@ where
 sp_api::decl_runtime_apis! {
 	/// This runtime api allows people to query the size of the liquidity pools
 	/// and quote prices for swaps.
+	#[api_version(3)]
 	pub trait AssetConversionApi<Balance, AssetBalance, AssetId> where
 		Balance: Codec + MaybeDisplay,
 		AssetBalance: frame_support::traits::tokens::Balance,
@@ -1240,6 +1509,25 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Finds the best route for [`Pallet::swap_exact_tokens_for_tokens`], searching all pools
+		/// for a path of up to `MaxSwapPathLength` assets.
+		///
+		/// Returns the path along with the amounts expected at each hop.
+		#[api_version(2)]
+		fn quote_best_path_exact_tokens_for_tokens(asset1: AssetId, asset2: AssetId, amount: AssetBalance) -> Option<SwapRoute<AssetId, AssetBalance>>;
+
+		/// Finds the best route for [`Pallet::swap_tokens_for_exact_tokens`], searching all pools
+		/// for a path of up to `MaxSwapPathLength` assets.
+		///
+		/// Returns the path along with the amounts expected at each hop.
+		#[api_version(2)]
+		fn quote_best_path_tokens_for_exact_tokens(asset1: AssetId, asset2: AssetId, amount: AssetBalance) -> Option<SwapRoute<AssetId, AssetBalance>>;
+
+		/// Returns the time-weighted average price of `asset1` in `asset2` over at least the last
+		/// `window` blocks, as provided by [`Pallet::twap`].
+		#[api_version(3)]
+		fn get_twap(asset1: AssetId, asset2: AssetId, window: u32) -> Option<sp_runtime::FixedU128>;
 	}
 }
 
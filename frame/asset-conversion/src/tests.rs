@@ -20,9 +20,10 @@ use frame_support::{
 	assert_noop, assert_ok,
 	instances::Instance1,
 	traits::{fungible::Inspect, fungibles::InspectEnumerable, Get},
+	BoundedVec,
 };
 use sp_arithmetic::Permill;
-use sp_runtime::{DispatchError, TokenError};
+use sp_runtime::{DispatchError, FixedPointNumber, FixedU128, TokenError};
 
 fn events() -> Vec<Event<Test>> {
 	let result = System::events()
@@ -1411,3 +1412,250 @@ fn cannot_block_pool_creation() {
 		));
 	});
 }
+
+#[test]
+fn find_swap_paths_respects_max_path_length() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let token_3 = NativeOrAssetId::Asset(3);
+		let token_4 = NativeOrAssetId::Asset(4);
+		let token_5 = NativeOrAssetId::Asset(5);
+
+		create_tokens(user, vec![token_2, token_3, token_4, token_5]);
+		// A chain of pools: 1 <-> 2 <-> 3 <-> 4 <-> 5
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_2, token_3));
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_3, token_4));
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_4, token_5));
+
+		assert_eq!(
+			AssetConversion::find_swap_paths(&token_1, &token_4),
+			vec![BoundedVec::<_, <Test as Config>::MaxSwapPathLength>::try_from(vec![
+				token_1, token_2, token_3, token_4
+			])
+			.unwrap()]
+		);
+		// Would require 5 assets in the path, while `MaxSwapPathLength` is 4.
+		assert!(AssetConversion::find_swap_paths(&token_1, &token_5).is_empty());
+		// Same asset.
+		assert!(AssetConversion::find_swap_paths(&token_1, &token_1).is_empty());
+	});
+}
+
+#[test]
+fn can_quote_best_path() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let token_3 = NativeOrAssetId::Asset(3);
+		let token_4 = NativeOrAssetId::Asset(4);
+
+		create_tokens(user, vec![token_2, token_3, token_4]);
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_2, token_3));
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_3));
+
+		let ed = get_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 20000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 20000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, 20000));
+
+		// Deep pools through token 2, a shallow direct pool between tokens 1 and 3.
+		for (asset1, asset2, amount1, amount2) in [
+			(token_1, token_2, 10000, 10000),
+			(token_2, token_3, 10000, 10000),
+			(token_1, token_3, 1000, 100),
+		] {
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				asset1,
+				asset2,
+				amount1,
+				amount2,
+				1,
+				1,
+				user,
+			));
+		}
+
+		let amount = 100;
+
+		// The route through token 2 yields more than the direct pool.
+		let multi_hop: BoundedVec<_, <Test as Config>::MaxSwapPathLength> =
+			bvec![token_1, token_2, token_3];
+		let direct: BoundedVec<_, <Test as Config>::MaxSwapPathLength> = bvec![token_1, token_3];
+		let multi_hop_amounts = AssetConversion::get_amounts_out(&amount, &multi_hop).unwrap();
+		let direct_amounts = AssetConversion::get_amounts_out(&amount, &direct).unwrap();
+		assert!(multi_hop_amounts.last() > direct_amounts.last());
+		assert_eq!(
+			AssetConversion::quote_best_path_exact_tokens_for_tokens(token_1, token_3, amount),
+			Some(SwapRoute { path: multi_hop.clone().into_inner(), amounts: multi_hop_amounts })
+		);
+
+		// The route through token 2 is also the cheapest to receive an exact amount.
+		let multi_hop_amounts = AssetConversion::get_amounts_in(&amount, &multi_hop).unwrap();
+		assert!(AssetConversion::get_amounts_in(&amount, &direct).is_err());
+		assert_eq!(
+			AssetConversion::quote_best_path_tokens_for_exact_tokens(token_1, token_3, amount),
+			Some(SwapRoute { path: multi_hop.into_inner(), amounts: multi_hop_amounts })
+		);
+
+		// Swapping into token 2, the direct pool beats the detour through the shallow pool.
+		assert_eq!(
+			AssetConversion::quote_best_path_exact_tokens_for_tokens(token_1, token_2, amount),
+			Some(SwapRoute {
+				path: vec![token_1, token_2],
+				amounts: vec![
+					amount,
+					AssetConversion::get_amount_out(&amount, &10000, &10000).unwrap()
+				],
+			})
+		);
+
+		// No pool contains token 4.
+		assert_eq!(
+			AssetConversion::quote_best_path_exact_tokens_for_tokens(token_1, token_4, amount),
+			None
+		);
+		assert_eq!(
+			AssetConversion::quote_best_path_tokens_for_exact_tokens(token_4, token_1, amount),
+			None
+		);
+	});
+}
+
+#[test]
+fn twap_averages_prices_over_the_window() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let pool_id = (token_1, token_2);
+
+		create_tokens(user, vec![token_2]);
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+
+		let ed = get_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+
+		// a pool without liquidity has no price
+		assert_eq!(AssetConversion::twap(&pool_id, 1), None);
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_2,
+			10000,
+			1000,
+			1,
+			1,
+			user,
+		));
+		let price1 = FixedU128::saturating_from_rational(1000, 10000);
+		let price2 = FixedU128::saturating_from_rational(10000, 1000);
+
+		// the liquidity was provided too recently
+		assert_eq!(AssetConversion::twap(&pool_id, 1), None);
+		assert_eq!(AssetConversion::twap(&pool_id, 0), None);
+
+		System::set_block_number(11);
+		assert_eq!(AssetConversion::twap(&pool_id, 10), Some((price1, price2)));
+		assert_eq!(AssetConversion::twap(&pool_id, 5), Some((price1, price2)));
+		assert_eq!(AssetConversion::twap(&pool_id, 11), None);
+		assert_eq!(AssetConversion::get_twap(&token_2, &token_1, 10), Some(price2));
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2, token_1],
+			1000,
+			1,
+			user,
+			false,
+		));
+		let (reserve1, reserve2) = AssetConversion::get_reserves(&token_1, &token_2).unwrap();
+		let new_price1 = FixedU128::saturating_from_rational(reserve2, reserve1);
+		let new_price2 = FixedU128::saturating_from_rational(reserve1, reserve2);
+
+		// a swap doesn't move the average within the block it's made in
+		assert_eq!(AssetConversion::twap(&pool_id, 10), Some((price1, price2)));
+
+		System::set_block_number(21);
+		assert_eq!(AssetConversion::twap(&pool_id, 10), Some((new_price1, new_price2)));
+
+		let average = |old: FixedU128, new: FixedU128| {
+			FixedU128::from_inner((old.into_inner() * 10 + new.into_inner() * 10) / 20)
+		};
+		assert_eq!(
+			AssetConversion::twap(&pool_id, 20),
+			Some((average(price1, new_price1), average(price2, new_price2)))
+		);
+		assert_eq!(
+			AssetConversion::get_twap(&token_1, &token_2, 20),
+			Some(average(price1, new_price1))
+		);
+	});
+}
+
+#[test]
+fn twap_keeps_a_bounded_number_of_observations() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let pool_id = (token_1, token_2);
+
+		create_tokens(user, vec![token_2]);
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+
+		let ed = get_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_2,
+			10000,
+			10000,
+			1,
+			1,
+			user,
+		));
+
+		let max_observations: u32 = <Test as Config>::MaxPriceObservations::get();
+		for block in 2..=(max_observations as u64 + 1) {
+			System::set_block_number(block);
+			// only the first change of the reserves within a block is observed
+			for _ in 0..2 {
+				assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+					RuntimeOrigin::signed(user),
+					bvec![token_1, token_2],
+					10,
+					1,
+					user,
+					false,
+				));
+			}
+		}
+
+		let ring = PriceObservationRings::<Test>::get(&pool_id);
+		assert_eq!(ring.len, max_observations);
+		assert_eq!(
+			PriceObservations::<Test>::iter_prefix(&pool_id).count() as u32,
+			max_observations
+		);
+		// the observation made when the liquidity was added has been overwritten
+		let oldest = AssetConversion::price_observation(&pool_id, &ring, 0).unwrap();
+		assert_eq!(oldest.block, 2);
+		let latest = AssetConversion::price_observation(&pool_id, &ring, ring.len - 1).unwrap();
+		assert_eq!(latest.block, max_observations as u64 + 1);
+
+		let now = System::block_number();
+		assert!(AssetConversion::twap(&pool_id, now - 2).is_some());
+		assert_eq!(AssetConversion::twap(&pool_id, now - 1), None);
+	});
+}
@@ -21,6 +21,10 @@ use sp_std::cmp::Ordering;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{FixedU128, RuntimeDebug};
+use sp_std::vec::Vec;
 
 pub(super) type PoolIdOf<T> = (<T as Config>::MultiAssetId, <T as Config>::MultiAssetId);
 
@@ -31,6 +35,47 @@ pub struct PoolInfo<PoolAssetId> {
 	pub lp_token: PoolAssetId,
 }
 
+/// The cumulative prices of a pool at a given block, from which time-weighted average prices
+/// can be derived.
+///
+/// Each price is the amount of one asset in the pool per unit of the other, summed once per block
+/// since the pool received liquidity. The sums wrap on overflow; only the difference between two
+/// observations is meaningful.
+#[derive(
+	Decode, Encode, Default, Clone, Copy, PartialEq, Eq, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
+pub struct PriceCumulative<BlockNumber> {
+	/// The block the prices were accumulated up to.
+	pub block: BlockNumber,
+	/// The cumulative price of the pool's first asset, denominated in its second asset.
+	pub price1_cumulative: FixedU128,
+	/// The cumulative price of the pool's second asset, denominated in its first asset.
+	pub price2_cumulative: FixedU128,
+}
+
+/// Where the price observations of a pool are kept within its ring buffer of
+/// `MaxPriceObservations` slots.
+#[derive(
+	Decode, Encode, Default, Clone, Copy, PartialEq, Eq, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
+pub struct PriceObservationRing {
+	/// The slot the next observation will be written to.
+	pub next: u32,
+	/// The number of observations kept, ending with the one before `next`.
+	pub len: u32,
+}
+
+/// A route to swap one asset for another through the existing pools.
+#[derive(Decode, Encode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapRoute<MultiAssetId, AssetBalance> {
+	/// The assets to swap through, starting with the asset provided and ending with the asset
+	/// received. Can be used as the `path` of a swap.
+	pub path: Vec<MultiAssetId>,
+	/// The amount of each asset along the `path`, as computed from the current reserves.
+	pub amounts: Vec<AssetBalance>,
+}
+
 /// A trait that converts between a MultiAssetId and either the native currency or an AssetId.
 pub trait MultiAssetIdConverter<MultiAssetId, AssetId> {
 	/// Returns the MultiAssetId reperesenting the native currency of the chain.
@@ -65,6 +110,7 @@ where
 
 /// An implementation of MultiAssetId that can be either Native or an asset.
 #[derive(Decode, Encode, Default, MaxEncodedLen, TypeInfo, Clone, Copy, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum NativeOrAssetId<AssetId>
 where
 	AssetId: Ord,
@@ -43,6 +43,10 @@
 // --header=./HEADER-APACHE2
 // --template=./.maintain/frame-weight-template.hbs
 
+// TODO: Regenerate with the command above. The TWAP price observations make `add_liquidity`,
+// `remove_liquidity` and the swaps read and write `PriceObservationRings` and `PriceObservations`
+// once for every pool they touch, which these weights don't account for yet.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -54,6 +54,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Account::<T, I>::get(id, who.borrow()).map(|a| a.balance)
 	}
 
+	/// Get the frozen balance of `who` for asset `id`, the largest of its freezes and of the
+	/// `Freezer` hook, or `None` if the account is not frozen.
+	pub(super) fn frozen_balance(id: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
+		let frozen = Freezes::<T, I>::get(&id, who).into_iter().map(|f| f.amount).max();
+		// `None` is less than any `Some`.
+		T::Freezer::frozen_balance(id, who).max(frozen)
+	}
+
 	/// Get the total supply of an asset `id`.
 	pub fn total_supply(id: T::AssetId) -> T::Balance {
 		Self::maybe_total_supply(id).unwrap_or_default()
@@ -116,6 +124,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Remove
 	}
 
+	/// Clean up after the asset-account of `who` was removed, removing its freezes and calling the
+	/// `FrozenBalance::died` hook.
+	fn account_died(id: T::AssetId, who: &T::AccountId) {
+		Freezes::<T, I>::remove(&id, who);
+		T::Freezer::died(id, who);
+	}
+
 	/// Returns `true` when the balance of `account` can be increased by `amount`.
 	///
 	/// - `id`: The id of the asset that should be increased.
@@ -140,7 +155,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			if account.status.is_blocked() {
 				return DepositConsequence::Blocked
 			}
-			if account.balance.checked_add(&amount).is_none() {
+			if account
+				.balance
+				.checked_add(&amount)
+				.and_then(|balance| balance.checked_add(&account.held))
+				.is_none()
+			{
 				return DepositConsequence::Overflow
 			}
 		} else {
@@ -187,8 +207,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			return Frozen
 		}
 		if let Some(rest) = account.balance.checked_sub(&amount) {
-			if let Some(frozen) = T::Freezer::frozen_balance(id.clone(), who) {
-				match frozen.checked_add(&details.min_balance) {
+			if let Some(frozen) = Self::frozen_balance(id.clone(), who) {
+				// Freezes apply to the total balance, so the funds on hold count towards them.
+				match frozen.saturating_sub(account.held).checked_add(&details.min_balance) {
 					Some(required) if rest < required => return Frozen,
 					None => return Overflow,
 					_ => {},
@@ -196,7 +217,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			}
 
 			if rest < details.min_balance {
-				if keep_alive {
+				// An account with funds on hold must stay alive.
+				if keep_alive || !account.held.is_zero() {
 					WouldDie
 				} else {
 					ReducedToZero(rest)
@@ -222,13 +244,15 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let account = Account::<T, I>::get(&id, who).ok_or(Error::<T, I>::NoAccount)?;
 		ensure!(!account.status.is_frozen(), Error::<T, I>::Frozen);
 
-		let amount = if let Some(frozen) = T::Freezer::frozen_balance(id, who) {
+		let amount = if let Some(frozen) = Self::frozen_balance(id, who) {
 			// Frozen balance: account CANNOT be deleted
-			let required =
-				frozen.checked_add(&details.min_balance).ok_or(ArithmeticError::Overflow)?;
+			let required = frozen
+				.saturating_sub(account.held)
+				.checked_add(&details.min_balance)
+				.ok_or(ArithmeticError::Overflow)?;
 			account.balance.saturating_sub(required)
 		} else {
-			if keep_alive {
+			if keep_alive || !account.held.is_zero() {
 				// We want to keep the account around.
 				account.balance.saturating_sub(details.min_balance)
 			} else {
@@ -331,6 +355,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			&who,
 			AssetAccountOf::<T, I> {
 				balance: Zero::zero(),
+				held: Zero::zero(),
 				status: AccountStatus::Liquid,
 				reason,
 				extra: T::Extra::default(),
@@ -350,6 +375,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let mut details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
 		ensure!(matches!(details.status, Live | Frozen), Error::<T, I>::IncorrectStatus);
 		ensure!(account.balance.is_zero() || allow_burn, Error::<T, I>::WouldBurn);
+		ensure!(account.held.is_zero(), Error::<T, I>::ContainsHolds);
 
 		if let Some(deposit) = account.reason.take_deposit() {
 			T::Currency::unreserve(&who, deposit);
@@ -365,7 +391,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 		Asset::<T, I>::insert(&id, details);
 		// Executing a hook here is safe, since it is not in a `mutate`.
-		T::Freezer::died(id, &who);
+		Self::account_died(id, &who);
 		Ok(())
 	}
 
@@ -383,6 +409,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		ensure!(!account.status.is_frozen(), Error::<T, I>::Frozen);
 		ensure!(caller == &depositor || caller == &details.admin, Error::<T, I>::NoPermission);
 		ensure!(account.balance.is_zero(), Error::<T, I>::WouldBurn);
+		ensure!(account.held.is_zero(), Error::<T, I>::ContainsHolds);
 
 		T::Currency::unreserve(&depositor, deposit);
 
@@ -396,7 +423,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 		Asset::<T, I>::insert(&id, details);
 		// Executing a hook here is safe, since it is not in a `mutate`.
-		T::Freezer::died(id, &who);
+		Self::account_died(id, &who);
 		return Ok(())
 	}
 
@@ -462,6 +489,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						ensure!(amount >= details.min_balance, TokenError::BelowMinimum);
 						*maybe_account = Some(AssetAccountOf::<T, I> {
 							balance: amount,
+							held: Zero::zero(),
 							reason: Self::new_account(beneficiary, details, None)?,
 							status: AccountStatus::Liquid,
 							extra: T::Extra::default(),
@@ -564,7 +592,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		// Execute hook outside of `mutate`.
 		if let Some(Remove) = target_died {
-			T::Freezer::died(id, target);
+			Self::account_died(id, target);
 		}
 		Ok(actual)
 	}
@@ -588,7 +616,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let (balance, died) =
 			Self::transfer_and_die(id.clone(), source, dest, amount, maybe_need_admin, f)?;
 		if let Some(Remove) = died {
-			T::Freezer::died(id, source);
+			Self::account_died(id, source);
 		}
 		Ok(balance)
 	}
@@ -657,6 +685,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					maybe_account @ None => {
 						*maybe_account = Some(AssetAccountOf::<T, I> {
 							balance: credit,
+							held: Zero::zero(),
 							status: AccountStatus::Liquid,
 							reason: Self::new_account(dest, details, None)?,
 							extra: T::Extra::default(),
@@ -770,6 +799,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					}
 					if let Remove = Self::dead_account(&who, &mut details, &v.reason, false) {
 						Account::<T, I>::remove(&id, &who);
+						// The funds on hold are destroyed along with the asset.
+						Holds::<T, I>::remove(&id, &who);
 						dead_accounts.push(who);
 					} else {
 						// deposit may have been released, need to update `Account`
@@ -785,7 +816,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			})?;
 
 		for who in &dead_accounts {
-			T::Freezer::died(id.clone(), &who);
+			Self::account_died(id.clone(), &who);
 		}
 
 		Self::deposit_event(Event::AccountsDestroyed {
@@ -946,7 +977,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		// Execute hook outside of `mutate`.
 		if let Some(Remove) = owner_died {
-			T::Freezer::died(id, owner);
+			Self::account_died(id, owner);
 		}
 		Ok(())
 	}
@@ -21,7 +21,7 @@
 use frame_support::{
 	defensive,
 	traits::tokens::{
-		Fortitude,
+		Fortitude::{self, Force},
 		Precision::{self, BestEffort},
 		Preservation::{self, Expendable},
 		Provenance::{self, Minted},
@@ -47,7 +47,9 @@ impl<T: Config<I>, I: 'static> fungibles::Inspect<<T as SystemConfig>::AccountId
 	}
 
 	fn total_balance(asset: Self::AssetId, who: &<T as SystemConfig>::AccountId) -> Self::Balance {
-		Pallet::<T, I>::balance(asset, who)
+		Account::<T, I>::get(asset, who)
+			.map(|a| a.balance.saturating_add(a.held))
+			.unwrap_or_else(Zero::zero)
 	}
 
 	fn reducible_balance(
@@ -168,6 +170,171 @@ impl<T: Config<I>, I: 'static> fungibles::Unbalanced<T::AccountId> for Pallet<T,
 	// TODO: #13196 implement deactivate/reactivate once we have inactive balance tracking.
 }
 
+impl<T: Config<I>, I: 'static> fungibles::hold::Inspect<T::AccountId> for Pallet<T, I> {
+	type Reason = T::RuntimeHoldReason;
+
+	fn total_balance_on_hold(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Account::<T, I>::get(asset, who).map(|a| a.held).unwrap_or_else(Zero::zero)
+	}
+
+	fn reducible_total_balance_on_hold(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		force: Fortitude,
+	) -> T::Balance {
+		let account = match Account::<T, I>::get(&asset, who) {
+			Some(account) => account,
+			None => return Zero::zero(),
+		};
+		let unavailable = if force == Force {
+			Zero::zero()
+		} else {
+			// The freezes apply to the total balance, so we can discount the balance which isn't
+			// on hold from the amount which the funds on hold must provide to satisfy them.
+			Self::frozen_balance(asset, who)
+				.unwrap_or_default()
+				.saturating_sub(account.balance)
+		};
+		account.held.saturating_sub(unavailable)
+	}
+
+	fn balance_on_hold(asset: T::AssetId, reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
+		Holds::<T, I>::get(asset, who)
+			.iter()
+			.find(|x| &x.id == reason)
+			.map_or_else(Zero::zero, |x| x.amount)
+	}
+
+	fn hold_available(asset: T::AssetId, reason: &Self::Reason, who: &T::AccountId) -> bool {
+		if !Account::<T, I>::contains_key(&asset, who) {
+			return false
+		}
+		let holds = Holds::<T, I>::get(asset, who);
+		if holds.is_full() && !holds.iter().any(|x| &x.id == reason) {
+			return false
+		}
+		true
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::hold::Unbalanced<T::AccountId> for Pallet<T, I> {
+	fn set_balance_on_hold(
+		asset: T::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let mut holds = Holds::<T, I>::get(&asset, who);
+		let mut increase = true;
+		let mut delta = amount;
+
+		if let Some(item) = holds.iter_mut().find(|x| &x.id == reason) {
+			delta = item.amount.max(amount).saturating_sub(item.amount.min(amount));
+			increase = amount > item.amount;
+			item.amount = amount;
+			holds.retain(|x| !x.amount.is_zero());
+		} else if !amount.is_zero() {
+			holds
+				.try_push(IdAmount { id: *reason, amount })
+				.map_err(|_| Error::<T, I>::TooManyHolds)?;
+		}
+
+		Account::<T, I>::try_mutate(&asset, who, |maybe_account| -> DispatchResult {
+			let account = maybe_account.as_mut().ok_or(Error::<T, I>::NoAccount)?;
+			account.held = if increase {
+				account.held.checked_add(&delta).ok_or(ArithmeticError::Overflow)?
+			} else {
+				account.held.checked_sub(&delta).ok_or(ArithmeticError::Underflow)?
+			};
+			Ok(())
+		})?;
+
+		if holds.is_empty() {
+			Holds::<T, I>::remove(asset, who);
+		} else {
+			Holds::<T, I>::insert(asset, who, holds);
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::hold::Balanced<T::AccountId> for Pallet<T, I> {}
+
+impl<T: Config<I>, I: 'static> fungibles::hold::Mutate<T::AccountId> for Pallet<T, I> {}
+
+impl<T: Config<I>, I: 'static> fungibles::freeze::Inspect<T::AccountId> for Pallet<T, I> {
+	type Id = T::RuntimeFreezeReason;
+
+	fn balance_frozen(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> T::Balance {
+		Freezes::<T, I>::get(asset, who)
+			.iter()
+			.find(|x| &x.id == id)
+			.map_or_else(Zero::zero, |x| x.amount)
+	}
+
+	fn can_freeze(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Freezes::<T, I>::get(asset, who);
+		!freezes.is_full() || freezes.iter().any(|x| &x.id == id)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::freeze::Mutate<T::AccountId> for Pallet<T, I> {
+	fn set_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Self::thaw(asset, id, who)
+		}
+		ensure!(Account::<T, I>::contains_key(&asset, who), Error::<T, I>::NoAccount);
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		if let Some(item) = freezes.iter_mut().find(|x| &x.id == id) {
+			item.amount = amount;
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Freezes::<T, I>::insert(asset, who, freezes);
+		Ok(())
+	}
+
+	fn extend_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(())
+		}
+		ensure!(Account::<T, I>::contains_key(&asset, who), Error::<T, I>::NoAccount);
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		if let Some(item) = freezes.iter_mut().find(|x| &x.id == id) {
+			item.amount = item.amount.max(amount);
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Freezes::<T, I>::insert(asset, who, freezes);
+		Ok(())
+	}
+
+	fn thaw(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		freezes.retain(|x| &x.id != id);
+		if freezes.is_empty() {
+			Freezes::<T, I>::remove(asset, who);
+		} else {
+			Freezes::<T, I>::insert(asset, who, freezes);
+		}
+		Ok(())
+	}
+}
+
 impl<T: Config<I>, I: 'static> fungibles::Create<T::AccountId> for Pallet<T, I> {
 	fn create(
 		id: T::AssetId,
@@ -130,6 +130,18 @@
 //! Using `CallbackHandle` associated type, user can configure custom callback functions which are
 //! executed when new asset is created or an existing asset is destroyed.
 //!
+//! ### Holds and Freezes
+//!
+//! The pallet implements `fungibles::hold` and `fungibles::freeze`, for other pallets to place
+//! funds of an account on hold, identified by a `RuntimeHoldReason`, or freeze them, identified by
+//! a `RuntimeFreezeReason`.
+//!
+//! Funds on hold are no longer part of the `balance` of the account, but still count towards its
+//! total balance and the supply of the asset. An account with funds on hold is kept alive.
+//!
+//! Freezes apply to the total balance of the account, including funds on hold. They are combined
+//! with the `Freezer` hook, the largest frozen balance being enforced.
+//!
 //! ## Related Modules
 //!
 //! * [`System`](../frame_system/index.html)
@@ -210,7 +222,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -311,6 +323,20 @@ pub mod pallet {
 		/// respected in all permissionless operations.
 		type Freezer: FrozenBalance<Self::AssetId, Self::AccountId, Self::Balance>;
 
+		/// The overarching hold reason.
+		type RuntimeHoldReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The maximum number of holds that can exist on an asset account at any time.
+		#[pallet::constant]
+		type MaxHolds: Get<u32>;
+
+		/// The maximum number of freezes that can exist on an asset account at any time.
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
+
 		/// Additional data to be stored with an account's asset balance.
 		type Extra: Member + Parameter + Default + MaxEncodedLen;
 
@@ -345,6 +371,30 @@ pub mod pallet {
 		AssetAccountOf<T, I>,
 	>;
 
+	#[pallet::storage]
+	/// Holds on the balances of asset accounts.
+	pub type Holds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::RuntimeHoldReason, T::Balance>, T::MaxHolds>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// Freezes on the balances of asset accounts.
+	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::RuntimeFreezeReason, T::Balance>, T::MaxFreezes>,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	/// Approved balance transfers. First balance is the amount approved for transfer. Second
 	/// is the amount of `T::Currency` reserved for storing this.
@@ -572,6 +622,12 @@ pub mod pallet {
 		NotFrozen,
 		/// Callback action resulted in error
 		CallbackFailed,
+		/// Number of holds on the asset account exceed `MaxHolds`.
+		TooManyHolds,
+		/// Number of freezes on the asset account exceed `MaxFreezes`.
+		TooManyFreezes,
+		/// The asset-account has funds on hold.
+		ContainsHolds,
 	}
 
 	#[pallet::call(weight(<T as Config<I>>::WeightInfo))]
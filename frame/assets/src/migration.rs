@@ -136,3 +136,93 @@ pub mod v1 {
 		}
 	}
 }
+
+pub mod v2 {
+	use frame_support::{
+		migrations::{SteppedMigration, SteppedMigrationError},
+		pallet_prelude::*,
+		storage::unhashed,
+		traits::PalletInfoAccess,
+		weights::WeightMeter,
+	};
+
+	use super::*;
+
+	#[derive(Decode)]
+	pub struct OldAssetAccount<Balance, DepositBalance, Extra, AccountId> {
+		pub balance: Balance,
+		pub status: AccountStatus,
+		pub reason: ExistenceReason<DepositBalance, AccountId>,
+		pub extra: Extra,
+	}
+
+	impl<Balance: Zero, DepositBalance, Extra, AccountId>
+		OldAssetAccount<Balance, DepositBalance, Extra, AccountId>
+	{
+		fn migrate_to_v2(self) -> AssetAccount<Balance, DepositBalance, Extra, AccountId> {
+			AssetAccount {
+				balance: self.balance,
+				held: Zero::zero(),
+				status: self.status,
+				reason: self.reason,
+				extra: self.extra,
+			}
+		}
+	}
+
+	/// Migrate the asset accounts to track their balance on hold, which is initially zero.
+	///
+	/// This is a [`SteppedMigration`], run by `pallet-migrations` over as many blocks as the number
+	/// of accounts requires. Its cursor is the last account migrated.
+	pub struct MigrateToV2<T, I = ()>(sp_std::marker::PhantomData<(T, I)>);
+	impl<T: Config<I>, I: 'static> SteppedMigration for MigrateToV2<T, I> {
+		type Cursor = (T::AssetId, T::AccountId);
+		type Identifier = ([u8; 16], u8);
+
+		fn id() -> Self::Identifier {
+			(*b"pallet-assets-v2", <Pallet<T, I> as PalletInfoAccess>::index() as u8)
+		}
+
+		fn step(
+			mut cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			// Reading the storage version, and writing it once done.
+			let base = T::DbWeight::get().reads_writes(1, 1);
+			// Reading the next key and the old account, and writing the new one.
+			let per_account = T::DbWeight::get().reads_writes(2, 1);
+			let required = base.saturating_add(per_account);
+			if !meter.can_accrue(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required })
+			}
+			meter.defensive_saturating_accrue(base);
+			if Pallet::<T, I>::on_chain_storage_version() != 1 {
+				log::info!(
+					target: LOG_TARGET,
+					"Migration did not execute. This probably should be removed"
+				);
+				return Ok(None)
+			}
+
+			let mut keys = match cursor.as_ref() {
+				Some((id, who)) =>
+					Account::<T, I>::iter_keys_from(Account::<T, I>::hashed_key_for(id, who)),
+				None => Account::<T, I>::iter_keys(),
+			};
+			while meter.check_accrue(per_account) {
+				let Some((id, who)) = keys.next() else {
+					Pallet::<T, I>::current_storage_version().put::<Pallet<T, I>>();
+					log::info!(target: LOG_TARGET, "Upgraded the accounts to storage version 2");
+					return Ok(None)
+				};
+				let old = unhashed::get::<
+					OldAssetAccount<T::Balance, DepositBalanceOf<T, I>, T::Extra, T::AccountId>,
+				>(&Account::<T, I>::hashed_key_for(&id, &who))
+				.ok_or(SteppedMigrationError::Failed)?;
+				Account::<T, I>::insert(&id, &who, old.migrate_to_v2());
+				cursor = Some((id, who));
+			}
+			Ok(cursor)
+		}
+	}
+}
@@ -1776,3 +1776,211 @@ fn asset_destroy_refund_existence_deposit() {
 		assert_eq!(Balances::reserved_balance(&admin), 0);
 	});
 }
+
+#[test]
+fn hold_and_release_should_work() {
+	use frame_support::traits::tokens::{
+		fungibles::{
+			hold::{Inspect as _, Mutate as _},
+			Inspect as _,
+		},
+		Precision::Exact,
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		assert_ok!(Assets::hold(0, &TestId::Foo, &1, 30));
+		assert_eq!(Assets::balance(0, 1), 70);
+		assert_eq!(Assets::total_balance(0, &1), 100);
+		assert_eq!(Assets::balance_on_hold(0, &TestId::Foo, &1), 30);
+		assert_eq!(Assets::total_balance_on_hold(0, &1), 30);
+		assert_eq!(Assets::total_supply(0), 100);
+
+		// the minimum balance must remain outside of the holds...
+		assert_noop!(Assets::hold(0, &TestId::Bar, &1, 61), TokenError::FundsUnavailable);
+		// ...and the account can't be removed while it has funds on hold.
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 70),
+			Error::<Test>::BalanceLow
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 60));
+		assert_eq!(Assets::balance(0, 1), 10);
+
+		assert_ok!(Assets::release(0, &TestId::Foo, &1, 30, Exact));
+		assert_eq!(Assets::balance(0, 1), 40);
+		assert_eq!(Assets::total_balance_on_hold(0, &1), 0);
+		assert!(!Holds::<Test>::contains_key(0, 1));
+
+		// with no funds on hold, the account can be removed again.
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 40));
+		assert!(Assets::maybe_balance(0, 1).is_none());
+	});
+}
+
+#[test]
+fn holds_are_bounded() {
+	use frame_support::traits::tokens::fungibles::hold::{Inspect as _, Mutate as _};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		assert!(!Assets::hold_available(0, &TestId::Foo, &2));
+		assert_ok!(Assets::hold(0, &TestId::Foo, &1, 10));
+		assert_ok!(Assets::hold(0, &TestId::Bar, &1, 10));
+		assert!(!Assets::hold_available(0, &TestId::Baz, &1));
+		assert_noop!(Assets::hold(0, &TestId::Baz, &1, 10), TokenError::CannotCreateHold);
+		// increasing an existing hold is still fine.
+		assert_ok!(Assets::hold(0, &TestId::Foo, &1, 10));
+		assert_eq!(Assets::balance_on_hold(0, &TestId::Foo, &1), 20);
+	});
+}
+
+#[test]
+fn burning_and_transferring_held_funds_should_work() {
+	use frame_support::traits::tokens::{
+		fungibles::hold::{Balanced as _, Inspect as _, Mutate as _},
+		Fortitude::Polite,
+		Precision::Exact,
+		Restriction::{Free, OnHold},
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 100));
+		assert_ok!(Assets::hold(0, &TestId::Foo, &1, 60));
+
+		assert_eq!(Assets::burn_held(0, &TestId::Foo, &1, 20, Exact, Polite), Ok(20));
+		assert_eq!(Assets::total_supply(0), 180);
+		assert_eq!(Assets::balance_on_hold(0, &TestId::Foo, &1), 40);
+
+		assert_eq!(
+			Assets::transfer_on_hold(0, &TestId::Foo, &1, &2, 10, Exact, Free, Polite),
+			Ok(10)
+		);
+		assert_eq!(Assets::balance(0, 2), 110);
+		assert_eq!(
+			Assets::transfer_on_hold(0, &TestId::Foo, &1, &2, 10, Exact, OnHold, Polite),
+			Ok(10)
+		);
+		assert_eq!(Assets::balance_on_hold(0, &TestId::Foo, &2), 10);
+
+		let (credit, remaining) = Assets::slash(0, &TestId::Foo, &1, 30);
+		assert_eq!((credit.peek(), remaining), (20, 10));
+		drop(credit);
+		assert_eq!(Assets::total_balance_on_hold(0, &1), 0);
+		assert_eq!(Assets::total_supply(0), 160);
+	});
+}
+
+#[test]
+fn freezes_should_work_with_the_freezer() {
+	use frame_support::traits::tokens::fungibles::{
+		freeze::{Inspect as _, Mutate},
+		hold::Mutate as _,
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		assert_noop!(Assets::set_freeze(0, &TestId::Foo, &2, 50), Error::<Test>::NoAccount);
+		assert_ok!(Assets::set_freeze(0, &TestId::Foo, &1, 50));
+		assert_ok!(Assets::extend_freeze(0, &TestId::Foo, &1, 40));
+		assert_eq!(Assets::balance_frozen(0, &TestId::Foo, &1), 50);
+		assert_ok!(Assets::set_freeze(0, &TestId::Bar, &1, 30));
+		assert!(!Assets::can_freeze(0, &TestId::Baz, &1));
+		assert_noop!(Assets::set_freeze(0, &TestId::Baz, &1, 10), Error::<Test>::TooManyFreezes);
+
+		// the largest freeze applies...
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 41),
+			Error::<Test>::BalanceLow
+		);
+		// ...unless the freezer freezes more.
+		set_frozen_balance(0, 1, 60);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 31),
+			Error::<Test>::BalanceLow
+		);
+		clear_frozen_balance(0, 1);
+
+		// funds on hold count towards the freezes.
+		assert_ok!(Assets::hold(0, &TestId::Foo, &1, 20));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 40));
+		assert_eq!(Assets::balance(0, 1), 40);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 1),
+			Error::<Test>::BalanceLow
+		);
+
+		assert_ok!(<Assets as Mutate<_>>::thaw(0, &TestId::Foo, &1));
+		assert_eq!(Assets::balance_frozen(0, &TestId::Foo, &1), 0);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 20));
+		assert_eq!(Assets::balance(0, 1), 20);
+	});
+}
+
+#[test]
+fn holds_and_freezes_are_removed_with_the_asset() {
+	use frame_support::traits::tokens::fungibles::{freeze::Mutate as _, hold::Mutate as _};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::hold(0, &TestId::Foo, &1, 20));
+		assert_ok!(Assets::set_freeze(0, &TestId::Foo, &1, 50));
+
+		assert_ok!(Assets::start_destroy(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::destroy_accounts(RuntimeOrigin::signed(1), 0));
+		assert!(Assets::maybe_balance(0, 1).is_none());
+		assert!(!Holds::<Test>::contains_key(0, 1));
+		assert!(!Freezes::<Test>::contains_key(0, 1));
+		assert_eq!(hooks(), vec![Hook::Died(0, 1)]);
+	});
+}
+
+#[test]
+fn migration_to_v2_should_work() {
+	use codec::Encode;
+	use frame_support::{
+		migrations::SteppedMigration,
+		traits::{GetStorageVersion, StorageVersion},
+		weights::WeightMeter,
+	};
+
+	new_test_ext().execute_with(|| {
+		// accounts as encoded before the balance on hold was tracked.
+		for who in 1..=3u64 {
+			let old =
+				(100 * who, AccountStatus::Liquid, ExistenceReason::<u64, u64>::Sufficient, ());
+			frame_support::storage::unhashed::put_raw(
+				&Account::<Test>::hashed_key_for(999, who),
+				&old.encode(),
+			);
+		}
+		StorageVersion::new(1).put::<Assets>();
+
+		let mut cursor = None;
+		loop {
+			let mut meter = WeightMeter::max_limit();
+			cursor = migration::v2::MigrateToV2::<Test>::step(cursor, &mut meter).unwrap();
+			if cursor.is_none() {
+				break
+			}
+		}
+
+		assert_eq!(Assets::on_chain_storage_version(), 2);
+		for who in 1..=3u64 {
+			let account = Account::<Test>::get(999, who).unwrap();
+			assert_eq!((account.balance(), account.held()), (100 * who, 0));
+		}
+
+		// nothing is left to do once the storage was migrated
+		let mut meter = WeightMeter::max_limit();
+		assert_eq!(migration::v2::MigrateToV2::<Test>::step(None, &mut meter), Ok(None));
+	});
+}
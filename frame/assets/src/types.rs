@@ -175,8 +175,10 @@ impl AccountStatus {
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct AssetAccount<Balance, DepositBalance, Extra, AccountId> {
-	/// The balance.
+	/// The balance, excluding any funds on hold.
 	pub(super) balance: Balance,
+	/// The balance on hold, for all hold reasons.
+	pub(super) held: Balance,
 	/// The status of the account.
 	pub(super) status: AccountStatus,
 	/// The reason for the existence of the account.
@@ -192,6 +194,20 @@ impl<Balance: Copy, DepositBalance, Extra, AccountId>
 	pub fn balance(&self) -> Balance {
 		self.balance
 	}
+
+	/// Directly access the balance on hold of an account, irrelevant of status.
+	pub fn held(&self) -> Balance {
+		self.held
+	}
+}
+
+/// An identifier and balance, for holds and freezes.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct IdAmount<Id, Balance> {
+	/// An identifier for this item.
+	pub id: Id,
+	/// Some amount for this item.
+	pub amount: Balance,
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -213,6 +229,10 @@ pub struct AssetMetadata<DepositBalance, BoundedString> {
 /// Trait for allowing a minimum balance on the account to be specified, beyond the
 /// `minimum_balance` of the asset. This is additive - the `minimum_balance` of the asset must be
 /// met *and then* anything here in addition.
+///
+/// This is reconciled with the freezes placed through `fungibles::freeze::Mutate`, the largest of
+/// them all being enforced. Like those, it applies to the total balance of the account, including
+/// any funds on hold.
 pub trait FrozenBalance<AssetId, AccountId, Balance> {
 	/// Return the frozen balance.
 	///
@@ -109,6 +109,82 @@ benchmarks! {
 	);
 }
 
+/// Baselines for the experimental BLS12-381 host functions.
+#[cfg(feature = "bls-experimental")]
+pub mod bls {
+	use super::*;
+	use sp_application_crypto::bls381;
+
+	mod crypto {
+		use sp_application_crypto::{app_crypto, bls381, KeyTypeId};
+
+		pub const TEST_KEY_TYPE_ID: KeyTypeId = KeyTypeId(*b"test");
+		app_crypto!(bls381, TEST_KEY_TYPE_ID);
+	}
+	pub type SignerId = crypto::Public;
+
+	pub struct Pallet<T: Config>(System<T>);
+	pub trait Config: frame_system::Config {}
+
+	/// Generate `n` signers and their signatures on `msg`.
+	fn signers(n: u32, msg: &[u8]) -> (Vec<bls381::Public>, Vec<bls381::Signature>) {
+		(0..n)
+			.map(|_| {
+				let public = SignerId::generate_pair(None);
+				let signature = public.sign(&msg).unwrap();
+				(public.into(), signature.into())
+			})
+			.unzip()
+	}
+
+	benchmarks! {
+		bls381_verification {
+			let i in 0 .. 100;
+
+			let public = SignerId::generate_pair(None);
+
+			let sigs_count: u8 = i.try_into().unwrap();
+			let msg_and_sigs: Vec<_> = (0..sigs_count).map(|j| {
+				let msg = vec![j, j];
+				(msg.clone(), public.sign(&msg).unwrap())
+			})
+			.collect();
+		}: {
+			msg_and_sigs.iter().for_each(|(msg, sig)| {
+				assert!(sig.verify(&msg[..], &public));
+			});
+		}
+
+		bls381_aggregate_verification {
+			let n in 1 .. 100;
+
+			let msg = b"committee message".to_vec();
+			let (publics, sigs) = signers(n, &msg);
+		}: {
+			assert!(sp_io::crypto::bls381_aggregate_verify(&sigs, &msg, &publics));
+		}
+
+		bls381_fast_aggregate_verification {
+			let n in 1 .. 100;
+
+			let msg = b"committee message".to_vec();
+			let (publics, sigs) = signers(n, &msg);
+			let aggregate = sp_io::crypto::bls381_aggregate_signatures(&sigs).unwrap();
+		}: {
+			assert!(sp_io::crypto::bls381_fast_aggregate_verify(&aggregate, &msg, &publics));
+		}
+
+		impl_benchmark_test_suite!(
+			Pallet,
+			crate::baseline::mock::new_test_ext(),
+			crate::baseline::mock::Test,
+		);
+	}
+
+	#[cfg(test)]
+	impl Config for super::mock::Test {}
+}
+
 #[cfg(test)]
 pub mod mock {
 	use super::*;
@@ -117,9 +117,10 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Codec, Encode};
+use codec::{Codec, Decode, Encode};
 use frame_support::{
 	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	migrations::MultiStepMigrator as MultiStepMigratorT,
 	pallet_prelude::InvalidTransaction,
 	traits::{
 		EnsureInherentsAreFirst, ExecuteBlock, OffchainWorker, OnFinalize, OnIdle, OnInitialize,
@@ -128,14 +129,15 @@ use frame_support::{
 	weights::Weight,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
+use sp_dry_run::{DryRunEffects, DryRunError};
 use sp_runtime::{
 	generic::Digest,
 	traits::{
 		self, Applyable, CheckEqual, Checkable, Dispatchable, Header, NumberFor, One,
 		ValidateUnsigned, Zero,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult,
+	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
+	ApplyExtrinsicResult, ApplyExtrinsicResultWithInfo, DispatchResultWithInfo,
 };
 use sp_std::{marker::PhantomData, prelude::*};
 
@@ -160,6 +162,9 @@ pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
 ///   used to call hooks e.g. `on_initialize`.
 /// - `OnRuntimeUpgrade`: Custom logic that should be called after a runtime upgrade. Modules are
 ///   already called by `AllPalletsWithSystem`. It will be called before all modules will be called.
+/// - `MultiStepMigrator`: The runner of the multi-block migrations, such as `pallet-migrations`.
+///   Its steps run at the end of every block while a migration is ongoing, and no non-mandatory
+///   extrinsic is applied until then.
 pub struct Executive<
 	System,
 	Block,
@@ -167,6 +172,7 @@ pub struct Executive<
 	UnsignedValidator,
 	AllPalletsWithSystem,
 	OnRuntimeUpgrade = (),
+	MultiStepMigrator = (),
 >(
 	PhantomData<(
 		System,
@@ -175,6 +181,7 @@ pub struct Executive<
 		UnsignedValidator,
 		AllPalletsWithSystem,
 		OnRuntimeUpgrade,
+		MultiStepMigrator,
 	)>,
 );
 
@@ -192,8 +199,17 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
+		CMultiStepMigrator: MultiStepMigratorT,
 	> ExecuteBlock<Block>
-	for Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+	for Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		CMultiStepMigrator,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -210,6 +226,7 @@ where
 			UnsignedValidator,
 			AllPalletsWithSystem,
 			COnRuntimeUpgrade,
+			CMultiStepMigrator,
 		>::execute_block(block);
 	}
 }
@@ -230,7 +247,17 @@ impl<
 			+ OffchainWorker<BlockNumberFor<System>>
 			+ frame_support::traits::TryState<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		CMultiStepMigrator: MultiStepMigratorT,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		CMultiStepMigrator,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -278,9 +305,10 @@ where
 			} else {
 				uxt.unchecked_into_checked_i_know_what_i_am_doing(&Default::default())
 			}?;
+			let dispatch_info = xt.get_dispatch_info();
+			Self::ensure_not_migrating(&dispatch_info)?;
 			<frame_system::Pallet<System>>::note_extrinsic(encoded);
 
-			let dispatch_info = xt.get_dispatch_info();
 			let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 			<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
@@ -398,7 +426,17 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		CMultiStepMigrator: MultiStepMigratorT,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		CMultiStepMigrator,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -546,7 +584,16 @@ where
 	fn idle_and_finalize_hook(block_number: NumberFor<Block>) {
 		let weight = <frame_system::Pallet<System>>::block_weight();
 		let max_weight = <System::BlockWeights as frame_support::traits::Get<_>>::get().max_block;
-		let remaining_weight = max_weight.saturating_sub(weight.total());
+		let mut remaining_weight = max_weight.saturating_sub(weight.total());
+
+		if CMultiStepMigrator::ongoing() {
+			let used_weight = CMultiStepMigrator::step(remaining_weight);
+			<frame_system::Pallet<System>>::register_extra_weight_unchecked(
+				used_weight,
+				DispatchClass::Mandatory,
+			);
+			remaining_weight = remaining_weight.saturating_sub(used_weight);
+		}
 
 		if remaining_weight.all_gt(Weight::zero()) {
 			let used_weight = <AllPalletsWithSystem as OnIdle<BlockNumberFor<System>>>::on_idle(
@@ -567,6 +614,13 @@ where
 	/// This doesn't attempt to validate anything regarding the block, but it builds a list of uxt
 	/// hashes.
 	pub fn apply_extrinsic(uxt: Block::Extrinsic) -> ApplyExtrinsicResult {
+		Self::apply_extrinsic_with_post_info(uxt).map(|r| r.map(|_| ()).map_err(|e| e.error))
+	}
+
+	/// Same as [`Self::apply_extrinsic`], but returns the post dispatch info of the extrinsic.
+	fn apply_extrinsic_with_post_info(
+		uxt: Block::Extrinsic,
+	) -> ApplyExtrinsicResultWithInfo<PostDispatchInfo> {
 		sp_io::init_tracing();
 		let encoded = uxt.encode();
 		let encoded_len = encoded.len();
@@ -574,6 +628,8 @@ where
 				ext=?sp_core::hexdisplay::HexDisplay::from(&encoded)));
 		// Verify that the signature is good.
 		let xt = uxt.check(&Default::default())?;
+		let dispatch_info = xt.get_dispatch_info();
+		Self::ensure_not_migrating(&dispatch_info)?;
 
 		// We don't need to make sure to `note_extrinsic` only after we know it's going to be
 		// executed to prevent it from leaking in storage since at this point, it will either
@@ -583,7 +639,6 @@ where
 		// AUDIT: Under no circumstances may this function panic from here onwards.
 
 		// Decode parameters and dispatch
-		let dispatch_info = xt.get_dispatch_info();
 		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 		// Mandatory(inherents) are not allowed to fail.
@@ -596,7 +651,74 @@ where
 
 		<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
 
-		Ok(r.map(|_| ()).map_err(|e| e.error))
+		Ok(r)
+	}
+
+	/// Dry-run `uxt`, as if it were applied in the block after `block_hash`.
+	///
+	/// The block is initialized as in [`Self::validate_transaction`], without running the
+	/// `on_initialize` hooks. Changes made to storage should be discarded.
+	pub fn dry_run_extrinsic(
+		uxt: Block::Extrinsic,
+		block_hash: Block::Hash,
+	) -> Result<DryRunEffects, DryRunError> {
+		Self::initialize_dry_run(&block_hash);
+		let result =
+			Self::apply_extrinsic_with_post_info(uxt).map_err(DryRunError::InvalidExtrinsic)?;
+		Ok(Self::dry_run_effects(result))
+	}
+
+	/// Dry-run the encoded `call`, dispatched from the encoded `OriginCaller` `origin`, as if it
+	/// were dispatched in the block after `block_hash`.
+	///
+	/// The block is initialized as in [`Self::validate_transaction`], without running the
+	/// `on_initialize` hooks. Changes made to storage should be discarded.
+	pub fn dry_run_call<OriginCaller>(
+		origin: &[u8],
+		call: &[u8],
+		block_hash: Block::Hash,
+	) -> Result<DryRunEffects, DryRunError>
+	where
+		OriginCaller: Decode + Into<OriginOf<Block::Extrinsic, Context>>,
+		CallOf<Block::Extrinsic, Context>: Decode,
+	{
+		let origin =
+			OriginCaller::decode(&mut &origin[..]).map_err(|_| DryRunError::InvalidOrigin)?;
+		let call = <CallOf<Block::Extrinsic, Context>>::decode(&mut &call[..])
+			.map_err(|_| DryRunError::InvalidCall)?;
+
+		Self::initialize_dry_run(&block_hash);
+		let result = call.dispatch(origin.into());
+		Ok(Self::dry_run_effects(result))
+	}
+
+	fn initialize_dry_run(block_hash: &Block::Hash) {
+		<frame_system::Pallet<System>>::initialize(
+			&(frame_system::Pallet::<System>::block_number() + One::one()),
+			block_hash,
+			&Default::default(),
+		);
+		// Only report the events of the dry-run.
+		<frame_system::Pallet<System>>::reset_events();
+		<frame_system::Pallet<System>>::note_finished_initialize();
+	}
+
+	fn dry_run_effects(result: DispatchResultWithInfo<PostDispatchInfo>) -> DryRunEffects {
+		DryRunEffects {
+			execution_result: result.encode(),
+			emitted_events: <frame_system::Pallet<System>>::read_events_no_consensus()
+				.map(|record| record.event.encode())
+				.collect(),
+		}
+	}
+
+	/// Only mandatory extrinsics may be applied, or transactions validated, while a multi-block
+	/// migration is ongoing.
+	fn ensure_not_migrating(dispatch_info: &DispatchInfo) -> Result<(), TransactionValidityError> {
+		if dispatch_info.class != DispatchClass::Mandatory && CMultiStepMigrator::ongoing() {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
+		Ok(())
 	}
 
 	fn final_checks(header: &frame_system::pallet_prelude::HeaderFor<System>) {
@@ -663,6 +785,8 @@ where
 		if dispatch_info.class == DispatchClass::Mandatory {
 			return Err(InvalidTransaction::MandatoryValidation.into())
 		}
+		// Don't let the pool accept transactions no block would apply.
+		Self::ensure_not_migrating(&dispatch_info)?;
 
 		within_span! {
 			sp_tracing::Level::TRACE, "validate";
@@ -707,7 +831,7 @@ mod tests {
 	};
 
 	use frame_support::{
-		assert_err, parameter_types,
+		assert_err, assert_ok, parameter_types,
 		traits::{fungible, ConstU32, ConstU64, ConstU8, Currency},
 		weights::{ConstantMultiplier, IdentityFee, RuntimeDbWeight, Weight, WeightToFee},
 	};
@@ -929,6 +1053,22 @@ mod tests {
 	parameter_types! {
 		pub static RuntimeVersionTestValues: sp_version::RuntimeVersion =
 			Default::default();
+		pub static MigrationOngoing: bool = false;
+		pub static MigrationSteps: Vec<Weight> = vec![];
+	}
+
+	/// A multi-block migration which runs for as long as `MigrationOngoing` is set, recording the
+	/// weight available to each step.
+	struct MockedMigrator;
+	impl MultiStepMigratorT for MockedMigrator {
+		fn ongoing() -> bool {
+			MigrationOngoing::get()
+		}
+
+		fn step(remaining: Weight) -> Weight {
+			MigrationSteps::mutate(|steps| steps.push(remaining));
+			Weight::from_parts(100, 0)
+		}
 	}
 
 	type SignedExtra = (
@@ -960,6 +1100,7 @@ mod tests {
 		Runtime,
 		AllPalletsWithSystem,
 		CustomOnRuntimeUpgrade,
+		MockedMigrator,
 	>;
 
 	fn extra(nonce: u64, fee: Balance) -> SignedExtra {
@@ -1614,4 +1755,128 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn only_mandatory_extrinsics_are_applied_while_migrating() {
+		let xt = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+		let inherent = TestXt::new(RuntimeCall::Custom(custom::Call::inherent_call {}), None);
+
+		new_test_ext(1).execute_with(|| {
+			MigrationOngoing::set(true);
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+
+			assert_ok!(Executive::apply_extrinsic(inherent));
+			assert_err!(
+				Executive::apply_extrinsic(xt.clone()),
+				TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)
+			);
+			assert_eq!(<frame_system::Pallet<Runtime>>::extrinsic_index(), Some(1));
+
+			// The migration steps with the remaining weight of the block, before `on_idle`.
+			let remaining = <Runtime as frame_system::Config>::BlockWeights::get()
+				.max_block
+				.saturating_sub(<frame_system::Pallet<Runtime>>::block_weight().total());
+			Executive::finalize_block();
+			assert_eq!(MigrationSteps::get(), vec![remaining]);
+
+			// Once the migration completed, extrinsics are applied again.
+			MigrationOngoing::set(false);
+			Executive::initialize_block(&Header::new(
+				2,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			assert_ok!(Executive::apply_extrinsic(xt));
+			Executive::finalize_block();
+			assert_eq!(MigrationSteps::get().len(), 1);
+		});
+	}
+
+	#[test]
+	fn transactions_are_invalid_while_migrating() {
+		let xt = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+
+		new_test_ext(1).execute_with(|| {
+			MigrationOngoing::set(true);
+			assert_eq!(
+				Executive::validate_transaction(
+					TransactionSource::External,
+					xt.clone(),
+					Default::default(),
+				),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)),
+			);
+
+			MigrationOngoing::set(false);
+			assert!(Executive::validate_transaction(
+				TransactionSource::External,
+				xt,
+				Default::default(),
+			)
+			.is_ok());
+		});
+	}
+
+	#[test]
+	fn dry_run_reports_effects() {
+		let xt = TestXt::new(call_transfer(2, 69), sign_extra(1, 0, 0));
+		let transferred =
+			RuntimeEvent::Balances(pallet_balances::Event::Transfer { from: 1, to: 2, amount: 69 });
+		let decode_events = |effects: &DryRunEffects| {
+			effects
+				.emitted_events
+				.iter()
+				.map(|e| RuntimeEvent::decode(&mut &e[..]).unwrap())
+				.collect::<Vec<_>>()
+		};
+
+		new_test_ext(2).execute_with(|| {
+			let effects = Executive::dry_run_extrinsic(xt, H256::default()).unwrap();
+			let result = DispatchResultWithInfo::<PostDispatchInfo>::decode(
+				&mut &effects.execution_result[..],
+			)
+			.unwrap();
+			assert!(result.is_ok());
+			assert!(decode_events(&effects).contains(&transferred));
+
+			let origin = OriginCaller::system(frame_system::RawOrigin::Signed(1)).encode();
+			let effects = Executive::dry_run_call::<OriginCaller>(
+				&origin,
+				&call_transfer(2, 69).encode(),
+				H256::default(),
+			)
+			.unwrap();
+			assert!(decode_events(&effects).contains(&transferred));
+
+			// Dispatch errors are reported in the result.
+			let effects = Executive::dry_run_call::<OriginCaller>(
+				&origin,
+				&call_transfer(2, 1_000).encode(),
+				H256::default(),
+			)
+			.unwrap();
+			let result = DispatchResultWithInfo::<PostDispatchInfo>::decode(
+				&mut &effects.execution_result[..],
+			)
+			.unwrap();
+			assert!(result.is_err());
+
+			assert_eq!(
+				Executive::dry_run_call::<OriginCaller>(&[], &[], H256::default()),
+				Err(DryRunError::InvalidOrigin)
+			);
+			assert_eq!(
+				Executive::dry_run_call::<OriginCaller>(&origin, &[], H256::default()),
+				Err(DryRunError::InvalidCall)
+			);
+		});
+	}
 }
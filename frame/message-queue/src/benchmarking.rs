@@ -275,6 +275,70 @@ mod benchmarks {
 		assert!(Pages::<T>::contains_key(&origin, 0), "Page must be updated");
 	}
 
+	// Worst case for `set_queue_priority` where the queue is inserted as the last one.
+	#[benchmark]
+	fn set_queue_priority() -> Result<(), BenchmarkError> {
+		let admin =
+			T::QueueAdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let higher = QueuePriority { priority: 1, weight_share: Perbill::from_percent(1) };
+		let queues = (1..T::MaxPrioritizedQueues::get()).map(|i| (i.into(), higher)).collect();
+		PrioritizedQueues::<T>::put(BoundedVec::truncate_from(queues));
+		let priority = QueuePriority { priority: 0, weight_share: Perbill::from_percent(10) };
+
+		#[extrinsic_call]
+		_(admin as T::RuntimeOrigin, 0u32.into(), Some(priority));
+
+		assert_last_event::<T>(
+			Event::QueuePrioritySet { origin: 0.into(), priority: Some(priority) }.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn suspend_queue() -> Result<(), BenchmarkError> {
+		let admin =
+			T::QueueAdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(admin as T::RuntimeOrigin, 0u32.into());
+
+		assert!(SuspendedQueues::<T>::contains_key(&0u32.into()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resume_queue() -> Result<(), BenchmarkError> {
+		let admin =
+			T::QueueAdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		SuspendedQueues::<T>::insert(&0u32.into(), ());
+
+		#[extrinsic_call]
+		_(admin as T::RuntimeOrigin, 0u32.into());
+
+		assert!(!SuspendedQueues::<T>::contains_key(&0u32.into()));
+		Ok(())
+	}
+
+	// `service_prioritized_queues` without any queue processing.
+	#[benchmark]
+	fn service_prioritized_queues(n: Linear<0, { T::MaxPrioritizedQueues::get() }>) {
+		let priority = QueuePriority { priority: 0, weight_share: Perbill::from_percent(10) };
+		let queues = (0..n)
+			.map(|i| {
+				let origin: MessageOriginOf<T> = i.into();
+				BookStateFor::<T>::insert(&origin, empty_book::<T>());
+				(origin, priority)
+			})
+			.collect();
+		PrioritizedQueues::<T>::put(BoundedVec::truncate_from(queues));
+		let mut meter = WeightMeter::max_limit();
+
+		#[block]
+		{
+			MessageQueue::<T>::service_prioritized_queues(&mut meter, Weight::MAX, Weight::MAX);
+		}
+	}
+
 	impl_benchmark_test_suite! {
 		MessageQueue,
 		crate::mock::new_test_ext::<crate::integration_test::Test>(),
@@ -35,6 +35,7 @@ use frame_support::{
 	parameter_types,
 	traits::{ConstU32, ConstU64},
 };
+use frame_system::EnsureRoot;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use rand_distr::Pareto;
 use sp_core::H256;
@@ -93,6 +94,8 @@ impl Config for Test {
 	type HeapSize = HeapSize;
 	type MaxStale = MaxStale;
 	type ServiceWeight = ServiceWeight;
+	type QueueAdminOrigin = EnsureRoot<u64>;
+	type MaxPrioritizedQueues = ConstU32<16>;
 }
 
 /// Simulates heavy usage by enqueueing and processing large amounts of messages.
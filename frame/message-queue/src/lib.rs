@@ -113,6 +113,21 @@
 //! to advance to the next *ready* queue and service it. This continues until there are no more
 //! queues on which it can make progress or not enough weight to check that.
 //!
+//! # Scenario: Prioritized queues
+//!
+//! The [`Config::QueueAdminOrigin`] can give a queue a [`QueuePriority`] through
+//! [`Pallet::set_queue_priority`]. Before rotating the `ReadyRing`, each prioritized queue is
+//! serviced in order of descending priority with up to its `weight_share` of the weight limit.
+//! Prioritized queues stay in the `ReadyRing` and are serviced again in the round-robin with any
+//! weight that is left.
+//!
+//! # Scenario: Queue suspension
+//!
+//! On top of [`Config::QueuePausedQuery`], the [`Config::QueueAdminOrigin`] can suspend a single
+//! queue through [`Pallet::suspend_queue`] until it is resumed through [`Pallet::resume_queue`].
+//! A suspended queue is treated exactly like a paused one: it still accepts messages but none of
+//! them are executed.
+//!
 //! # Scenario: Overweight execution
 //!
 //! A permanently over-weight message which was skipped by the message processing will never be
@@ -142,6 +157,9 @@
 //!   queues via their `ready_neighbours` fields. A `Queue` is *ready* if it contains at least one
 //!   `Message` which can be processed. Can be empty.
 //! - `ServiceHead`: A pointer into the `ReadyRing` to the next `Queue` to be serviced.
+//! - `PrioritizedQueues`: The queues which are serviced before rotating the `ReadyRing`, in the
+//!   order they are serviced in.
+//! - `SuspendedQueues`: The queues which were suspended by the [`Config::QueueAdminOrigin`].
 //! - (`un`)`processed`: A message is marked as *processed* after it was executed by the pallet. A
 //!   message which was either: not yet executed or could not be executed remains as `unprocessed`
 //!   which is the default state for a message after being enqueued.
@@ -206,7 +224,7 @@ use scale_info::TypeInfo;
 use sp_arithmetic::traits::{BaseArithmetic, Unsigned};
 use sp_runtime::{
 	traits::{One, Zero},
-	SaturatedConversion, Saturating,
+	Perbill, SaturatedConversion, Saturating,
 };
 use sp_std::{fmt::Debug, ops::Deref, prelude::*, vec};
 use sp_weights::WeightMeter;
@@ -424,6 +442,29 @@ impl<MessageOrigin> Default for BookState<MessageOrigin> {
 	}
 }
 
+/// How a prioritized queue is serviced.
+#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug, PartialEq, Eq)]
+pub struct QueuePriority {
+	/// Prioritized queues are serviced in order of descending priority. Queues of equal priority
+	/// are serviced in the order they were prioritized in.
+	pub priority: u8,
+	/// The share of the weight limit which the queue may use before the `ReadyRing` is rotated.
+	pub weight_share: Perbill,
+}
+
+/// A permanently overweight message, as exposed by the [`MessageQueueApi`].
+#[derive(Clone, Encode, Decode, TypeInfo, RuntimeDebug, PartialEq, Eq)]
+pub struct OverweightMessage<Size> {
+	/// The page which the message is stored in.
+	pub page_index: PageIndex,
+	/// The index of the message in its page.
+	pub message_index: Size,
+	/// The `blake2_256` hash of the message.
+	pub hash: [u8; 32],
+	/// The length of the message.
+	pub len: u32,
+}
+
 /// Handler code for when the items in a queue change.
 pub trait OnQueueChanged<Id> {
 	/// Note that the queue `id` now has `item_count` items in it, taking up `items_size` bytes.
@@ -502,6 +543,13 @@ pub mod pallet {
 		/// `ServiceQueues::service_queues` manually.
 		#[pallet::constant]
 		type ServiceWeight: Get<Option<Weight>>;
+
+		/// The origin which may prioritize, suspend and resume queues.
+		type QueueAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of queues which can be prioritized at once.
+		#[pallet::constant]
+		type MaxPrioritizedQueues: Get<u32>;
 	}
 
 	#[pallet::event]
@@ -520,6 +568,12 @@ pub mod pallet {
 		},
 		/// This page was reaped.
 		PageReaped { origin: MessageOriginOf<T>, index: PageIndex },
+		/// The priority of a queue was set or, if `None`, removed.
+		QueuePrioritySet { origin: MessageOriginOf<T>, priority: Option<QueuePriority> },
+		/// A queue was suspended.
+		QueueSuspended { origin: MessageOriginOf<T> },
+		/// A queue was resumed.
+		QueueResumed { origin: MessageOriginOf<T> },
 	}
 
 	#[pallet::error]
@@ -546,6 +600,12 @@ pub mod pallet {
 		///
 		/// This can change at any time and may resolve in the future by re-trying.
 		QueuePaused,
+		/// There are already `MaxPrioritizedQueues` prioritized queues.
+		TooManyPrioritizedQueues,
+		/// The queue is already suspended.
+		AlreadySuspended,
+		/// The queue is not suspended.
+		NotSuspended,
 	}
 
 	/// The index of the first and last (non-empty) pages.
@@ -569,6 +629,19 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The prioritized queues, in the order they are serviced in.
+	#[pallet::storage]
+	pub(super) type PrioritizedQueues<T: Config> = StorageValue<
+		_,
+		BoundedVec<(MessageOriginOf<T>, QueuePriority), T::MaxPrioritizedQueues>,
+		ValueQuery,
+	>;
+
+	/// The queues which are suspended until they are resumed.
+	#[pallet::storage]
+	pub(super) type SuspendedQueues<T: Config> =
+		StorageMap<_, Twox64Concat, MessageOriginOf<T>, (), OptionQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
@@ -629,6 +702,74 @@ pub mod pallet {
 				Self::do_execute_overweight(message_origin, page, index, weight_limit)?;
 			Ok(Some(actual_weight).into())
 		}
+
+		/// Set the priority of a queue or, if `priority` is `None`, remove it.
+		///
+		/// - `origin`: Must be the `QueueAdminOrigin`.
+		/// - `message_origin`: The origin of the queue.
+		/// - `priority`: The new priority of the queue.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_queue_priority())]
+		pub fn set_queue_priority(
+			origin: OriginFor<T>,
+			message_origin: MessageOriginOf<T>,
+			priority: Option<QueuePriority>,
+		) -> DispatchResult {
+			T::QueueAdminOrigin::ensure_origin(origin)?;
+			PrioritizedQueues::<T>::try_mutate(|queues| {
+				queues.retain(|(o, _)| o != &message_origin);
+				if let Some(priority) = priority {
+					let index = queues
+						.iter()
+						.position(|(_, p)| p.priority < priority.priority)
+						.unwrap_or(queues.len());
+					queues
+						.try_insert(index, (message_origin.clone(), priority))
+						.map_err(|_| Error::<T>::TooManyPrioritizedQueues)?;
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			Self::deposit_event(Event::QueuePrioritySet { origin: message_origin, priority });
+			Ok(())
+		}
+
+		/// Suspend a queue so that none of its messages are executed until it is resumed.
+		///
+		/// - `origin`: Must be the `QueueAdminOrigin`.
+		/// - `message_origin`: The origin of the queue.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::suspend_queue())]
+		pub fn suspend_queue(
+			origin: OriginFor<T>,
+			message_origin: MessageOriginOf<T>,
+		) -> DispatchResult {
+			T::QueueAdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				!SuspendedQueues::<T>::contains_key(&message_origin),
+				Error::<T>::AlreadySuspended
+			);
+			SuspendedQueues::<T>::insert(&message_origin, ());
+			Self::deposit_event(Event::QueueSuspended { origin: message_origin });
+			Ok(())
+		}
+
+		/// Resume a queue which was suspended through `suspend_queue`.
+		///
+		/// The queue stays paused if [`Config::QueuePausedQuery`] says so.
+		///
+		/// - `origin`: Must be the `QueueAdminOrigin`.
+		/// - `message_origin`: The origin of the queue.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::resume_queue())]
+		pub fn resume_queue(
+			origin: OriginFor<T>,
+			message_origin: MessageOriginOf<T>,
+		) -> DispatchResult {
+			T::QueueAdminOrigin::ensure_origin(origin)?;
+			SuspendedQueues::<T>::take(&message_origin).ok_or(Error::<T>::NotSuspended)?;
+			Self::deposit_event(Event::QueueResumed { origin: message_origin });
+			Ok(())
+		}
 	}
 }
 
@@ -755,55 +896,162 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn do_enqueue_message(
+	/// Enqueue `messages` into the queue of `origin`.
+	///
+	/// The book state and the last page are only read and written once for the whole batch.
+	fn do_enqueue_messages<'a>(
 		origin: &MessageOriginOf<T>,
-		message: BoundedSlice<u8, MaxMessageLenOf<T>>,
+		messages: impl Iterator<Item = BoundedSlice<'a, u8, MaxMessageLenOf<T>>>,
 	) {
-		let mut book_state = BookStateFor::<T>::get(origin);
-		book_state.message_count.saturating_inc();
-		book_state
-			.size
-			// This should be payload size, but here the payload *is* the message.
-			.saturating_accrue(message.len() as u64);
+		let mut messages = messages.peekable();
+		if messages.peek().is_none() {
+			return
+		}
 
+		let mut book_state = BookStateFor::<T>::get(origin);
+		// The last page, if it can still be appended to.
+		let mut page = None;
 		if book_state.end > book_state.begin {
 			debug_assert!(book_state.ready_neighbours.is_some(), "Must be in ready ring if ready");
-			// Already have a page in progress - attempt to append.
-			let last = book_state.end - 1;
-			let mut page = match Pages::<T>::get(origin, last) {
-				Some(p) => p,
-				None => {
-					defensive!("Corruption: referenced page doesn't exist.");
-					return
-				},
-			};
-			if page.try_append_message::<T>(message).is_ok() {
-				Pages::<T>::insert(origin, last, &page);
-				BookStateFor::<T>::insert(origin, book_state);
-				return
+			page = Pages::<T>::get(origin, book_state.end - 1);
+			if page.is_none() {
+				defensive!("Corruption: referenced page doesn't exist.");
 			}
 		} else {
 			debug_assert!(
 				book_state.ready_neighbours.is_none(),
 				"Must not be in ready ring if not ready"
 			);
-			// insert into ready queue.
-			match Self::ready_ring_knit(origin) {
-				Ok(neighbours) => book_state.ready_neighbours = Some(neighbours),
-				Err(()) => {
-					defensive!("Ring state invalid when knitting");
-				},
+		}
+
+		for message in messages {
+			book_state.message_count.saturating_inc();
+			book_state
+				.size
+				// This should be payload size, but here the payload *is* the message.
+				.saturating_accrue(message.len() as u64);
+
+			if let Some(ref mut page) = page {
+				if page.try_append_message::<T>(message).is_ok() {
+					continue
+				}
+			}
+			if book_state.ready_neighbours.is_none() {
+				// insert into ready queue.
+				match Self::ready_ring_knit(origin) {
+					Ok(neighbours) => book_state.ready_neighbours = Some(neighbours),
+					Err(()) => {
+						defensive!("Ring state invalid when knitting");
+					},
+				}
+			}
+			// No room on the page or no page - link in a new page.
+			if let Some(full) = page.take() {
+				Pages::<T>::insert(origin, book_state.end - 1, full);
 			}
+			book_state.end.saturating_inc();
+			book_state.count.saturating_inc();
+			page = Some(Page::from_message::<T>(message));
+		}
+
+		if let Some(page) = page {
+			Pages::<T>::insert(origin, book_state.end - 1, page);
 		}
-		// No room on the page or no page - link in a new page.
-		book_state.end.saturating_inc();
-		book_state.count.saturating_inc();
-		let page = Page::from_message::<T>(message);
-		Pages::<T>::insert(origin, book_state.end - 1, page);
 		// NOTE: `T::QueueChangeHandler` is called by the caller.
 		BookStateFor::<T>::insert(origin, book_state);
 	}
 
+	/// Whether the queue of `origin` is suspended or paused by [`Config::QueuePausedQuery`].
+	fn is_paused(origin: &MessageOriginOf<T>) -> bool {
+		SuspendedQueues::<T>::contains_key(origin) || T::QueuePausedQuery::is_paused(origin)
+	}
+
+	/// Service the prioritized queues, each with up to its share of `weight_limit`.
+	///
+	/// Messages which do not fit into a queue's share are left for the round-robin servicing.
+	fn service_prioritized_queues(
+		weight: &mut WeightMeter,
+		weight_limit: Weight,
+		overweight_limit: Weight,
+	) {
+		let queues = PrioritizedQueues::<T>::get();
+		if !weight.check_accrue(T::WeightInfo::service_prioritized_queues(queues.len() as u32)) {
+			return
+		}
+
+		for (origin, priority) in queues {
+			if priority.weight_share.is_zero() ||
+				BookStateFor::<T>::get(&origin).ready_neighbours.is_none()
+			{
+				continue
+			}
+			let share = (priority.weight_share * weight_limit).min(weight.remaining());
+			let mut share_meter = WeightMeter::from_limit(share);
+			Self::service_queue(origin, &mut share_meter, overweight_limit);
+			weight.defensive_saturating_accrue(share_meter.consumed);
+		}
+	}
+
+	/// The permanently overweight messages of the queue of `origin`.
+	///
+	/// Iterates all pages of the queue, so this is only meant to be used off-chain.
+	pub fn overweight_messages(origin: &MessageOriginOf<T>) -> Vec<OverweightMessage<T::Size>> {
+		let book_state = BookStateFor::<T>::get(origin);
+		let mut pages = Pages::<T>::iter_prefix(origin)
+			.filter(|(page_index, _)| *page_index <= book_state.begin)
+			.collect::<Vec<_>>();
+		pages.sort_by_key(|(page_index, _)| *page_index);
+
+		let mut messages = Vec::new();
+		for (page_index, page) in pages {
+			for index in 0.. {
+				let Some((pos, is_processed, payload)) = page.peek_index(index) else { break };
+				// Messages from the `first` one onwards are still queued.
+				if page_index == book_state.begin && pos >= page.first.into() as usize {
+					break
+				}
+				if !is_processed {
+					messages.push(OverweightMessage {
+						page_index,
+						message_index: index.saturated_into(),
+						hash: sp_io::hashing::blake2_256(payload),
+						len: payload.len() as u32,
+					});
+				}
+			}
+		}
+		messages
+	}
+
+	/// The queue and the page of it which will be serviced next, if any.
+	///
+	/// Walks the `ReadyRing`, so this is only meant to be used off-chain.
+	pub fn next_serviced_page() -> Option<(MessageOriginOf<T>, PageIndex)> {
+		let serviceable = |origin: &MessageOriginOf<T>, book_state: &BookStateOf<T>| {
+			book_state.ready_neighbours.is_some() && !Self::is_paused(origin)
+		};
+
+		for (origin, priority) in PrioritizedQueues::<T>::get() {
+			let book_state = BookStateFor::<T>::get(&origin);
+			if !priority.weight_share.is_zero() && serviceable(&origin, &book_state) {
+				return Some((origin, book_state.begin))
+			}
+		}
+
+		let head = ServiceHead::<T>::get()?;
+		let mut origin = head.clone();
+		loop {
+			let book_state = BookStateFor::<T>::get(&origin);
+			if serviceable(&origin, &book_state) {
+				return Some((origin, book_state.begin))
+			}
+			origin = book_state.ready_neighbours?.next;
+			if origin == head {
+				return None
+			}
+		}
+	}
+
 	/// Try to execute a single message that was marked as overweight.
 	///
 	/// The `weight_limit` is the weight that can be consumed to execute the message. The base
@@ -815,7 +1063,7 @@ impl<T: Config> Pallet<T> {
 		weight_limit: Weight,
 	) -> Result<Weight, Error<T>> {
 		let mut book_state = BookStateFor::<T>::get(&origin);
-		ensure!(!T::QueuePausedQuery::is_paused(&origin), Error::<T>::QueuePaused);
+		ensure!(!Self::is_paused(&origin), Error::<T>::QueuePaused);
 
 		let mut page = Pages::<T>::get(&origin, page_index).ok_or(Error::<T>::NoPage)?;
 		let (pos, is_processed, payload) =
@@ -957,7 +1205,7 @@ impl<T: Config> Pallet<T> {
 
 		let mut book_state = BookStateFor::<T>::get(&origin);
 		let mut total_processed = 0;
-		if T::QueuePausedQuery::is_paused(&origin) {
+		if Self::is_paused(&origin) {
 			let next_ready = book_state.ready_neighbours.as_ref().map(|x| x.next.clone());
 			return (false, next_ready)
 		}
@@ -1253,6 +1501,8 @@ impl<T: Config> ServiceQueues for Pallet<T> {
 		let overweight_limit = weight_limit;
 		let mut weight = WeightMeter::from_limit(weight_limit);
 
+		Self::service_prioritized_queues(&mut weight, weight_limit, overweight_limit);
+
 		let mut next = match Self::bump_service_head(&mut weight) {
 			Some(h) => h,
 			None => return weight.consumed,
@@ -1320,7 +1570,7 @@ impl<T: Config> EnqueueMessage<MessageOriginOf<T>> for Pallet<T> {
 		message: BoundedSlice<u8, Self::MaxMessageLen>,
 		origin: <T::MessageProcessor as ProcessMessage>::Origin,
 	) {
-		Self::do_enqueue_message(&origin, message);
+		Self::do_enqueue_messages(&origin, sp_std::iter::once(message));
 		let book_state = BookStateFor::<T>::get(&origin);
 		T::QueueChangeHandler::on_queue_changed(origin, book_state.message_count, book_state.size);
 	}
@@ -1329,9 +1579,7 @@ impl<T: Config> EnqueueMessage<MessageOriginOf<T>> for Pallet<T> {
 		messages: impl Iterator<Item = BoundedSlice<'a, u8, Self::MaxMessageLen>>,
 		origin: <T::MessageProcessor as ProcessMessage>::Origin,
 	) {
-		for message in messages {
-			Self::do_enqueue_message(&origin, message);
-		}
+		Self::do_enqueue_messages(&origin, messages);
 		let book_state = BookStateFor::<T>::get(&origin);
 		T::QueueChangeHandler::on_queue_changed(origin, book_state.message_count, book_state.size);
 	}
@@ -1353,3 +1601,21 @@ impl<T: Config> EnqueueMessage<MessageOriginOf<T>> for Pallet<T> {
 		Footprint { count: book_state.message_count, size: book_state.size }
 	}
 }
+
+sp_api::decl_runtime_apis! {
+	/// The API to monitor the message queues.
+	pub trait MessageQueueApi<MessageOrigin, Size>
+	where
+		MessageOrigin: Codec,
+		Size: Codec,
+	{
+		/// The number and total size of the unprocessed messages of the queue of `origin`.
+		fn footprint(origin: MessageOrigin) -> Footprint;
+
+		/// The permanently overweight messages of the queue of `origin`.
+		fn overweight_messages(origin: MessageOrigin) -> Vec<OverweightMessage<Size>>;
+
+		/// The queue and the page of it which will be serviced next, if any.
+		fn next_serviced_page() -> Option<(MessageOrigin, PageIndex)>;
+	}
+}
@@ -28,6 +28,7 @@ use frame_support::{
 	parameter_types,
 	traits::{ConstU32, ConstU64},
 };
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
@@ -84,6 +85,8 @@ impl Config for Test {
 	type HeapSize = HeapSize;
 	type MaxStale = MaxStale;
 	type ServiceWeight = ServiceWeight;
+	type QueueAdminOrigin = EnsureRoot<u64>;
+	type MaxPrioritizedQueues = ConstU32<3>;
 }
 
 /// Mocked `WeightInfo` impl with allows to set the weight per call.
@@ -147,6 +150,21 @@ impl crate::weights::WeightInfo for MockedWeightInfo {
 	fn ready_ring_unknit() -> Weight {
 		WeightForCall::get().get("ready_ring_unknit").copied().unwrap_or_default()
 	}
+	fn set_queue_priority() -> Weight {
+		WeightForCall::get().get("set_queue_priority").copied().unwrap_or_default()
+	}
+	fn suspend_queue() -> Weight {
+		WeightForCall::get().get("suspend_queue").copied().unwrap_or_default()
+	}
+	fn resume_queue() -> Weight {
+		WeightForCall::get().get("resume_queue").copied().unwrap_or_default()
+	}
+	fn service_prioritized_queues(_n: u32) -> Weight {
+		WeightForCall::get()
+			.get("service_prioritized_queues")
+			.copied()
+			.unwrap_or_default()
+	}
 }
 
 parameter_types! {
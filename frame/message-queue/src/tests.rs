@@ -1458,3 +1458,197 @@ fn service_queue_suspension_ready_ring_works() {
 		);
 	});
 }
+
+#[test]
+fn enqueue_messages_equals_enqueue_message() {
+	use MessageOrigin::*;
+	let msgs = ["a", "bc", "def", "weight=1", "ghij", "k", "lmnopqrstu"];
+	let state = || (BookStateFor::<Test>::get(Here).encode(), MessageQueue::debug_info());
+
+	let batched = test_closure(|| {
+		MessageQueue::enqueue_message(msg("z"), Here);
+		MessageQueue::enqueue_messages(msgs.iter().map(|m| msg(m)), Here);
+		state()
+	});
+	let single = test_closure(|| {
+		MessageQueue::enqueue_message(msg("z"), Here);
+		for m in msgs.iter() {
+			MessageQueue::enqueue_message(msg(m), Here);
+		}
+		state()
+	});
+	assert_eq!(batched, single);
+
+	test_closure(|| {
+		// An empty batch does not create a book.
+		MessageQueue::enqueue_messages(sp_std::iter::empty(), Here);
+		assert_eq!(BookStateFor::<Test>::iter().count(), 0);
+		assert!(ServiceHead::<Test>::get().is_none());
+	});
+}
+
+#[test]
+fn set_queue_priority_works() {
+	use MessageOrigin::*;
+	test_closure(|| {
+		let priority = |priority| QueuePriority { priority, weight_share: Perbill::one() };
+		assert_noop!(
+			MessageQueue::set_queue_priority(RuntimeOrigin::signed(1), Here, Some(priority(1))),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(MessageQueue::set_queue_priority(
+			RuntimeOrigin::root(),
+			Here,
+			Some(priority(1))
+		));
+		assert_last_event::<Test>(
+			Event::QueuePrioritySet { origin: Here, priority: Some(priority(1)) }.into(),
+		);
+		assert_ok!(MessageQueue::set_queue_priority(
+			RuntimeOrigin::root(),
+			There,
+			Some(priority(2))
+		));
+		assert_ok!(MessageQueue::set_queue_priority(
+			RuntimeOrigin::root(),
+			Everywhere(0),
+			Some(priority(1))
+		));
+		assert_eq!(
+			PrioritizedQueues::<Test>::get().into_inner(),
+			vec![(There, priority(2)), (Here, priority(1)), (Everywhere(0), priority(1))]
+		);
+		assert_noop!(
+			MessageQueue::set_queue_priority(
+				RuntimeOrigin::root(),
+				Everywhere(1),
+				Some(priority(1))
+			),
+			Error::<Test>::TooManyPrioritizedQueues
+		);
+
+		// Updating a queue moves it.
+		assert_ok!(MessageQueue::set_queue_priority(
+			RuntimeOrigin::root(),
+			Here,
+			Some(priority(3))
+		));
+		assert_eq!(
+			PrioritizedQueues::<Test>::get().into_inner(),
+			vec![(Here, priority(3)), (There, priority(2)), (Everywhere(0), priority(1))]
+		);
+		assert_ok!(MessageQueue::set_queue_priority(RuntimeOrigin::root(), There, None));
+		assert_last_event::<Test>(Event::QueuePrioritySet { origin: There, priority: None }.into());
+		assert_eq!(
+			PrioritizedQueues::<Test>::get().into_inner(),
+			vec![(Here, priority(3)), (Everywhere(0), priority(1))]
+		);
+	});
+}
+
+#[test]
+fn prioritized_queue_is_serviced_first() {
+	use MessageOrigin::*;
+	test_closure(|| {
+		MessageQueue::enqueue_messages(vec![msg("a"), msg("ab"), msg("abc")].into_iter(), Here);
+		MessageQueue::enqueue_messages(vec![msg("x"), msg("xy"), msg("xyz")].into_iter(), There);
+		QueueChanges::take();
+
+		let priority = QueuePriority { priority: 0, weight_share: Perbill::from_percent(50) };
+		assert_ok!(MessageQueue::set_queue_priority(RuntimeOrigin::root(), There, Some(priority)));
+		assert_eq!(MessageQueue::next_serviced_page(), Some((There, 0)));
+
+		// `There` uses its share, then the round-robin continues with `Here`.
+		assert_eq!(MessageQueue::service_queues(2.into_weight()), 2.into_weight());
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("x"), There), (vmsg("a"), Here)]);
+		assert_eq!(QueueChanges::take(), vec![(There, 2, 5), (Here, 2, 5)]);
+
+		// A message heavier than the share is serviced in the round-robin.
+		MessageQueue::enqueue_message(msg("weight=2"), Everywhere(0));
+		let priority = QueuePriority { priority: 1, weight_share: Perbill::from_percent(25) };
+		assert_ok!(MessageQueue::set_queue_priority(
+			RuntimeOrigin::root(),
+			Everywhere(0),
+			Some(priority)
+		));
+		assert_eq!(MessageQueue::service_queues(6.into_weight()), 6.into_weight());
+		assert_eq!(
+			MessagesProcessed::take(),
+			vec![
+				(vmsg("xy"), There),
+				(vmsg("xyz"), There),
+				(vmsg("ab"), Here),
+				(vmsg("abc"), Here),
+				(vmsg("weight=2"), Everywhere(0))
+			]
+		);
+	});
+}
+
+#[test]
+fn suspend_and_resume_queue_works() {
+	use MessageOrigin::*;
+	test_closure(|| {
+		MessageQueue::enqueue_message(msg("a"), Here);
+		MessageQueue::enqueue_message(msg("x"), There);
+
+		assert_noop!(
+			MessageQueue::suspend_queue(RuntimeOrigin::signed(1), Here),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			MessageQueue::resume_queue(RuntimeOrigin::root(), Here),
+			Error::<Test>::NotSuspended
+		);
+		assert_ok!(MessageQueue::suspend_queue(RuntimeOrigin::root(), Here));
+		assert_last_event::<Test>(Event::QueueSuspended { origin: Here }.into());
+		assert_noop!(
+			MessageQueue::suspend_queue(RuntimeOrigin::root(), Here),
+			Error::<Test>::AlreadySuspended
+		);
+		assert_eq!(MessageQueue::next_serviced_page(), Some((There, 0)));
+
+		// Only `There` is serviced.
+		MessageQueue::service_queues(Weight::MAX);
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("x"), There)]);
+		assert_eq!(MessageQueue::next_serviced_page(), None);
+		// Suspended queues still accept messages.
+		MessageQueue::enqueue_message(msg("ab"), Here);
+		MessageQueue::service_queues(Weight::MAX);
+		assert!(MessagesProcessed::take().is_empty());
+
+		assert_ok!(MessageQueue::resume_queue(RuntimeOrigin::root(), Here));
+		assert_last_event::<Test>(Event::QueueResumed { origin: Here }.into());
+		assert_eq!(MessageQueue::next_serviced_page(), Some((Here, 0)));
+		MessageQueue::service_queues(Weight::MAX);
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("a"), Here), (vmsg("ab"), Here)]);
+	});
+}
+
+#[test]
+fn overweight_messages_works() {
+	use MessageOrigin::*;
+	test_closure(|| {
+		MessageQueue::enqueue_messages(vec![msg("weight=6"), msg("a")].into_iter(), Here);
+		assert!(MessageQueue::overweight_messages(&Here).is_empty());
+
+		// Mark the first message as permanently overweight.
+		MessageQueue::service_queues(4.into_weight());
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("a"), Here)]);
+		let overweight = vec![OverweightMessage {
+			page_index: 0,
+			message_index: 0,
+			hash: blake2_256(b"weight=6"),
+			len: 8,
+		}];
+		assert_eq!(MessageQueue::overweight_messages(&Here), overweight);
+
+		// Queued messages are not overweight.
+		MessageQueue::enqueue_message(msg("b"), Here);
+		assert_eq!(MessageQueue::overweight_messages(&Here), overweight);
+
+		assert_ok!(<MessageQueue as ServiceQueues>::execute_overweight(Weight::MAX, (Here, 0, 0)));
+		assert!(MessageQueue::overweight_messages(&Here).is_empty());
+	});
+}
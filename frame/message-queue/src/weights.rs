@@ -63,6 +63,10 @@ pub trait WeightInfo {
 	fn reap_page() -> Weight;
 	fn execute_overweight_page_removed() -> Weight;
 	fn execute_overweight_page_updated() -> Weight;
+	fn set_queue_priority() -> Weight;
+	fn suspend_queue() -> Weight;
+	fn resume_queue() -> Weight;
+	fn service_prioritized_queues(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_message_queue using the Substrate node and recommended hardware.
@@ -186,6 +190,56 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: MessageQueue PrioritizedQueues (r:1 w:1)
+	/// Proof: MessageQueue PrioritizedQueues (max_values: Some(1), max_size: Some(145), added: 640, mode: MaxEncodedLen)
+	fn set_queue_priority() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `154`
+		//  Estimated: `1630`
+		// Minimum execution time: 9_112_000 picoseconds.
+		Weight::from_parts(9_480_000, 1630)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MessageQueue SuspendedQueues (r:1 w:1)
+	/// Proof: MessageQueue SuspendedQueues (max_values: None, max_size: Some(12), added: 2487, mode: MaxEncodedLen)
+	fn suspend_queue() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `3477`
+		// Minimum execution time: 8_203_000 picoseconds.
+		Weight::from_parts(8_517_000, 3477)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MessageQueue SuspendedQueues (r:1 w:1)
+	/// Proof: MessageQueue SuspendedQueues (max_values: None, max_size: Some(12), added: 2487, mode: MaxEncodedLen)
+	fn resume_queue() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `84`
+		//  Estimated: `3477`
+		// Minimum execution time: 8_845_000 picoseconds.
+		Weight::from_parts(9_201_000, 3477)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MessageQueue PrioritizedQueues (r:1 w:0)
+	/// Proof: MessageQueue PrioritizedQueues (max_values: Some(1), max_size: Some(145), added: 640, mode: MaxEncodedLen)
+	/// Storage: MessageQueue BookStateFor (r:16 w:0)
+	/// Proof: MessageQueue BookStateFor (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 16]`.
+	fn service_prioritized_queues(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `13 + n * (58 ±0)`
+		//  Estimated: `1630 + n * (2524 ±0)`
+		// Minimum execution time: 2_611_000 picoseconds.
+		Weight::from_parts(3_102_000, 1630)
+			// Standard Error: 1_950
+			.saturating_add(Weight::from_parts(4_287_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2524).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -308,4 +362,54 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: MessageQueue PrioritizedQueues (r:1 w:1)
+	/// Proof: MessageQueue PrioritizedQueues (max_values: Some(1), max_size: Some(145), added: 640, mode: MaxEncodedLen)
+	fn set_queue_priority() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `154`
+		//  Estimated: `1630`
+		// Minimum execution time: 9_112_000 picoseconds.
+		Weight::from_parts(9_480_000, 1630)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MessageQueue SuspendedQueues (r:1 w:1)
+	/// Proof: MessageQueue SuspendedQueues (max_values: None, max_size: Some(12), added: 2487, mode: MaxEncodedLen)
+	fn suspend_queue() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `3477`
+		// Minimum execution time: 8_203_000 picoseconds.
+		Weight::from_parts(8_517_000, 3477)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MessageQueue SuspendedQueues (r:1 w:1)
+	/// Proof: MessageQueue SuspendedQueues (max_values: None, max_size: Some(12), added: 2487, mode: MaxEncodedLen)
+	fn resume_queue() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `84`
+		//  Estimated: `3477`
+		// Minimum execution time: 8_845_000 picoseconds.
+		Weight::from_parts(9_201_000, 3477)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MessageQueue PrioritizedQueues (r:1 w:0)
+	/// Proof: MessageQueue PrioritizedQueues (max_values: Some(1), max_size: Some(145), added: 640, mode: MaxEncodedLen)
+	/// Storage: MessageQueue BookStateFor (r:16 w:0)
+	/// Proof: MessageQueue BookStateFor (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 16]`.
+	fn service_prioritized_queues(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `13 + n * (58 ±0)`
+		//  Estimated: `1630 + n * (2524 ±0)`
+		// Minimum execution time: 2_611_000 picoseconds.
+		Weight::from_parts(3_102_000, 1630)
+			// Standard Error: 1_950
+			.saturating_add(Weight::from_parts(4_287_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2524).saturating_mul(n.into()))
+	}
 }
@@ -0,0 +1,383 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Migrations Pallet
+//!
+//! Runs the multi-block migrations of the runtime.
+//!
+//! Migrations run by `OnRuntimeUpgrade` must fit within a single block. The
+//! [`SteppedMigration`](frame_support::migrations::SteppedMigration)s listed in
+//! [`Config::Migrations`] are instead run one step at a time, at the end of every block, and their
+//! progress is persisted in [`Cursor`] in between.
+//!
+//! ## Overview
+//!
+//! A runtime upgrade starts the migrations, which then run one after another, each with as many
+//! steps per block as the remaining weight of the block, capped to [`Config::MaxServiceWeight`],
+//! allows. The pallet implements [`MultiStepMigrator`], so that `frame-executive` runs these steps
+//! and does not apply any non-mandatory extrinsic while a migration is ongoing.
+//!
+//! Completed migrations are recorded in [`Historic`], and skipped should they be run again.
+//!
+//! A migration which fails, or takes more blocks than its
+//! [`max_blocks`](frame_support::migrations::SteppedMigration::max_blocks), fails the whole
+//! upgrade. [`Config::FailedMigrationHandler`] then decides if the chain stays stuck, or resumes
+//! without the remaining migrations.
+//!
+//! As no non-mandatory extrinsic is applied while the migrations are stuck, governance can not
+//! unstick them, not even with [`Pallet::force_set_cursor`]. Recovering a stuck chain takes a hard
+//! fork.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	defensive,
+	migrations::{
+		FailedMigrationHandler, FailedMigrationHandling, MultiStepMigrator, SteppedMigrationError,
+		SteppedMigrations,
+	},
+	traits::Get,
+	weights::{Weight, WeightMeter},
+	BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// The progress of the multi-block migrations.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum MigrationCursor<Cursor, BlockNumber> {
+	/// A migration is ongoing.
+	Active(ActiveCursor<Cursor, BlockNumber>),
+	/// A migration failed, and the remaining ones are not run.
+	Stuck,
+}
+
+impl<Cursor, BlockNumber> MigrationCursor<Cursor, BlockNumber> {
+	/// The cursor of the ongoing migration, if the migrations are not stuck.
+	pub fn as_active(&self) -> Option<&ActiveCursor<Cursor, BlockNumber>> {
+		match self {
+			MigrationCursor::Active(active) => Some(active),
+			MigrationCursor::Stuck => None,
+		}
+	}
+}
+
+/// The progress of the ongoing migration.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ActiveCursor<Cursor, BlockNumber> {
+	/// The index of the migration in [`Config::Migrations`].
+	pub index: u32,
+	/// The cursor of the migration, `None` if it did not start yet.
+	pub inner_cursor: Option<Cursor>,
+	/// The block the migration started in.
+	pub started_at: BlockNumber,
+}
+
+impl<Cursor, BlockNumber> ActiveCursor<Cursor, BlockNumber> {
+	/// Move on to the next migration, starting in block `now`.
+	fn goto_next(&mut self, now: BlockNumber) {
+		self.index.saturating_inc();
+		self.inner_cursor = None;
+		self.started_at = now;
+	}
+}
+
+/// The encoded cursor of a migration.
+pub type RawCursorOf<T> = BoundedVec<u8, <T as Config>::CursorMaxLen>;
+/// The encoded identifier of a migration.
+pub type IdentifierOf<T> = BoundedVec<u8, <T as Config>::IdentifierMaxLen>;
+/// The progress of the multi-block migrations of a runtime.
+pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The multi-block migrations to run after a runtime upgrade, in order.
+		type Migrations: SteppedMigrations;
+
+		/// The maximum length of the encoded cursor of a migration.
+		#[pallet::constant]
+		type CursorMaxLen: Get<u32>;
+
+		/// The maximum length of the encoded identifier of a migration.
+		#[pallet::constant]
+		type IdentifierMaxLen: Get<u32>;
+
+		/// Decides what to do once a migration failed.
+		type FailedMigrationHandler: FailedMigrationHandler;
+
+		/// The maximum weight the migrations may consume in a block.
+		#[pallet::constant]
+		type MaxServiceWeight: Get<Weight>;
+	}
+
+	/// The progress of the multi-block migrations, if any is ongoing or stuck.
+	#[pallet::storage]
+	pub type Cursor<T: Config> = StorageValue<_, CursorOf<T>, OptionQuery>;
+
+	/// The identifiers of the completed migrations, which are skipped if run again.
+	#[pallet::storage]
+	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, IdentifierOf<T>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A runtime upgrade started `migrations` multi-block migrations.
+		UpgradeStarted { migrations: u32 },
+		/// All the migrations of the runtime upgrade completed.
+		UpgradeCompleted,
+		/// A migration failed, and the remaining ones will not run.
+		UpgradeFailed,
+		/// The migration at `index` was skipped, as it already completed before.
+		MigrationSkipped { index: u32 },
+		/// The migration at `index` advanced, `took` blocks after it started.
+		MigrationAdvanced { index: u32, took: BlockNumberFor<T> },
+		/// The migration at `index` completed, `took` blocks after it started.
+		MigrationCompleted { index: u32, took: BlockNumberFor<T> },
+		/// The migration at `index` failed, `took` blocks after it started.
+		MigrationFailed { index: u32, took: BlockNumberFor<T> },
+		/// Some completed migrations were removed from [`Historic`].
+		HistoricCleared { removed: u32 },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			Self::onboard_new_migrations()
+		}
+
+		fn integrity_test() {
+			for n in 0..T::Migrations::len() {
+				let id =
+					T::Migrations::nth_id(n).expect("`n` is less than the number of migrations");
+				assert!(
+					id.len() <= T::IdentifierMaxLen::get() as usize,
+					"the identifier of migration {} is longer than `IdentifierMaxLen`",
+					n,
+				);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Overwrite the progress of the multi-block migrations.
+		///
+		/// `None` stops the migrations, and resumes applying extrinsics. As no extrinsic is applied
+		/// while migrations are ongoing or stuck, this can only be dispatched from within the
+		/// runtime then, such as by a call scheduled beforehand.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn force_set_cursor(
+			origin: OriginFor<T>,
+			cursor: Option<CursorOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Cursor::<T>::set(cursor);
+			Ok(())
+		}
+
+		/// Remove the given completed migrations from [`Historic`], so that they run again.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(ids.len() as u64))]
+		pub fn clear_historic(origin: OriginFor<T>, ids: Vec<IdentifierOf<T>>) -> DispatchResult {
+			ensure_root(origin)?;
+			let mut removed = 0;
+			for id in ids {
+				if Historic::<T>::take(id).is_some() {
+					removed += 1;
+				}
+			}
+			Self::deposit_event(Event::HistoricCleared { removed });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Start the migrations of a runtime upgrade.
+	///
+	/// An upgrade while migrations are ongoing fails them, as the new runtime may not know about
+	/// their cursors.
+	fn onboard_new_migrations() -> Weight {
+		if let Some(cursor) = Cursor::<T>::get() {
+			log::error!(target: LOG_TARGET, "ongoing migrations interrupted by a runtime upgrade");
+			Self::upgrade_failed(cursor.as_active().map(|active| active.index));
+			return T::DbWeight::get().reads_writes(1, 1)
+		}
+
+		let migrations = T::Migrations::len();
+		if migrations == 0 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		Cursor::<T>::put(MigrationCursor::Active(ActiveCursor {
+			index: 0,
+			inner_cursor: None,
+			started_at: frame_system::Pallet::<T>::block_number(),
+		}));
+		Self::deposit_event(Event::UpgradeStarted { migrations });
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	/// The migration at `index` failed, `took` blocks after it started.
+	fn migration_failed(index: u32, took: BlockNumberFor<T>) {
+		Self::deposit_event(Event::MigrationFailed { index, took });
+		Self::upgrade_failed(Some(index));
+	}
+
+	/// The migrations failed, at the migration at `index` if known.
+	fn upgrade_failed(index: Option<u32>) {
+		Self::deposit_event(Event::UpgradeFailed);
+		match T::FailedMigrationHandler::failed(index) {
+			FailedMigrationHandling::KeepStuck => Cursor::<T>::put(MigrationCursor::Stuck),
+			FailedMigrationHandling::ForceUnstuck => Cursor::<T>::kill(),
+		}
+	}
+}
+
+impl<T: Config> MultiStepMigrator for Pallet<T> {
+	fn ongoing() -> bool {
+		Cursor::<T>::exists()
+	}
+
+	fn step(remaining: Weight) -> Weight {
+		let mut meter = WeightMeter::from_limit(remaining.min(T::MaxServiceWeight::get()));
+		// Reading the cursor, and writing it back.
+		meter.defensive_saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+
+		let mut cursor = match Cursor::<T>::get() {
+			None => return meter.consumed,
+			Some(MigrationCursor::Stuck) => {
+				log::error!(target: LOG_TARGET, "migrations are stuck");
+				return meter.consumed
+			},
+			Some(MigrationCursor::Active(cursor)) => cursor,
+		};
+
+		let now = frame_system::Pallet::<T>::block_number();
+		loop {
+			let Some(id) = T::Migrations::nth_id(cursor.index) else {
+				Cursor::<T>::kill();
+				Self::deposit_event(Event::UpgradeCompleted);
+				return meter.consumed
+			};
+			// Reading and writing `Historic`.
+			if !meter.check_accrue(T::DbWeight::get().reads_writes(1, 1)) {
+				break
+			}
+
+			let index = cursor.index;
+			let took = now.saturating_sub(cursor.started_at);
+			let Ok(id) = IdentifierOf::<T>::try_from(id) else {
+				defensive!("the length of identifiers is checked by the integrity test");
+				Self::migration_failed(index, took);
+				return meter.consumed
+			};
+			if cursor.inner_cursor.is_none() && Historic::<T>::contains_key(&id) {
+				Self::deposit_event(Event::MigrationSkipped { index });
+				cursor.goto_next(now);
+				continue
+			}
+
+			let max_blocks = T::Migrations::nth_max_blocks(index).flatten();
+			if max_blocks.map_or(false, |max| took > max.into()) {
+				log::error!(target: LOG_TARGET, "migration {} took too many blocks", index);
+				Self::migration_failed(index, took);
+				return meter.consumed
+			}
+
+			let consumed = meter.consumed;
+			let inner_cursor = cursor.inner_cursor.clone().map(|c| c.into_inner());
+			match T::Migrations::nth_transactional_step(index, inner_cursor, &mut meter) {
+				Some(Ok(Some(next))) => {
+					let Ok(next) = RawCursorOf::<T>::try_from(next) else {
+						log::error!(target: LOG_TARGET, "migration {} returned a too long cursor", index);
+						Self::migration_failed(index, took);
+						return meter.consumed
+					};
+					cursor.inner_cursor = Some(next);
+					Self::deposit_event(Event::MigrationAdvanced { index, took });
+					// A step which consumed no weight would be repeated forever.
+					if meter.consumed == consumed {
+						break
+					}
+				},
+				Some(Ok(None)) => {
+					Historic::<T>::insert(id, ());
+					Self::deposit_event(Event::MigrationCompleted { index, took });
+					cursor.goto_next(now);
+				},
+				Some(Err(SteppedMigrationError::InsufficientWeight { required })) => {
+					if !required.all_lte(T::MaxServiceWeight::get()) {
+						log::error!(
+							target: LOG_TARGET,
+							"migration {} requires more weight than `MaxServiceWeight`",
+							index,
+						);
+						Self::migration_failed(index, took);
+						return meter.consumed
+					}
+
+					// Retry the step in the next block.
+					break
+				},
+				Some(Err(e)) => {
+					log::error!(target: LOG_TARGET, "migration {} failed: {:?}", index, e);
+					Self::migration_failed(index, took);
+					return meter.consumed
+				},
+				None => {
+					defensive!("`index` is less than the number of migrations");
+					Self::migration_failed(index, took);
+					return meter.consumed
+				},
+			}
+		}
+
+		Cursor::<T>::put(MigrationCursor::Active(cursor));
+		meter.consumed
+	}
+}
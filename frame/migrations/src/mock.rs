@@ -0,0 +1,177 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the migrations pallet.
+
+use crate as pallet_migrations;
+use codec::{Decode, Encode};
+use frame_support::{
+	migrations::{
+		FailedMigrationHandler, FailedMigrationHandling, SteppedMigrationError, SteppedMigrations,
+	},
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+	weights::{Weight, WeightMeter},
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Migrations: pallet_migrations::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// The weight of every step of a mocked migration.
+pub const STEP_WEIGHT: Weight = Weight::from_parts(100, 0);
+
+/// What a mocked migration does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MockedMigrationKind {
+	/// Complete in the given number of steps.
+	SucceedAfter(u32),
+	/// Fail at the given step.
+	FailAfter(u32),
+}
+
+parameter_types! {
+	/// The mocked migrations, as their identifier and what they do.
+	pub static MockedMigrations: Vec<(u8, MockedMigrationKind)> = vec![];
+	pub static MaxBlocks: Option<u32> = None;
+	pub static Handling: FailedMigrationHandling = FailedMigrationHandling::KeepStuck;
+	pub static MaxServiceWeight: Weight = Weight::from_parts(250, 0);
+}
+
+/// Runs the [`MockedMigrations`], each step consuming [`STEP_WEIGHT`].
+pub struct MockedMigrator;
+impl SteppedMigrations for MockedMigrator {
+	fn len() -> u32 {
+		MockedMigrations::get().len() as u32
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		MockedMigrations::get().get(n as usize).map(|(id, _)| vec![*id])
+	}
+
+	fn nth_max_blocks(n: u32) -> Option<Option<u32>> {
+		MockedMigrations::get().get(n as usize).map(|_| MaxBlocks::get())
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let (_, kind) = MockedMigrations::get().get(n as usize).copied()?;
+		let steps = match cursor.map(|c| u32::decode(&mut &c[..])).transpose() {
+			Ok(steps) => steps.unwrap_or(0),
+			Err(_) => return Some(Err(SteppedMigrationError::InvalidCursor)),
+		};
+		if !meter.check_accrue(STEP_WEIGHT) {
+			return Some(Err(SteppedMigrationError::InsufficientWeight { required: STEP_WEIGHT }))
+		}
+
+		let steps = steps + 1;
+		Some(match kind {
+			MockedMigrationKind::SucceedAfter(total) if steps >= total => Ok(None),
+			MockedMigrationKind::FailAfter(total) if steps >= total =>
+				Err(SteppedMigrationError::Failed),
+			_ => Ok(Some(steps.encode())),
+		})
+	}
+}
+
+pub struct MockedFailedMigrationHandler;
+impl FailedMigrationHandler for MockedFailedMigrationHandler {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		Handling::get()
+	}
+}
+
+impl pallet_migrations::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Migrations = MockedMigrator;
+	type CursorMaxLen = ConstU32<16>;
+	type IdentifierMaxLen = ConstU32<16>;
+	type FailedMigrationHandler = MockedFailedMigrationHandler;
+	type MaxServiceWeight = MaxServiceWeight;
+}
+
+pub fn new_test_ext(migrations: Vec<(u8, MockedMigrationKind)>) -> sp_io::TestExternalities {
+	MockedMigrations::set(migrations);
+	let t = RuntimeGenesisConfig { system: Default::default() }.build_storage().unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Step the migrations at the end of every block, up to and including block `n`.
+pub fn run_to_block(n: u64) {
+	use frame_support::migrations::MultiStepMigrator;
+
+	while System::block_number() <= n {
+		Migrations::step(Weight::MAX);
+		System::set_block_number(System::block_number() + 1);
+	}
+}
+
+/// The events of the pallet deposited since the last call.
+pub fn migration_events() -> Vec<pallet_migrations::Event<Test>> {
+	let events = System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			RuntimeEvent::Migrations(e) => Some(e),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	events
+}
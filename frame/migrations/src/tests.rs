@@ -0,0 +1,220 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the migrations pallet.
+
+use super::{Event, *};
+use crate::mock::{MockedMigrationKind::*, *};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
+use sp_runtime::DispatchError;
+
+fn ongoing() -> bool {
+	<Migrations as MultiStepMigrator>::ongoing()
+}
+
+#[test]
+fn migrations_run_over_several_blocks() {
+	new_test_ext(vec![(0, SucceedAfter(3)), (1, SucceedAfter(1))]).execute_with(|| {
+		assert!(!ongoing());
+		Migrations::on_runtime_upgrade();
+		assert!(ongoing());
+		assert_eq!(migration_events(), vec![Event::UpgradeStarted { migrations: 2 }]);
+
+		// Only two steps fit in a block.
+		run_to_block(1);
+		assert!(ongoing());
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::MigrationAdvanced { index: 0, took: 0 },
+				Event::MigrationAdvanced { index: 0, took: 0 },
+			]
+		);
+
+		run_to_block(2);
+		assert!(!ongoing());
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::MigrationCompleted { index: 0, took: 1 },
+				Event::MigrationCompleted { index: 1, took: 0 },
+				Event::UpgradeCompleted,
+			]
+		);
+		assert!(Historic::<Test>::contains_key(IdentifierOf::<Test>::truncate_from(vec![0])));
+		assert!(Historic::<Test>::contains_key(IdentifierOf::<Test>::truncate_from(vec![1])));
+
+		// Nothing happens once the migrations completed.
+		run_to_block(3);
+		assert_eq!(migration_events(), vec![]);
+	});
+}
+
+#[test]
+fn completed_migrations_are_skipped() {
+	new_test_ext(vec![(0, SucceedAfter(1)), (1, SucceedAfter(1))]).execute_with(|| {
+		Historic::<Test>::insert(IdentifierOf::<Test>::truncate_from(vec![0]), ());
+
+		Migrations::on_runtime_upgrade();
+		run_to_block(1);
+		assert!(!ongoing());
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationSkipped { index: 0 },
+				Event::MigrationCompleted { index: 1, took: 0 },
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_keeps_chain_stuck() {
+	new_test_ext(vec![(0, FailAfter(2)), (1, SucceedAfter(1))]).execute_with(|| {
+		Migrations::on_runtime_upgrade();
+		run_to_block(1);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert!(ongoing());
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationAdvanced { index: 0, took: 0 },
+				Event::MigrationFailed { index: 0, took: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+
+		// The remaining migrations do not run.
+		run_to_block(2);
+		assert!(ongoing());
+		assert_eq!(migration_events(), vec![]);
+		assert!(!Historic::<Test>::contains_key(IdentifierOf::<Test>::truncate_from(vec![1])));
+
+		// Until governance intervenes.
+		assert_ok!(Migrations::force_set_cursor(RuntimeOrigin::root(), None));
+		assert!(!ongoing());
+	});
+}
+
+#[test]
+fn failed_migration_can_resume_chain() {
+	new_test_ext(vec![(0, FailAfter(1)), (1, SucceedAfter(1))]).execute_with(|| {
+		Handling::set(FailedMigrationHandling::ForceUnstuck);
+
+		Migrations::on_runtime_upgrade();
+		run_to_block(1);
+		assert!(!ongoing());
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationFailed { index: 0, took: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+		assert!(!Historic::<Test>::contains_key(IdentifierOf::<Test>::truncate_from(vec![1])));
+	});
+}
+
+#[test]
+fn migration_exceeding_max_service_weight_fails() {
+	new_test_ext(vec![(0, SucceedAfter(1))]).execute_with(|| {
+		MaxServiceWeight::set(Weight::from_parts(50, 0));
+
+		Migrations::on_runtime_upgrade();
+		run_to_block(1);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 1 },
+				Event::MigrationFailed { index: 0, took: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+	});
+}
+
+#[test]
+fn migration_exceeding_max_blocks_fails() {
+	new_test_ext(vec![(0, SucceedAfter(10))]).execute_with(|| {
+		MaxBlocks::set(Some(1));
+
+		Migrations::on_runtime_upgrade();
+		run_to_block(2);
+		assert!(ongoing());
+		migration_events();
+
+		run_to_block(3);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(
+			migration_events(),
+			vec![Event::MigrationFailed { index: 0, took: 2 }, Event::UpgradeFailed]
+		);
+	});
+}
+
+#[test]
+fn steps_are_limited_by_remaining_weight() {
+	new_test_ext(vec![(0, SucceedAfter(3))]).execute_with(|| {
+		Migrations::on_runtime_upgrade();
+		migration_events();
+
+		assert_eq!(<Migrations as MultiStepMigrator>::step(STEP_WEIGHT), STEP_WEIGHT);
+		assert_eq!(migration_events(), vec![Event::MigrationAdvanced { index: 0, took: 0 }]);
+		assert_eq!(<Migrations as MultiStepMigrator>::step(Weight::zero()), Weight::zero());
+		assert_eq!(migration_events(), vec![]);
+		assert!(ongoing());
+	});
+}
+
+#[test]
+fn upgrade_during_migrations_fails_them() {
+	new_test_ext(vec![(0, SucceedAfter(3))]).execute_with(|| {
+		Migrations::on_runtime_upgrade();
+		run_to_block(1);
+		migration_events();
+
+		Migrations::on_runtime_upgrade();
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(migration_events(), vec![Event::UpgradeFailed]);
+	});
+}
+
+#[test]
+fn calls_require_root() {
+	new_test_ext(vec![]).execute_with(|| {
+		assert_noop!(
+			Migrations::force_set_cursor(RuntimeOrigin::signed(1), None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Migrations::clear_historic(RuntimeOrigin::signed(1), vec![]),
+			DispatchError::BadOrigin
+		);
+
+		let id = IdentifierOf::<Test>::truncate_from(vec![0]);
+		Historic::<Test>::insert(&id, ());
+		assert_ok!(Migrations::clear_historic(RuntimeOrigin::root(), vec![id.clone()]));
+		assert!(!Historic::<Test>::contains_key(&id));
+		assert_eq!(migration_events(), vec![Event::HistoricCleared { removed: 1 }]);
+	});
+}
@@ -104,6 +104,7 @@ impl pallet_scheduler::Config for Test {
 	type WeightInfo = ();
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type Preimages = Preimage;
+	type MaxTaskWeight = MaxWeight;
 }
 impl pallet_balances::Config for Test {
 	type MaxReserves = ();
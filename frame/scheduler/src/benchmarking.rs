@@ -307,5 +307,94 @@ benchmarks! {
 		);
 	}
 
+	reschedule_named {
+		let s in 1 .. T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+		let new_when = when + One::one();
+		let periodic = Some((BlockNumberFor::<T>::one(), 10));
+
+		fill_schedule::<T>(when, s)?;
+	}: _(RawOrigin::Root, u32_to_name(0), new_when, periodic)
+	verify {
+		ensure!(
+			Lookup::<T>::get(u32_to_name(0)) == Some((new_when, 0)),
+			"didn't move the task"
+		);
+	}
+
+	schedule_retry {
+		let s in 1 .. T::MaxScheduledPerBlock::get();
+		let now = BLOCK_NUMBER.into();
+		let wake = now + One::one();
+		let task = make_task::<T>(false, false, false, None, 0);
+		let retry_config =
+			RetryConfig { total_retries: 10, remaining: 10, period: BlockNumberFor::<T>::one() };
+
+		fill_schedule::<T>(wake, s - 1)?;
+	}: {
+		Scheduler::<T>::schedule_retry(
+			&mut WeightMeter::max_limit(),
+			now,
+			now,
+			0,
+			&task,
+			retry_config,
+		);
+	} verify {
+		ensure!(Retries::<T>::get((wake, s - 1)).is_some(), "didn't schedule the retry");
+	}
+
+	set_retry {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+	}: _(RawOrigin::Root, (when, 0), 10, BlockNumberFor::<T>::one())
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_some(), "didn't set the retry configuration");
+	}
+
+	set_retry_named {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+	}: _(RawOrigin::Root, u32_to_name(0), 10, BlockNumberFor::<T>::one())
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_some(), "didn't set the retry configuration");
+	}
+
+	cancel_retry {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		Scheduler::<T>::set_retry(
+			RawOrigin::Root.into(),
+			(when, 0),
+			10,
+			BlockNumberFor::<T>::one(),
+		)?;
+	}: _(RawOrigin::Root, (when, 0))
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_none(), "didn't cancel the retry configuration");
+	}
+
+	cancel_retry_named {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		Scheduler::<T>::set_retry_named(
+			RawOrigin::Root.into(),
+			u32_to_name(0),
+			10,
+			BlockNumberFor::<T>::one(),
+		)?;
+	}: _(RawOrigin::Root, u32_to_name(0))
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_none(), "didn't cancel the retry configuration");
+	}
+
 	impl_benchmark_test_suite!(Scheduler, crate::mock::new_test_ext(), crate::mock::Test);
 }
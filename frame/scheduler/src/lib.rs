@@ -47,6 +47,12 @@
 //! * `schedule_named` - augments the `schedule` interface with an additional `Vec<u8>` parameter
 //!   that can be used for identification.
 //! * `cancel_named` - the named complement to the cancel function.
+//! * `reschedule_named` - move a named task to another block and/or change its period.
+//! * `set_retry` - retry a task a number of times, with an exponential backoff, whenever its
+//!   dispatch fails.
+//! * `set_retry_named` - the named complement to the `set_retry` function.
+//! * `cancel_retry` - stop retrying a task on failure.
+//! * `cancel_retry_named` - the named complement to the `cancel_retry` function.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -60,7 +66,7 @@ mod mock;
 mod tests;
 pub mod weights;
 
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Codec, Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::{
 		DispatchError, DispatchResult, Dispatchable, GetDispatchInfo, Parameter, RawOrigin,
@@ -80,7 +86,7 @@ use frame_system::{
 use scale_info::TypeInfo;
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{BadOrigin, One, Saturating, Zero},
+	traits::{AtLeast32BitUnsigned, BadOrigin, One, Saturating, Zero},
 	BoundedVec, RuntimeDebug,
 };
 use sp_std::{borrow::Borrow, cmp::Ordering, marker::PhantomData, prelude::*};
@@ -148,6 +154,42 @@ pub type ScheduledOf<T> = Scheduled<
 	<T as frame_system::Config>::AccountId,
 >;
 
+/// The configuration of the retry mechanism for a given task along with its current state.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct RetryConfig<Period> {
+	/// Initial amount of retries allowed.
+	pub total_retries: u8,
+	/// Amount of retries left.
+	pub remaining: u8,
+	/// Period of time between the failure and the first retry, doubled for every further retry.
+	pub period: Period,
+}
+
+impl<Period: AtLeast32BitUnsigned + Copy> RetryConfig<Period> {
+	/// The delay before the next retry, backing off exponentially with every retry already made.
+	pub fn delay(&self) -> Period {
+		let attempts = self.total_retries.saturating_sub(self.remaining);
+		(0..attempts).fold(self.period, |delay, _| delay.saturating_mul(2u32.into()))
+	}
+}
+
+/// A task in the agenda, as exposed by the [`SchedulerApi`].
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct AgendaEntry<BlockNumber> {
+	/// The block the task is due at.
+	pub when: BlockNumber,
+	/// The index of the task within the agenda of its block.
+	pub index: u32,
+	/// The name of the task, if it has one.
+	pub id: Option<TaskName>,
+	/// The priority of the task.
+	pub priority: schedule::Priority,
+	/// The period and the number of further repetitions of the task, if it is periodic.
+	pub maybe_periodic: Option<schedule::Period<BlockNumber>>,
+	/// The retry configuration of the task, if any.
+	pub retry: Option<RetryConfig<BlockNumber>>,
+}
+
 pub(crate) trait MarginalWeightInfo: WeightInfo {
 	fn service_task(maybe_lookup_len: Option<usize>, named: bool, periodic: bool) -> Weight {
 		let base = Self::service_task_base();
@@ -232,6 +274,15 @@ pub mod pallet {
 
 		/// The preimage provider with which we look up call hashes to get the call.
 		type Preimages: QueryPreimage + StorePreimage;
+
+		/// The maximum weight a single task may consume.
+		///
+		/// Tasks whose call is heavier are not dispatched and are reported as permanently
+		/// overweight, so they can't hold up the rest of the agenda. They are postponed to their
+		/// next retry if they have one, and otherwise kept in their agenda, along with their
+		/// preimage and name, so they can be rescheduled or cancelled.
+		#[pallet::constant]
+		type MaxTaskWeight: Get<Weight>;
 	}
 
 	#[pallet::storage]
@@ -255,6 +306,16 @@ pub mod pallet {
 	pub(crate) type Lookup<T: Config> =
 		StorageMap<_, Twox64Concat, TaskName, TaskAddress<BlockNumberFor<T>>>;
 
+	/// Retry configurations for items to be executed, indexed by task address.
+	#[pallet::storage]
+	pub type Retries<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		TaskAddress<BlockNumberFor<T>>,
+		RetryConfig<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	/// Events type.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -274,7 +335,24 @@ pub mod pallet {
 		/// The given task was unable to be renewed since the agenda is full at that block.
 		PeriodicFailed { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
 		/// The given task can never be executed since it is overweight.
+		///
+		/// Tasks over `MaxTaskWeight` are postponed rather than aborted; see
+		/// [`Config::MaxTaskWeight`].
 		PermanentlyOverweight { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
+		/// The period of the given named task was changed.
+		PeriodChanged { task: TaskAddress<BlockNumberFor<T>>, id: TaskName },
+		/// Set a retry configuration for some task.
+		RetrySet {
+			task: TaskAddress<BlockNumberFor<T>>,
+			id: Option<TaskName>,
+			period: BlockNumberFor<T>,
+			retries: u8,
+		},
+		/// Cancel a retry configuration for some task.
+		RetryCancelled { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
+		/// The given task was unable to be retried since the agenda is full at that block or
+		/// there was not enough weight to reschedule it.
+		RetryFailed { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
 	}
 
 	#[pallet::error]
@@ -289,6 +367,8 @@ pub mod pallet {
 		RescheduleNoChange,
 		/// Attempt to use a non-named function on a named task.
 		Named,
+		/// The period between retries must not be zero.
+		ZeroRetryPeriod,
 	}
 
 	#[pallet::hooks]
@@ -414,6 +494,90 @@ pub mod pallet {
 			)?;
 			Ok(())
 		}
+
+		/// Move a named task to another block and/or change its period.
+		///
+		/// The period is given like on scheduling, so its count includes the upcoming execution.
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::reschedule_named(T::MaxScheduledPerBlock::get()))]
+		pub fn reschedule_named(
+			origin: OriginFor<T>,
+			id: TaskName,
+			when: BlockNumberFor<T>,
+			maybe_periodic: Option<schedule::Period<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_update_named(
+				Some(origin.caller().clone()),
+				id,
+				DispatchTime::At(when),
+				maybe_periodic,
+			)?;
+			Ok(())
+		}
+
+		/// Set a retry configuration for a task so that, in case its scheduled run fails, it will
+		/// be retried after `period` blocks, for a total amount of `retries` retries or until it
+		/// succeeds.
+		///
+		/// Every further retry waits twice as long as the previous one. Tasks which need to be
+		/// retried are placed in the agenda of the retry's block as anonymous, non-periodic
+		/// tasks, except for non-periodic named tasks which keep their name. Periodic tasks keep
+		/// their retry configuration, with a full amount of retries, for their next run.
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_retry())]
+		pub fn set_retry(
+			origin: OriginFor<T>,
+			task: TaskAddress<BlockNumberFor<T>>,
+			retries: u8,
+			period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_set_retry(Some(origin.caller().clone()), task, retries, period)
+		}
+
+		/// Set a retry configuration for a named task so that, in case its scheduled run fails,
+		/// it will be retried after `period` blocks, for a total amount of `retries` retries or
+		/// until it succeeds.
+		///
+		/// See [`Pallet::set_retry`] for how retries are scheduled.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_retry_named())]
+		pub fn set_retry_named(
+			origin: OriginFor<T>,
+			id: TaskName,
+			retries: u8,
+			period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			let task = Lookup::<T>::get(id).ok_or(Error::<T>::NotFound)?;
+			Self::do_set_retry(Some(origin.caller().clone()), task, retries, period)
+		}
+
+		/// Removes the retry configuration of a task.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_retry())]
+		pub fn cancel_retry(
+			origin: OriginFor<T>,
+			task: TaskAddress<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_cancel_retry(Some(origin.caller().clone()), task)
+		}
+
+		/// Cancel the retry configuration of a named task.
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_retry_named())]
+		pub fn cancel_retry_named(origin: OriginFor<T>, id: TaskName) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			let task = Lookup::<T>::get(id).ok_or(Error::<T>::NotFound)?;
+			Self::do_cancel_retry(Some(origin.caller().clone()), task)
+		}
 	}
 }
 
@@ -828,6 +992,7 @@ impl<T: Config> Pallet<T> {
 			if let Some(id) = s.maybe_id {
 				Lookup::<T>::remove(id);
 			}
+			Retries::<T>::remove((when, index));
 			Self::cleanup_agenda(when);
 			Self::deposit_event(Event::Canceled { when, index });
 			Ok(())
@@ -854,7 +1019,9 @@ impl<T: Config> Pallet<T> {
 		Self::cleanup_agenda(when);
 		Self::deposit_event(Event::Canceled { when, index });
 
-		Self::place_task(new_time, task).map_err(|x| x.0)
+		let new_address = Self::place_task(new_time, task).map_err(|x| x.0)?;
+		Self::move_retry_config((when, index), new_address);
+		Ok(new_address)
 	}
 
 	fn do_schedule_named(
@@ -917,6 +1084,7 @@ impl<T: Config> Pallet<T> {
 					}
 					Ok(())
 				})?;
+				Retries::<T>::remove((when, index));
 				Self::cleanup_agenda(when);
 				Self::deposit_event(Event::Canceled { when, index });
 				Ok(())
@@ -945,7 +1113,165 @@ impl<T: Config> Pallet<T> {
 		})?;
 		Self::cleanup_agenda(when);
 		Self::deposit_event(Event::Canceled { when, index });
-		Self::place_task(new_time, task).map_err(|x| x.0)
+		let new_address = Self::place_task(new_time, task).map_err(|x| x.0)?;
+		Self::move_retry_config((when, index), new_address);
+		Ok(new_address)
+	}
+
+	/// Move the named task `id` to `new_time` and set its period to `maybe_periodic`.
+	///
+	/// Unlike `do_reschedule_named`, the task may stay at the block it is currently due at as long
+	/// as its period changes.
+	fn do_update_named(
+		origin: Option<T::PalletsOrigin>,
+		id: TaskName,
+		new_time: DispatchTime<BlockNumberFor<T>>,
+		maybe_periodic: Option<schedule::Period<BlockNumberFor<T>>>,
+	) -> Result<TaskAddress<BlockNumberFor<T>>, DispatchError> {
+		let new_time = Self::resolve_time(new_time)?;
+
+		let (when, index) = Lookup::<T>::get(id).ok_or(Error::<T>::NotFound)?;
+
+		// sanitize maybe_periodic
+		let maybe_periodic = maybe_periodic
+			.filter(|p| p.1 > 1 && !p.0.is_zero())
+			// Remove one from the number of repetitions since we will schedule one now.
+			.map(|(p, c)| (p, c - 1));
+
+		let mut agenda = Agenda::<T>::get(when);
+		let slot = agenda.get_mut(index as usize).ok_or(Error::<T>::NotFound)?;
+		let task = slot.as_mut().ok_or(Error::<T>::NotFound)?;
+		if let Some(ref o) = origin {
+			Self::ensure_privilege(o, &task.origin)?;
+		}
+		let period_changed = task.maybe_periodic != maybe_periodic;
+		ensure!(new_time != when || period_changed, Error::<T>::RescheduleNoChange);
+		task.maybe_periodic = maybe_periodic;
+
+		let new_address = if new_time == when {
+			Agenda::<T>::insert(when, agenda);
+			(when, index)
+		} else {
+			let task = slot.take().ok_or(Error::<T>::NotFound)?;
+			Agenda::<T>::insert(when, agenda);
+			Self::cleanup_agenda(when);
+			Self::deposit_event(Event::Canceled { when, index });
+			let new_address = Self::place_task(new_time, task).map_err(|x| x.0)?;
+			Self::move_retry_config((when, index), new_address);
+			new_address
+		};
+
+		if period_changed {
+			Self::deposit_event(Event::PeriodChanged { task: new_address, id });
+		}
+		Ok(new_address)
+	}
+
+	fn do_set_retry(
+		origin: Option<T::PalletsOrigin>,
+		(when, index): TaskAddress<BlockNumberFor<T>>,
+		retries: u8,
+		period: BlockNumberFor<T>,
+	) -> DispatchResult {
+		ensure!(!period.is_zero(), Error::<T>::ZeroRetryPeriod);
+		let agenda = Agenda::<T>::get(when);
+		let task = agenda
+			.get(index as usize)
+			.and_then(Option::as_ref)
+			.ok_or(Error::<T>::NotFound)?;
+		if let Some(ref o) = origin {
+			Self::ensure_privilege(o, &task.origin)?;
+		}
+		Retries::<T>::insert(
+			(when, index),
+			RetryConfig { total_retries: retries, remaining: retries, period },
+		);
+		Self::deposit_event(Event::RetrySet {
+			task: (when, index),
+			id: task.maybe_id,
+			period,
+			retries,
+		});
+		Ok(())
+	}
+
+	fn do_cancel_retry(
+		origin: Option<T::PalletsOrigin>,
+		(when, index): TaskAddress<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		let agenda = Agenda::<T>::get(when);
+		let task = agenda
+			.get(index as usize)
+			.and_then(Option::as_ref)
+			.ok_or(Error::<T>::NotFound)?;
+		if let Some(ref o) = origin {
+			Self::ensure_privilege(o, &task.origin)?;
+		}
+		Retries::<T>::take((when, index)).ok_or(Error::<T>::NotFound)?;
+		Self::deposit_event(Event::RetryCancelled { task: (when, index), id: task.maybe_id });
+		Ok(())
+	}
+
+	/// Ensure that `origin` has at least the privileges of the origin which scheduled a task.
+	fn ensure_privilege(
+		origin: &T::PalletsOrigin,
+		scheduled_origin: &T::PalletsOrigin,
+	) -> DispatchResult {
+		if matches!(
+			T::OriginPrivilegeCmp::cmp_privilege(origin, scheduled_origin),
+			Some(Ordering::Less) | None
+		) {
+			return Err(BadOrigin.into())
+		}
+		Ok(())
+	}
+
+	/// Move the retry configuration of the task at `from`, if any, to `to`.
+	fn move_retry_config(from: TaskAddress<BlockNumberFor<T>>, to: TaskAddress<BlockNumberFor<T>>) {
+		if let Some(retry_config) = Retries::<T>::take(from) {
+			Retries::<T>::insert(to, retry_config);
+		}
+	}
+
+	/// The tasks scheduled in the blocks from `from` to `to`, inclusive.
+	///
+	/// The range isn't bounded, so this is only meant to be used off-chain through the
+	/// [`SchedulerApi`].
+	pub fn agenda(
+		from: BlockNumberFor<T>,
+		to: BlockNumberFor<T>,
+	) -> Vec<AgendaEntry<BlockNumberFor<T>>> {
+		let mut entries = Vec::new();
+		let mut when = from;
+		while when <= to {
+			for (index, task) in Agenda::<T>::get(when).into_iter().enumerate() {
+				let Some(task) = task else { continue };
+				let index = index as u32;
+				entries.push(AgendaEntry {
+					when,
+					index,
+					id: task.maybe_id,
+					priority: task.priority,
+					maybe_periodic: task.maybe_periodic,
+					retry: Retries::<T>::get((when, index)),
+				});
+			}
+			if when == to {
+				break
+			}
+			when.saturating_inc();
+		}
+		entries
+	}
+
+	/// The block the named task `id` is next due at, if it is scheduled.
+	pub fn next_run(id: TaskName) -> Option<BlockNumberFor<T>> {
+		Lookup::<T>::get(id).and_then(|(when, index)| {
+			Agenda::<T>::get(when)
+				.get(index as usize)
+				.and_then(Option::as_ref)
+				.map(|_| when)
+		})
 	}
 }
 
@@ -954,6 +1280,8 @@ enum ServiceTaskError {
 	Unavailable,
 	/// Could not be executed due to weight limitations.
 	Overweight,
+	/// Can never be executed since it exceeds the weight limit of a single task.
+	PermanentlyOverweight,
 }
 use ServiceTaskError::*;
 
@@ -1090,6 +1418,7 @@ impl<T: Config> Pallet<T> {
 			},
 			Err(Overweight) if is_first => {
 				T::Preimages::drop(&task.call);
+				Retries::<T>::remove((when, agenda_index));
 				Self::deposit_event(Event::PermanentlyOverweight {
 					task: (when, agenda_index),
 					id: task.maybe_id,
@@ -1097,12 +1426,29 @@ impl<T: Config> Pallet<T> {
 				Err((Unavailable, Some(task)))
 			},
 			Err(Overweight) => Err((Overweight, Some(task))),
+			Err(PermanentlyOverweight) => {
+				Self::deposit_event(Event::PermanentlyOverweight {
+					task: (when, agenda_index),
+					id: task.maybe_id,
+				});
+				Err((Unavailable, Self::postpone_overweight(weight, now, when, agenda_index, task)))
+			},
 			Ok(result) => {
+				let failed = result.is_err();
 				Self::deposit_event(Event::Dispatched {
 					task: (when, agenda_index),
 					id: task.maybe_id,
 					result,
 				});
+
+				weight.check_accrue(T::DbWeight::get().reads_writes(1, 1));
+				let maybe_retry_config = Retries::<T>::take((when, agenda_index));
+				match maybe_retry_config {
+					Some(retry_config) if failed && retry_config.remaining > 0 =>
+						Self::schedule_retry(weight, now, when, agenda_index, &task, retry_config),
+					_ => {},
+				}
+
 				if let &Some((period, count)) = &task.maybe_periodic {
 					if count > 1 {
 						task.maybe_periodic = Some((period, count - 1));
@@ -1111,7 +1457,17 @@ impl<T: Config> Pallet<T> {
 					}
 					let wake = now.saturating_add(period);
 					match Self::place_task(wake, task) {
-						Ok(_) => {},
+						Ok(new_address) =>
+							if let Some(retry_config) = maybe_retry_config {
+								// the next run starts over with the full amount of retries
+								Retries::<T>::insert(
+									new_address,
+									RetryConfig {
+										remaining: retry_config.total_retries,
+										..retry_config
+									},
+								);
+							},
 						Err((_, task)) => {
 							// TODO: Leave task in storage somewhere for it to be rescheduled
 							// manually.
@@ -1130,6 +1486,87 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Schedule a retry of the failed `task`, which ran at the agenda `when` at `agenda_index`.
+	///
+	/// A non-periodic task is retried as is. A periodic task keeps its schedule, while the retry is
+	/// an anonymous, non-periodic clone of it.
+	fn schedule_retry(
+		weight: &mut WeightMeter,
+		now: BlockNumberFor<T>,
+		when: BlockNumberFor<T>,
+		agenda_index: u32,
+		task: &ScheduledOf<T>,
+		retry_config: RetryConfig<BlockNumberFor<T>>,
+	) {
+		if !weight.check_accrue(T::WeightInfo::schedule_retry(T::MaxScheduledPerBlock::get())) {
+			Self::deposit_event(Event::RetryFailed {
+				task: (when, agenda_index),
+				id: task.maybe_id,
+			});
+			return
+		}
+
+		let wake = now.saturating_add(retry_config.delay());
+		let mut retry = task.clone();
+		if retry.maybe_periodic.take().is_some() {
+			retry.maybe_id = None;
+		}
+		match Self::place_task(wake, retry) {
+			Ok(new_address) => {
+				if let Some(hash) = task.call.lookup_hash() {
+					// The retry holds onto the call on its own.
+					T::Preimages::request(&hash);
+				}
+				Retries::<T>::insert(
+					new_address,
+					RetryConfig { remaining: retry_config.remaining - 1, ..retry_config },
+				);
+			},
+			Err(_) => Self::deposit_event(Event::RetryFailed {
+				task: (when, agenda_index),
+				id: task.maybe_id,
+			}),
+		}
+	}
+
+	/// Postpone a task which exceeds `MaxTaskWeight`, which ran at the agenda `when` at
+	/// `agenda_index`.
+	///
+	/// The task is moved to the block of its next retry if it has one left. Otherwise, or if that
+	/// agenda is full, it is handed back to stay in its agenda, addressable by its name, for it to
+	/// be rescheduled or cancelled.
+	fn postpone_overweight(
+		weight: &mut WeightMeter,
+		now: BlockNumberFor<T>,
+		when: BlockNumberFor<T>,
+		agenda_index: u32,
+		mut task: ScheduledOf<T>,
+	) -> Option<ScheduledOf<T>> {
+		weight.check_accrue(T::DbWeight::get().reads_writes(1, 1));
+		if let Some(retry_config) = Retries::<T>::get((when, agenda_index)) {
+			let schedule_retry_weight =
+				T::WeightInfo::schedule_retry(T::MaxScheduledPerBlock::get());
+			if retry_config.remaining > 0 && weight.check_accrue(schedule_retry_weight) {
+				let wake = now.saturating_add(retry_config.delay());
+				match Self::place_task(wake, task) {
+					Ok(new_address) => {
+						Retries::<T>::remove((when, agenda_index));
+						Retries::<T>::insert(
+							new_address,
+							RetryConfig { remaining: retry_config.remaining - 1, ..retry_config },
+						);
+						return None
+					},
+					Err((_, unplaced)) => task = unplaced,
+				}
+			}
+		}
+		if let Some(ref id) = task.maybe_id {
+			Lookup::<T>::insert(id, (when, agenda_index));
+		}
+		Some(task)
+	}
+
 	/// Make a dispatch to the given `call` from the given `origin`, ensuring that the `weight`
 	/// counter does not exceed its limit and that it is counted accurately (e.g. accounted using
 	/// post info if available).
@@ -1146,6 +1583,9 @@ impl<T: Config> Pallet<T> {
 			_ => T::WeightInfo::execute_dispatch_unsigned(),
 		};
 		let call_weight = call.get_dispatch_info().weight;
+		if call_weight.any_gt(T::MaxTaskWeight::get()) {
+			return Err(PermanentlyOverweight)
+		}
 		// We only allow a scheduled call if it cannot push the weight past the limit.
 		let max_weight = base_weight.saturating_add(call_weight);
 
@@ -1313,6 +1753,16 @@ impl<T: Config> schedule::v3::Named<BlockNumberFor<T>, <T as Config>::RuntimeCal
 	}
 }
 
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows to inspect the scheduler's agenda.
+	pub trait SchedulerApi<BlockNumber> where BlockNumber: Codec {
+		/// Returns the tasks scheduled in the blocks from `from` to `to`, inclusive.
+		fn agenda(from: BlockNumber, to: BlockNumber) -> Vec<AgendaEntry<BlockNumber>>;
+		/// Returns the block the named task `id` is next due at, if it is scheduled.
+		fn next_run(id: TaskName) -> Option<BlockNumber>;
+	}
+}
+
 /// Maps a pallet error to an `schedule::v3` error.
 fn map_err_to_v3_err<T: Config>(err: DispatchError) -> DispatchError {
 	if err == DispatchError::from(Error::<T>::NotFound) {
@@ -66,6 +66,16 @@ pub mod logger {
 		Logged(u32, Weight),
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call was dispatched outside of the block range set in `Threshold`.
+		OutsideThreshold,
+	}
+
+	/// The range of blocks, inclusive, in which `timed_log` succeeds.
+	#[pallet::storage]
+	pub type Threshold<T: Config> = StorageValue<_, (BlockNumberFor<T>, BlockNumberFor<T>)>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T>
 	where
@@ -90,6 +100,20 @@ pub mod logger {
 			});
 			Ok(())
 		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(*weight)]
+		pub fn timed_log(origin: OriginFor<T>, i: u32, weight: Weight) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some((start, end)) = Threshold::<T>::get() {
+				ensure!(start <= now && now <= end, Error::<T>::OutsideThreshold);
+			}
+			Self::deposit_event(Event::Logged(i, weight));
+			Log::mutate(|log| {
+				log.push((origin.caller().clone(), i));
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -99,7 +123,7 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
-		Logger: logger::{Pallet, Call, Event<T>},
+		Logger: logger::{Pallet, Call, Storage, Event<T>},
 		Scheduler: scheduler::{Pallet, Call, Storage, Event<T>},
 		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
 	}
@@ -198,10 +222,29 @@ impl WeightInfo for TestWeightInfo {
 	fn cancel_named(_s: u32) -> Weight {
 		Weight::from_parts(50, 0)
 	}
+	fn reschedule_named(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn schedule_retry(_s: u32) -> Weight {
+		Weight::from_parts(100000, 0)
+	}
+	fn set_retry() -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn set_retry_named() -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_retry() -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_retry_named() -> Weight {
+		Weight::from_parts(50, 0)
+	}
 }
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
 		BlockWeights::get().max_block;
+	pub storage MaxTaskWeight: Weight = MaximumSchedulerWeight::get();
 }
 
 impl Config for Test {
@@ -215,6 +258,7 @@ impl Config for Test {
 	type WeightInfo = TestWeightInfo;
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type Preimages = Preimage;
+	type MaxTaskWeight = MaxTaskWeight;
 }
 
 pub type LoggerCall = logger::Call<Test>;
@@ -1852,3 +1852,301 @@ fn reschedule_named_last_task_removes_agenda() {
 		assert!(Agenda::<Test>::get(when).len() == 0);
 	});
 }
+
+#[test]
+fn reschedule_named_changes_time_and_period() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::log {
+			i: 42,
+			weight: Weight::from_parts(10, 0),
+		}));
+		let name = [1u8; 32];
+		assert_ok!(Scheduler::schedule_named(RuntimeOrigin::root(), name, 4, None, 127, call));
+		assert_eq!(Scheduler::next_run(name), Some(4));
+
+		// Nothing changes.
+		assert_noop!(
+			Scheduler::reschedule_named(RuntimeOrigin::root(), name, 4, None),
+			Error::<Test>::RescheduleNoChange,
+		);
+		// Only the period changes.
+		assert_ok!(Scheduler::reschedule_named(RuntimeOrigin::root(), name, 4, Some((3, 3))));
+		System::assert_last_event(crate::Event::PeriodChanged { task: (4, 0), id: name }.into());
+		assert_eq!(Agenda::<Test>::get(4)[0].as_ref().unwrap().maybe_periodic, Some((3, 2)));
+
+		// Move it and shorten the period.
+		assert_ok!(Scheduler::reschedule_named(RuntimeOrigin::root(), name, 5, Some((3, 2))));
+		assert!(Agenda::<Test>::get(4).is_empty());
+		assert_eq!(Scheduler::next_run(name), Some(5));
+
+		run_to_block(4);
+		assert!(logger::log().is_empty());
+		run_to_block(5);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert_eq!(Scheduler::next_run(name), Some(8));
+		run_to_block(100);
+		assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32)]);
+		assert_eq!(Scheduler::next_run(name), None);
+
+		assert_noop!(
+			Scheduler::reschedule_named(RuntimeOrigin::root(), name, 200, None),
+			Error::<Test>::NotFound,
+		);
+	});
+}
+
+#[test]
+fn retries_back_off_until_success() {
+	new_test_ext().execute_with(|| {
+		logger::Threshold::<Test>::put((10, 100));
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::timed_log {
+			i: 42,
+			weight: Weight::from_parts(10, 0),
+		}));
+		let name = [1u8; 32];
+		assert_ok!(Scheduler::schedule_named(RuntimeOrigin::root(), name, 4, None, 127, call));
+		assert_noop!(
+			Scheduler::set_retry_named(RuntimeOrigin::root(), name, 3, 0),
+			Error::<Test>::ZeroRetryPeriod,
+		);
+		assert_ok!(Scheduler::set_retry_named(RuntimeOrigin::root(), name, 3, 1));
+		assert_eq!(
+			Retries::<Test>::get((4, 0)),
+			Some(RetryConfig { total_retries: 3, remaining: 3, period: 1 })
+		);
+
+		// Fails and is retried a block later.
+		run_to_block(4);
+		assert!(logger::log().is_empty());
+		assert_eq!(Scheduler::next_run(name), Some(5));
+		assert_eq!(
+			Retries::<Test>::get((5, 0)),
+			Some(RetryConfig { total_retries: 3, remaining: 2, period: 1 })
+		);
+		// The delay doubles with every retry.
+		run_to_block(5);
+		assert_eq!(Scheduler::next_run(name), Some(7));
+		run_to_block(7);
+		assert_eq!(Scheduler::next_run(name), Some(11));
+		assert_eq!(
+			Retries::<Test>::get((11, 0)),
+			Some(RetryConfig { total_retries: 3, remaining: 0, period: 1 })
+		);
+		assert!(logger::log().is_empty());
+
+		// The last retry is within the threshold.
+		run_to_block(11);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert_eq!(Scheduler::next_run(name), None);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn retries_run_out() {
+	new_test_ext().execute_with(|| {
+		logger::Threshold::<Test>::put((100, 100));
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::timed_log {
+			i: 42,
+			weight: Weight::from_parts(10, 0),
+		}));
+		assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), 4, None, 127, call));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 1, 2));
+
+		run_to_block(4);
+		assert_eq!(Agenda::<Test>::get(6).len(), 1);
+		run_to_block(6);
+		// No retries left.
+		System::assert_has_event(
+			crate::Event::Dispatched {
+				task: (6, 0),
+				id: None,
+				result: Err(logger::Error::<Test>::OutsideThreshold.into()),
+			}
+			.into(),
+		);
+		run_to_block(50);
+		assert!(logger::log().is_empty());
+		assert_eq!(Agenda::<Test>::iter().count(), 0);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn periodic_task_keeps_its_schedule_while_retrying() {
+	new_test_ext().execute_with(|| {
+		logger::Threshold::<Test>::put((8, 100));
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::timed_log {
+			i: 42,
+			weight: Weight::from_parts(10, 0),
+		}));
+		assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), 4, Some((5, 3)), 127, call));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 2, 1));
+
+		run_to_block(4);
+		// The retry is a one-off task, the periodic task keeps all of its retries.
+		let retry = Agenda::<Test>::get(5)[0].clone().unwrap();
+		assert_eq!(retry.maybe_periodic, None);
+		assert_eq!(
+			Retries::<Test>::get((5, 0)),
+			Some(RetryConfig { total_retries: 2, remaining: 1, period: 1 })
+		);
+		assert_eq!(
+			Retries::<Test>::get((9, 0)),
+			Some(RetryConfig { total_retries: 2, remaining: 2, period: 1 })
+		);
+
+		run_to_block(5);
+		assert!(Retries::<Test>::get((7, 0)).is_some());
+		run_to_block(7);
+		assert!(logger::log().is_empty());
+
+		run_to_block(9);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert_eq!(
+			Retries::<Test>::get((14, 0)),
+			Some(RetryConfig { total_retries: 2, remaining: 2, period: 1 })
+		);
+		run_to_block(100);
+		assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32)]);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn cancel_retry_works() {
+	new_test_ext().execute_with(|| {
+		logger::Threshold::<Test>::put((100, 100));
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::timed_log {
+			i: 42,
+			weight: Weight::from_parts(10, 0),
+		}));
+		let name = [1u8; 32];
+		assert_ok!(Scheduler::schedule_named(RuntimeOrigin::root(), name, 4, None, 127, call));
+		assert_noop!(
+			Scheduler::cancel_retry_named(RuntimeOrigin::root(), name),
+			Error::<Test>::NotFound,
+		);
+		assert_ok!(Scheduler::set_retry_named(RuntimeOrigin::root(), name, 10, 1));
+		assert_ok!(Scheduler::cancel_retry(RuntimeOrigin::root(), (4, 0)));
+		System::assert_last_event(
+			crate::Event::RetryCancelled { task: (4, 0), id: Some(name) }.into(),
+		);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+
+		// Cancelling the task also drops its retry configuration.
+		assert_ok!(Scheduler::set_retry_named(RuntimeOrigin::root(), name, 10, 1));
+		assert_ok!(Scheduler::cancel_named(RuntimeOrigin::root(), name));
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn agenda_lists_scheduled_tasks() {
+	new_test_ext().execute_with(|| {
+		let call =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_parts(10, 0) });
+		assert_ok!(Scheduler::schedule_named(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			4,
+			None,
+			127,
+			Box::new(call.clone()),
+		));
+		assert_ok!(Scheduler::schedule(
+			RuntimeOrigin::root(),
+			4,
+			Some((10, 3)),
+			0,
+			Box::new(call.clone()),
+		));
+		assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), 20, None, 1, Box::new(call)));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 1), 5, 2));
+		assert_ok!(Scheduler::cancel_named(RuntimeOrigin::root(), [1u8; 32]));
+
+		assert_eq!(
+			Scheduler::agenda(1, 10),
+			vec![AgendaEntry {
+				when: 4,
+				index: 1,
+				id: None,
+				priority: 0,
+				maybe_periodic: Some((10, 2)),
+				retry: Some(RetryConfig { total_retries: 5, remaining: 5, period: 2 }),
+			}]
+		);
+		assert_eq!(Scheduler::agenda(4, 20).len(), 2);
+		assert_eq!(Scheduler::agenda(20, 20)[0].priority, 1);
+		assert!(Scheduler::agenda(5, 19).is_empty());
+		assert_eq!(Scheduler::next_run([1u8; 32]), None);
+	});
+}
+
+#[test]
+fn tasks_heavier_than_max_task_weight_are_not_dispatched() {
+	new_test_ext().execute_with(|| {
+		MaxTaskWeight::set(&Weight::from_parts(100, 0));
+		let heavy =
+			RuntimeCall::Logger(LoggerCall::log { i: 69, weight: Weight::from_parts(101, 0) });
+		let hash = <Test as frame_system::Config>::Hashing::hash_of(&heavy);
+		let len = heavy.using_encoded(|x| x.len()) as u32;
+		let light =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_parts(100, 0) });
+		assert_ok!(Scheduler::do_schedule_named(
+			[1u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Bounded::Lookup { hash, len },
+		));
+		assert_ok!(Preimage::note_preimage(RuntimeOrigin::signed(0), heavy.encode()));
+		assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), 4, None, 127, Box::new(light)));
+
+		run_to_block(100);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		System::assert_has_event(
+			crate::Event::PermanentlyOverweight { task: (4, 0), id: Some([1u8; 32]) }.into(),
+		);
+		// The task is kept along with its preimage and name, so it can be rescheduled.
+		assert!(Agenda::<Test>::get(4)[0].is_some());
+		assert!(Preimage::len(&hash).is_some());
+		assert_eq!(Lookup::<Test>::get([1u8; 32]), Some((4, 0)));
+
+		// Once the limit is raised, rescheduling the task gets it dispatched.
+		MaxTaskWeight::set(&Weight::from_parts(101, 0));
+		assert_ok!(Scheduler::do_reschedule_named([1u8; 32], DispatchTime::At(101)));
+		run_to_block(101);
+		assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 69u32)]);
+		assert!(Preimage::len(&hash).is_none());
+	});
+}
+
+#[test]
+fn tasks_heavier_than_max_task_weight_are_postponed_to_their_retries() {
+	new_test_ext().execute_with(|| {
+		MaxTaskWeight::set(&Weight::from_parts(100, 0));
+		let heavy =
+			RuntimeCall::Logger(LoggerCall::log { i: 69, weight: Weight::from_parts(101, 0) });
+		assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), 4, None, 127, Box::new(heavy)));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 2, 3));
+
+		run_to_block(4);
+		System::assert_has_event(
+			crate::Event::PermanentlyOverweight { task: (4, 0), id: None }.into(),
+		);
+		assert!(Agenda::<Test>::get(4)[0].is_none());
+		assert!(Agenda::<Test>::get(7)[0].is_some());
+		assert_eq!(
+			Retries::<Test>::get((7, 0)),
+			Some(RetryConfig { total_retries: 2, remaining: 1, period: 3 })
+		);
+
+		// The limit is raised before the retry is due.
+		MaxTaskWeight::set(&Weight::from_parts(101, 0));
+		run_to_block(7);
+		assert_eq!(logger::log(), vec![(root(), 69u32)]);
+		assert!(Retries::<Test>::get((7, 0)).is_none());
+	});
+}
@@ -0,0 +1,248 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the staking pallet.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use pallet_staking_runtime_api::{
+	EraValidator, PendingReward, StakerExposure, UnbondingChunk, ValidatorExposure,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	traits::{Block as BlockT, MaybeDisplay},
+	Perbill,
+};
+use sp_staking::EraIndex;
+
+pub use pallet_staking_runtime_api::StakerInfoApi as StakerInfoRuntimeApi;
+
+#[rpc(client, server)]
+pub trait StakingApi<BlockHash, AccountId> {
+	#[method(name = "staking_pendingRewards")]
+	fn pending_rewards(
+		&self,
+		stash: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PendingReward<AccountId, NumberOrHex>>>;
+
+	#[method(name = "staking_exposure")]
+	fn exposure(
+		&self,
+		stash: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<StakerExposure<AccountId, NumberOrHex>>>;
+
+	#[method(name = "staking_unbonding")]
+	fn unbonding(
+		&self,
+		stash: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<UnbondingChunk<NumberOrHex>>>;
+
+	#[method(name = "staking_estimatedApy")]
+	fn estimated_apy(&self, at: Option<BlockHash>) -> RpcResult<Perbill>;
+
+	#[method(name = "staking_eraValidators")]
+	fn era_validators(
+		&self,
+		era: Option<EraIndex>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<EraValidator<AccountId, NumberOrHex>>>;
+}
+
+/// Provides RPC methods to query the rewards, exposure and unbonding of stakers.
+pub struct Staking<C, P> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Staking<C, P> {
+	/// Creates a new instance of the Staking Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(error: impl ToString, desc: &'static str) -> CallError {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), desc, Some(error.to_string())))
+}
+
+/// Converts a balance into its RPC representation.
+fn into_rpc_balance<Balance>(value: Balance) -> RpcResult<NumberOrHex>
+where
+	Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	value.try_into().map_err(|_| {
+		JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+			ErrorCode::InvalidParams.code(),
+			format!("{} doesn't fit in NumberOrHex representation", value),
+			None::<()>,
+		)))
+	})
+}
+
+impl<C, Block, AccountId, Balance> StakingApiServer<<Block as BlockT>::Hash, AccountId>
+	for Staking<C, (Block, Balance)>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: StakerInfoRuntimeApi<Block, Balance, AccountId>,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn pending_rewards(
+		&self,
+		stash: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<PendingReward<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rewards = api
+			.pending_rewards(at_hash, stash)
+			.map_err(|e| runtime_error(e, "Unable to query pending rewards."))?;
+
+		rewards
+			.into_iter()
+			.map(|reward| -> RpcResult<_> {
+				Ok(PendingReward {
+					era: reward.era,
+					validator: reward.validator,
+					amount: into_rpc_balance(reward.amount)?,
+				})
+			})
+			.collect()
+	}
+
+	fn exposure(
+		&self,
+		stash: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<StakerExposure<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let exposure = api
+			.exposure(at_hash, stash)
+			.map_err(|e| runtime_error(e, "Unable to query the exposure."))?;
+
+		exposure
+			.map(|exposure| -> RpcResult<_> {
+				let validator = exposure
+					.validator
+					.map(|validator| -> RpcResult<_> {
+						Ok(ValidatorExposure {
+							total: into_rpc_balance(validator.total)?,
+							own: into_rpc_balance(validator.own)?,
+							nominator_count: validator.nominator_count,
+							rank: validator.rank,
+						})
+					})
+					.transpose()?;
+				let nominations = exposure
+					.nominations
+					.into_iter()
+					.map(|(validator, value)| -> RpcResult<_> {
+						Ok((validator, into_rpc_balance(value)?))
+					})
+					.collect::<RpcResult<Vec<_>>>()?;
+
+				Ok(StakerExposure { era: exposure.era, validator, nominations })
+			})
+			.transpose()
+	}
+
+	fn unbonding(
+		&self,
+		stash: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<UnbondingChunk<NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let chunks = api
+			.unbonding(at_hash, stash)
+			.map_err(|e| runtime_error(e, "Unable to query the unbonding schedule."))?;
+
+		chunks
+			.into_iter()
+			.map(|chunk| -> RpcResult<_> {
+				Ok(UnbondingChunk { value: into_rpc_balance(chunk.value)?, era: chunk.era })
+			})
+			.collect()
+	}
+
+	fn estimated_apy(&self, at: Option<Block::Hash>) -> RpcResult<Perbill> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.estimated_apy(at_hash)
+			.map_err(|e| runtime_error(e, "Unable to estimate the APY.").into())
+	}
+
+	fn era_validators(
+		&self,
+		era: Option<EraIndex>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<EraValidator<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let validators = api
+			.era_validators(at_hash, era)
+			.map_err(|e| runtime_error(e, "Unable to query the era validators."))?;
+
+		validators
+			.into_iter()
+			.map(|validator| -> RpcResult<_> {
+				Ok(EraValidator {
+					stash: validator.stash,
+					total: into_rpc_balance(validator.total)?,
+					own: into_rpc_balance(validator.own)?,
+					nominator_count: validator.nominator_count,
+					commission: validator.commission,
+					blocked: validator.blocked,
+					reward_points: validator.reward_points,
+				})
+			})
+			.collect()
+	}
+}
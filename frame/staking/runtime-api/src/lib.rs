@@ -20,13 +20,53 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
+use sp_runtime::Perbill;
+use sp_staking::EraIndex;
+use sp_std::vec::Vec;
+
+pub use sp_staking::{
+	EraValidator, PendingReward, StakerExposure, UnbondingChunk, ValidatorExposure,
+};
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait StakingApi<Balance>
 		where
 			Balance: Codec,
 	{
 		/// Returns the nominations quota for a nominator with a given balance.
 		fn nominations_quota(balance: Balance) -> u32;
+
+		/// Returns the number of eras stake unbonded now, of the given amount, would remain
+		/// locked for.
+		#[api_version(2)]
+		fn unbonding_duration(value: Balance) -> EraIndex;
+
+		/// Returns the amount of stake scheduled to be unlocked at each upcoming era, by era.
+		#[api_version(2)]
+		fn unbonding_queue() -> Vec<(EraIndex, Balance)>;
+	}
+
+	/// Queries of the rewards, exposure and unbonding of stakers.
+	pub trait StakerInfoApi<Balance, AccountId>
+		where
+			Balance: Codec,
+			AccountId: Codec,
+	{
+		/// Returns the rewards of a stash which can still be paid out, per era and validator.
+		fn pending_rewards(stash: AccountId) -> Vec<PendingReward<AccountId, Balance>>;
+
+		/// Returns the stake of a stash exposed in the active era, as a validator and a
+		/// nominator, or `None` if it is not exposed.
+		fn exposure(stash: AccountId) -> Option<StakerExposure<AccountId, Balance>>;
+
+		/// Returns the chunks of the stake of a stash being unbonded.
+		fn unbonding(stash: AccountId) -> Vec<UnbondingChunk<Balance>>;
+
+		/// Returns the estimated annual percentage yield of staking in the active era.
+		fn estimated_apy() -> Perbill;
+
+		/// Returns the validators of an era, or of the active era if `None`.
+		fn era_validators(era: Option<EraIndex>) -> Vec<EraValidator<AccountId, Balance>>;
 	}
 }
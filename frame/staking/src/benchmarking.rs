@@ -280,6 +280,10 @@ benchmarks! {
 		let ledger = Ledger::<T>::get(&controller).ok_or("ledger not created before")?;
 		let original_bonded: BalanceOf<T> = ledger.active;
 
+		// the unbonding duration scales with the stake of the active era
+		ActiveEra::<T>::put(ActiveEraInfo { index: 0, start: None });
+		ErasTotalStake::<T>::insert(0, total_issuance);
+
 		whitelist_account!(controller);
 	}: _(RawOrigin::Signed(controller.clone()), amount)
 	verify {
@@ -649,17 +653,15 @@ benchmarks! {
 		// so the sum of unlocking chunks puts voter into the dest bag.
 		assert!(value * l.into() + origin_weight > origin_weight);
 		assert!(value * l.into() + origin_weight <= dest_weight);
-		let unlock_chunk = UnlockChunk::<BalanceOf<T>> {
-			value,
-			era: EraIndex::zero(),
-		};
 
 		let stash = scenario.origin_stash1.clone();
 		let controller = scenario.origin_controller1;
 		let mut staking_ledger = Ledger::<T>::get(controller.clone()).unwrap();
 
-		for _ in 0 .. l {
-			staking_ledger.unlocking.try_push(unlock_chunk.clone()).unwrap()
+		// every chunk is removed from the unbonding queue of its own era
+		for era in 1 ..= l {
+			staking_ledger.unlocking.try_push(UnlockChunk { value, era }).unwrap();
+			Staking::<T>::enqueue_unbonding(0, era, value);
 		}
 		Ledger::<T>::insert(controller.clone(), staking_ledger.clone());
 		let original_bonded: BalanceOf<T> = staking_ledger.active;
@@ -119,6 +119,11 @@
 //! biggest stakers can claim their reward. This is to limit the i/o cost to mutate storage for each
 //! nominator's account.
 //!
+//! If [`Config::MaxAutoPayoutsPerBlock`] is not zero, the validators of ended eras are also paid
+//! out automatically, oldest era first and a bounded number per block, with the weight left at the
+//! end of blocks. Eras which were not paid out before they fell out of [`Config::HistoryDepth`]
+//! are skipped.
+//!
 //! Slashing can occur at any point in time, once misbehavior is reported. Once slashing is
 //! determined, a value is deducted from the balance of the validator and all the nominators who
 //! voted for this validator (values are deducted from the _stash_ account of the slashed entity).
@@ -240,6 +245,8 @@
 //! - Controller account, (obviously) not increasing the staked value.
 //! - Stash account, not increasing the staked value.
 //! - Stash account, also increasing the staked value.
+//! - Stash account for a part of the reward, also increasing the staked value, and another account
+//!   for the rest.
 //!
 //! ### Additional Fund Management Operations
 //!
@@ -247,11 +254,13 @@
 //!
 //! The controller account can free a portion (or all) of the funds using the
 //! [`unbond`](Call::unbond) call. Note that the funds are not immediately
-//! accessible. Instead, a duration denoted by
-//! [`Config::BondingDuration`] (in number of eras) must
-//! pass until the funds can actually be removed. Once the `BondingDuration` is over, the
+//! accessible. Instead, a number of eras must pass until the funds can actually be removed. This
+//! unbonding duration ranges from [`Config::MinUnbondingDuration`] to
+//! [`Config::BondingDuration`], depending on the fraction of the total stake which is already
+//! unbonding, as tracked by the [`UnbondingQueue`]. Once the unbonding duration is over, the
 //! [`withdraw_unbonded`](Call::withdraw_unbonded) call can be used to actually
-//! withdraw the funds.
+//! withdraw the funds. Funds a deferred slash can reach remain locked until the era after the slash
+//! is applied.
 //!
 //! Note that there is a limitation to the number of fund-chunks that can be scheduled to be
 //! unlocked in the future via [`unbond`](Call::unbond). In case this maximum
@@ -312,11 +321,13 @@ use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Convert, Saturating, StaticLookup, Zero},
 	Perbill, Perquintill, Rounding, RuntimeDebug,
 };
-pub use sp_staking::StakerStatus;
 use sp_staking::{
 	offence::{Offence, OffenceError, ReportOffence},
 	EraIndex, OnStakingUpdate, SessionIndex,
 };
+pub use sp_staking::{
+	EraValidator, PendingReward, StakerExposure, StakerStatus, UnbondingChunk, ValidatorExposure,
+};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 pub use weights::WeightInfo;
 
@@ -396,6 +407,14 @@ pub enum RewardDestination<AccountId> {
 	Account(AccountId),
 	/// Receive no reward.
 	None,
+	/// Pay the `staked` part into the stash account, increasing the amount at stake accordingly,
+	/// and the rest into `account`.
+	Split {
+		/// The part of the reward which is staked.
+		staked: Perbill,
+		/// The account the rest of the reward is paid into.
+		account: AccountId,
+	},
 }
 
 impl<AccountId> Default for RewardDestination<AccountId> {
@@ -512,6 +531,7 @@ impl<T: Config> StakingLedger<T> {
 			if unlocking_balance + last.value <= value {
 				unlocking_balance += last.value;
 				self.active += last.value;
+				<Pallet<T>>::dequeue_unbonding(last.era, last.value);
 				self.unlocking.pop();
 			} else {
 				let diff = value - unlocking_balance;
@@ -519,6 +539,7 @@ impl<T: Config> StakingLedger<T> {
 				unlocking_balance += diff;
 				self.active += diff;
 				last.value -= diff;
+				<Pallet<T>>::dequeue_unbonding(last.era, diff);
 			}
 
 			if unlocking_balance >= value {
@@ -535,8 +556,9 @@ impl<T: Config> StakingLedger<T> {
 	/// such:
 	///
 	/// - If any unlocking chunks exist that are scheduled to be unlocked at `slash_era +
-	///   bonding_duration` and onwards, the slash is divided equally between the active ledger and
-	///   the unlocking chunks.
+	///   min_unbonding_duration` and onwards, the slash is divided equally between the active
+	///   ledger and the unlocking chunks. As the unbonding duration is dynamic, these are all the
+	///   chunks which may have been unbonded at or after `slash_era`.
 	/// - If no such chunks exist, then only the active balance is slashed.
 	///
 	/// Note that the above is only a *preference*. If for any reason the active ledger, with or
@@ -567,8 +589,9 @@ impl<T: Config> StakingLedger<T> {
 		let pre_slash_total = self.total;
 
 		// for a `slash_era = x`, any chunk that is scheduled to be unlocked at era `x + 28`
-		// (assuming 28 is the bonding duration) onwards should be slashed.
-		let slashable_chunks_start = slash_era + T::BondingDuration::get();
+		// (assuming 28 is the minimum unbonding duration) onwards should be slashed.
+		let slashable_chunks_start =
+			slash_era + T::MinUnbondingDuration::get().min(T::BondingDuration::get());
 
 		// `Some(ratio)` if this is proportional, with `ratio`, `None` otherwise. In both cases, we
 		// slash first the active chunk, and then `slash_chunks_priority`.
@@ -656,7 +679,9 @@ impl<T: Config> StakingLedger<T> {
 			}
 
 			if let Some(chunk) = self.unlocking.get_mut(i).defensive() {
+				let pre_slash_value = chunk.value;
 				slash_out_of(&mut chunk.value, &mut remaining_slash);
+				<Pallet<T>>::dequeue_unbonding(chunk.era, pre_slash_value - chunk.value);
 				// write the new slashed value of this chunk to the map.
 				slashed_unlocking.insert(chunk.era, chunk.value);
 			} else {
@@ -58,6 +58,70 @@ impl Default for ObsoleteReleases {
 #[storage_alias]
 type StorageVersion<T: Config> = StorageValue<Pallet<T>, ObsoleteReleases, ValueQuery>;
 
+pub mod v14 {
+	use super::*;
+	use frame_support::{
+		migrations::{SteppedMigration, SteppedMigrationError},
+		weights::WeightMeter,
+	};
+
+	/// Populate the `UnbondingQueue` and `TotalUnbonding` from the unlocking chunks of all
+	/// ledgers.
+	///
+	/// This is a [`SteppedMigration`], run by `pallet-migrations` over as many blocks as the number
+	/// of ledgers requires. Its cursor is the controller of the last ledger migrated.
+	pub struct MigrateToV14<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> SteppedMigration for MigrateToV14<T> {
+		type Cursor = T::AccountId;
+		type Identifier = [u8; 18];
+
+		fn id() -> Self::Identifier {
+			*b"pallet-staking-v14"
+		}
+
+		fn step(
+			mut cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			// Reading the storage version and the current era, and writing the version once done.
+			let base = T::DbWeight::get().reads_writes(2, 1);
+			// Reading the next key and the ledger, and queueing each of its unlocking chunks.
+			let chunks = T::MaxUnlockingChunks::get() as u64;
+			let per_ledger = T::DbWeight::get().reads_writes(2 + 2 * chunks, 2 * chunks);
+			let required = base.saturating_add(per_ledger);
+			if !meter.can_accrue(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required })
+			}
+			meter.defensive_saturating_accrue(base);
+			if Pallet::<T>::on_chain_storage_version() != 13 {
+				log!(warn, "Skipping v14, should be removed");
+				return Ok(None)
+			}
+
+			// Chunks of eras which already started are unlocked, and not queued.
+			let current_era = CurrentEra::<T>::get().unwrap_or(0);
+			let mut controllers = match cursor.as_ref() {
+				Some(controller) =>
+					Ledger::<T>::iter_keys_from(Ledger::<T>::hashed_key_for(controller)),
+				None => Ledger::<T>::iter_keys(),
+			};
+			while meter.check_accrue(per_ledger) {
+				let Some(controller) = controllers.next() else {
+					Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+					log!(info, "v14 applied successfully");
+					return Ok(None)
+				};
+				let ledger = Ledger::<T>::get(&controller).ok_or(SteppedMigrationError::Failed)?;
+				for chunk in ledger.unlocking {
+					Pallet::<T>::enqueue_unbonding(current_era, chunk.era, chunk.value);
+				}
+				cursor = Some(controller);
+			}
+			Ok(cursor)
+		}
+	}
+}
+
 pub mod v13 {
 	use super::*;
 
@@ -232,6 +232,8 @@ parameter_types! {
 	pub static MaxNominations: u32 = 16;
 	pub static HistoryDepth: u32 = 80;
 	pub static MaxUnlockingChunks: u32 = 32;
+	pub static MaxAutoPayoutsPerBlock: u32 = 0;
+	pub static MinUnbondingDuration: EraIndex = 3;
 	pub static RewardOnUnbalanceWasCalled: bool = false;
 	pub static MaxWinners: u32 = 100;
 }
@@ -295,10 +297,12 @@ impl crate::pallet::pallet::Config for Test {
 	type SlashDeferDuration = SlashDeferDuration;
 	type AdminOrigin = EnsureOneOrRoot;
 	type BondingDuration = BondingDuration;
+	type MinUnbondingDuration = MinUnbondingDuration;
 	type SessionInterface = Self;
 	type EraPayout = ConvertCurve<RewardCurve>;
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = ConstU32<64>;
+	type MaxAutoPayoutsPerBlock = MaxAutoPayoutsPerBlock;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
 	type ElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	type GenesisElectionProvider = Self::ElectionProvider;
@@ -36,7 +36,7 @@ use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::historical;
 use sp_runtime::{
 	traits::{Bounded, Convert, One, SaturatedConversion, Saturating, StaticLookup, Zero},
-	Perbill,
+	FixedPointNumber, FixedU128, Perbill,
 };
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
@@ -46,9 +46,10 @@ use sp_staking::{
 use sp_std::prelude::*;
 
 use crate::{
-	log, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, EraPayout, Exposure, ExposureOf,
-	Forcing, IndividualExposure, MaxWinnersOf, Nominations, PositiveImbalanceOf, RewardDestination,
-	SessionInterface, StakingLedger, ValidatorPrefs,
+	inflation, log, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, EraPayout,
+	EraValidator, Exposure, ExposureOf, Forcing, IndividualExposure, MaxWinnersOf, Nominations,
+	PendingReward, PositiveImbalanceOf, RewardDestination, SessionInterface, StakerExposure,
+	StakingLedger, UnbondingChunk, UnlockChunk, ValidatorExposure, ValidatorPrefs,
 };
 
 use super::{pallet::*, STAKING_ID};
@@ -190,40 +191,13 @@ impl<T: Config> Pallet<T> {
 
 		<Ledger<T>>::insert(&controller, &ledger);
 
-		// Get Era reward points. It has TOTAL and INDIVIDUAL
-		// Find the fraction of the era reward that belongs to the validator
-		// Take that fraction of the eras rewards to split to nominator and validator
-		//
-		// Then look at the validator, figure out the proportion of their reward
-		// which goes to them and each of their nominators.
-
-		let era_reward_points = <ErasRewardPoints<T>>::get(&era);
-		let total_reward_points = era_reward_points.total;
-		let validator_reward_points = era_reward_points
-			.individual
-			.get(&ledger.stash)
-			.copied()
-			.unwrap_or_else(Zero::zero);
-
 		// Nothing to do if they have no reward points.
-		if validator_reward_points.is_zero() {
+		let Some((validator_commission_payout, validator_leftover_payout)) =
+			Self::validator_era_payout(era, era_payout, &ledger.stash)
+		else {
 			return Ok(Some(T::WeightInfo::payout_stakers_alive_staked(0)).into())
-		}
-
-		// This is the fraction of the total reward that the validator and the
-		// nominators will get.
-		let validator_total_reward_part =
-			Perbill::from_rational(validator_reward_points, total_reward_points);
-
-		// This is how much validator + nominators are entitled to.
-		let validator_total_payout = validator_total_reward_part * era_payout;
-
-		let validator_prefs = Self::eras_validator_prefs(&era, &validator_stash);
-		// Validator first gets a cut off the top.
-		let validator_commission = validator_prefs.commission;
-		let validator_commission_payout = validator_commission * validator_total_payout;
+		};
 
-		let validator_leftover_payout = validator_total_payout - validator_commission_payout;
 		// Now let's calculate how this is split to the validator.
 		let validator_exposure_part = Perbill::from_rational(exposure.own, exposure.total);
 		let validator_staking_payout = validator_exposure_part * validator_leftover_payout;
@@ -273,6 +247,117 @@ impl<T: Config> Pallet<T> {
 		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count)).into())
 	}
 
+	/// The part of the `era_payout` of an era which goes to a validator and its nominators, as
+	/// the validator's commission and the leftover to split by the exposure of each staker.
+	///
+	/// Returns `None` if the validator earned no reward points in the era.
+	pub(crate) fn validator_era_payout(
+		era: EraIndex,
+		era_payout: BalanceOf<T>,
+		validator_stash: &T::AccountId,
+	) -> Option<(BalanceOf<T>, BalanceOf<T>)> {
+		// Get Era reward points. It has TOTAL and INDIVIDUAL
+		// Find the fraction of the era reward that belongs to the validator
+		// Take that fraction of the eras rewards to split to nominator and validator
+		let era_reward_points = <ErasRewardPoints<T>>::get(&era);
+		let total_reward_points = era_reward_points.total;
+		let validator_reward_points = era_reward_points
+			.individual
+			.get(validator_stash)
+			.copied()
+			.unwrap_or_else(Zero::zero);
+
+		if validator_reward_points.is_zero() {
+			return None
+		}
+
+		// This is the fraction of the total reward that the validator and the
+		// nominators will get.
+		let validator_total_reward_part =
+			Perbill::from_rational(validator_reward_points, total_reward_points);
+
+		// This is how much validator + nominators are entitled to.
+		let validator_total_payout = validator_total_reward_part * era_payout;
+
+		let validator_prefs = Self::eras_validator_prefs(&era, validator_stash);
+		// Validator first gets a cut off the top.
+		let validator_commission = validator_prefs.commission;
+		let validator_commission_payout = validator_commission * validator_total_payout;
+
+		let validator_leftover_payout = validator_total_payout - validator_commission_payout;
+		Some((validator_commission_payout, validator_leftover_payout))
+	}
+
+	/// Pay out the validators of the ended eras which can still be paid out, oldest first, at
+	/// most `T::MaxAutoPayoutsPerBlock` of them and within `remaining_weight`, resuming from where
+	/// the previous block left off.
+	///
+	/// Returns the weight consumed.
+	pub(crate) fn do_auto_payout(remaining_weight: Weight) -> Weight {
+		let max_payouts = T::MaxAutoPayoutsPerBlock::get();
+		let mut consumed = T::DbWeight::get().reads(3);
+		if max_payouts.is_zero() || remaining_weight.any_lt(consumed) {
+			return Weight::zero()
+		}
+
+		let last_era = match Self::active_era() {
+			Some(active_era) if !active_era.index.is_zero() => active_era.index - 1,
+			_ => return consumed,
+		};
+		// eras older than this can't be paid out anymore, see `do_payout_stakers`
+		let oldest_era =
+			Self::current_era().unwrap_or(last_era).saturating_sub(T::HistoryDepth::get());
+
+		let (era, last_key) = match AutoPayoutCursor::<T>::get() {
+			Some((era, None)) => (era.saturating_add(1), None),
+			Some((era, last_key)) => (era, last_key),
+			None => (oldest_era, None),
+		};
+		let (mut era, last_key) =
+			if era < oldest_era { (oldest_era, None) } else { (era, last_key) };
+		if era > last_era {
+			return consumed
+		}
+
+		let mut validators = match last_key {
+			Some(last_key) => <ErasStakersClipped<T>>::iter_prefix_from(era, last_key),
+			None => <ErasStakersClipped<T>>::iter_prefix(era),
+		};
+
+		// the cursor is written once all payouts are done
+		consumed.saturating_accrue(T::DbWeight::get().writes(1));
+		let payout_weight =
+			T::WeightInfo::payout_stakers_alive_staked(T::MaxNominatorRewardedPerValidator::get());
+
+		let mut payouts = 0;
+		let cursor = loop {
+			if payouts >= max_payouts ||
+				remaining_weight.any_lt(consumed.saturating_add(payout_weight))
+			{
+				break (era, Some(validators.last_raw_key().to_vec()))
+			}
+			match validators.next() {
+				Some((validator, _exposure)) => {
+					// validators which were already paid out are skipped by `do_payout_stakers`
+					let actual_weight = match Self::do_payout_stakers(validator, era) {
+						Ok(post_info) => post_info.actual_weight,
+						Err(error) => error.post_info.actual_weight,
+					};
+					consumed.saturating_accrue(actual_weight.unwrap_or(payout_weight));
+					payouts += 1;
+				},
+				None if era < last_era => {
+					era += 1;
+					validators = <ErasStakersClipped<T>>::iter_prefix(era);
+				},
+				None => break (era, None),
+			}
+		};
+
+		AutoPayoutCursor::<T>::put(cursor);
+		consumed
+	}
+
 	/// Update the ledger for a controller.
 	///
 	/// This will also update the stash lock.
@@ -298,21 +383,38 @@ impl<T: Config> Pallet<T> {
 			RewardDestination::Controller => Self::bonded(stash)
 				.map(|controller| T::Currency::deposit_creating(&controller, amount)),
 			RewardDestination::Stash => T::Currency::deposit_into_existing(stash, amount).ok(),
-			RewardDestination::Staked => Self::bonded(stash)
-				.and_then(|c| Self::ledger(&c).map(|l| (c, l)))
-				.and_then(|(controller, mut l)| {
-					l.active += amount;
-					l.total += amount;
-					let r = T::Currency::deposit_into_existing(stash, amount).ok();
-					Self::update_ledger(&controller, &l);
-					r
-				}),
+			RewardDestination::Staked => Self::make_staked_payout(stash, amount),
 			RewardDestination::Account(dest_account) =>
 				Some(T::Currency::deposit_creating(&dest_account, amount)),
 			RewardDestination::None => None,
+			RewardDestination::Split { staked, account } => {
+				let staked_amount = staked * amount;
+				let mut imbalance =
+					T::Currency::deposit_creating(&account, amount.saturating_sub(staked_amount));
+				if let Some(staked_imbalance) = Self::make_staked_payout(stash, staked_amount) {
+					imbalance.subsume(staked_imbalance);
+				}
+				Some(imbalance)
+			},
 		}
 	}
 
+	/// Pay `amount` into `stash`, increasing the amount at stake accordingly.
+	fn make_staked_payout(
+		stash: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Option<PositiveImbalanceOf<T>> {
+		Self::bonded(stash).and_then(|c| Self::ledger(&c).map(|l| (c, l))).and_then(
+			|(controller, mut l)| {
+				l.active += amount;
+				l.total += amount;
+				let r = T::Currency::deposit_into_existing(stash, amount).ok();
+				Self::update_ledger(&controller, &l);
+				r
+			},
+		)
+	}
+
 	/// Plan a new session potentially trigger a new era.
 	fn new_session(
 		session_index: SessionIndex,
@@ -489,6 +591,10 @@ impl<T: Config> Pallet<T> {
 		});
 		ErasStartSessionIndex::<T>::insert(&new_planned_era, &start_session_index);
 
+		// Funds scheduled to be unlocked at the new era can now be withdrawn.
+		let unlocked = UnbondingQueue::<T>::take(new_planned_era);
+		TotalUnbonding::<T>::mutate(|total| *total = total.saturating_sub(unlocked));
+
 		// Clean old era information.
 		if let Some(old_era) = new_planned_era.checked_sub(T::HistoryDepth::get() + 1) {
 			Self::clear_era_information(old_era);
@@ -663,7 +769,11 @@ impl<T: Config> Pallet<T> {
 		slashing::clear_stash_metadata::<T>(stash, num_slashing_spans)?;
 
 		<Bonded<T>>::remove(stash);
-		<Ledger<T>>::remove(&controller);
+		if let Some(ledger) = <Ledger<T>>::take(&controller) {
+			for chunk in ledger.unlocking {
+				Self::dequeue_unbonding(chunk.era, chunk.value);
+			}
+		}
 
 		<Payee<T>>::remove(stash);
 		Self::do_remove_validator(stash);
@@ -674,6 +784,99 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The number of eras funds unbonded now, along with `value` more, remain locked for.
+	///
+	/// This scales from `MinUnbondingDuration` up to `BondingDuration` with the fraction of the
+	/// total stake of the active era which is unbonding.
+	pub fn unbonding_duration(value: BalanceOf<T>) -> EraIndex {
+		let max = T::BondingDuration::get();
+		let min = T::MinUnbondingDuration::get().min(max);
+		let total_stake = Self::active_era()
+			.map(|era| Self::eras_total_stake(era.index))
+			.unwrap_or_default();
+		if total_stake.is_zero() {
+			return max
+		}
+
+		let unbonding = Self::total_unbonding().saturating_add(value);
+		min + Perbill::from_rational(unbonding, total_stake).mul_ceil(max - min)
+	}
+
+	/// Add `value` to the unbonding queue, to be unlocked at `era`.
+	///
+	/// Nothing is added if `era` is not after `current_era`, as the funds are already unlocked.
+	pub(crate) fn enqueue_unbonding(current_era: EraIndex, era: EraIndex, value: BalanceOf<T>) {
+		if era <= current_era || value.is_zero() {
+			return
+		}
+
+		UnbondingQueue::<T>::mutate(era, |queued| *queued = queued.saturating_add(value));
+		TotalUnbonding::<T>::mutate(|total| *total = total.saturating_add(value));
+	}
+
+	/// Remove `value` from the unbonding queue of `era`, as it was rebonded, slashed or removed.
+	///
+	/// This is a no-op if the funds of `era` were already unlocked.
+	pub(crate) fn dequeue_unbonding(era: EraIndex, value: BalanceOf<T>) {
+		UnbondingQueue::<T>::mutate_exists(era, |maybe_queued| {
+			if let Some(queued) = maybe_queued {
+				let removed = value.min(*queued);
+				*queued -= removed;
+				TotalUnbonding::<T>::mutate(|total| *total = total.saturating_sub(removed));
+				if queued.is_zero() {
+					*maybe_queued = None;
+				}
+			}
+		});
+	}
+
+	/// Keep the unlocking chunks of `stash` which a slash of `slash_era` can reach locked until
+	/// the era after the slash is applied, in `apply_era`.
+	///
+	/// Returns the number of storage reads and writes.
+	pub(crate) fn hold_unlocking_for_slash(
+		stash: &T::AccountId,
+		slash_era: EraIndex,
+		apply_era: EraIndex,
+	) -> (u64, u64) {
+		let Some(controller) = Self::bonded(stash) else { return (1, 0) };
+		let Some(mut ledger) = Self::ledger(&controller) else { return (2, 0) };
+
+		// the same chunks `StakingLedger::slash` slashes
+		let slashable_chunks_start =
+			slash_era.saturating_add(T::MinUnbondingDuration::get().min(T::BondingDuration::get()));
+		let unlock_era = apply_era.saturating_add(1);
+		let (mut held, mut held_chunks) = (BalanceOf::<T>::zero(), 0);
+		let mut unlocking = Vec::with_capacity(ledger.unlocking.len());
+		for chunk in ledger.unlocking {
+			if chunk.era >= slashable_chunks_start && chunk.era < unlock_era {
+				Self::dequeue_unbonding(chunk.era, chunk.value);
+				held = held.saturating_add(chunk.value);
+				held_chunks += 1;
+			} else {
+				unlocking.push(chunk);
+			}
+		}
+		if held_chunks == 0 {
+			return (2, 0)
+		}
+
+		// `unlocking` stays sorted by era, with one chunk per era.
+		let index = unlocking.partition_point(|chunk| chunk.era < unlock_era);
+		match unlocking.get_mut(index).filter(|chunk| chunk.era == unlock_era) {
+			Some(chunk) => chunk.value = chunk.value.saturating_add(held),
+			None => unlocking.insert(index, UnlockChunk { value: held, era: unlock_era }),
+		}
+		ledger.unlocking = unlocking
+			.try_into()
+			.expect("held chunks are replaced by at most one chunk; qed");
+
+		let current_era = Self::current_era().unwrap_or(0);
+		Self::enqueue_unbonding(current_era, unlock_era, held);
+		Self::update_ledger(&controller, &ledger);
+		(7 + 2 * held_chunks, 4 + 2 * held_chunks)
+	}
+
 	/// Clear all era information for given era.
 	pub(crate) fn clear_era_information(era_index: EraIndex) {
 		let mut cursor = <ErasStakers<T>>::clear_prefix(era_index, u32::MAX, None);
@@ -992,6 +1195,183 @@ impl<T: Config> Pallet<T> {
 	pub fn api_nominations_quota(_balance: BalanceOf<T>) -> u32 {
 		T::MaxNominations::get()
 	}
+
+	/// Returns the rewards of `stash` which are still claimable through `payout_stakers`, for
+	/// each era and validator.
+	///
+	/// Used by the runtime API.
+	pub fn api_pending_rewards(
+		stash: T::AccountId,
+	) -> Vec<PendingReward<T::AccountId, BalanceOf<T>>> {
+		let (Some(current_era), Some(active_era)) = (Self::current_era(), Self::active_era())
+		else {
+			return Vec::new()
+		};
+
+		let mut rewards = Vec::new();
+		let first_era = current_era.saturating_sub(T::HistoryDepth::get());
+		for era in first_era..active_era.index {
+			let Some(era_payout) = <ErasValidatorReward<T>>::get(&era) else { continue };
+
+			for (validator, exposure) in <ErasStakersClipped<T>>::iter_prefix(era) {
+				let stake = if validator == stash {
+					exposure.own
+				} else if let Some(nominator) = exposure.others.iter().find(|n| n.who == stash) {
+					nominator.value
+				} else {
+					continue
+				};
+
+				let claimed = Self::bonded(&validator)
+					.and_then(Self::ledger)
+					.map_or(true, |ledger| ledger.claimed_rewards.contains(&era));
+				if claimed {
+					continue
+				}
+
+				let Some((commission_payout, leftover_payout)) =
+					Self::validator_era_payout(era, era_payout, &validator)
+				else {
+					continue
+				};
+
+				let mut amount = Perbill::from_rational(stake, exposure.total) * leftover_payout;
+				if validator == stash {
+					amount += commission_payout;
+				}
+				rewards.push(PendingReward { era, validator, amount });
+			}
+		}
+		rewards
+	}
+
+	/// Returns the stake of `stash` exposed in the active era, as a validator and as a nominator.
+	///
+	/// Used by the runtime API.
+	pub fn api_exposure(stash: T::AccountId) -> Option<StakerExposure<T::AccountId, BalanceOf<T>>> {
+		let era = Self::active_era()?.index;
+
+		let mut validator = None;
+		let mut nominations = Vec::new();
+		let mut validator_totals = Vec::new();
+		for (who, exposure) in <ErasStakers<T>>::iter_prefix(era) {
+			if who == stash {
+				validator = Some(ValidatorExposure {
+					total: exposure.total,
+					own: exposure.own,
+					nominator_count: exposure.others.len() as u32,
+					rank: 0,
+				});
+			} else if let Some(nominator) = exposure.others.iter().find(|n| n.who == stash) {
+				nominations.push((who.clone(), nominator.value));
+			}
+			validator_totals.push(exposure.total);
+		}
+
+		if let Some(validator) = validator.as_mut() {
+			let more_backed =
+				validator_totals.iter().filter(|total| **total > validator.total).count();
+			validator.rank = more_backed as u32 + 1;
+		}
+
+		(validator.is_some() || !nominations.is_empty()).then(|| StakerExposure {
+			era,
+			validator,
+			nominations,
+		})
+	}
+
+	/// Returns the chunks of the stake of `stash` being unbonded, with the era at which each of
+	/// them can be withdrawn.
+	///
+	/// Used by the runtime API.
+	pub fn api_unbonding(stash: T::AccountId) -> Vec<UnbondingChunk<BalanceOf<T>>> {
+		Self::bonded(&stash)
+			.and_then(Self::ledger)
+			.map(|ledger| {
+				ledger
+					.unlocking
+					.into_iter()
+					.map(|chunk| UnbondingChunk { value: chunk.value, era: chunk.era })
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Returns the amount of stake scheduled to be unlocked at each upcoming era, sorted by era.
+	///
+	/// Used by the runtime API.
+	pub fn api_unbonding_queue() -> Vec<(EraIndex, BalanceOf<T>)> {
+		let mut queue = UnbondingQueue::<T>::iter().collect::<Vec<_>>();
+		queue.sort_by_key(|(era, _)| *era);
+		queue
+	}
+
+	/// Returns the estimated annual percentage yield of staking in the active era, assuming eras
+	/// of `era_duration_millis` and that rewards are restaked every era.
+	///
+	/// This is derived from the `T::EraPayout` of the active era stake and total issuance, and
+	/// does not account for validator commissions.
+	///
+	/// Used by the runtime API.
+	pub fn api_estimated_apy(era_duration_millis: u64) -> Perbill {
+		let total_staked = Self::active_era()
+			.map(|era| Self::eras_total_stake(era.index))
+			.unwrap_or_default();
+		if total_staked.is_zero() || era_duration_millis.is_zero() {
+			return Perbill::zero()
+		}
+
+		let (staker_payout, _rest) = T::EraPayout::era_payout(
+			total_staked,
+			T::Currency::total_issuance(),
+			era_duration_millis,
+		);
+		let era_yield = FixedU128::saturating_from_rational(staker_payout, total_staked);
+		let eras_per_year =
+			(inflation::MILLISECONDS_PER_YEAR / era_duration_millis).saturated_into();
+
+		let annual_yield = (FixedU128::one() + era_yield)
+			.saturating_pow(eras_per_year)
+			.saturating_sub(FixedU128::one());
+		Perbill::from_parts(
+			annual_yield
+				.saturating_mul_int(Perbill::ACCURACY as u128)
+				.min(Perbill::ACCURACY as u128) as u32,
+		)
+	}
+
+	/// Returns the validators of `era`, or of the active era if `None`, with their backing,
+	/// commission and reward points.
+	///
+	/// Used by the runtime API.
+	pub fn api_era_validators(
+		era: Option<EraIndex>,
+	) -> Vec<EraValidator<T::AccountId, BalanceOf<T>>> {
+		let Some(era) = era.or_else(|| Self::active_era().map(|era| era.index)) else {
+			return Vec::new()
+		};
+
+		let reward_points = <ErasRewardPoints<T>>::get(&era);
+		<ErasStakers<T>>::iter_prefix(era)
+			.map(|(stash, exposure)| {
+				let prefs = Self::eras_validator_prefs(&era, &stash);
+				EraValidator {
+					total: exposure.total,
+					own: exposure.own,
+					nominator_count: exposure.others.len() as u32,
+					commission: prefs.commission,
+					blocked: prefs.blocked,
+					reward_points: reward_points
+						.individual
+						.get(&stash)
+						.copied()
+						.unwrap_or_default(),
+					stash,
+				}
+			})
+			.collect()
+	}
 }
 
 impl<T: Config> ElectionDataProvider for Pallet<T> {
@@ -1377,10 +1757,19 @@ where
 						active_era,
 						slash_era + slash_defer_duration + 1,
 					);
-					UnappliedSlashes::<T>::mutate(
-						slash_era.saturating_add(slash_defer_duration).saturating_add(One::one()),
-						move |for_later| for_later.push(unapplied),
-					);
+					let apply_era =
+						slash_era.saturating_add(slash_defer_duration).saturating_add(One::one());
+					// the slashed stake can't be withdrawn before the slash is applied
+					for stash in sp_std::iter::once(&unapplied.validator)
+						.chain(unapplied.others.iter().map(|(stash, _)| stash))
+					{
+						let (reads, writes) =
+							Self::hold_unlocking_for_slash(stash, slash_era, apply_era);
+						add_db_reads_writes(reads, writes);
+					}
+					UnappliedSlashes::<T>::mutate(apply_era, move |for_later| {
+						for_later.push(unapplied)
+					});
 					add_db_reads_writes(1, 1);
 				}
 			} else {
@@ -1730,6 +2119,7 @@ impl<T: Config> Pallet<T> {
 		Self::check_nominators()?;
 		Self::check_exposures()?;
 		Self::check_ledgers()?;
+		Self::check_unbonding_queue()?;
 		Self::check_count()
 	}
 
@@ -1758,6 +2148,30 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	fn check_unbonding_queue() -> Result<(), TryRuntimeError> {
+		// ensures the unbonding queue matches the unlocking chunks of all ledgers.
+		let current_era = Self::current_era().unwrap_or(0);
+		let mut expected =
+			sp_std::collections::btree_map::BTreeMap::<EraIndex, BalanceOf<T>>::new();
+		for ledger in Ledger::<T>::iter_values() {
+			for chunk in ledger.unlocking.into_iter().filter(|chunk| chunk.era > current_era) {
+				let queued = expected.entry(chunk.era).or_default();
+				*queued = queued.saturating_add(chunk.value);
+			}
+		}
+
+		let mut total = BalanceOf::<T>::zero();
+		for (era, queued) in UnbondingQueue::<T>::iter() {
+			ensure!(era > current_era, "unlocked era in unbonding queue");
+			ensure!(expected.remove(&era) == Some(queued), "wrong unbonding queue amount");
+			total = total.saturating_add(queued);
+		}
+		ensure!(expected.is_empty(), "unlocking chunk missing from unbonding queue");
+		ensure!(total == Self::total_unbonding(), "wrong total unbonding");
+
+		Ok(())
+	}
+
 	fn check_exposures() -> Result<(), TryRuntimeError> {
 		// a check per validator to ensure the exposure struct is always sane.
 		let era = Self::active_era().unwrap().index;
@@ -66,7 +66,7 @@ pub mod pallet {
 	use super::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(13);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(14);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -187,6 +187,21 @@ pub mod pallet {
 		#[pallet::constant]
 		type SlashDeferDuration: Get<EraIndex>;
 
+		/// Minimum number of eras that unbonded funds must remain locked for.
+		///
+		/// The unbonding duration of new unbonding requests scales from this value up to
+		/// `BondingDuration` with the fraction of the total stake which is already unbonding. If
+		/// this is equal to `BondingDuration`, unbonding always takes `BondingDuration` eras.
+		///
+		/// Offences can be reported up to `BondingDuration` eras after they happened. When a
+		/// slash is deferred, the unlocking chunks it can slash are held until the era after it is
+		/// applied, but funds withdrawn before the offence is reported can't be slashed anymore.
+		///
+		/// This should be greater than the slash defer duration and no more than the bonding
+		/// duration.
+		#[pallet::constant]
+		type MinUnbondingDuration: Get<EraIndex>;
+
 		/// The origin which can manage less critical staking parameters that does not require root.
 		///
 		/// Supported actions: (1) cancel deferred slash, (2) set minimum commission.
@@ -210,6 +225,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxNominatorRewardedPerValidator: Get<u32>;
 
+		/// The maximum number of validators of the ended eras to pay out automatically in each
+		/// block, using the weight left in `on_idle`.
+		///
+		/// Automatic payouts are disabled if this is zero, in which case rewards are only paid out
+		/// through `payout_stakers`.
+		#[pallet::constant]
+		type MaxAutoPayoutsPerBlock: Get<u32>;
+
 		/// The fraction of the validator set that is safe to be offending.
 		/// After the threshold is reached a new era will be forced.
 		type OffendingValidatorsThreshold: Get<Perbill>;
@@ -481,6 +504,20 @@ pub mod pallet {
 	pub type ErasTotalStake<T: Config> =
 		StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>, ValueQuery>;
 
+	/// The amount of stake scheduled to be unlocked at the start of each era.
+	///
+	/// Entries are removed once their era becomes the current era, as the stake is then free to
+	/// be withdrawn.
+	#[pallet::storage]
+	#[pallet::getter(fn unbonding_queue)]
+	pub type UnbondingQueue<T: Config> =
+		StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>, ValueQuery>;
+
+	/// The total amount of stake in the `UnbondingQueue`.
+	#[pallet::storage]
+	#[pallet::getter(fn total_unbonding)]
+	pub type TotalUnbonding<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
 	/// Mode of era forcing.
 	#[pallet::storage]
 	#[pallet::getter(fn force_era)]
@@ -581,6 +618,16 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ChillThreshold<T: Config> = StorageValue<_, Percent, OptionQuery>;
 
+	/// The progress of the automatic payout of the ended eras.
+	///
+	/// This is the era being paid out, and the raw storage key in `ErasStakersClipped` of the last
+	/// validator paid out, or `None` once all of its validators were paid out. Eras are paid out
+	/// oldest first, starting from the oldest era within `HistoryDepth`.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub(crate) type AutoPayoutCursor<T: Config> =
+		StorageValue<_, (EraIndex, Option<Vec<u8>>), OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -780,6 +827,10 @@ pub mod pallet {
 			// `on_finalize` weight is tracked in `on_initialize`
 		}
 
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::do_auto_payout(remaining_weight)
+		}
+
 		fn integrity_test() {
 			// ensure that we funnel the correct value to the `DataProvider::MaxVotesPerVoter`;
 			assert_eq!(
@@ -796,14 +847,26 @@ pub mod pallet {
 			);
 
 			sp_std::if_std! {
-				sp_io::TestExternalities::new_empty().execute_with(||
+				sp_io::TestExternalities::new_empty().execute_with(|| {
 					assert!(
 						T::SlashDeferDuration::get() < T::BondingDuration::get() || T::BondingDuration::get() == 0,
 						"As per documentation, slash defer duration ({}) should be less than bonding duration ({}).",
 						T::SlashDeferDuration::get(),
 						T::BondingDuration::get(),
-					)
-				);
+					);
+					assert!(
+						T::MinUnbondingDuration::get() <= T::BondingDuration::get(),
+						"As per documentation, min unbonding duration ({}) should be no more than bonding duration ({}).",
+						T::MinUnbondingDuration::get(),
+						T::BondingDuration::get(),
+					);
+					assert!(
+						T::SlashDeferDuration::get() < T::MinUnbondingDuration::get() || T::MinUnbondingDuration::get() == 0,
+						"As per documentation, slash defer duration ({}) should be less than min unbonding duration ({}).",
+						T::SlashDeferDuration::get(),
+						T::MinUnbondingDuration::get(),
+					);
+				});
 			}
 		}
 
@@ -1008,7 +1071,11 @@ pub mod pallet {
 				ensure!(ledger.active >= min_active_bond, Error::<T>::InsufficientBond);
 
 				// Note: in case there is no current era it is fine to bond one era more.
-				let era = Self::current_era().unwrap_or(0) + T::BondingDuration::get();
+				let current_era = Self::current_era().unwrap_or(0);
+				// Funds never unlock before the ones which were unbonded earlier, so that
+				// `unlocking` stays sorted by era.
+				let era = (current_era + Self::unbonding_duration(value))
+					.max(ledger.unlocking.last().map_or(0, |chunk| chunk.era));
 				if let Some(chunk) = ledger.unlocking.last_mut().filter(|chunk| chunk.era == era) {
 					// To keep the chunk count down, we only keep one chunk per era. Since
 					// `unlocking` is a FiFo queue, if a chunk exists for `era` we know that it will
@@ -1020,6 +1087,7 @@ pub mod pallet {
 						.try_push(UnlockChunk { value, era })
 						.map_err(|_| Error::<T>::NoMoreChunks)?;
 				};
+				Self::enqueue_unbonding(current_era, era, value);
 				// NOTE: ledger must be updated prior to calling `Self::weight_of`.
 				Self::update_ledger(&controller, &ledger);
 
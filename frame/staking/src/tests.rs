@@ -4234,6 +4234,118 @@ fn payout_to_any_account_works() {
 	})
 }
 
+#[test]
+fn payout_split_between_stake_and_account_works() {
+	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+		bond_validator(11, 1000);
+		bond_nominator(1234, 100, vec![11]);
+		assert_ok!(Staking::set_payee(
+			RuntimeOrigin::signed(1234),
+			RewardDestination::Split { staked: Perbill::from_percent(60), account: 42 }
+		));
+
+		mock::start_active_era(1);
+		Staking::reward_by_ids(vec![(11, 1)]);
+		mock::start_active_era(2);
+
+		let reward = Staking::api_pending_rewards(1234)[0].amount;
+		assert!(reward > 0);
+		let init_stake = Staking::ledger(&1234).unwrap().active;
+		let init_balance = Balances::free_balance(1234);
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+
+		// 60% of the reward is staked, the rest paid into the account
+		let staked = Perbill::from_percent(60) * reward;
+		assert_eq!(Staking::ledger(&1234).unwrap().active, init_stake + staked);
+		assert_eq!(Balances::free_balance(1234), init_balance + staked);
+		assert_eq!(Balances::free_balance(42), reward - staked);
+		System::assert_has_event(RuntimeEvent::Staking(Event::Rewarded {
+			stash: 1234,
+			amount: reward,
+		}));
+	})
+}
+
+#[test]
+fn auto_payout_pays_previous_era_validators() {
+	ExtBuilder::default().build_and_execute(|| {
+		Pallet::<Test>::reward_by_ids(vec![(11, 1), (21, 1)]);
+		mock::start_active_era(1);
+		let unpaid = |validator| !Staking::api_pending_rewards(validator).is_empty();
+		assert!(unpaid(11) && unpaid(21));
+
+		// disabled by default
+		assert_eq!(
+			<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX),
+			Weight::zero()
+		);
+		assert!(unpaid(11) && unpaid(21));
+
+		MaxAutoPayoutsPerBlock::set(1);
+
+		// not enough weight for a payout
+		let payout_weight = <Test as Config>::WeightInfo::payout_stakers_alive_staked(64);
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), payout_weight);
+		assert!(unpaid(11) && unpaid(21));
+
+		// one validator is paid out per block
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+		assert!(unpaid(11) != unpaid(21));
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+		assert!(!unpaid(11) && !unpaid(21));
+		assert!(Staking::api_pending_rewards(101).is_empty());
+		assert!(AutoPayoutCursor::<Test>::get().unwrap().1.is_some());
+
+		// the end of the era is noted without paying anything else
+		let balance_11 = Balances::total_balance(&11);
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(AutoPayoutCursor::<Test>::get(), Some((0, None)));
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(Balances::total_balance(&11), balance_11);
+
+		// the next era is paid out once it ended
+		Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+		mock::start_active_era(2);
+		assert!(unpaid(11));
+		MaxAutoPayoutsPerBlock::set(3);
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+		assert!(!unpaid(11));
+		assert_eq!(AutoPayoutCursor::<Test>::get(), Some((1, None)));
+	})
+}
+
+#[test]
+fn auto_payout_catches_up_on_eras_within_history_depth() {
+	ExtBuilder::default().build_and_execute(|| {
+		let unpaid_eras = |validator| {
+			Staking::api_pending_rewards(validator)
+				.into_iter()
+				.map(|r| r.era)
+				.collect::<Vec<_>>()
+		};
+		let last_era = HistoryDepth::get() + 2;
+		for era in 0..=last_era {
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(era + 1);
+		}
+		// the first 3 eras fell out of the history depth
+		assert_eq!(unpaid_eras(11), (3..=last_era).collect::<Vec<_>>());
+
+		// the payouts start from the oldest era which can be paid out and carry on through the
+		// eras
+		let validators_per_era = ErasStakersClipped::<Test>::iter_prefix(3).count() as u32;
+		MaxAutoPayoutsPerBlock::set(validators_per_era + 1);
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+		assert!(matches!(AutoPayoutCursor::<Test>::get(), Some((4, Some(_)))));
+		assert!(unpaid_eras(11).iter().all(|&era| era >= 4));
+
+		MaxAutoPayoutsPerBlock::set(u32::MAX);
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(unpaid_eras(11), Vec::<EraIndex>::new());
+		assert_eq!(AutoPayoutCursor::<Test>::get(), Some((last_era, None)));
+	})
+}
+
 #[test]
 fn session_buffering_with_offset() {
 	// similar to live-chains, have some offset for the first session
@@ -5843,3 +5955,344 @@ mod staking_interface {
 		})
 	}
 }
+
+mod runtime_api {
+	use super::*;
+
+	#[test]
+	fn pending_rewards_match_payouts() {
+		ExtBuilder::default().nominate(true).build_and_execute(|| {
+			Payee::<Test>::insert(11, RewardDestination::Stash);
+			Payee::<Test>::insert(101, RewardDestination::Stash);
+
+			// nothing is claimable before the first era ends
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			assert!(Staking::api_pending_rewards(11).is_empty());
+
+			mock::start_active_era(1);
+
+			// 21 earned no points, so only the payout of 11 is pending
+			let validator_rewards = Staking::api_pending_rewards(11);
+			let nominator_rewards = Staking::api_pending_rewards(101);
+			assert_eq!(validator_rewards.len(), 1);
+			assert_eq!(nominator_rewards.len(), 1);
+			assert_eq!((validator_rewards[0].era, validator_rewards[0].validator), (0, 11));
+			assert_eq!((nominator_rewards[0].era, nominator_rewards[0].validator), (0, 11));
+			assert!(Staking::api_pending_rewards(21).is_empty());
+
+			let init_balance_11 = Balances::total_balance(&11);
+			let init_balance_101 = Balances::total_balance(&101);
+			assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 0));
+			assert_eq!(Balances::total_balance(&11), init_balance_11 + validator_rewards[0].amount);
+			assert_eq!(
+				Balances::total_balance(&101),
+				init_balance_101 + nominator_rewards[0].amount
+			);
+
+			// claimed rewards are not pending anymore
+			assert!(Staking::api_pending_rewards(11).is_empty());
+			assert!(Staking::api_pending_rewards(101).is_empty());
+		});
+	}
+
+	#[test]
+	fn exposure_and_era_validators_work() {
+		ExtBuilder::default().nominate(true).build_and_execute(|| {
+			mock::start_active_era(1);
+			Pallet::<Test>::reward_by_ids(vec![(21, 3)]);
+			assert_ok!(Staking::validate(
+				RuntimeOrigin::signed(11),
+				ValidatorPrefs { commission: Perbill::from_percent(10), blocked: false }
+			));
+
+			let exposure_11 = Staking::eras_stakers(1, 11);
+			let exposure_21 = Staking::eras_stakers(1, 21);
+			assert!(exposure_21.total > exposure_11.total);
+
+			let exposure = Staking::api_exposure(11).unwrap();
+			assert_eq!(exposure.era, 1);
+			assert_eq!(
+				exposure.validator,
+				Some(ValidatorExposure {
+					total: exposure_11.total,
+					own: exposure_11.own,
+					nominator_count: 1,
+					rank: 2,
+				})
+			);
+			assert!(exposure.nominations.is_empty());
+
+			let exposure = Staking::api_exposure(101).unwrap();
+			assert_eq!(exposure.validator, None);
+			let mut nominations = exposure.nominations;
+			nominations.sort();
+			assert_eq!(
+				nominations,
+				vec![(11, exposure_11.others[0].value), (21, exposure_21.others[0].value)]
+			);
+			assert_eq!(Staking::api_exposure(41), None);
+
+			// the commission change applies from the next era on
+			let mut validators = Staking::api_era_validators(None);
+			validators.sort_by_key(|v| v.stash);
+			assert_eq!(
+				validators,
+				vec![
+					EraValidator {
+						stash: 11,
+						total: exposure_11.total,
+						own: exposure_11.own,
+						nominator_count: 1,
+						commission: Perbill::zero(),
+						blocked: false,
+						reward_points: 0,
+					},
+					EraValidator {
+						stash: 21,
+						total: exposure_21.total,
+						own: exposure_21.own,
+						nominator_count: 1,
+						commission: Perbill::zero(),
+						blocked: false,
+						reward_points: 3,
+					},
+				]
+			);
+
+			mock::start_active_era(2);
+			let validator_11 = Staking::api_era_validators(Some(2))
+				.into_iter()
+				.find(|v| v.stash == 11)
+				.unwrap();
+			assert_eq!(validator_11.commission, Perbill::from_percent(10));
+		});
+	}
+
+	#[test]
+	fn unbonding_and_apy_work() {
+		ExtBuilder::default().build_and_execute(|| {
+			mock::start_active_era(1);
+			assert!(Staking::api_unbonding(11).is_empty());
+
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 100));
+			mock::start_active_era(2);
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 50));
+
+			assert_eq!(
+				Staking::api_unbonding(11),
+				vec![
+					UnbondingChunk { value: 100, era: 1 + BondingDuration::get() },
+					UnbondingChunk { value: 50, era: 2 + BondingDuration::get() },
+				]
+			);
+			assert!(Staking::api_unbonding(21).is_empty());
+
+			// keep the yield below 100% by not inflating the total issuance
+			let _ = Balances::make_free_balance_be(&999, 0);
+			assert_eq!(Staking::api_estimated_apy(0), Perbill::zero());
+
+			// the yield compounds every era
+			let daily = Staking::api_estimated_apy(24 * 3600 * 1000);
+			let yearly = Staking::api_estimated_apy(36525 * 24 * 36 * 1000);
+			assert!(yearly > Perbill::zero() && yearly < Perbill::one());
+			assert!(daily > yearly);
+		});
+	}
+}
+
+mod unbonding_queue {
+	use super::*;
+	use frame_support::{
+		migrations::{SteppedMigration, SteppedMigrationError},
+		weights::WeightMeter,
+	};
+
+	#[test]
+	fn unbonding_duration_scales_with_unbonding_stake() {
+		ExtBuilder::default().nominate(false).build_and_execute(|| {
+			MinUnbondingDuration::set(1);
+			mock::start_active_era(1);
+			assert_eq!(Staking::eras_total_stake(1), 2000);
+
+			// scales from the minimum unbonding duration up to the bonding duration
+			assert_eq!(Staking::unbonding_duration(0), 1);
+			assert_eq!(Staking::unbonding_duration(1000), 2);
+			assert_eq!(Staking::unbonding_duration(1001), 3);
+			assert_eq!(Staking::unbonding_duration(2000), 3);
+			assert_eq!(Staking::unbonding_duration(4000), 3);
+
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 100));
+			assert_eq!(
+				Staking::ledger(&11).unwrap().unlocking,
+				vec![UnlockChunk { value: 100, era: 3 }]
+			);
+			assert_eq!(Staking::unbonding_queue(3), 100);
+			assert_eq!(Staking::total_unbonding(), 100);
+
+			// the stake already unbonding counts towards the duration
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(21), 900));
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(21), 50));
+			assert_eq!(
+				Staking::ledger(&21).unwrap().unlocking,
+				vec![UnlockChunk { value: 900, era: 3 }, UnlockChunk { value: 50, era: 4 }]
+			);
+			assert_eq!(Staking::api_unbonding_queue(), vec![(3, 1000), (4, 50)]);
+			assert_eq!(Staking::total_unbonding(), 1050);
+
+			// rebonding removes stake from the queue
+			assert_ok!(Staking::rebond(RuntimeOrigin::signed(21), 950));
+			assert_eq!(Staking::api_unbonding_queue(), vec![(3, 100)]);
+			assert_eq!(Staking::total_unbonding(), 100);
+
+			// funds never unlock before the ones unbonded earlier
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(21), 950));
+			assert_ok!(Staking::rebond(RuntimeOrigin::signed(21), 900));
+			assert_eq!(Staking::api_unbonding_queue(), vec![(3, 100), (4, 50)]);
+			assert_eq!(Staking::unbonding_duration(10), 2);
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(21), 10));
+			assert_eq!(
+				Staking::ledger(&21).unwrap().unlocking,
+				vec![UnlockChunk { value: 60, era: 4 }]
+			);
+			assert_eq!(Staking::api_unbonding_queue(), vec![(3, 100), (4, 60)]);
+
+			// the queue of an era is cleared once it is unlocked
+			mock::start_active_era(3);
+			assert_eq!(Staking::api_unbonding_queue(), vec![(4, 60)]);
+			assert_eq!(Staking::total_unbonding(), 60);
+
+			assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(11), 0));
+			assert!(Staking::ledger(&11).unwrap().unlocking.is_empty());
+			assert_eq!(Staking::total_unbonding(), 60);
+		});
+	}
+
+	#[test]
+	fn slashing_removes_stake_from_the_queue() {
+		ExtBuilder::default().nominate(false).build_and_execute(|| {
+			MinUnbondingDuration::set(1);
+			mock::start_active_era(1);
+
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 500));
+			assert_eq!(Staking::unbonding_queue(3), 500);
+
+			// the chunk may have been unbonded after the offence, so it is slashed as well
+			on_offence_now(
+				&[OffenceDetails {
+					offender: (11, Staking::eras_stakers(active_era(), 11)),
+					reporters: vec![],
+				}],
+				&[Perbill::from_percent(10)],
+			);
+
+			let ledger = Staking::ledger(&11).unwrap();
+			assert_eq!(ledger.active, 450);
+			assert_eq!(ledger.unlocking, vec![UnlockChunk { value: 450, era: 3 }]);
+			assert_eq!(Staking::unbonding_queue(3), 450);
+			assert_eq!(Staking::total_unbonding(), 450);
+
+			// killing the stash clears its chunks from the queue
+			assert_ok!(Staking::force_unstake(RuntimeOrigin::root(), 11, 2));
+			assert_eq!(Staking::api_unbonding_queue(), vec![]);
+			assert_eq!(Staking::total_unbonding(), 0);
+		});
+	}
+
+	#[test]
+	fn deferred_slashes_hold_the_chunks_they_can_slash() {
+		ExtBuilder::default()
+			.nominate(false)
+			.slash_defer_duration(2)
+			.build_and_execute(|| {
+				mock::start_active_era(1);
+				let exposure = Staking::eras_stakers(1, 11);
+				assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 500));
+				assert_eq!(
+					Staking::ledger(&11).unwrap().unlocking,
+					vec![UnlockChunk { value: 500, era: 4 }]
+				);
+
+				// an offence of era 1 is reported late, and is applied as the chunk unlocks
+				mock::start_active_era(3);
+				on_offence_in_era(
+					&[OffenceDetails { offender: (11, exposure), reporters: vec![] }],
+					&[Perbill::from_percent(10)],
+					1,
+					DisableStrategy::WhenSlashed,
+				);
+				assert_eq!(
+					Staking::ledger(&11).unwrap().unlocking,
+					vec![UnlockChunk { value: 500, era: 5 }]
+				);
+				assert_eq!(Staking::api_unbonding_queue(), vec![(5, 500)]);
+				assert_eq!(Staking::total_unbonding(), 500);
+
+				// so it can't be withdrawn before it is slashed
+				mock::start_active_era(4);
+				assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(11), 0));
+				let ledger = Staking::ledger(&11).unwrap();
+				assert_eq!(ledger.unlocking.len(), 1);
+				assert_eq!(ledger.unlocking[0].era, 5);
+				assert!(ledger.unlocking[0].value < 500);
+
+				mock::start_active_era(5);
+				assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(11), 0));
+				assert!(Staking::ledger(&11).unwrap().unlocking.is_empty());
+				assert_eq!(Staking::total_unbonding(), 0);
+			});
+	}
+
+	#[test]
+	fn migration_to_v14_populates_the_queue() {
+		ExtBuilder::default().nominate(false).build_and_execute(|| {
+			mock::start_active_era(1);
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 100));
+			mock::start_active_era(2);
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 50));
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(21), 200));
+			let queue = Staking::api_unbonding_queue();
+			assert_eq!(
+				queue,
+				vec![(1 + BondingDuration::get(), 100), (2 + BondingDuration::get(), 250)]
+			);
+
+			// given a v13 chain without the queue
+			let _ = UnbondingQueue::<Test>::clear(u32::MAX, None);
+			TotalUnbonding::<Test>::kill();
+			StorageVersion::new(13).put::<Staking>();
+
+			// with only enough weight for a single ledger per step
+			let Err(SteppedMigrationError::InsufficientWeight { required }) =
+				migrations::v14::MigrateToV14::<Test>::step(
+					None,
+					&mut WeightMeter::from_limit(Weight::zero()),
+				)
+			else {
+				panic!("a step can't run without weight")
+			};
+			let mut steps = 0;
+			let mut cursor = None;
+			loop {
+				let mut meter = WeightMeter::from_limit(required);
+				cursor = migrations::v14::MigrateToV14::<Test>::step(cursor, &mut meter).unwrap();
+				steps += 1;
+				if cursor.is_none() {
+					break
+				}
+			}
+
+			// each ledger took a step, and the last one found none left.
+			assert_eq!(steps, Ledger::<Test>::iter().count() + 1);
+			assert_eq!(Staking::on_chain_storage_version(), 14);
+			assert_eq!(Staking::api_unbonding_queue(), queue);
+			assert_eq!(Staking::total_unbonding(), 350);
+
+			// nothing is left to do once the queue was populated
+			assert_eq!(
+				migrations::v14::MigrateToV14::<Test>::step(None, &mut WeightMeter::max_limit()),
+				Ok(None)
+			);
+			assert_eq!(Staking::total_unbonding(), 350);
+		});
+	}
+}
@@ -148,14 +148,22 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: VoterList ListBags (r:2 w:2)
 	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: Staking ActiveEra (r:1 w:0)
+	/// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+	/// Storage: Staking ErasTotalStake (r:1 w:0)
+	/// Proof: Staking ErasTotalStake (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking TotalUnbonding (r:1 w:1)
+	/// Proof: Staking TotalUnbonding (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Staking UnbondingQueue (r:1 w:1)
+	/// Proof: Staking UnbondingQueue (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
 	fn unbond() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2233`
 		//  Estimated: `8877`
 		// Minimum execution time: 99_901_000 picoseconds.
 		Weight::from_parts(102_919_000, 8877)
-			.saturating_add(T::DbWeight::get().reads(12_u64))
-			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	/// Storage: Staking Ledger (r:1 w:1)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
@@ -544,17 +552,24 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: VoterList ListBags (r:2 w:2)
 	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: Staking UnbondingQueue (r:32 w:32)
+	/// Proof: Staking UnbondingQueue (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking TotalUnbonding (r:1 w:1)
+	/// Proof: Staking TotalUnbonding (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
 	/// The range of component `l` is `[1, 32]`.
 	fn rebond(l: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2029 + l * (7 ±0)`
-		//  Estimated: `8877`
+		//  Estimated: `8877 + l * (2503 ±0)`
 		// Minimum execution time: 90_068_000 picoseconds.
 		Weight::from_parts(93_137_456, 8877)
 			// Standard Error: 4_799
 			.saturating_add(Weight::from_parts(54_421, 0).saturating_mul(l.into()))
-			.saturating_add(T::DbWeight::get().reads(9_u64))
-			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(l.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2503).saturating_mul(l.into()))
 	}
 	/// Storage: Staking Bonded (r:1 w:1)
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
@@ -858,14 +873,22 @@ impl WeightInfo for () {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: VoterList ListBags (r:2 w:2)
 	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: Staking ActiveEra (r:1 w:0)
+	/// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+	/// Storage: Staking ErasTotalStake (r:1 w:0)
+	/// Proof: Staking ErasTotalStake (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking TotalUnbonding (r:1 w:1)
+	/// Proof: Staking TotalUnbonding (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Staking UnbondingQueue (r:1 w:1)
+	/// Proof: Staking UnbondingQueue (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
 	fn unbond() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2233`
 		//  Estimated: `8877`
 		// Minimum execution time: 99_901_000 picoseconds.
 		Weight::from_parts(102_919_000, 8877)
-			.saturating_add(RocksDbWeight::get().reads(12_u64))
-			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	/// Storage: Staking Ledger (r:1 w:1)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
@@ -1254,17 +1277,24 @@ impl WeightInfo for () {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: VoterList ListBags (r:2 w:2)
 	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: Staking UnbondingQueue (r:32 w:32)
+	/// Proof: Staking UnbondingQueue (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking TotalUnbonding (r:1 w:1)
+	/// Proof: Staking TotalUnbonding (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
 	/// The range of component `l` is `[1, 32]`.
 	fn rebond(l: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2029 + l * (7 ±0)`
-		//  Estimated: `8877`
+		//  Estimated: `8877 + l * (2503 ±0)`
 		// Minimum execution time: 90_068_000 picoseconds.
 		Weight::from_parts(93_137_456, 8877)
 			// Standard Error: 4_799
 			.saturating_add(Weight::from_parts(54_421, 0).saturating_mul(l.into()))
-			.saturating_add(RocksDbWeight::get().reads(9_u64))
-			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(l.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2503).saturating_mul(l.into()))
 	}
 	/// Storage: Staking Bonded (r:1 w:1)
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
@@ -0,0 +1,58 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helper functions for implementing [`sp_genesis_builder::GenesisBuilder`] for runtimes.
+//!
+//! Provides common logic. For more info refer to [`sp_genesis_builder::GenesisBuilder`].
+
+use crate::traits::BuildGenesisConfig;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_genesis_builder::{PresetId, Result as BuildResult};
+use sp_runtime::format_runtime_string;
+use sp_std::vec::Vec;
+
+/// Get the default `GenesisConfig` as a JSON blob. For more info refer to
+/// [`sp_genesis_builder::GenesisBuilder::create_default_config`]
+pub fn create_default_config<GC: BuildGenesisConfig + Serialize>() -> Vec<u8> {
+	serde_json::to_string(&GC::default())
+		.expect("serialization to json is expected to work. qed.")
+		.into_bytes()
+}
+
+/// Build `GenesisConfig` from a JSON blob not using any defaults and store it in the storage. For
+/// more info refer to [`sp_genesis_builder::GenesisBuilder::build_config`].
+pub fn build_config<GC: BuildGenesisConfig + DeserializeOwned>(json: Vec<u8>) -> BuildResult {
+	let gc = serde_json::from_slice::<GC>(&json)
+		.map_err(|e| format_runtime_string!("Invalid JSON blob: {}", e))?;
+	<GC as BuildGenesisConfig>::build(&gc);
+	Ok(())
+}
+
+/// Get the JSON patch of the preset `id` as a JSON blob, using `patch_for` to get the patch of a
+/// preset from its name. For more info refer to [`sp_genesis_builder::GenesisBuilder::get_preset`].
+pub fn get_preset(
+	id: &PresetId,
+	patch_for: impl FnOnce(&str) -> Option<serde_json::Value>,
+) -> Option<Vec<u8>> {
+	let patch = patch_for(id.as_str()?)?;
+	Some(
+		serde_json::to_string(&patch)
+			.expect("serialization to json is expected to work. qed.")
+			.into_bytes(),
+	)
+}
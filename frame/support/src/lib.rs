@@ -76,6 +76,7 @@ pub mod inherent;
 #[macro_use]
 pub mod error;
 pub mod dispatch_context;
+pub mod genesis_builder_helper;
 pub mod instances;
 pub mod migrations;
 pub mod traits;
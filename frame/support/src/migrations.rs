@@ -17,16 +17,16 @@
 // limitations under the License.
 
 use crate::{
+	storage::transactional::with_transaction,
 	traits::{GetStorageVersion, NoStorageVersionSet, PalletInfoAccess, StorageVersion},
-	weights::{RuntimeDbWeight, Weight},
+	weights::{RuntimeDbWeight, Weight, WeightMeter},
 };
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use impl_trait_for_tuples::impl_for_tuples;
 use sp_core::Get;
 use sp_io::{hashing::twox_128, storage::clear_prefix, KillStorageResult};
-use sp_std::marker::PhantomData;
-
-#[cfg(feature = "try-runtime")]
-use sp_std::vec::Vec;
+use sp_runtime::{DispatchError, RuntimeDebug, TransactionOutcome};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 #[cfg(feature = "experimental")]
 use crate::traits::OnRuntimeUpgrade;
@@ -348,3 +348,253 @@ impl<P: Get<&'static str>, DbWeight: Get<RuntimeDbWeight>> frame_support::traits
 		Ok(())
 	}
 }
+
+/// The error of a [`SteppedMigration::step`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum SteppedMigrationError {
+	/// The remaining weight is not enough to do anything, the step must be retried later.
+	///
+	/// The migration fails if `required` exceeds the weight which could ever be given to it.
+	InsufficientWeight {
+		/// The weight needed to make any progress.
+		required: Weight,
+	},
+	/// The cursor could not be decoded, or does not make sense to the migration.
+	InvalidCursor,
+	/// The migration failed and can not continue.
+	Failed,
+}
+
+/// A migration which runs over several steps, possibly spread over several blocks.
+///
+/// Its progress is persisted in between steps as a [`Self::Cursor`], which lets large storage
+/// rewrites be broken into steps which fit within the weight available in a block.
+///
+/// Stepped migrations are run by an implementation of [`MultiStepMigrator`], such as
+/// `pallet-migrations`, which lists them in a [`SteppedMigrations`] tuple.
+pub trait SteppedMigration {
+	/// The progress of the migration, in between two steps.
+	type Cursor: FullCodec + MaxEncodedLen;
+
+	/// The unique identifier of the migration, so that it is never run twice.
+	type Identifier: FullCodec + MaxEncodedLen;
+
+	/// The unique identifier of this migration.
+	fn id() -> Self::Identifier;
+
+	/// The maximum number of blocks the migration may take, after which it is considered failed.
+	///
+	/// `None` lets the migration run for as long as it needs to.
+	fn max_blocks() -> Option<u32> {
+		None
+	}
+
+	/// Do the next step of the migration, from `cursor` or from the start if `cursor` is `None`.
+	///
+	/// Returns the cursor to continue from, or `None` once the migration is complete. The weight
+	/// consumed must be accrued to `meter`, and the step must not consume more than it allows.
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError>;
+
+	/// Same as [`Self::step`], but rolls back all changes to the storage if the step failed.
+	fn transactional_step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		with_transaction::<_, DispatchError, _>(|| match Self::step(cursor, meter) {
+			Ok(cursor) => TransactionOutcome::Commit(Ok(Ok(cursor))),
+			Err(e) => TransactionOutcome::Rollback(Ok(Err(e))),
+		})
+		.unwrap_or(Err(SteppedMigrationError::Failed))
+	}
+}
+
+/// A list of [`SteppedMigration`]s, run one after another.
+///
+/// This is implemented for a single migration, and for tuples of migrations. Cursors and
+/// identifiers are exchanged in their encoded form, so that the migrations of the list may use
+/// different types for them.
+pub trait SteppedMigrations {
+	/// The number of migrations in the list.
+	fn len() -> u32;
+
+	/// The encoded identifier of the `n`th migration, or `None` if there is no such migration.
+	fn nth_id(n: u32) -> Option<Vec<u8>>;
+
+	/// The [`SteppedMigration::max_blocks`] of the `n`th migration, or `None` if there is no such
+	/// migration.
+	fn nth_max_blocks(n: u32) -> Option<Option<u32>>;
+
+	/// Do the next [`SteppedMigration::transactional_step`] of the `n`th migration, from the
+	/// encoded `cursor`.
+	///
+	/// Returns `None` if there is no such migration.
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>>;
+}
+
+impl SteppedMigrations for () {
+	fn len() -> u32 {
+		0
+	}
+
+	fn nth_id(_n: u32) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn nth_max_blocks(_n: u32) -> Option<Option<u32>> {
+		None
+	}
+
+	fn nth_transactional_step(
+		_n: u32,
+		_cursor: Option<Vec<u8>>,
+		_meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		None
+	}
+}
+
+impl<T: SteppedMigration> SteppedMigrations for T {
+	fn len() -> u32 {
+		1
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		(n == 0).then(|| T::id().encode())
+	}
+
+	fn nth_max_blocks(n: u32) -> Option<Option<u32>> {
+		(n == 0).then(T::max_blocks)
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		if n != 0 {
+			return None
+		}
+
+		let cursor = match cursor.map(|c| T::Cursor::decode(&mut &c[..])).transpose() {
+			Ok(cursor) => cursor,
+			Err(_) => return Some(Err(SteppedMigrationError::InvalidCursor)),
+		};
+		Some(T::transactional_step(cursor, meter).map(|cursor| cursor.map(|c| c.encode())))
+	}
+}
+
+#[impl_for_tuples(1, 30)]
+impl SteppedMigrations for Tuple {
+	fn len() -> u32 {
+		for_tuples!( #( Tuple::len() )+* )
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if n < i + Tuple::len() {
+				return Tuple::nth_id(n - i)
+			}
+			i += Tuple::len();
+		)* );
+		None
+	}
+
+	fn nth_max_blocks(n: u32) -> Option<Option<u32>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if n < i + Tuple::len() {
+				return Tuple::nth_max_blocks(n - i)
+			}
+			i += Tuple::len();
+		)* );
+		None
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if n < i + Tuple::len() {
+				return Tuple::nth_transactional_step(n - i, cursor, meter)
+			}
+			i += Tuple::len();
+		)* );
+		None
+	}
+}
+
+/// Something which runs the steps of multi-block migrations, in between blocks.
+///
+/// `frame-executive` calls [`Self::step`] at the end of every block while [`Self::ongoing`], and
+/// does not apply any non-mandatory extrinsic until then.
+pub trait MultiStepMigrator {
+	/// Whether a multi-block migration is ongoing.
+	fn ongoing() -> bool;
+
+	/// Do the next steps of the ongoing migrations within `remaining` weight, returning the weight
+	/// consumed.
+	fn step(remaining: Weight) -> Weight;
+}
+
+impl MultiStepMigrator for () {
+	fn ongoing() -> bool {
+		false
+	}
+
+	fn step(_remaining: Weight) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// What to do once a multi-block migration failed.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum FailedMigrationHandling {
+	/// Keep the migrations stuck, so that no non-mandatory extrinsic is applied anymore.
+	///
+	/// This includes any extrinsic which would unstick the chain, such as a runtime upgrade, so
+	/// recovering from this takes a hard fork.
+	KeepStuck,
+	/// Forget about the failed and remaining migrations, and resume applying extrinsics.
+	///
+	/// Any storage the failed migration was part-way through is left as it is.
+	ForceUnstuck,
+}
+
+/// Something which decides what to do once a multi-block migration failed.
+pub trait FailedMigrationHandler {
+	/// The migration at index `migration` failed, if known.
+	fn failed(migration: Option<u32>) -> FailedMigrationHandling;
+}
+
+/// Keep the chain stuck once a multi-block migration failed.
+///
+/// See [`FailedMigrationHandling::KeepStuck`], recovering from this takes a hard fork.
+pub struct FreezeChainOnFailedMigration;
+impl FailedMigrationHandler for FreezeChainOnFailedMigration {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		FailedMigrationHandling::KeepStuck
+	}
+}
+
+/// Resume applying extrinsics once a multi-block migration failed, leaving the remaining
+/// migrations undone.
+///
+/// Only use this if every migration either rolls back or records its failure, as the storage a
+/// failed migration was part-way through is otherwise left in a mix of the old and new formats.
+pub struct ResumeChainOnFailedMigration;
+impl FailedMigrationHandler for ResumeChainOnFailedMigration {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		FailedMigrationHandling::ForceUnstuck
+	}
+}
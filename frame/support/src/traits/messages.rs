@@ -117,7 +117,9 @@ impl<OverweightAddr> ServiceQueues for NoopServiceQueues<OverweightAddr> {
 }
 
 /// The resource footprint of a queue.
-#[derive(Default, Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+#[derive(
+	Default, Copy, Clone, Eq, PartialEq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
 pub struct Footprint {
 	pub count: u64,
 	pub size: u64,
@@ -47,11 +47,13 @@ use sp_std::prelude::*;
 
 use codec::{Decode, Encode};
 use frame_support::{
-	dispatch::{DispatchInfo, DispatchResult, PostDispatchInfo},
+	dispatch::{DispatchInfo, DispatchResult, GetDispatchInfo, PostDispatchInfo},
+	storage::{with_transaction_unchecked, TransactionOutcome},
 	traits::{
 		tokens::fungibles::{Balanced, Inspect},
 		IsType,
 	},
+	weights::Weight,
 	DefaultNoBound,
 };
 use pallet_transaction_payment::OnChargeTransaction;
@@ -145,6 +147,59 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> Pallet<T>
+where
+	T::RuntimeCall:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	AssetBalanceOf<T>: Send + Sync + FixedPointOperand,
+	BalanceOf<T>: Send
+		+ Sync
+		+ FixedPointOperand
+		+ Into<ChargeAssetBalanceOf<T>>
+		+ Into<ChargeAssetLiquidityOf<T>>
+		+ From<ChargeAssetLiquidityOf<T>>,
+	ChargeAssetIdOf<T>: Send + Sync,
+{
+	/// Query the fee `who` would finally pay, in the asset `asset_id`, for an extrinsic of
+	/// length `len` dispatching `call` without a tip.
+	///
+	/// The fee is withdrawn and then corrected through [`Config::OnChargeAssetTransaction`],
+	/// exactly as [`ChargeAssetTxPayment`] would, refund of the surplus swapped back to the
+	/// asset included. All changes to the state are then reverted. As `call` isn't dispatched,
+	/// the fee is corrected to `actual_weight`, the weight the call is expected to actually
+	/// consume, or kept for its declared weight if `None`.
+	pub fn query_fee_in_asset(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		len: u32,
+		asset_id: ChargeAssetIdOf<T>,
+		actual_weight: Option<Weight>,
+	) -> Result<AssetBalanceOf<T>, TransactionValidityError> {
+		let info = call.get_dispatch_info();
+		let post_info = PostDispatchInfo { actual_weight, pays_fee: info.pays_fee };
+		let tip = Zero::zero();
+
+		with_transaction_unchecked(|| {
+			let payment = ChargeAssetTxPayment::<T>::from(tip, Some(asset_id))
+				.withdraw_fee(who, call, &info, len as usize)
+				.and_then(|(_fee, initial_payment)| match initial_payment {
+					InitialPayment::Asset(already_withdrawn) =>
+						ChargeAssetTxPayment::<T>::correct_and_deposit_asset_fee(
+							who,
+							&info,
+							&post_info,
+							len as usize,
+							tip,
+							already_withdrawn,
+							asset_id,
+						),
+					InitialPayment::Native(_) | InitialPayment::Nothing => Ok(Zero::zero()),
+				});
+			TransactionOutcome::Rollback(payment)
+		})
+	}
+}
+
 /// Require payment for transaction inclusion and optionally include a tip to gain additional
 /// priority in the queue. Allows paying via both `Currency` as well as `fungibles::Balanced`.
 ///
@@ -167,6 +222,7 @@ where
 		+ Sync
 		+ FixedPointOperand
 		+ Into<ChargeAssetBalanceOf<T>>
+		+ Into<ChargeAssetLiquidityOf<T>>
 		+ From<ChargeAssetLiquidityOf<T>>,
 	ChargeAssetIdOf<T>: Send + Sync,
 {
@@ -215,6 +271,35 @@ where
 			.map_err(|_| -> TransactionValidityError { InvalidTransaction::Payment.into() })
 		}
 	}
+
+	/// Fee correction logic for a fee paid in an asset, returning the fee finally paid in the
+	/// asset.
+	fn correct_and_deposit_asset_fee(
+		who: &T::AccountId,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		tip: BalanceOf<T>,
+		already_withdrawn: (LiquidityInfoOf<T>, BalanceOf<T>, AssetBalanceOf<T>),
+		asset_id: ChargeAssetIdOf<T>,
+	) -> Result<AssetBalanceOf<T>, TransactionValidityError> {
+		let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+			len as u32, info, post_info, tip,
+		);
+
+		let (used_for_fee, received_exchanged, asset_consumed) = already_withdrawn;
+		T::OnChargeAssetTransaction::correct_and_deposit_fee(
+			who,
+			info,
+			post_info,
+			actual_fee.into(),
+			tip.into(),
+			used_for_fee.into(),
+			received_exchanged.into(),
+			asset_id,
+			asset_consumed.into(),
+		)
+	}
 }
 
 impl<T: Config> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
@@ -311,22 +396,15 @@ where
 						asset_id.is_some(),
 						"For that payment type the `asset_id` should be set"
 					);
-					let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
-						len as u32, info, post_info, tip,
-					);
-
 					if let Some(asset_id) = asset_id {
-						let (used_for_fee, received_exchanged, asset_consumed) = already_withdrawn;
-						let converted_fee = T::OnChargeAssetTransaction::correct_and_deposit_fee(
+						let converted_fee = Self::correct_and_deposit_asset_fee(
 							&who,
 							info,
 							post_info,
-							actual_fee.into(),
-							tip.into(),
-							used_for_fee.into(),
-							received_exchanged.into(),
+							len,
+							tip,
+							already_withdrawn,
 							asset_id,
-							asset_consumed.into(),
 						)?;
 
 						Pallet::<T>::deposit_event(Event::<T>::AssetTxFeePaid {
@@ -40,7 +40,8 @@ use sp_std::prelude::*;
 
 use codec::{Decode, Encode};
 use frame_support::{
-	dispatch::{DispatchInfo, DispatchResult, PostDispatchInfo},
+	dispatch::{DispatchInfo, DispatchResult, GetDispatchInfo, PostDispatchInfo},
+	storage::{with_transaction_unchecked, TransactionOutcome},
 	traits::{
 		tokens::{
 			fungibles::{Balanced, Credit, Inspect},
@@ -48,6 +49,7 @@ use frame_support::{
 		},
 		IsType,
 	},
+	weights::Weight,
 	DefaultNoBound,
 };
 use pallet_transaction_payment::OnChargeTransaction;
@@ -141,6 +143,54 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> Pallet<T>
+where
+	T::RuntimeCall:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	AssetBalanceOf<T>: Send + Sync + FixedPointOperand,
+	BalanceOf<T>: Send + Sync + FixedPointOperand + IsType<ChargeAssetBalanceOf<T>>,
+	ChargeAssetIdOf<T>: Send + Sync,
+	Credit<T::AccountId, T::Fungibles>: IsType<ChargeAssetLiquidityOf<T>>,
+{
+	/// Query the fee `who` would finally pay, in the asset `asset_id`, for an extrinsic of
+	/// length `len` dispatching `call` without a tip.
+	///
+	/// The fee is withdrawn and then corrected through [`Config::OnChargeAssetTransaction`],
+	/// exactly as [`ChargeAssetTxPayment`] would, before all changes to the state are reverted.
+	/// As `call` isn't dispatched, the fee is corrected to `actual_weight`, the weight the call
+	/// is expected to actually consume, or kept for its declared weight if `None`.
+	pub fn query_fee_in_asset(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		len: u32,
+		asset_id: ChargeAssetIdOf<T>,
+		actual_weight: Option<Weight>,
+	) -> Result<AssetBalanceOf<T>, TransactionValidityError> {
+		let info = call.get_dispatch_info();
+		let post_info = PostDispatchInfo { actual_weight, pays_fee: info.pays_fee };
+		let tip = Zero::zero();
+
+		with_transaction_unchecked(|| {
+			let payment = ChargeAssetTxPayment::<T>::from(tip, Some(asset_id))
+				.withdraw_fee(who, call, &info, len as usize)
+				.and_then(|(_fee, initial_payment)| match initial_payment {
+					InitialPayment::Asset(already_withdrawn) =>
+						ChargeAssetTxPayment::<T>::correct_and_deposit_asset_fee(
+							who,
+							&info,
+							&post_info,
+							len as usize,
+							tip,
+							already_withdrawn,
+						)
+						.map(|(converted_fee, _converted_tip)| converted_fee),
+					InitialPayment::Native(_) | InitialPayment::Nothing => Ok(Zero::zero()),
+				});
+			TransactionOutcome::Rollback(payment)
+		})
+	}
+}
+
 /// Require the transactor pay for themselves and maybe include a tip to gain additional priority
 /// in the queue. Allows paying via both `Currency` as well as `fungibles::Balanced`.
 ///
@@ -198,6 +248,30 @@ where
 			.map_err(|_| -> TransactionValidityError { InvalidTransaction::Payment.into() })
 		}
 	}
+
+	/// Fee correction logic for a fee paid in an asset, returning the fee and the tip finally
+	/// paid in the asset as (fee, tip).
+	fn correct_and_deposit_asset_fee(
+		who: &T::AccountId,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		tip: BalanceOf<T>,
+		already_withdrawn: Credit<T::AccountId, T::Fungibles>,
+	) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), TransactionValidityError> {
+		let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+			len as u32, info, post_info, tip,
+		);
+
+		T::OnChargeAssetTransaction::correct_and_deposit_fee(
+			who,
+			info,
+			post_info,
+			actual_fee.into(),
+			tip.into(),
+			already_withdrawn.into(),
+		)
+	}
 }
 
 impl<T: Config> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
@@ -281,19 +355,14 @@ where
 					)?;
 				},
 				InitialPayment::Asset(already_withdrawn) => {
-					let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
-						len as u32, info, post_info, tip,
-					);
-
-					let (converted_fee, converted_tip) =
-						T::OnChargeAssetTransaction::correct_and_deposit_fee(
-							&who,
-							info,
-							post_info,
-							actual_fee.into(),
-							tip.into(),
-							already_withdrawn.into(),
-						)?;
+					let (converted_fee, converted_tip) = Self::correct_and_deposit_asset_fee(
+						&who,
+						info,
+						post_info,
+						len,
+						tip,
+						already_withdrawn,
+					)?;
 					Pallet::<T>::deposit_event(Event::<T>::AssetTxFeePaid {
 						who,
 						actual_fee: converted_fee,
@@ -202,6 +202,127 @@ fn transaction_payment_in_asset_possible() {
 		});
 }
 
+#[test]
+fn query_fee_in_asset_quotes_the_fee_paid() {
+	let base_weight = 5;
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(Weight::from_parts(base_weight, 0))
+		.build()
+		.execute_with(|| {
+			// create the asset
+			let asset_id = 1;
+			let min_balance = 2;
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				42,   /* owner */
+				true, /* is_sufficient */
+				min_balance
+			));
+
+			// mint into the caller account
+			let caller = 1;
+			let beneficiary = <Runtime as system::Config>::Lookup::unlookup(caller);
+			let balance = 10_000_000;
+			assert_ok!(Assets::mint_into(asset_id.into(), &beneficiary, balance));
+
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			let info = call.get_dispatch_info();
+			let len = 10;
+			let fee = (base_weight + info.weight.ref_time() + len as u64) * min_balance /
+				ExistentialDeposit::get();
+
+			assert_eq!(
+				AssetTxPayment::query_fee_in_asset(&caller, &call, len, asset_id, None),
+				Ok(fee)
+			);
+			// nothing was charged by the quote
+			assert_eq!(Assets::balance(asset_id, caller), balance);
+			assert_eq!(Assets::balance(asset_id, BLOCK_AUTHOR), 0);
+
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(asset_id))
+				.pre_dispatch(&caller, &call, &info, len as usize)
+				.unwrap();
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				Some(pre),
+				&info,
+				&default_post_info(),
+				len as usize,
+				&Ok(())
+			));
+			assert_eq!(Assets::balance(asset_id, caller), balance - fee);
+			assert_eq!(Assets::balance(asset_id, BLOCK_AUTHOR), fee);
+		});
+}
+
+#[test]
+fn query_fee_in_asset_models_the_weight_refund() {
+	let base_weight = 5;
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(Weight::from_parts(base_weight, 0))
+		.build()
+		.execute_with(|| {
+			// create the asset
+			let asset_id = 1;
+			let min_balance = 2;
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				42,   /* owner */
+				true, /* is_sufficient */
+				min_balance
+			));
+
+			// mint into the caller account
+			let caller = 1;
+			let beneficiary = <Runtime as system::Config>::Lookup::unlookup(caller);
+			let balance = 10_000_000;
+			assert_ok!(Assets::mint_into(asset_id.into(), &beneficiary, balance));
+
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			let info = call.get_dispatch_info();
+			let len = 10;
+			// the call turns out to consume no weight at all, so only its length is paid for
+			let actual_weight = Weight::zero();
+			let fee = (base_weight + len as u64) * min_balance / ExistentialDeposit::get();
+
+			let quote = AssetTxPayment::query_fee_in_asset(
+				&caller,
+				&call,
+				len,
+				asset_id,
+				Some(actual_weight),
+			)
+			.unwrap();
+			assert_eq!(quote, fee);
+			assert!(
+				quote <
+					AssetTxPayment::query_fee_in_asset(&caller, &call, len, asset_id, None)
+						.unwrap()
+			);
+			// nothing was charged by the quotes
+			assert_eq!(Assets::balance(asset_id, caller), balance);
+			assert_eq!(Assets::balance(asset_id, BLOCK_AUTHOR), 0);
+
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(asset_id))
+				.pre_dispatch(&caller, &call, &info, len as usize)
+				.unwrap();
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				Some(pre),
+				&info,
+				&post_info_from_weight(actual_weight),
+				len as usize,
+				&Ok(())
+			));
+			assert_eq!(Assets::balance(asset_id, caller), balance - fee);
+			assert_eq!(Assets::balance(asset_id, BLOCK_AUTHOR), fee);
+		});
+}
+
 #[test]
 fn transaction_payment_without_fee() {
 	let base_weight = 5;
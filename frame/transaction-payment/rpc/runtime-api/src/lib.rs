@@ -21,7 +21,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use sp_runtime::traits::MaybeDisplay;
+use sp_runtime::{traits::MaybeDisplay, transaction_validity::TransactionValidityError};
 
 pub use pallet_transaction_payment::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
 
@@ -54,4 +54,43 @@ sp_api::decl_runtime_apis! {
 		/// Query the output of the current `LengthToFee` given some input.
 		fn query_length_to_fee(length: u32) -> Balance;
 	}
+
+	pub trait AssetTransactionPaymentApi<AssetId, AssetBalance>
+	where
+		AssetId: Codec,
+		AssetBalance: Codec + MaybeDisplay,
+	{
+		/// Query the fee the signer of a given extrinsic would finally pay for it in the given
+		/// asset, excluding the tip.
+		///
+		/// The fee is refunded down to `actual_weight`, the weight the extrinsic is expected to
+		/// actually consume, if given. Unsigned extrinsics don't pay any fee.
+		fn query_fee_in_asset(
+			uxt: Block::Extrinsic,
+			len: u32,
+			asset_id: AssetId,
+			actual_weight: Option<sp_weights::Weight>,
+		) -> Result<AssetBalance, TransactionValidityError>;
+	}
+
+	pub trait AssetTransactionPaymentCallApi<AccountId, Call, AssetId, AssetBalance>
+	where
+		AccountId: Codec,
+		Call: Codec,
+		AssetId: Codec,
+		AssetBalance: Codec + MaybeDisplay,
+	{
+		/// Query the fee a given account would finally pay in the given asset for an extrinsic
+		/// dispatching a given encoded `Call`, excluding the tip.
+		///
+		/// The fee is refunded down to `actual_weight`, the weight the call is expected to
+		/// actually consume, if given.
+		fn query_call_fee_in_asset(
+			who: AccountId,
+			call: Call,
+			len: u32,
+			asset_id: AssetId,
+			actual_weight: Option<sp_weights::Weight>,
+		) -> Result<AssetBalance, TransactionValidityError>;
+	}
 }
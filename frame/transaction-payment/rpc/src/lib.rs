@@ -33,7 +33,10 @@ use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
 
-pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
+pub use pallet_transaction_payment_rpc_runtime_api::{
+	AssetTransactionPaymentApi as AssetTransactionPaymentRuntimeApi,
+	TransactionPaymentApi as TransactionPaymentRuntimeApi,
+};
 
 #[rpc(client, server)]
 pub trait TransactionPaymentApi<BlockHash, ResponseType> {
@@ -48,6 +51,18 @@ pub trait TransactionPaymentApi<BlockHash, ResponseType> {
 	) -> RpcResult<FeeDetails<NumberOrHex>>;
 }
 
+#[rpc(client, server)]
+pub trait AssetTransactionPaymentApi<BlockHash, AssetId> {
+	#[method(name = "payment_queryFeeInAsset")]
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		actual_weight: Option<sp_weights::Weight>,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+}
+
 /// Provides RPC methods to query a dispatchable's class, weight and fee.
 pub struct TransactionPayment<C, P> {
 	/// Shared reference to the client.
@@ -62,12 +77,28 @@ impl<C, P> TransactionPayment<C, P> {
 	}
 }
 
+/// Provides RPC methods to query the fee of a dispatchable when paid in an asset.
+pub struct AssetTransactionPayment<C, P> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> AssetTransactionPayment<C, P> {
+	/// Creates a new instance of the AssetTransactionPayment Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
 /// Error type of this RPC api.
 pub enum Error {
 	/// The transaction was not decodable.
 	DecodeError,
 	/// The call to runtime failed.
 	RuntimeError,
+	/// The fee can't be paid in the requested asset.
+	PaymentError,
 }
 
 impl From<Error> for i32 {
@@ -75,6 +106,7 @@ impl From<Error> for i32 {
 		match e {
 			Error::RuntimeError => 1,
 			Error::DecodeError => 2,
+			Error::PaymentError => 3,
 		}
 	}
 }
@@ -176,3 +208,59 @@ where
 		})
 	}
 }
+
+impl<C, Block, AssetId, AssetBalance>
+	AssetTransactionPaymentApiServer<<Block as BlockT>::Hash, AssetId>
+	for AssetTransactionPayment<C, (Block, AssetBalance)>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AssetTransactionPaymentRuntimeApi<Block, AssetId, AssetBalance>,
+	AssetId: Codec + Send + Sync + 'static,
+	AssetBalance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		actual_weight: Option<sp_weights::Weight>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let encoded_len = encoded_xt.len() as u32;
+
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to query fee in asset.",
+				Some(format!("{:?}", e)),
+			))
+		})?;
+		let fee = api
+			.query_fee_in_asset(at_hash, uxt, encoded_len, asset_id, actual_weight)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query fee in asset.",
+					Some(e.to_string()),
+				))
+			})?
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::PaymentError.into(),
+					"Unable to pay the fee in asset.",
+					Some(format!("{:?}", e)),
+				))
+			})?;
+
+		fee.try_into().map_err(|_| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				ErrorCode::InvalidParams.code(),
+				format!("{} doesn't fit in NumberOrHex representation", fee),
+				None::<()>,
+			)))
+		})
+	}
+}
@@ -26,6 +26,7 @@ use frame_support::weights::Weight;
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api for testing the execution of a runtime upgrade.
+	#[api_version(2)]
 	pub trait TryRuntime {
 		/// dry-run runtime upgrades, returning the total weight consumed.
 		///
@@ -50,5 +51,12 @@ sp_api::decl_runtime_apis! {
 			signature_check: bool,
 			try_state: TryStateSelect,
 		) -> Weight;
+
+		/// Whether a multi-block migration is ongoing.
+		///
+		/// The multi-block migrations started by a runtime upgrade run at the end of the next
+		/// blocks, for as long as this returns `true`.
+		#[api_version(2)]
+		fn multi_block_migrations_ongoing() -> bool;
 	}
 }
@@ -48,6 +48,7 @@ pub use sp_std::{ops::Deref, vec::Vec};
 pub mod bls377;
 #[cfg(feature = "bls-experimental")]
 pub mod bls381;
+pub mod ecdsa;
 pub mod ed25519;
 pub mod sr25519;
 mod traits;
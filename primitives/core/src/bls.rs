@@ -31,9 +31,11 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use w3f_bls::{DoublePublicKey, DoubleSignature, EngineBLS, SerializableToBytes, TinyBLS381};
+use w3f_bls::{
+	DoublePublicKey, DoubleSignature, EngineBLS, Message, SerializableToBytes, TinyBLS381,
+};
 #[cfg(feature = "full_crypto")]
-use w3f_bls::{DoublePublicKeyScheme, Keypair, Message, SecretKey};
+use w3f_bls::{DoublePublicKeyScheme, Keypair, SecretKey};
 
 use sp_runtime_interface::pass_by::PassByInner;
 use sp_std::{convert::TryFrom, marker::PhantomData, ops::Deref};
@@ -53,6 +55,50 @@ pub mod bls377 {
 	pub type Public = super::Public<TinyBLS377>;
 	/// BLS12-377 signature.
 	pub type Signature = super::Signature<TinyBLS377>;
+	/// BLS12-377 signature aggregated from signatures on a single message.
+	pub type AggregateSignature = super::AggregateSignature<TinyBLS377>;
+
+	/// Verify a BLS12-377 `signature` by `public` on `message`.
+	///
+	/// Unlike `Pair::verify`, this is available without `full_crypto`, so runtimes can verify
+	/// signatures in pure Rust.
+	pub fn verify(signature: &Signature, message: &[u8], public: &Public) -> bool {
+		super::verify(signature, message, public)
+	}
+
+	/// Aggregate BLS12-377 signatures on a single message.
+	///
+	/// Returns `None` if `signatures` is empty or any of them is malformed.
+	pub fn aggregate_signatures(signatures: &[Signature]) -> Option<AggregateSignature> {
+		super::aggregate_signatures(signatures)
+	}
+
+	/// Verify BLS12-377 signatures by `public_keys` on the same `message`.
+	///
+	/// The signatures are aggregated so only a single pairing check is performed. This is only
+	/// sound if every public key has been proven to be in the possession of its owner.
+	pub fn aggregate_verify(
+		signatures: &[Signature],
+		message: &[u8],
+		public_keys: &[Public],
+	) -> bool {
+		signatures.len() == public_keys.len() &&
+			aggregate_signatures(signatures).map_or(false, |signature| {
+				fast_aggregate_verify(&signature, message, public_keys)
+			})
+	}
+
+	/// Verify a BLS12-377 aggregate signature by `public_keys` on `message`.
+	///
+	/// This is only sound if every public key has been proven to be in the possession of its
+	/// owner.
+	pub fn fast_aggregate_verify(
+		signature: &AggregateSignature,
+		message: &[u8],
+		public_keys: &[Public],
+	) -> bool {
+		super::fast_aggregate_verify(signature, message, public_keys)
+	}
 
 	impl super::HardJunctionId for TinyBLS377 {
 		const ID: &'static str = "BLS12377HDKD";
@@ -74,6 +120,50 @@ pub mod bls381 {
 	pub type Public = super::Public<TinyBLS381>;
 	/// BLS12-381 signature.
 	pub type Signature = super::Signature<TinyBLS381>;
+	/// BLS12-381 signature aggregated from signatures on a single message.
+	pub type AggregateSignature = super::AggregateSignature<TinyBLS381>;
+
+	/// Verify a BLS12-381 `signature` by `public` on `message`.
+	///
+	/// Unlike `Pair::verify`, this is available without `full_crypto`, so runtimes can verify
+	/// signatures in pure Rust.
+	pub fn verify(signature: &Signature, message: &[u8], public: &Public) -> bool {
+		super::verify(signature, message, public)
+	}
+
+	/// Aggregate BLS12-381 signatures on a single message.
+	///
+	/// Returns `None` if `signatures` is empty or any of them is malformed.
+	pub fn aggregate_signatures(signatures: &[Signature]) -> Option<AggregateSignature> {
+		super::aggregate_signatures(signatures)
+	}
+
+	/// Verify BLS12-381 signatures by `public_keys` on the same `message`.
+	///
+	/// The signatures are aggregated so only a single pairing check is performed. This is only
+	/// sound if every public key has been proven to be in the possession of its owner.
+	pub fn aggregate_verify(
+		signatures: &[Signature],
+		message: &[u8],
+		public_keys: &[Public],
+	) -> bool {
+		signatures.len() == public_keys.len() &&
+			aggregate_signatures(signatures).map_or(false, |signature| {
+				fast_aggregate_verify(&signature, message, public_keys)
+			})
+	}
+
+	/// Verify a BLS12-381 aggregate signature by `public_keys` on `message`.
+	///
+	/// This is only sound if every public key has been proven to be in the possession of its
+	/// owner.
+	pub fn fast_aggregate_verify(
+		signature: &AggregateSignature,
+		message: &[u8],
+		public_keys: &[Public],
+	) -> bool {
+		super::fast_aggregate_verify(signature, message, public_keys)
+	}
 
 	impl super::HardJunctionId for TinyBLS381 {
 		const ID: &'static str = "BLS12381HDKD";
@@ -97,6 +187,10 @@ const PUBLIC_KEY_SERIALIZED_SIZE: usize =
 const SIGNATURE_SERIALIZED_SIZE: usize =
 	<DoubleSignature<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
 
+// Aggregate signature serialized size
+const AGGREGATE_SIGNATURE_SERIALIZED_SIZE: usize =
+	<w3f_bls::Signature<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
+
 /// A secret seed.
 ///
 /// It's not called a "secret key" because ring doesn't expose the secret keys
@@ -392,11 +486,164 @@ impl<T> UncheckedFrom<[u8; SIGNATURE_SERIALIZED_SIZE]> for Signature<T> {
 	}
 }
 
+impl<T> PassByInner for Signature<T> {
+	type Inner = [u8; SIGNATURE_SERIALIZED_SIZE];
+
+	fn into_inner(self) -> Self::Inner {
+		self.inner
+	}
+
+	fn inner(&self) -> &Self::Inner {
+		&self.inner
+	}
+
+	fn from_inner(inner: Self::Inner) -> Self {
+		Self { inner, _phantom: PhantomData }
+	}
+}
+
 impl<T: BlsBound> CryptoType for Signature<T> {
 	#[cfg(feature = "full_crypto")]
 	type Pair = Pair<T>;
 }
 
+/// A generic BLS signature aggregated from signatures on a single message.
+///
+/// Unlike [`Signature`], this only carries the signature itself and not the proof binding it
+/// to the signer's public key in the signature group, as the latter can't be aggregated.
+#[derive(Copy, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct AggregateSignature<T> {
+	inner: [u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE],
+	_phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for AggregateSignature<T> {
+	fn clone(&self) -> Self {
+		Self { inner: self.inner, _phantom: PhantomData }
+	}
+}
+
+impl<T> PartialEq for AggregateSignature<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+
+impl<T> Eq for AggregateSignature<T> {}
+
+impl<T> TryFrom<&[u8]> for AggregateSignature<T> {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() != AGGREGATE_SIGNATURE_SERIALIZED_SIZE {
+			return Err(())
+		}
+		let mut inner = [0u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE];
+		inner.copy_from_slice(data);
+		Ok(AggregateSignature::unchecked_from(inner))
+	}
+}
+
+impl<T> AsRef<[u8]> for AggregateSignature<T> {
+	fn as_ref(&self) -> &[u8] {
+		&self.inner[..]
+	}
+}
+
+impl<T> sp_std::fmt::Debug for AggregateSignature<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.inner))
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T> UncheckedFrom<[u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE]> for AggregateSignature<T> {
+	fn unchecked_from(data: [u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE]) -> Self {
+		AggregateSignature { inner: data, _phantom: PhantomData }
+	}
+}
+
+impl<T> PassByInner for AggregateSignature<T> {
+	type Inner = [u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE];
+
+	fn into_inner(self) -> Self::Inner {
+		self.inner
+	}
+
+	fn inner(&self) -> &Self::Inner {
+		&self.inner
+	}
+
+	fn from_inner(inner: Self::Inner) -> Self {
+		Self { inner, _phantom: PhantomData }
+	}
+}
+
+/// Verify a double signature against a double public key.
+fn verify<T: EngineBLS>(signature: &Signature<T>, message: &[u8], public: &Public<T>) -> bool {
+	let Ok(public) = DoublePublicKey::<T>::from_bytes(&public.inner) else { return false };
+	let Ok(signature) = DoubleSignature::<T>::from_bytes(&signature.inner) else { return false };
+
+	signature.verify(&Message::new(b"", message), &public)
+}
+
+/// Sum the signatures, dropping the proofs which bind them to the signers' keys.
+fn aggregate_signatures<T: EngineBLS>(
+	signatures: &[Signature<T>],
+) -> Option<AggregateSignature<T>> {
+	let signature_size = <w3f_bls::Signature<T> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
+	if signature_size != AGGREGATE_SIGNATURE_SERIALIZED_SIZE {
+		return None
+	}
+
+	// A serialized double signature starts with the signature itself
+	let mut signatures = signatures.iter().map(|signature| {
+		w3f_bls::Signature::<T>::from_bytes(&signature.inner[..signature_size])
+			.ok()
+			.map(|s| s.0)
+	});
+	let mut aggregate = signatures.next()??;
+	for signature in signatures {
+		aggregate += signature?;
+	}
+
+	let mut inner = [0u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE];
+	inner.copy_from_slice(&w3f_bls::Signature::<T>(aggregate).to_bytes());
+	Some(AggregateSignature::unchecked_from(inner))
+}
+
+/// Verify an aggregate signature against the sum of the keys in the public key group.
+fn fast_aggregate_verify<T: EngineBLS>(
+	signature: &AggregateSignature<T>,
+	message: &[u8],
+	public_keys: &[Public<T>],
+) -> bool {
+	let Ok(signature) = w3f_bls::Signature::<T>::from_bytes(&signature.inner) else { return false };
+
+	// A serialized double public key ends with the key in the public key group
+	let Some(offset) = <DoublePublicKey<T> as SerializableToBytes>::SERIALIZED_BYTES_SIZE
+		.checked_sub(<w3f_bls::PublicKey<T> as SerializableToBytes>::SERIALIZED_BYTES_SIZE)
+	else {
+		return false
+	};
+	let mut public_keys = public_keys.iter().map(|public| {
+		w3f_bls::PublicKey::<T>::from_bytes(&public.inner[offset..]).ok().map(|p| p.0)
+	});
+	let Some(Some(mut aggregate)) = public_keys.next() else { return false };
+	for public in public_keys {
+		let Some(public) = public else { return false };
+		aggregate += public;
+	}
+
+	signature.verify(&Message::new(b"", message), &w3f_bls::PublicKey::<T>(aggregate))
+}
+
 /// A key pair.
 #[cfg(feature = "full_crypto")]
 pub struct Pair<T: EngineBLS>(Keypair<T>);
@@ -472,26 +719,7 @@ impl<T: BlsBound> TraitPair for Pair<T> {
 	}
 
 	fn verify<M: AsRef<[u8]>>(sig: &Self::Signature, message: M, pubkey: &Self::Public) -> bool {
-		let pubkey_array: [u8; PUBLIC_KEY_SERIALIZED_SIZE] =
-			match <[u8; PUBLIC_KEY_SERIALIZED_SIZE]>::try_from(pubkey.as_ref()) {
-				Ok(pk) => pk,
-				Err(_) => return false,
-			};
-		let public_key = match w3f_bls::double::DoublePublicKey::<T>::from_bytes(&pubkey_array) {
-			Ok(pk) => pk,
-			Err(_) => return false,
-		};
-
-		let sig_array = match sig.inner[..].try_into() {
-			Ok(s) => s,
-			Err(_) => return false,
-		};
-		let sig = match w3f_bls::double::DoubleSignature::from_bytes(sig_array) {
-			Ok(s) => s,
-			Err(_) => return false,
-		};
-
-		sig.verify(&Message::new(b"", message.as_ref()), &public_key)
+		verify(sig, message.as_ref(), pubkey)
 	}
 
 	/// Get the seed for this key.
@@ -673,4 +901,49 @@ mod test {
 		// Poorly-sized
 		assert!(deserialize_signature("\"abc123\"").is_err());
 	}
+
+	#[test]
+	fn aggregate_verify_works() {
+		let pairs: Vec<_> = (0..4u8)
+			.map(|i| bls381::Pair::from_seed(&[i + 1; SECRET_KEY_SERIALIZED_SIZE]))
+			.collect();
+		let publics: Vec<_> = pairs.iter().map(|pair| pair.public()).collect();
+		let message = b"Something important";
+		let signatures: Vec<_> = pairs.iter().map(|pair| pair.sign(&message[..])).collect();
+
+		assert!(bls381::aggregate_verify(&signatures, &message[..], &publics));
+		assert!(!bls381::aggregate_verify(&signatures, b"Something else", &publics));
+		// Every signer has to be accounted for
+		assert!(!bls381::aggregate_verify(&signatures[1..], &message[..], &publics));
+		assert!(!bls381::aggregate_verify(&signatures[1..], &message[..], &publics[..3]));
+		assert!(!bls381::aggregate_verify(&[], &message[..], &[]));
+
+		let aggregate = bls381::aggregate_signatures(&signatures).unwrap();
+		assert!(bls381::fast_aggregate_verify(&aggregate, &message[..], &publics));
+		assert!(!bls381::fast_aggregate_verify(&aggregate, &message[..], &publics[1..]));
+		assert!(!bls381::fast_aggregate_verify(&aggregate, b"Something else", &publics));
+
+		// A single signature aggregates to itself
+		let single = bls381::aggregate_signatures(&signatures[..1]).unwrap();
+		assert!(bls381::fast_aggregate_verify(&single, &message[..], &publics[..1]));
+		assert!(bls381::aggregate_signatures(&[]).is_none());
+	}
+
+	#[test]
+	fn aggregate_verify_works_on_bls377() {
+		let pairs: Vec<_> = (0..4u8)
+			.map(|i| Pair::from_seed(&[i + 1; SECRET_KEY_SERIALIZED_SIZE]))
+			.collect();
+		let publics: Vec<_> = pairs.iter().map(|pair| pair.public()).collect();
+		let message = b"Something important";
+		let signatures: Vec<_> = pairs.iter().map(|pair| pair.sign(&message[..])).collect();
+
+		assert!(bls377::verify(&signatures[0], &message[..], &publics[0]));
+		assert!(!bls377::verify(&signatures[0], &message[..], &publics[1]));
+		assert!(bls377::aggregate_verify(&signatures, &message[..], &publics));
+
+		let aggregate = bls377::aggregate_signatures(&signatures).unwrap();
+		assert!(bls377::fast_aggregate_verify(&aggregate, &message[..], &publics));
+		assert!(!bls377::fast_aggregate_verify(&aggregate, &message[..], &publics[1..]));
+	}
 }
@@ -0,0 +1,667 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// tag::description[]
+//! Simple ECDSA secp256k1 API.
+// end::description[]
+
+#[cfg(feature = "full_crypto")]
+use sp_std::vec::Vec;
+
+use crate::crypto::ByteArray;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+#[cfg(feature = "serde")]
+use crate::crypto::Ss58Codec;
+use crate::crypto::{
+	CryptoType, CryptoTypeId, Derive, FromEntropy, Public as TraitPublic, UncheckedFrom,
+};
+#[cfg(feature = "full_crypto")]
+use crate::crypto::{DeriveError, DeriveJunction, Pair as TraitPair, SecretStringError};
+#[cfg(feature = "full_crypto")]
+use core::convert::TryFrom;
+#[cfg(feature = "full_crypto")]
+use k256::ecdsa::{RecoveryId, SigningKey, VerifyingKey};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sp_runtime_interface::pass_by::PassByInner;
+#[cfg(all(not(feature = "std"), feature = "serde"))]
+use sp_std::alloc::{format, string::String};
+use sp_std::ops::Deref;
+
+/// An identifier used to match public keys against ecdsa keys
+pub const CRYPTO_ID: CryptoTypeId = CryptoTypeId(*b"ecds");
+
+/// The byte length of a compressed public key.
+pub const PUBLIC_KEY_SERIALIZED_SIZE: usize = 33;
+
+/// The byte length of a signature, including its recovery ID.
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 65;
+
+/// A secret seed (which is bytewise essentially equivalent to a SecretKey).
+#[cfg(feature = "full_crypto")]
+type Seed = [u8; 32];
+
+/// The ECDSA compressed public key.
+#[cfg_attr(feature = "full_crypto", derive(Hash))]
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	PassByInner,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct Public(pub [u8; PUBLIC_KEY_SERIALIZED_SIZE]);
+
+/// A key pair.
+#[cfg(feature = "full_crypto")]
+#[derive(Clone)]
+pub struct Pair {
+	public: Public,
+	secret: SigningKey,
+}
+
+impl FromEntropy for Public {
+	fn from_entropy(input: &mut impl codec::Input) -> Result<Self, codec::Error> {
+		let mut result = Self([0u8; PUBLIC_KEY_SERIALIZED_SIZE]);
+		input.read(&mut result.0[..])?;
+		Ok(result)
+	}
+}
+
+impl AsRef<[u8; PUBLIC_KEY_SERIALIZED_SIZE]> for Public {
+	fn as_ref(&self) -> &[u8; PUBLIC_KEY_SERIALIZED_SIZE] {
+		&self.0
+	}
+}
+
+impl AsRef<[u8]> for Public {
+	fn as_ref(&self) -> &[u8] {
+		&self.0[..]
+	}
+}
+
+impl AsMut<[u8]> for Public {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.0[..]
+	}
+}
+
+impl Deref for Public {
+	type Target = [u8];
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl TryFrom<&[u8]> for Public {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() != Self::LEN {
+			return Err(())
+		}
+		let mut r = [0u8; Self::LEN];
+		r.copy_from_slice(data);
+		Ok(Self::unchecked_from(r))
+	}
+}
+
+impl From<Public> for [u8; PUBLIC_KEY_SERIALIZED_SIZE] {
+	fn from(x: Public) -> Self {
+		x.0
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl From<Pair> for Public {
+	fn from(x: Pair) -> Self {
+		x.public()
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::str::FromStr for Public {
+	type Err = crate::crypto::PublicError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::from_ss58check(s)
+	}
+}
+
+impl UncheckedFrom<[u8; PUBLIC_KEY_SERIALIZED_SIZE]> for Public {
+	fn unchecked_from(x: [u8; PUBLIC_KEY_SERIALIZED_SIZE]) -> Self {
+		Public::from_raw(x)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for Public {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{}", self.to_ss58check())
+	}
+}
+
+impl sp_std::fmt::Debug for Public {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		let s = self.to_ss58check();
+		write!(f, "{} ({}...)", crate::hexdisplay::HexDisplay::from(&self.0), &s[0..8])
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Public {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&self.to_ss58check())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Public {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Public::from_ss58check(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+/// A signature (a 512-bit value, plus 8 bits for the recovery ID).
+#[cfg_attr(feature = "full_crypto", derive(Hash))]
+#[derive(Clone, Encode, Decode, MaxEncodedLen, PassByInner, TypeInfo, PartialEq, Eq)]
+pub struct Signature(pub [u8; SIGNATURE_SERIALIZED_SIZE]);
+
+impl TryFrom<&[u8]> for Signature {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		Signature::from_slice(data).ok_or(())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Signature {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&array_bytes::bytes2hex("", self))
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Signature {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let signature_hex = array_bytes::hex2bytes(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))?;
+		Signature::try_from(signature_hex.as_ref())
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+impl From<Signature> for [u8; SIGNATURE_SERIALIZED_SIZE] {
+	fn from(v: Signature) -> [u8; SIGNATURE_SERIALIZED_SIZE] {
+		v.0
+	}
+}
+
+impl AsRef<[u8; SIGNATURE_SERIALIZED_SIZE]> for Signature {
+	fn as_ref(&self) -> &[u8; SIGNATURE_SERIALIZED_SIZE] {
+		&self.0
+	}
+}
+
+impl AsRef<[u8]> for Signature {
+	fn as_ref(&self) -> &[u8] {
+		&self.0[..]
+	}
+}
+
+impl AsMut<[u8]> for Signature {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.0[..]
+	}
+}
+
+impl sp_std::fmt::Debug for Signature {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.0))
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl UncheckedFrom<[u8; SIGNATURE_SERIALIZED_SIZE]> for Signature {
+	fn unchecked_from(data: [u8; SIGNATURE_SERIALIZED_SIZE]) -> Signature {
+		Signature(data)
+	}
+}
+
+impl Signature {
+	/// A new instance from the given 65-byte `data`.
+	///
+	/// NOTE: No checking goes on to ensure this is a real signature. Only use it if
+	/// you are certain that the array actually is a signature. GIGO!
+	pub fn from_raw(data: [u8; SIGNATURE_SERIALIZED_SIZE]) -> Signature {
+		Signature(data)
+	}
+
+	/// A new instance from the given slice that should be 65 bytes long.
+	///
+	/// NOTE: No checking goes on to ensure this is a real signature. Only use it if
+	/// you are certain that the array actually is a signature. GIGO!
+	pub fn from_slice(data: &[u8]) -> Option<Self> {
+		if data.len() != SIGNATURE_SERIALIZED_SIZE {
+			return None
+		}
+		let mut r = [0u8; SIGNATURE_SERIALIZED_SIZE];
+		r.copy_from_slice(data);
+		Some(Signature(r))
+	}
+
+	/// Recover the public key from this signature and a message.
+	///
+	/// The message is hashed with `blake2_256`, matching `Pair::sign`.
+	#[cfg(feature = "full_crypto")]
+	pub fn recover<M: AsRef<[u8]>>(&self, message: M) -> Option<Public> {
+		self.recover_prehashed(&sp_core_hashing::blake2_256(message.as_ref()))
+	}
+
+	/// Recover the public key from this signature and a pre-hashed message.
+	#[cfg(feature = "full_crypto")]
+	pub fn recover_prehashed(&self, message: &[u8; 32]) -> Option<Public> {
+		let mut rid = RecoveryId::from_byte(self.0[64])?;
+		let mut sig = k256::ecdsa::Signature::from_slice(&self.0[..64]).ok()?;
+		// k256 only accepts low-S signatures. Normalizing S negates the nonce point, so the
+		// parity of its y coordinate has to be flipped as well.
+		if let Some(normalized) = sig.normalize_s() {
+			sig = normalized;
+			rid = RecoveryId::new(!rid.is_y_odd(), rid.is_x_reduced());
+		}
+		VerifyingKey::recover_from_prehash(message, &sig, rid)
+			.ok()
+			.map(|key| Public::from_verifying_key(&key))
+	}
+}
+
+impl Public {
+	/// A new instance from the given 33-byte `data`.
+	///
+	/// NOTE: No checking goes on to ensure this is a real public key. Only use it if
+	/// you are certain that the array actually is a pubkey. GIGO!
+	pub fn from_raw(data: [u8; PUBLIC_KEY_SERIALIZED_SIZE]) -> Self {
+		Public(data)
+	}
+
+	/// Create a new instance from a full (uncompressed) public key.
+	///
+	/// Both the 65-byte SEC1 encoding and the 64-byte encoding without the leading `0x04` tag
+	/// are accepted. The key is checked to be a valid curve point.
+	#[cfg(feature = "full_crypto")]
+	pub fn from_full(full: &[u8]) -> Result<Self, ()> {
+		let mut tagged = [4u8; 65];
+		let full = match full.len() {
+			64 => {
+				tagged[1..].copy_from_slice(full);
+				&tagged[..]
+			},
+			65 => full,
+			_ => return Err(()),
+		};
+		VerifyingKey::from_sec1_bytes(full)
+			.map(|key| Self::from_verifying_key(&key))
+			.map_err(|_| ())
+	}
+
+	#[cfg(feature = "full_crypto")]
+	fn from_verifying_key(key: &VerifyingKey) -> Self {
+		let mut r = [0u8; PUBLIC_KEY_SERIALIZED_SIZE];
+		r.copy_from_slice(key.to_encoded_point(true).as_bytes());
+		Public(r)
+	}
+}
+
+impl ByteArray for Public {
+	const LEN: usize = PUBLIC_KEY_SERIALIZED_SIZE;
+}
+
+impl TraitPublic for Public {}
+
+impl Derive for Public {}
+
+/// Derive a single hard junction.
+#[cfg(feature = "full_crypto")]
+fn derive_hard_junction(secret_seed: &Seed, cc: &[u8; 32]) -> Seed {
+	("Secp256k1HDKD", secret_seed, cc).using_encoded(sp_core_hashing::blake2_256)
+}
+
+#[cfg(feature = "full_crypto")]
+impl TraitPair for Pair {
+	type Public = Public;
+	type Seed = Seed;
+	type Signature = Signature;
+
+	/// Make a new key pair from secret seed material. The slice must be 32 bytes long and a
+	/// valid secp256k1 scalar or it will return an error.
+	///
+	/// You should never need to use this; generate(), generate_with_phrase
+	fn from_seed_slice(seed_slice: &[u8]) -> Result<Pair, SecretStringError> {
+		if seed_slice.len() != 32 {
+			return Err(SecretStringError::InvalidSeedLength)
+		}
+		let secret =
+			SigningKey::from_slice(seed_slice).map_err(|_| SecretStringError::InvalidSeed)?;
+		let public = Public::from_verifying_key(secret.verifying_key());
+		Ok(Pair { public, secret })
+	}
+
+	/// Derive a child key from a series of given junctions.
+	fn derive<Iter: Iterator<Item = DeriveJunction>>(
+		&self,
+		path: Iter,
+		_seed: Option<Seed>,
+	) -> Result<(Pair, Option<Seed>), DeriveError> {
+		let mut acc = self.seed();
+		for j in path {
+			match j {
+				DeriveJunction::Soft(_cc) => return Err(DeriveError::SoftKeyInPath),
+				DeriveJunction::Hard(cc) => acc = derive_hard_junction(&acc, &cc),
+			}
+		}
+		Ok((Self::from_seed(&acc), Some(acc)))
+	}
+
+	/// Get the public key.
+	fn public(&self) -> Public {
+		self.public
+	}
+
+	/// Sign a message.
+	///
+	/// The message is hashed with `blake2_256` before being signed.
+	fn sign(&self, message: &[u8]) -> Signature {
+		self.sign_prehashed(&sp_core_hashing::blake2_256(message))
+	}
+
+	/// Verify a signature on a message.
+	///
+	/// Returns true if the signature is good.
+	fn verify<M: AsRef<[u8]>>(sig: &Self::Signature, message: M, public: &Self::Public) -> bool {
+		sig.recover(message).map_or(false, |actual| actual == *public)
+	}
+
+	/// Return a vec filled with raw data.
+	fn to_raw_vec(&self) -> Vec<u8> {
+		self.seed().to_vec()
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl Pair {
+	/// Get the seed for this key.
+	pub fn seed(&self) -> Seed {
+		let mut seed = [0u8; 32];
+		seed.copy_from_slice(&self.secret.to_bytes());
+		seed
+	}
+
+	/// Exactly as `from_string` except that if no matches are found then, the the first 32
+	/// characters are taken (padded with spaces as necessary) and used as the MiniSecretKey.
+	#[cfg(feature = "std")]
+	pub fn from_legacy_string(s: &str, password_override: Option<&str>) -> Pair {
+		Self::from_string(s, password_override).unwrap_or_else(|_| {
+			let mut padded_seed: Seed = [b' '; 32];
+			let len = s.len().min(32);
+			padded_seed[..len].copy_from_slice(&s.as_bytes()[..len]);
+			Self::from_seed(&padded_seed)
+		})
+	}
+
+	/// Sign a pre-hashed message.
+	pub fn sign_prehashed(&self, message: &[u8; 32]) -> Signature {
+		let (sig, rid) = self
+			.secret
+			.sign_prehash_recoverable(message)
+			.expect("signing only fails for digests of an invalid length; 32 bytes is valid; qed");
+		let mut r = [0u8; SIGNATURE_SERIALIZED_SIZE];
+		r[..64].copy_from_slice(&sig.to_bytes());
+		r[64] = rid.to_byte();
+		Signature(r)
+	}
+
+	/// Verify a signature on a pre-hashed message.
+	///
+	/// Returns true if the signature is good.
+	pub fn verify_prehashed(sig: &Signature, message: &[u8; 32], public: &Public) -> bool {
+		sig.recover_prehashed(message).map_or(false, |actual| actual == *public)
+	}
+}
+
+impl CryptoType for Public {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair;
+}
+
+impl CryptoType for Signature {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair;
+}
+
+#[cfg(feature = "full_crypto")]
+impl CryptoType for Pair {
+	type Pair = Pair;
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::crypto::DEV_PHRASE;
+	use serde_json;
+
+	#[test]
+	fn default_phrase_should_be_used() {
+		assert_eq!(
+			Pair::from_string("//Alice///password", None).unwrap().public(),
+			Pair::from_string(&format!("{}//Alice", DEV_PHRASE), Some("password"))
+				.unwrap()
+				.public(),
+		);
+	}
+
+	#[test]
+	fn seed_and_derive_should_work() {
+		let seed = array_bytes::hex2array_unchecked(
+			"9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+		);
+		let pair = Pair::from_seed(&seed);
+		assert_eq!(pair.seed(), seed);
+		let path = vec![DeriveJunction::Hard([0u8; 32])];
+		let derived = pair.derive(path.into_iter(), None).ok().unwrap().0;
+		assert_eq!(derived.seed(), derive_hard_junction(&seed, &[0u8; 32]));
+		assert_ne!(derived.public(), pair.public());
+	}
+
+	#[test]
+	fn soft_derivation_is_rejected() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let path = vec![DeriveJunction::Soft([0u8; 32])];
+		assert!(matches!(pair.derive(path.into_iter(), None), Err(DeriveError::SoftKeyInPath)));
+	}
+
+	#[test]
+	fn invalid_seeds_are_rejected() {
+		assert_eq!(
+			Pair::from_seed_slice(&[1u8; 31]).err(),
+			Some(SecretStringError::InvalidSeedLength)
+		);
+		// Zero isn't a valid secp256k1 scalar.
+		assert_eq!(Pair::from_seed_slice(&[0u8; 32]).err(), Some(SecretStringError::InvalidSeed));
+		// Neither is anything at or above the group order.
+		assert_eq!(Pair::from_seed_slice(&[0xff; 32]).err(), Some(SecretStringError::InvalidSeed));
+	}
+
+	#[test]
+	fn generated_pair_should_work() {
+		let (pair, _) = Pair::generate();
+		let public = pair.public();
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		assert!(Pair::verify(&signature, &message[..], &public));
+		assert!(!Pair::verify(&signature, b"Something else", &public));
+	}
+
+	#[test]
+	fn seeded_pair_should_work() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+		assert_eq!(Pair::from_seed(b"12345678901234567890123456789012").public(), public);
+		// Compressed SEC1 encodings are tagged by the parity of the y coordinate.
+		assert!(public.0[0] == 2 || public.0[0] == 3);
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		assert!(Pair::verify(&signature, &message[..], &public));
+		assert!(!Pair::verify(&signature, "Other message", &public));
+		assert!(!Pair::verify(&signature, &message[..], &Pair::from_seed(&[1u8; 32]).public()));
+	}
+
+	#[test]
+	fn signature_recovery_works() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		assert_eq!(signature.recover(&message[..]), Some(pair.public()));
+		assert_ne!(signature.recover(b"Something else"), Some(pair.public()));
+
+		// An out-of-range recovery ID is rejected.
+		let mut bad = signature.clone();
+		bad.0[64] = 4;
+		assert_eq!(bad.recover(&message[..]), None);
+	}
+
+	#[test]
+	fn prehashed_signing_works() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let message = b"Something important";
+		let hash = sp_core_hashing::blake2_256(message);
+		let signature = pair.sign_prehashed(&hash);
+		assert!(Pair::verify_prehashed(&signature, &hash, &pair.public()));
+		assert!(!Pair::verify_prehashed(&signature, &[0u8; 32], &pair.public()));
+		// Signing the hash directly is equivalent to signing the message.
+		assert!(Pair::verify(&signature, &message[..], &pair.public()));
+	}
+
+	#[test]
+	fn high_s_signatures_are_recovered() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+
+		// Negate S and flip the recovery ID's parity, yielding the equivalent high-S signature.
+		let sig = k256::ecdsa::Signature::from_slice(&signature.0[..64]).unwrap();
+		let (r, s) = sig.split_scalars();
+		let high = k256::ecdsa::Signature::from_scalars(r, -*s).unwrap();
+		let mut high_s = [0u8; SIGNATURE_SERIALIZED_SIZE];
+		high_s[..64].copy_from_slice(&high.to_bytes());
+		high_s[64] = signature.0[64] ^ 1;
+		assert!(Pair::verify(&Signature(high_s), &message[..], &pair.public()));
+	}
+
+	#[test]
+	fn from_full_works() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let secret = SigningKey::from_slice(&pair.seed()).unwrap();
+		let uncompressed = secret.verifying_key().to_encoded_point(false);
+		assert_eq!(Public::from_full(uncompressed.as_bytes()), Ok(pair.public()));
+		assert_eq!(Public::from_full(&uncompressed.as_bytes()[1..]), Ok(pair.public()));
+		assert!(Public::from_full(&[0u8; 65]).is_err());
+		assert!(Public::from_full(&pair.public().0).is_err());
+	}
+
+	#[test]
+	fn generate_with_phrase_recovery_possible() {
+		let (pair1, phrase, _) = Pair::generate_with_phrase(None);
+		let (pair2, _) = Pair::from_phrase(&phrase, None).unwrap();
+
+		assert_eq!(pair1.public(), pair2.public());
+	}
+
+	#[test]
+	fn password_does_something() {
+		let (pair1, phrase, _) = Pair::generate_with_phrase(Some("password"));
+		let (pair2, _) = Pair::from_phrase(&phrase, None).unwrap();
+
+		assert_ne!(pair1.public(), pair2.public());
+	}
+
+	#[test]
+	fn ss58check_roundtrip_works() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+		let s = public.to_ss58check();
+		let cmp = Public::from_ss58check(&s).unwrap();
+		assert_eq!(cmp, public);
+	}
+
+	#[test]
+	fn signature_serialization_works() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		let serialized_signature = serde_json::to_string(&signature).unwrap();
+		// Signature is 65 bytes, so 130 chars + 2 quote chars
+		assert_eq!(serialized_signature.len(), 132);
+		let signature = serde_json::from_str(&serialized_signature).unwrap();
+		assert!(Pair::verify(&signature, &message[..], &pair.public()));
+	}
+
+	#[test]
+	fn signature_serialization_doesnt_panic() {
+		fn deserialize_signature(text: &str) -> Result<Signature, serde_json::error::Error> {
+			serde_json::from_str(text)
+		}
+		assert!(deserialize_signature("Not valid json.").is_err());
+		assert!(deserialize_signature("\"Not an actual signature.\"").is_err());
+		// Poorly-sized
+		assert!(deserialize_signature("\"abc123\"").is_err());
+	}
+}
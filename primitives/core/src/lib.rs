@@ -59,6 +59,7 @@ pub use paste;
 #[cfg(feature = "bls-experimental")]
 pub mod bls;
 pub mod defer;
+pub mod ecdsa;
 pub mod ed25519;
 pub mod hash;
 #[cfg(feature = "std")]
@@ -0,0 +1,230 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conformance checks for implementations of `Database`.
+//!
+//! These check that a database behaves as documented on `Database` and `Transaction`, and are
+//! meant to be run from the tests of every implementation. They panic on the first violation.
+
+use crate::{ColumnId, Database, Transaction};
+use std::sync::Arc;
+
+/// Columns of the database under test the checks write to.
+#[derive(Debug, Clone, Copy)]
+pub struct TestColumns {
+	/// A column without reference counting.
+	pub plain: ColumnId,
+	/// Another column without reference counting, distinct from `plain`.
+	pub other: ColumnId,
+	/// A column supporting `Transaction::store`, `Transaction::reference` and
+	/// `Transaction::release`. Its keys are 32 bytes long.
+	pub ref_counted: ColumnId,
+}
+
+fn commit<H: Clone + AsRef<[u8]>>(db: &dyn Database<H>, transaction: Transaction<H>) {
+	db.commit(transaction).expect("Committing a valid transaction must succeed");
+}
+
+fn hash<H: From<[u8; 32]>>(seed: u8) -> H {
+	// Spread the bytes, as some databases expect the keys of reference counted columns to be
+	// uniformly distributed.
+	let mut bytes = [0; 32];
+	for (i, byte) in bytes.iter_mut().enumerate() {
+		*byte = seed.wrapping_mul(167).wrapping_add((i as u8).wrapping_mul(71)) ^ 0x5a;
+	}
+	H::from(bytes)
+}
+
+/// Run all checks against `db`, which must be empty.
+pub fn check_all<H>(db: &dyn Database<H>, columns: TestColumns)
+where
+	H: Clone + AsRef<[u8]> + From<[u8; 32]>,
+{
+	check_set_get_remove(db, columns);
+	check_last_change_wins(db, columns);
+	check_columns_are_separate(db, columns);
+	check_large_transaction(db, columns);
+	check_reference_counting(db, columns);
+}
+
+/// Values set are returned by all getters until they're removed.
+pub fn check_set_get_remove<H: Clone + AsRef<[u8]>>(db: &dyn Database<H>, columns: TestColumns) {
+	let col = columns.plain;
+	let mut tx = Transaction::new();
+	tx.set(col, b"set_get_remove_a", b"a");
+	tx.set_from_vec(col, b"set_get_remove_b", vec![2; 1024 * 1024]);
+	commit(db, tx);
+
+	assert_eq!(db.get(col, b"set_get_remove_a"), Some(b"a".to_vec()));
+	assert!(db.contains(col, b"set_get_remove_a"));
+	assert_eq!(db.value_size(col, b"set_get_remove_b"), Some(1024 * 1024));
+	assert_eq!(crate::with_get(db, col, b"set_get_remove_b", |v| v.len()), Some(1024 * 1024));
+	assert_eq!(db.get(col, b"set_get_remove_c"), None);
+	assert!(!db.contains(col, b"set_get_remove_c"));
+	assert_eq!(db.value_size(col, b"set_get_remove_c"), None);
+
+	let mut tx = Transaction::new();
+	tx.set(col, b"set_get_remove_a", b"replaced");
+	tx.remove(col, b"set_get_remove_b");
+	commit(db, tx);
+
+	assert_eq!(db.get(col, b"set_get_remove_a"), Some(b"replaced".to_vec()));
+	assert_eq!(db.get(col, b"set_get_remove_b"), None);
+	assert!(!db.contains(col, b"set_get_remove_b"));
+}
+
+/// Changes to the same key within a transaction are applied in order.
+pub fn check_last_change_wins<H: Clone + AsRef<[u8]>>(db: &dyn Database<H>, columns: TestColumns) {
+	let col = columns.plain;
+	let mut tx = Transaction::new();
+	tx.set(col, b"last_change_wins_a", b"first");
+	tx.set(col, b"last_change_wins_a", b"second");
+	tx.set(col, b"last_change_wins_b", b"set");
+	tx.remove(col, b"last_change_wins_b");
+	commit(db, tx);
+
+	assert_eq!(db.get(col, b"last_change_wins_a"), Some(b"second".to_vec()));
+	assert_eq!(db.get(col, b"last_change_wins_b"), None);
+
+	let mut tx = Transaction::new();
+	tx.remove(col, b"last_change_wins_a");
+	tx.set(col, b"last_change_wins_a", b"third");
+	commit(db, tx);
+
+	assert_eq!(db.get(col, b"last_change_wins_a"), Some(b"third".to_vec()));
+}
+
+/// The same key holds independent values in different columns.
+pub fn check_columns_are_separate<H: Clone + AsRef<[u8]>>(
+	db: &dyn Database<H>,
+	columns: TestColumns,
+) {
+	let mut tx = Transaction::new();
+	tx.set(columns.plain, b"columns_are_separate", b"plain");
+	tx.set(columns.other, b"columns_are_separate", b"other");
+	commit(db, tx);
+
+	assert_eq!(db.get(columns.plain, b"columns_are_separate"), Some(b"plain".to_vec()));
+	assert_eq!(db.get(columns.other, b"columns_are_separate"), Some(b"other".to_vec()));
+
+	let mut tx = Transaction::new();
+	tx.remove(columns.other, b"columns_are_separate");
+	commit(db, tx);
+
+	assert_eq!(db.get(columns.plain, b"columns_are_separate"), Some(b"plain".to_vec()));
+	assert_eq!(db.get(columns.other, b"columns_are_separate"), None);
+}
+
+/// All changes of a transaction touching many keys are applied.
+pub fn check_large_transaction<H: Clone + AsRef<[u8]>>(db: &dyn Database<H>, columns: TestColumns) {
+	let key = |i: u32| [&b"large_transaction_"[..], &i.to_le_bytes()[..]].concat();
+	let mut tx = Transaction::new();
+	for i in 0..10_000u32 {
+		tx.set(columns.plain, &key(i), &i.to_be_bytes());
+	}
+	commit(db, tx);
+
+	for i in 0..10_000u32 {
+		assert_eq!(db.get(columns.plain, &key(i)), Some(i.to_be_bytes().to_vec()));
+	}
+}
+
+/// A stored preimage is kept until it was released as often as it was stored and referenced.
+pub fn check_reference_counting<H>(db: &dyn Database<H>, columns: TestColumns)
+where
+	H: Clone + AsRef<[u8]> + From<[u8; 32]>,
+{
+	let col = columns.ref_counted;
+	let key: H = hash(1);
+
+	let mut tx = Transaction::new();
+	tx.store(col, key.clone(), b"preimage".to_vec());
+	commit(db, tx);
+	assert_eq!(db.get(col, key.as_ref()), Some(b"preimage".to_vec()));
+
+	// Storing again only adds a reference.
+	let mut tx = Transaction::new();
+	tx.store(col, key.clone(), b"preimage".to_vec());
+	commit(db, tx);
+	let mut tx = Transaction::new();
+	tx.reference(col, key.clone());
+	commit(db, tx);
+
+	for _ in 0..2 {
+		let mut tx = Transaction::new();
+		tx.release(col, key.clone());
+		commit(db, tx);
+		assert_eq!(db.get(col, key.as_ref()), Some(b"preimage".to_vec()));
+	}
+
+	let mut tx = Transaction::new();
+	tx.release(col, key.clone());
+	commit(db, tx);
+	assert_eq!(db.get(col, key.as_ref()), None);
+	assert!(!db.contains(col, key.as_ref()));
+
+	// Referencing a missing preimage doesn't make it appear.
+	let missing: H = hash(2);
+	let mut tx = Transaction::new();
+	tx.reference(col, missing.clone());
+	commit(db, tx);
+	assert_eq!(db.get(col, missing.as_ref()), None);
+}
+
+/// Committed changes survive reopening the database.
+///
+/// Every call of `open` must open the same database, which must be empty on the first call. The
+/// previously opened instance is dropped before `open` is called again.
+pub fn check_persistence<H, F>(mut open: F, columns: TestColumns)
+where
+	H: Clone + AsRef<[u8]> + From<[u8; 32]>,
+	F: FnMut() -> Arc<dyn Database<H>>,
+{
+	let key: H = hash(3);
+	{
+		let db = open();
+		let mut tx = Transaction::new();
+		tx.set(columns.plain, b"persistence_a", b"a");
+		tx.set(columns.plain, b"persistence_b", b"b");
+		tx.store(columns.ref_counted, key.clone(), b"preimage".to_vec());
+		commit(&*db, tx);
+		let mut tx = Transaction::new();
+		tx.remove(columns.plain, b"persistence_b");
+		tx.store(columns.ref_counted, key.clone(), b"preimage".to_vec());
+		commit(&*db, tx);
+	}
+
+	{
+		let db = open();
+		assert_eq!(db.get(columns.plain, b"persistence_a"), Some(b"a".to_vec()));
+		assert_eq!(db.get(columns.plain, b"persistence_b"), None);
+		assert_eq!(db.get(columns.ref_counted, key.as_ref()), Some(b"preimage".to_vec()));
+
+		// The reference count survived as well.
+		let mut tx = Transaction::new();
+		tx.release(columns.ref_counted, key.clone());
+		commit(&*db, tx);
+	}
+
+	let db = open();
+	assert_eq!(db.get(columns.ref_counted, key.as_ref()), Some(b"preimage".to_vec()));
+	let mut tx = Transaction::new();
+	tx.release(columns.ref_counted, key.clone());
+	commit(&*db, tx);
+	assert_eq!(db.get(columns.ref_counted, key.as_ref()), None);
+}
@@ -0,0 +1,418 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistent, embedded implementation of `Database`, for tests and small development chains.
+//!
+//! Every committed transaction is appended to a log file as a single checksummed record, holding
+//! the resulting reference count and value of every key the transaction touched. The live data is
+//! kept in memory and rebuilt from the log when the database is opened, so this is not meant for
+//! production: the whole database has to fit in memory and opening it replays the entire log.
+//! Once the log holds considerably more entries than there are live keys, it is compacted by
+//! rewriting it from memory.
+//!
+//! A transaction is durable once `commit` returned. A record torn by a crash while it was written
+//! is discarded when the database is opened again.
+
+use crate::{error, Change, ColumnId, Database, Transaction};
+use parking_lot::RwLock;
+use std::{
+	collections::HashMap,
+	fs::{self, File, OpenOptions},
+	io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+	path::{Path, PathBuf},
+};
+
+const LOG_FILE: &str = "data.log";
+const COMPACTED_LOG_FILE: &str = "data.log.tmp";
+const MAGIC: &[u8; 8] = b"spfiledb";
+const VERSION: u32 = 1;
+const HEADER_LEN: u64 = 12;
+/// Length of the payload length and checksum preceding every record.
+const RECORD_HEADER_LEN: u64 = 12;
+/// Entries which may be written to the log on top of twice the number of live keys before the log
+/// is compacted.
+const COMPACTION_SLACK: u64 = 100_000;
+/// Size of the payload after which a new record is started when writing a snapshot.
+const SNAPSHOT_RECORD_SIZE: usize = 16 * 1024 * 1024;
+
+/// Options to open a [`FileDb`] with.
+#[derive(Debug, Clone, Default)]
+pub struct FileDbOptions {
+	/// Number of columns.
+	pub columns: u32,
+	/// Columns in which `Change::Set` and `Change::Remove` increase and decrease the reference
+	/// count of a value, instead of replacing it.
+	pub ref_counted: Vec<ColumnId>,
+}
+
+type Column = HashMap<Vec<u8>, (u32, Vec<u8>)>;
+
+struct Inner {
+	columns: Vec<Column>,
+	log: File,
+	/// Number of entries written to the log since it was last compacted.
+	log_entries: u64,
+}
+
+/// This implements `Database` as a log file in a directory, see the module documentation.
+///
+/// The database must not be opened by more than one instance at a time.
+pub struct FileDb {
+	path: PathBuf,
+	ref_counted: Vec<ColumnId>,
+	inner: RwLock<Inner>,
+}
+
+fn invalid_data(message: String) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn db_err(e: io::Error) -> error::DatabaseError {
+	error::DatabaseError(Box::new(e))
+}
+
+/// 64-bit FNV-1a, used to detect torn and corrupted records.
+fn checksum(data: &[u8]) -> u64 {
+	data.iter()
+		.fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn encode_entry(payload: &mut Vec<u8>, col: ColumnId, key: &[u8], value: Option<&(u32, Vec<u8>)>) {
+	payload.extend_from_slice(&col.to_le_bytes());
+	payload.extend_from_slice(&(key.len() as u32).to_le_bytes());
+	payload.extend_from_slice(key);
+	match value {
+		Some((rc, value)) => {
+			payload.extend_from_slice(&rc.to_le_bytes());
+			payload.extend_from_slice(&(value.len() as u32).to_le_bytes());
+			payload.extend_from_slice(value);
+		},
+		None => payload.extend_from_slice(&0u32.to_le_bytes()),
+	}
+}
+
+/// Apply the entries of a record to `columns`, returning the number of entries.
+fn apply_record(mut payload: &[u8], columns: &mut [Column]) -> io::Result<u64> {
+	fn take<'a>(payload: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+		if payload.len() < len {
+			return Err(invalid_data("Truncated log entry".into()))
+		}
+		let (taken, rest) = payload.split_at(len);
+		*payload = rest;
+		Ok(taken)
+	}
+	fn take_u32(payload: &mut &[u8]) -> io::Result<u32> {
+		let mut bytes = [0; 4];
+		bytes.copy_from_slice(take(payload, 4)?);
+		Ok(u32::from_le_bytes(bytes))
+	}
+
+	let mut entries = 0;
+	while !payload.is_empty() {
+		let col = take_u32(&mut payload)?;
+		let key_len = take_u32(&mut payload)? as usize;
+		let key = take(&mut payload, key_len)?.to_vec();
+		let rc = take_u32(&mut payload)?;
+		let column = columns
+			.get_mut(col as usize)
+			.ok_or_else(|| invalid_data(format!("Log entry for unknown column {}", col)))?;
+		if rc == 0 {
+			column.remove(&key);
+		} else {
+			let value_len = take_u32(&mut payload)? as usize;
+			let value = take(&mut payload, value_len)?.to_vec();
+			column.insert(key, (rc, value));
+		}
+		entries += 1;
+	}
+	Ok(entries)
+}
+
+/// Write a log holding the given columns to `path`, returning the number of entries written.
+fn write_snapshot(path: &Path, columns: &[Column]) -> io::Result<u64> {
+	fn write_record(writer: &mut impl Write, payload: &[u8]) -> io::Result<()> {
+		writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+		writer.write_all(&checksum(payload).to_le_bytes())?;
+		writer.write_all(payload)
+	}
+
+	let file = OpenOptions::new().write(true).create(true).truncate(true).open(path)?;
+	let mut writer = BufWriter::new(file);
+	writer.write_all(MAGIC)?;
+	writer.write_all(&VERSION.to_le_bytes())?;
+
+	let mut entries = 0;
+	let mut payload = Vec::new();
+	for (col, column) in columns.iter().enumerate() {
+		for (key, value) in column {
+			encode_entry(&mut payload, col as ColumnId, key, Some(value));
+			entries += 1;
+			if payload.len() >= SNAPSHOT_RECORD_SIZE {
+				write_record(&mut writer, &payload)?;
+				payload.clear();
+			}
+		}
+	}
+	if !payload.is_empty() {
+		write_record(&mut writer, &payload)?;
+	}
+
+	writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+	Ok(entries)
+}
+
+/// Make a rename within `dir` durable.
+fn sync_dir(dir: &Path) -> io::Result<()> {
+	#[cfg(unix)]
+	File::open(dir)?.sync_all()?;
+	#[cfg(not(unix))]
+	let _ = dir;
+	Ok(())
+}
+
+/// Apply the records of `log` to `columns`, returning the number of entries applied.
+///
+/// A torn record at the end of the log is truncated away.
+fn replay(log: &mut File, columns: &mut [Column]) -> io::Result<u64> {
+	let len = log.metadata()?.len();
+	let mut entries = 0;
+	let mut offset = HEADER_LEN;
+	{
+		let mut reader = BufReader::new(&*log);
+		let mut header = [0; HEADER_LEN as usize];
+		reader.read_exact(&mut header)?;
+		if &header[..8] != MAGIC || header[8..] != VERSION.to_le_bytes() {
+			return Err(invalid_data("Not a supported database log".into()))
+		}
+
+		while len - offset >= RECORD_HEADER_LEN {
+			let mut record_header = [0; RECORD_HEADER_LEN as usize];
+			reader.read_exact(&mut record_header)?;
+			let mut payload_len = [0; 4];
+			payload_len.copy_from_slice(&record_header[..4]);
+			let payload_len = u32::from_le_bytes(payload_len) as u64;
+			let mut expected_checksum = [0; 8];
+			expected_checksum.copy_from_slice(&record_header[4..]);
+
+			let end = offset + RECORD_HEADER_LEN + payload_len;
+			if end > len {
+				break
+			}
+			let mut payload = vec![0; payload_len as usize];
+			reader.read_exact(&mut payload)?;
+			if checksum(&payload) != u64::from_le_bytes(expected_checksum) {
+				// Only the last record may have been torn by a crash.
+				if end == len {
+					break
+				}
+				return Err(invalid_data(format!("Corrupted log record at offset {}", offset)))
+			}
+			entries += apply_record(&payload, columns)?;
+			offset = end;
+		}
+	}
+
+	if offset < len {
+		log.set_len(offset)?;
+		log.sync_all()?;
+	}
+	log.seek(SeekFrom::End(0))?;
+	Ok(entries)
+}
+
+impl FileDb {
+	/// Open the database in the directory `path`, creating it if it doesn't exist and `create` is
+	/// set.
+	pub fn open(path: &Path, options: FileDbOptions, create: bool) -> io::Result<FileDb> {
+		let log_path = path.join(LOG_FILE);
+		let compacted_log_path = path.join(COMPACTED_LOG_FILE);
+		let mut columns = vec![Column::new(); options.columns as usize];
+
+		if !log_path.exists() {
+			if !create {
+				return Err(io::Error::new(
+					io::ErrorKind::NotFound,
+					format!("No database found at {:?}", path),
+				))
+			}
+			fs::create_dir_all(path)?;
+			// Written under another name first, so a crash never leaves a partial header behind.
+			write_snapshot(&compacted_log_path, &columns)?;
+			fs::rename(&compacted_log_path, &log_path)?;
+			sync_dir(path)?;
+		} else if compacted_log_path.exists() {
+			// A compaction was interrupted before it replaced the log.
+			fs::remove_file(&compacted_log_path)?;
+		}
+
+		let mut log = OpenOptions::new().read(true).write(true).open(&log_path)?;
+		let log_entries = replay(&mut log, &mut columns)?;
+		Ok(FileDb {
+			path: path.to_path_buf(),
+			ref_counted: options.ref_counted,
+			inner: RwLock::new(Inner { columns, log, log_entries }),
+		})
+	}
+
+	/// Rewrite the log from the live data.
+	fn compact(&self, inner: &mut Inner) -> io::Result<()> {
+		let log_path = self.path.join(LOG_FILE);
+		let compacted_log_path = self.path.join(COMPACTED_LOG_FILE);
+		let entries = write_snapshot(&compacted_log_path, &inner.columns)?;
+		fs::rename(&compacted_log_path, &log_path)?;
+		sync_dir(&self.path)?;
+
+		inner.log = OpenOptions::new().read(true).write(true).open(&log_path)?;
+		inner.log.seek(SeekFrom::End(0))?;
+		inner.log_entries = entries;
+		Ok(())
+	}
+}
+
+impl Inner {
+	/// Append a record holding `payload` to the log.
+	fn append(&mut self, payload: &[u8]) -> io::Result<()> {
+		let payload_len = u32::try_from(payload.len()).map_err(|_| {
+			io::Error::new(io::ErrorKind::InvalidInput, "Transaction too large to be logged")
+		})?;
+		let start = self.log.seek(SeekFrom::End(0))?;
+
+		let mut record = Vec::with_capacity(RECORD_HEADER_LEN as usize + payload.len());
+		record.extend_from_slice(&payload_len.to_le_bytes());
+		record.extend_from_slice(&checksum(payload).to_le_bytes());
+		record.extend_from_slice(payload);
+		let result = self.log.write_all(&record).and_then(|_| self.log.sync_data());
+		if result.is_err() {
+			// Don't leave a partial record behind for the next one to be appended to.
+			let _ = self.log.set_len(start);
+		}
+		result
+	}
+}
+
+impl<H> Database<H> for FileDb
+where
+	H: Clone + AsRef<[u8]>,
+{
+	fn commit(&self, transaction: Transaction<H>) -> error::Result<()> {
+		let mut inner = self.inner.write();
+
+		// The resulting reference count and value of every key the transaction touches.
+		let mut changes: HashMap<(ColumnId, Vec<u8>), Option<(u32, Vec<u8>)>> = HashMap::new();
+		for change in transaction.0.into_iter() {
+			let (col, key) = match &change {
+				Change::Set(col, key, _) | Change::Remove(col, key) => (*col, key.clone()),
+				Change::Store(col, hash, _) |
+				Change::Reference(col, hash) |
+				Change::Release(col, hash) => (*col, hash.as_ref().to_vec()),
+			};
+			let column = inner.columns.get(col as usize).ok_or_else(|| {
+				db_err(io::Error::new(
+					io::ErrorKind::InvalidInput,
+					format!("Change to unknown column {}", col),
+				))
+			})?;
+			let entry = changes
+				.entry((col, key))
+				.or_insert_with_key(|(_, key)| column.get(key).cloned());
+
+			let ref_counted = self.ref_counted.contains(&col);
+			match change {
+				Change::Set(_, _, value) if !ref_counted => *entry = Some((1, value)),
+				Change::Remove(..) if !ref_counted => *entry = None,
+				Change::Set(_, _, value) | Change::Store(_, _, value) => match entry {
+					Some((rc, _)) => *rc = rc.saturating_add(1),
+					None => *entry = Some((1, value)),
+				},
+				Change::Reference(..) =>
+					if let Some((rc, _)) = entry {
+						*rc = rc.saturating_add(1);
+					},
+				Change::Remove(..) | Change::Release(..) =>
+					if let Some((rc, _)) = entry {
+						*rc -= 1;
+						if *rc == 0 {
+							*entry = None;
+						}
+					},
+			}
+		}
+
+		let mut payload = Vec::new();
+		for ((col, key), value) in &changes {
+			encode_entry(&mut payload, *col, key, value.as_ref());
+		}
+		inner.append(&payload).map_err(db_err)?;
+
+		inner.log_entries += changes.len() as u64;
+		for ((col, key), value) in changes {
+			let column = &mut inner.columns[col as usize];
+			match value {
+				Some(value) => {
+					column.insert(key, value);
+				},
+				None => {
+					column.remove(&key);
+				},
+			}
+		}
+
+		let live_entries = inner.columns.iter().map(|column| column.len() as u64).sum::<u64>();
+		if inner.log_entries > 2 * live_entries + COMPACTION_SLACK {
+			// The transaction is committed either way. Compacting is retried with the next commit
+			// should it fail.
+			let _ = self.compact(&mut inner);
+		}
+		Ok(())
+	}
+
+	fn get(&self, col: ColumnId, key: &[u8]) -> Option<Vec<u8>> {
+		let inner = self.inner.read();
+		inner.columns.get(col as usize).and_then(|c| c.get(key).map(|(_, v)| v.clone()))
+	}
+
+	fn contains(&self, col: ColumnId, key: &[u8]) -> bool {
+		let inner = self.inner.read();
+		inner.columns.get(col as usize).map_or(false, |c| c.contains_key(key))
+	}
+
+	fn value_size(&self, col: ColumnId, key: &[u8]) -> Option<usize> {
+		let inner = self.inner.read();
+		inner.columns.get(col as usize).and_then(|c| c.get(key).map(|(_, v)| v.len()))
+	}
+
+	fn supports_ref_counting(&self) -> bool {
+		!self.ref_counted.is_empty()
+	}
+
+	fn checkpoint(&self, path: &Path) -> error::Result<()> {
+		if path.exists() {
+			return Err(db_err(io::Error::new(
+				io::ErrorKind::AlreadyExists,
+				format!("Checkpoint path {:?} already exists", path),
+			)))
+		}
+
+		// Commits wait for the write lock, so the checkpoint is consistent.
+		let inner = self.inner.read();
+		fs::create_dir_all(path)
+			.and_then(|_| write_snapshot(&path.join(LOG_FILE), &inner.columns))
+			.map(|_| ())
+			.map_err(db_err)
+	}
+}
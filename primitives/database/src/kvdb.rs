@@ -18,10 +18,26 @@
 
 /// A wrapper around `kvdb::Database` that implements `sp_database::Database` trait
 use ::kvdb::{DBTransaction, KeyValueDB};
+use parking_lot::{RwLock, RwLockWriteGuard};
+use std::path::Path;
 
 use crate::{error, Change, ColumnId, Database, Transaction};
 
-struct DbAdapter<D: KeyValueDB + 'static>(D);
+type CheckpointFn<D> = Box<dyn Fn(&D, &Path, CommitsPaused) -> std::io::Result<()> + Send + Sync>;
+
+/// Holds back commits to a database while a checkpoint of it is started.
+///
+/// Commits resume once this is dropped.
+pub struct CommitsPaused<'a> {
+	_guard: RwLockWriteGuard<'a, ()>,
+}
+
+struct DbAdapter<D: KeyValueDB + 'static> {
+	db: D,
+	checkpoint: Option<CheckpointFn<D>>,
+	// Held for reading by every commit and for writing while a checkpoint is started.
+	commit_lock: RwLock<()>,
+}
 
 fn handle_err<T>(result: std::io::Result<T>) -> T {
 	match result {
@@ -38,7 +54,27 @@ where
 	D: KeyValueDB + 'static,
 	H: Clone + AsRef<[u8]>,
 {
-	std::sync::Arc::new(DbAdapter(db))
+	std::sync::Arc::new(DbAdapter { db, checkpoint: None, commit_lock: RwLock::new(()) })
+}
+
+/// Wrap a key-value database into a trait object that implements `sp_database::Database` and
+/// supports `Database::checkpoint`.
+///
+/// `checkpoint` is called with the wrapped database, the target path and a [`CommitsPaused`]
+/// guard, and is expected to write a complete copy of `db` to that path. It should drop the
+/// guard as soon as it holds a consistent view of `db`, such as a snapshot, so commits aren't
+/// held back for the whole copy.
+pub fn as_checkpointable_database<D, H, F>(db: D, checkpoint: F) -> std::sync::Arc<dyn Database<H>>
+where
+	D: KeyValueDB + 'static,
+	H: Clone + AsRef<[u8]>,
+	F: Fn(&D, &Path, CommitsPaused) -> std::io::Result<()> + Send + Sync + 'static,
+{
+	std::sync::Arc::new(DbAdapter {
+		db,
+		checkpoint: Some(Box::new(checkpoint)),
+		commit_lock: RwLock::new(()),
+	})
 }
 
 impl<D: KeyValueDB> DbAdapter<D> {
@@ -47,7 +83,7 @@ impl<D: KeyValueDB> DbAdapter<D> {
 		// Add a key suffix for the counter
 		let mut counter_key = key.to_vec();
 		counter_key.push(0);
-		Ok(match self.0.get(col, &counter_key).map_err(|e| error::DatabaseError(Box::new(e)))? {
+		Ok(match self.db.get(col, &counter_key).map_err(|e| error::DatabaseError(Box::new(e)))? {
 			Some(data) => {
 				let mut counter_data = [0; 4];
 				if data.len() != 4 {
@@ -67,6 +103,7 @@ impl<D: KeyValueDB> DbAdapter<D> {
 
 impl<D: KeyValueDB, H: Clone + AsRef<[u8]>> Database<H> for DbAdapter<D> {
 	fn commit(&self, transaction: Transaction<H>) -> error::Result<()> {
+		let _guard = self.commit_lock.read();
 		let mut tx = DBTransaction::new();
 		for change in transaction.0.into_iter() {
 			match change {
@@ -106,14 +143,32 @@ impl<D: KeyValueDB, H: Clone + AsRef<[u8]>> Database<H> for DbAdapter<D> {
 				},
 			}
 		}
-		self.0.write(tx).map_err(|e| error::DatabaseError(Box::new(e)))
+		self.db.write(tx).map_err(|e| error::DatabaseError(Box::new(e)))
 	}
 
 	fn get(&self, col: ColumnId, key: &[u8]) -> Option<Vec<u8>> {
-		handle_err(self.0.get(col, key))
+		handle_err(self.db.get(col, key))
 	}
 
 	fn contains(&self, col: ColumnId, key: &[u8]) -> bool {
-		handle_err(self.0.has_key(col, key))
+		handle_err(self.db.has_key(col, key))
+	}
+
+	fn checkpoint(&self, path: &Path) -> error::Result<()> {
+		let checkpoint = self.checkpoint.as_ref().ok_or_else(|| {
+			error::DatabaseError(Box::new(std::io::Error::new(
+				std::io::ErrorKind::Unsupported,
+				"Database does not support checkpoints",
+			)))
+		})?;
+		if path.exists() {
+			return Err(error::DatabaseError(Box::new(std::io::Error::new(
+				std::io::ErrorKind::AlreadyExists,
+				format!("Checkpoint path {:?} already exists", path),
+			))))
+		}
+
+		let paused = CommitsPaused { _guard: self.commit_lock.write() };
+		checkpoint(&self.db, path, paused).map_err(|e| error::DatabaseError(Box::new(e)))
 	}
 }
@@ -18,11 +18,14 @@
 
 //! The main database trait, allowing Substrate to store data persistently.
 
+pub mod conformance;
 pub mod error;
+mod file;
 mod kvdb;
 mod mem;
 
-pub use crate::kvdb::as_database;
+pub use crate::kvdb::{as_checkpointable_database, as_database, CommitsPaused};
+pub use file::{FileDb, FileDbOptions};
 pub use mem::MemDb;
 
 /// An identifier for a column.
@@ -116,6 +119,20 @@ pub trait Database<H: Clone + AsRef<[u8]>>: Send + Sync {
 	///
 	/// Not all database implementations use a prefix for keys, so this function may be a noop.
 	fn sanitize_key(&self, _key: &mut Vec<u8>) {}
+
+	/// Write a consistent, point-in-time copy of the database to `path`, without closing it.
+	///
+	/// `path` must not exist yet. Commits are held back until the implementation holds a
+	/// consistent view of the database, so the checkpoint never contains a partially applied
+	/// transaction.
+	///
+	/// Returns an error by default, for databases which can't take checkpoints.
+	fn checkpoint(&self, _path: &std::path::Path) -> error::Result<()> {
+		Err(error::DatabaseError(Box::new(std::io::Error::new(
+			std::io::ErrorKind::Unsupported,
+			"Database does not support checkpoints",
+		))))
+	}
 }
 
 impl<H> std::fmt::Debug for dyn Database<H> {
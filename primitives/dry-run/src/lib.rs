@@ -0,0 +1,79 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Substrate dry-run API
+//!
+//! This Runtime API allows to preview what an extrinsic, or a call dispatched from a given origin,
+//! would do if it were included in the next block. The runtime executes it on top of the state of
+//! the block the API is called at, and returns the result of the dispatch and the events emitted.
+//!
+//! As for every runtime API call, the changes to the storage are never committed. The node may
+//! collect them before they are discarded, in order to report the storage keys changed.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{transaction_validity::TransactionValidityError, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// The effects of a dry-run.
+///
+/// The runtime types are SCALE-encoded, so that nodes can handle the effects of any runtime.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DryRunEffects {
+	/// The encoded `DispatchResultWithPostInfo` of the dispatch.
+	pub execution_result: Vec<u8>,
+	/// The encoded events emitted, in order.
+	pub emitted_events: Vec<Vec<u8>>,
+}
+
+/// Why a dry-run could not be done.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum DryRunError {
+	/// The origin could not be decoded.
+	InvalidOrigin,
+	/// The call could not be decoded.
+	InvalidCall,
+	/// The extrinsic is invalid, and would not be included in a block.
+	InvalidExtrinsic(TransactionValidityError),
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to dry-run extrinsics and calls.
+	pub trait DryRunApi {
+		/// Dry-run `extrinsic`, as if it were applied in the block after `block_hash`.
+		///
+		/// `block_hash` must be the hash of the block the API is called at.
+		fn dry_run_extrinsic(
+			extrinsic: <Block as sp_runtime::traits::Block>::Extrinsic,
+			block_hash: <Block as sp_runtime::traits::Block>::Hash,
+		) -> Result<DryRunEffects, DryRunError>;
+
+		/// Dry-run the encoded `call`, dispatched from the encoded `origin`, as if it were
+		/// dispatched in the block after `block_hash`.
+		///
+		/// `origin` is an encoded `OriginCaller` of the runtime, and `call` an encoded
+		/// `RuntimeCall`. `block_hash` must be the hash of the block the API is called at.
+		fn dry_run_call(
+			origin: Vec<u8>,
+			call: Vec<u8>,
+			block_hash: <Block as sp_runtime::traits::Block>::Hash,
+		) -> Result<DryRunEffects, DryRunError>;
+	}
+}
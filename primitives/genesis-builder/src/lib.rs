@@ -30,12 +30,51 @@
 //!
 //! Providing externalities with empty storage and putting `GenesisConfig` into storage allows to
 //! catch and build the raw storage of `GenesisConfig` which is the foundation for genesis block.
+//!
+//! Since version 2, the runtime may also provide named presets. A preset is a JSON patch, which
+//! is applied on top of the default `GenesisConfig` to obtain the config of a well-known chain,
+//! such as a development chain. The names of the most common presets are defined here.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 
 /// The result type alias, used in build methods. `Err` contains formatted error message.
 pub type Result = core::result::Result<(), sp_runtime::RuntimeString>;
 
+/// The name of the preset for a development chain, run by a single node.
+pub const DEV_RUNTIME_PRESET: &str = "development";
+
+/// The name of the preset for a local testnet, run by several nodes.
+pub const LOCAL_TESTNET_RUNTIME_PRESET: &str = "local_testnet";
+
+/// The identifier of a genesis config preset: its UTF-8 encoded name.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PresetId(Vec<u8>);
+
+impl PresetId {
+	/// The name of the preset, if it is valid UTF-8.
+	pub fn as_str(&self) -> Option<&str> {
+		core::str::from_utf8(&self.0).ok()
+	}
+}
+
+impl From<&str> for PresetId {
+	fn from(name: &str) -> Self {
+		Self(name.as_bytes().to_vec())
+	}
+}
+
+impl AsRef<[u8]> for PresetId {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
 sp_api::decl_runtime_apis! {
 	/// API to interact with GenesisConfig for the runtime
+	#[api_version(2)]
 	pub trait GenesisBuilder {
 		/// Creates the default `GenesisConfig` and returns it as a JSON blob.
 		///
@@ -51,5 +90,17 @@ sp_api::decl_runtime_apis! {
 		///
 		/// Please note that provided json blob must contain all `GenesisConfig` fields, no defaults will be used.
 		fn build_config(json: sp_std::vec::Vec<u8>) -> Result;
+
+		/// Returns the names of the presets provided by the runtime.
+		#[api_version(2)]
+		fn preset_names() -> Vec<PresetId>;
+
+		/// Returns the JSON patch of the preset `id`, or `None` if the runtime has no such preset.
+		///
+		/// The patch is to be applied on top of the default `GenesisConfig`, as returned by
+		/// [`GenesisBuilder::create_default_config`]. The result may then be given to
+		/// [`GenesisBuilder::build_config`].
+		#[api_version(2)]
+		fn get_preset(id: &PresetId) -> Option<Vec<u8>>;
 	}
 }
@@ -45,9 +45,11 @@ use sp_core::{
 #[cfg(feature = "std")]
 use sp_keystore::KeystoreExt;
 
+#[cfg(feature = "bls-experimental")]
+use sp_core::bls381;
 use sp_core::{
 	crypto::KeyTypeId,
-	ed25519,
+	ecdsa, ed25519,
 	offchain::{
 		HttpError, HttpRequestId, HttpRequestStatus, OpaqueNetworkState, StorageKind, Timestamp,
 	},
@@ -98,6 +100,38 @@ impl From<MultiRemovalResults> for KillStorageResult {
 	}
 }
 
+/// Error verifying ECDSA signature
+#[derive(PassByCodec, Encode, Decode, Debug, PartialEq, Eq)]
+pub enum EcdsaVerifyError {
+	/// Incorrect value of R or S
+	BadRS,
+	/// Incorrect value of V
+	BadV,
+	/// Invalid signature
+	BadSignature,
+}
+
+/// Recover the public key which signed the pre-hashed message `msg`.
+///
+/// The recovery ID may be either `0..=3` or Ethereum's `27..=30`. High-S signatures are accepted.
+#[cfg(feature = "std")]
+fn secp256k1_ecdsa_recover_key(
+	sig: &[u8; 65],
+	msg: &[u8; 32],
+) -> Result<k256::ecdsa::VerifyingKey, EcdsaVerifyError> {
+	use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+	let v = if sig[64] >= 27 { sig[64] - 27 } else { sig[64] };
+	let mut rid = RecoveryId::from_byte(v).ok_or(EcdsaVerifyError::BadV)?;
+	let mut sig = Signature::from_slice(&sig[..64]).map_err(|_| EcdsaVerifyError::BadRS)?;
+	// Normalizing S negates the nonce point, flipping the parity of its y coordinate.
+	if let Some(normalized) = sig.normalize_s() {
+		sig = normalized;
+		rid = RecoveryId::new(!rid.is_y_odd(), rid.is_x_reduced());
+	}
+	VerifyingKey::recover_from_prehash(msg, &sig, rid).map_err(|_| EcdsaVerifyError::BadSignature)
+}
+
 /// Interface for accessing the storage from within the runtime.
 #[runtime_interface]
 pub trait Storage {
@@ -870,6 +904,203 @@ pub trait Crypto {
 	fn sr25519_verify(sig: &sr25519::Signature, msg: &[u8], pubkey: &sr25519::Public) -> bool {
 		sr25519::Pair::verify_deprecated(sig, msg, pubkey)
 	}
+
+	/// Returns all `ecdsa` public keys for the given key id from the keystore.
+	fn ecdsa_public_keys(&mut self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.ecdsa_public_keys(id)
+	}
+
+	/// Generate an `ecdsa` key for the given key type using an optional `seed` and
+	/// store it in the keystore.
+	///
+	/// The `seed` needs to be a valid utf8.
+	///
+	/// Returns the public key.
+	fn ecdsa_generate(&mut self, id: KeyTypeId, seed: Option<Vec<u8>>) -> ecdsa::Public {
+		let seed = seed.as_ref().map(|s| std::str::from_utf8(s).expect("Seed is valid utf8!"));
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.ecdsa_generate_new(id, seed)
+			.expect("`ecdsa_generate` failed")
+	}
+
+	/// Sign the given `msg` with the `ecdsa` key that corresponds to the given public key and
+	/// key type in the keystore.
+	///
+	/// Returns the signature.
+	fn ecdsa_sign(
+		&mut self,
+		id: KeyTypeId,
+		pub_key: &ecdsa::Public,
+		msg: &[u8],
+	) -> Option<ecdsa::Signature> {
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.ecdsa_sign(id, pub_key, msg)
+			.ok()
+			.flatten()
+	}
+
+	/// Sign the given a pre-hashed `msg` with the `ecdsa` key that corresponds to the given public
+	/// key and key type in the keystore.
+	///
+	/// Returns the signature.
+	fn ecdsa_sign_prehashed(
+		&mut self,
+		id: KeyTypeId,
+		pub_key: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Option<ecdsa::Signature> {
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.ecdsa_sign_prehashed(id, pub_key, msg)
+			.ok()
+			.flatten()
+	}
+
+	/// Verify `ecdsa` signature.
+	///
+	/// The message is hashed with `blake2_256` before verification.
+	///
+	/// Returns `true` when the verification was successful.
+	fn ecdsa_verify(sig: &ecdsa::Signature, msg: &[u8], pub_key: &ecdsa::Public) -> bool {
+		ecdsa::Pair::verify(sig, msg, pub_key)
+	}
+
+	/// Verify `ecdsa` signature with pre-hashed `msg`.
+	///
+	/// Returns `true` when the verification was successful.
+	fn ecdsa_verify_prehashed(
+		sig: &ecdsa::Signature,
+		msg: &[u8; 32],
+		pub_key: &ecdsa::Public,
+	) -> bool {
+		ecdsa::Pair::verify_prehashed(sig, msg, pub_key)
+	}
+
+	/// Verify and recover a SECP256k1 ECDSA signature.
+	///
+	/// - `sig` is passed in RSV format. V should be either `0/1/2/3` or `27/28/29/30`.
+	/// - `msg` is the pre-hashed message.
+	///
+	/// Returns `Err` if the signature is bad, otherwise the 64-byte uncompressed public key
+	/// (without the leading `0x04` tag).
+	fn secp256k1_ecdsa_recover(
+		sig: &[u8; 65],
+		msg: &[u8; 32],
+	) -> Result<[u8; 64], EcdsaVerifyError> {
+		let key = secp256k1_ecdsa_recover_key(sig, msg)?;
+		let mut res = [0u8; 64];
+		res.copy_from_slice(&key.to_encoded_point(false).as_bytes()[1..]);
+		Ok(res)
+	}
+
+	/// Verify and recover a SECP256k1 ECDSA signature.
+	///
+	/// - `sig` is passed in RSV format. V should be either `0/1/2/3` or `27/28/29/30`.
+	/// - `msg` is the pre-hashed message.
+	///
+	/// Returns `Err` if the signature is bad, otherwise the 33-byte compressed public key.
+	fn secp256k1_ecdsa_recover_compressed(
+		sig: &[u8; 65],
+		msg: &[u8; 32],
+	) -> Result<[u8; 33], EcdsaVerifyError> {
+		let key = secp256k1_ecdsa_recover_key(sig, msg)?;
+		let mut res = [0u8; 33];
+		res.copy_from_slice(key.to_encoded_point(true).as_bytes());
+		Ok(res)
+	}
+
+	/// Returns all `bls12-381` public keys for the given key id from the keystore.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&mut self, id: KeyTypeId) -> Vec<bls381::Public> {
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.bls381_public_keys(id)
+	}
+
+	/// Generate a `bls12-381` key for the given key type using an optional `seed` and
+	/// store it in the keystore.
+	///
+	/// The `seed` needs to be a valid utf8.
+	///
+	/// Returns the public key.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate(&mut self, id: KeyTypeId, seed: Option<Vec<u8>>) -> bls381::Public {
+		let seed = seed.as_ref().map(|s| std::str::from_utf8(s).expect("Seed is valid utf8!"));
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.bls381_generate_new(id, seed)
+			.expect("`bls381_generate` failed")
+	}
+
+	/// Sign the given `msg` with the `bls12-381` key that corresponds to the given public key
+	/// and key type in the keystore.
+	///
+	/// Returns the signature.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_sign(
+		&mut self,
+		id: KeyTypeId,
+		pub_key: &bls381::Public,
+		msg: &[u8],
+	) -> Option<bls381::Signature> {
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.bls381_sign(id, pub_key, msg)
+			.ok()
+			.flatten()
+	}
+
+	/// Verify a `bls12-381` signature.
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_verify(sig: &bls381::Signature, msg: &[u8], pub_key: &bls381::Public) -> bool {
+		bls381::Pair::verify(sig, msg, pub_key)
+	}
+
+	/// Aggregate `bls12-381` signatures on the same message into a single signature.
+	///
+	/// Returns `None` if `sigs` is empty or any of the signatures is malformed.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_aggregate_signatures(
+		sigs: &[bls381::Signature],
+	) -> Option<bls381::AggregateSignature> {
+		bls381::aggregate_signatures(sigs)
+	}
+
+	/// Verify `bls12-381` signatures by `pub_keys` on the same `msg`, where `sigs[i]` is the
+	/// signature by `pub_keys[i]`.
+	///
+	/// The signatures are aggregated and checked with a single pairing. The public keys must
+	/// have had their possession proven, such as when registering them.
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_aggregate_verify(
+		sigs: &[bls381::Signature],
+		msg: &[u8],
+		pub_keys: &[bls381::Public],
+	) -> bool {
+		bls381::aggregate_verify(sigs, msg, pub_keys)
+	}
+
+	/// Verify a `bls12-381` signature aggregated from signatures by `pub_keys` on `msg`.
+	///
+	/// The public keys must have had their possession proven, such as when registering them.
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_fast_aggregate_verify(
+		sig: &bls381::AggregateSignature,
+		msg: &[u8],
+		pub_keys: &[bls381::Public],
+	) -> bool {
+		bls381::fast_aggregate_verify(sig, msg, pub_keys)
+	}
 }
 
 /// Interface that provides functions for hashing with different algorithms.
@@ -1403,6 +1634,45 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn secp256k1_ecdsa_recover_works() {
+		let pair = ecdsa::Pair::from_seed(b"12345678901234567890123456789012");
+		let msg = sp_core::blake2_256(b"Hello world");
+		let mut sig = pair.sign_prehashed(&msg).0;
+
+		let compressed = crypto::secp256k1_ecdsa_recover_compressed(&sig, &msg).unwrap();
+		assert_eq!(ecdsa::Public::from_raw(compressed), pair.public());
+		let full = crypto::secp256k1_ecdsa_recover(&sig, &msg).unwrap();
+		assert_eq!(ecdsa::Public::from_full(&full), Ok(pair.public()));
+
+		// Ethereum-style recovery IDs are accepted.
+		sig[64] += 27;
+		assert_eq!(crypto::secp256k1_ecdsa_recover_compressed(&sig, &msg), Ok(compressed));
+
+		sig[64] = 31;
+		assert_eq!(crypto::secp256k1_ecdsa_recover(&sig, &msg), Err(EcdsaVerifyError::BadV));
+		assert_eq!(crypto::secp256k1_ecdsa_recover(&[0u8; 65], &msg), Err(EcdsaVerifyError::BadRS));
+
+		assert!(crypto::ecdsa_verify(&pair.sign(b"Hello world"), b"Hello world", &pair.public()));
+		assert!(crypto::ecdsa_verify_prehashed(&pair.sign_prehashed(&msg), &msg, &pair.public()));
+	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_aggregate_verify_works() {
+		let pairs: Vec<_> = (1..=3u8).map(|i| bls381::Pair::from_seed(&[i; 32])).collect();
+		let publics: Vec<_> = pairs.iter().map(|pair| pair.public()).collect();
+		let sigs: Vec<_> = pairs.iter().map(|pair| pair.sign(b"Hello world")).collect();
+
+		assert!(crypto::bls381_verify(&sigs[0], b"Hello world", &publics[0]));
+		assert!(crypto::bls381_aggregate_verify(&sigs, b"Hello world", &publics));
+		assert!(!crypto::bls381_aggregate_verify(&sigs, b"Hello world", &publics[1..]));
+
+		let aggregate = crypto::bls381_aggregate_signatures(&sigs).unwrap();
+		assert!(crypto::bls381_fast_aggregate_verify(&aggregate, b"Hello world", &publics));
+		assert!(!crypto::bls381_fast_aggregate_verify(&aggregate, b"Hello", &publics));
+	}
+
 	#[test]
 	fn clear_prefix_works() {
 		let mut t = BasicExternalities::new(Storage {
@@ -24,7 +24,7 @@ use crate::{Error, Keystore, KeystorePtr};
 use sp_core::{bls377, bls381};
 use sp_core::{
 	crypto::{ByteArray, KeyTypeId, Pair, VrfSecret},
-	ed25519, sr25519,
+	ecdsa, ed25519, sr25519,
 };
 
 use parking_lot::RwLock;
@@ -184,6 +184,37 @@ impl Keystore for MemoryKeystore {
 		self.sign::<ed25519::Pair>(key_type, public, msg)
 	}
 
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys::<ecdsa::Pair>(key_type)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		self.generate_new::<ecdsa::Pair>(key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		self.sign::<ecdsa::Pair>(key_type, public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		let sig = self.pair::<ecdsa::Pair>(key_type, public).map(|pair| pair.sign_prehashed(msg));
+		Ok(sig)
+	}
+
 	#[cfg(feature = "bls-experimental")]
 	fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
 		self.public_keys::<bls381::Pair>(key_type)
@@ -356,4 +387,42 @@ mod tests {
 		let result = preout.make_bytes::<32>(b"rand", &input, &pair.public());
 		assert!(result.is_ok());
 	}
+
+	#[test]
+	fn ecdsa_sign_prehashed_works() {
+		let store = MemoryKeystore::new();
+
+		let suri = "//Alice";
+		let pair = ecdsa::Pair::from_string(suri, None).unwrap();
+
+		let msg = sp_core::keccak_256(b"this should be a hashed message");
+
+		// no key in key store
+		let res = store.ecdsa_sign_prehashed(ECDSA, &pair.public(), &msg).unwrap();
+		assert!(res.is_none());
+
+		// insert key, sign again
+		store.insert(ECDSA, suri, pair.public().as_ref()).unwrap();
+
+		let res = store.ecdsa_sign_prehashed(ECDSA, &pair.public(), &msg).unwrap();
+		assert!(res.is_some());
+		assert!(ecdsa::Pair::verify_prehashed(&res.unwrap(), &msg, &pair.public()));
+	}
+
+	#[test]
+	fn ecdsa_sign_with_works() {
+		use codec::Decode;
+
+		let store = MemoryKeystore::new();
+		let public = store.ecdsa_generate_new(ECDSA, None).unwrap();
+		assert_eq!(store.ecdsa_public_keys(ECDSA), vec![public]);
+
+		let msg = b"Something important";
+		let sig = store
+			.sign_with(ECDSA, ecdsa::CRYPTO_ID, public.as_ref(), msg)
+			.unwrap()
+			.expect("key was generated in the store");
+		let sig = ecdsa::Signature::decode(&mut &sig[..]).unwrap();
+		assert!(ecdsa::Pair::verify(&sig, &msg[..], &public));
+	}
 }
@@ -90,6 +90,13 @@ impl IdentifyAccount for sp_core::sr25519::Public {
 	}
 }
 
+impl IdentifyAccount for sp_core::ecdsa::Public {
+	type AccountId = Self;
+	fn into_account(self) -> Self {
+		self
+	}
+}
+
 /// Means of signature verification.
 pub trait Verify {
 	/// Type of the signer.
@@ -120,6 +127,19 @@ impl Verify for sp_core::sr25519::Signature {
 	}
 }
 
+impl Verify for sp_core::ecdsa::Signature {
+	type Signer = sp_core::ecdsa::Public;
+	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &sp_core::ecdsa::Public) -> bool {
+		match sp_io::crypto::secp256k1_ecdsa_recover_compressed(
+			self.as_ref(),
+			&sp_io::hashing::blake2_256(msg.get()),
+		) {
+			Ok(pubkey) => signer.0 == pubkey,
+			_ => false,
+		}
+	}
+}
+
 /// Means of signature verification of an application key.
 pub trait AppVerify {
 	/// Type of the signer.
@@ -22,10 +22,10 @@
 //! approaches in general. Definitions related to sessions, slashing, etc go here.
 
 use crate::currency_to_vote::CurrencyToVote;
-use codec::{FullCodec, MaxEncodedLen};
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
-use sp_runtime::{DispatchError, DispatchResult, Saturating};
+use sp_runtime::{DispatchError, DispatchResult, Perbill, Saturating};
 use sp_std::{collections::btree_map::BTreeMap, ops::Sub, vec::Vec};
 
 pub mod offence;
@@ -38,6 +38,95 @@ pub type SessionIndex = u32;
 /// Counter for the number of eras that have passed.
 pub type EraIndex = u32;
 
+/// A reward of a stash which was earned in an era but not paid out yet.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct PendingReward<AccountId, Balance> {
+	/// The era the reward was earned in.
+	pub era: EraIndex,
+	/// The validator whose payout for the era includes the reward.
+	pub validator: AccountId,
+	/// The amount of the reward.
+	pub amount: Balance,
+}
+
+/// The stake of a validator in an era, as a validator.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct ValidatorExposure<Balance> {
+	/// The total balance backing the validator.
+	pub total: Balance,
+	/// The validator's own stake.
+	pub own: Balance,
+	/// The number of nominators backing the validator.
+	pub nominator_count: u32,
+	/// The position of the validator among the validators of the era, by total backing. The most
+	/// backed validator is ranked 1.
+	pub rank: u32,
+}
+
+/// The stake a stash has exposed to slashing in an era.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct StakerExposure<AccountId, Balance> {
+	/// The era of the exposure.
+	pub era: EraIndex,
+	/// The exposure of the stash as a validator, if it is validating in the era.
+	pub validator: Option<ValidatorExposure<Balance>>,
+	/// The stake of the stash exposed to each of the validators it is backing in the era.
+	pub nominations: Vec<(AccountId, Balance)>,
+}
+
+/// A chunk of the stake of a stash being unbonded.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct UnbondingChunk<Balance> {
+	/// The amount being unbonded.
+	pub value: Balance,
+	/// The era from which the amount can be withdrawn.
+	pub era: EraIndex,
+}
+
+/// A validator elected for an era.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct EraValidator<AccountId, Balance> {
+	/// The stash of the validator.
+	pub stash: AccountId,
+	/// The total balance backing the validator.
+	pub total: Balance,
+	/// The validator's own stake.
+	pub own: Balance,
+	/// The number of nominators backing the validator.
+	pub nominator_count: u32,
+	/// The commission of the validator in the era.
+	pub commission: Perbill,
+	/// Whether the validator was accepting new nominations in the era.
+	pub blocked: bool,
+	/// The reward points earned by the validator in the era so far.
+	pub reward_points: u32,
+}
+
 /// Representation of the status of a staker.
 #[derive(RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone))]
@@ -125,6 +125,15 @@ impl ExtrinsicBuilder {
 		Self::new_unsigned(PalletCall::storage_change { key, value })
 	}
 
+	/// Create builder for `PalletCall::child_storage_change` call using given parameters
+	pub fn new_child_storage_change(
+		storage_key: Vec<u8>,
+		key: Vec<u8>,
+		value: Option<Vec<u8>>,
+	) -> Self {
+		Self::new(PalletCall::child_storage_change { storage_key, key, value })
+	}
+
 	/// Create builder for `PalletCall::offchain_index_set` call using given parameters
 	pub fn new_offchain_index_set(key: Vec<u8>, value: Vec<u8>) -> Self {
 		Self::new(PalletCall::offchain_index_set { key, value })
@@ -502,6 +502,23 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_dry_run::DryRunApi<Block> for Runtime {
+		fn dry_run_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+			block_hash: <Block as BlockT>::Hash,
+		) -> Result<sp_dry_run::DryRunEffects, sp_dry_run::DryRunError> {
+			Executive::dry_run_extrinsic(extrinsic, block_hash)
+		}
+
+		fn dry_run_call(
+			origin: Vec<u8>,
+			call: Vec<u8>,
+			block_hash: <Block as BlockT>::Hash,
+		) -> Result<sp_dry_run::DryRunEffects, sp_dry_run::DryRunError> {
+			Executive::dry_run_call::<OriginCaller>(&origin, &call, block_hash)
+		}
+	}
+
 	impl sp_block_builder::BlockBuilder<Block> for Runtime {
 		fn apply_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> ApplyExtrinsicResult {
 			Executive::apply_extrinsic(extrinsic)
@@ -190,6 +190,24 @@ pub mod pallet {
 		pub fn read_and_panic(_origin: OriginFor<T>, count: u32) -> DispatchResult {
 			Self::execute_read(count, true)
 		}
+
+		/// Put/delete some data from the default child trie stored under `storage_key`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(100)]
+		pub fn child_storage_change(
+			origin: OriginFor<T>,
+			storage_key: Vec<u8>,
+			key: Vec<u8>,
+			value: Option<Vec<u8>>,
+		) -> DispatchResult {
+			frame_system::ensure_signed(origin)?;
+			let child_info = storage::child::ChildInfo::new_default(&storage_key);
+			match value {
+				Some(value) => storage::child::put_raw(&child_info, &key, &value),
+				None => storage::child::kill(&child_info, &key),
+			}
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
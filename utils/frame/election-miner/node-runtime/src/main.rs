@@ -0,0 +1,138 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mine a signed election solution for kitchensink-runtime, and submit it.
+
+use clap::Parser;
+use codec::Encode;
+use election_miner::{RoundInput, Solver};
+use kitchensink_runtime::{Block, Runtime, RuntimeCall};
+use node_primitives::{Hash, Nonce};
+use sp_core::{sr25519, Pair};
+use sp_runtime::generic::Era;
+use substrate_rpc_client::{rpc_params, ws_client, AuthorApi, ClientT, StateApi, WsClient};
+
+#[derive(Debug, Parser)]
+struct Opt {
+	/// The WebSocket RPC endpoint of the node to mine for.
+	#[arg(long, default_value = "ws://localhost:9944")]
+	uri: String,
+
+	/// The block to fetch the snapshot at. Defaults to the latest finalized block.
+	#[arg(long)]
+	at: Option<Hash>,
+
+	/// The solvers to mine with, in parallel, as `seq-phragmen` or `phragmms`, optionally followed
+	/// by `:<balancing iterations>`.
+	#[arg(long, value_delimiter = ',', default_value = "seq-phragmen:10,phragmms:10")]
+	solvers: Vec<Solver>,
+
+	/// The secret URI of the account submitting the solution.
+	#[arg(long)]
+	suri: String,
+
+	/// Mine and check the solution, without submitting it.
+	#[arg(long)]
+	dry_run: bool,
+}
+
+/// Create an immortal extrinsic of `call`, signed by `signer`.
+async fn create_extrinsic(
+	rpc: &WsClient,
+	signer: sr25519::Pair,
+	call: RuntimeCall,
+) -> Result<kitchensink_runtime::UncheckedExtrinsic, String> {
+	let account = sp_runtime::AccountId32::from(signer.public());
+	let genesis_hash: Hash = rpc
+		.request("chain_getBlockHash", rpc_params![0u32])
+		.await
+		.map_err(|e| format!("failed to fetch the genesis hash: {:?}", e))?;
+	let nonce: Nonce = rpc
+		.request("system_accountNextIndex", rpc_params![account.clone()])
+		.await
+		.map_err(|e| format!("failed to fetch the nonce: {:?}", e))?;
+	let version = StateApi::<Hash>::runtime_version(rpc, None)
+		.await
+		.map_err(|e| format!("failed to fetch the runtime version: {:?}", e))?;
+
+	let extra: kitchensink_runtime::SignedExtra = (
+		frame_system::CheckNonZeroSender::<Runtime>::new(),
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+		frame_system::CheckNonce::<Runtime>::from(nonce),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
+	);
+	let raw_payload = kitchensink_runtime::SignedPayload::from_raw(
+		call.clone(),
+		extra.clone(),
+		(
+			(),
+			version.spec_version,
+			version.transaction_version,
+			genesis_hash,
+			genesis_hash,
+			(),
+			(),
+			(),
+		),
+	);
+	let signature = raw_payload.using_encoded(|e| signer.sign(e));
+
+	Ok(kitchensink_runtime::UncheckedExtrinsic::new_signed(
+		call,
+		account.into(),
+		kitchensink_runtime::Signature::Sr25519(signature),
+		extra,
+	))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+	sp_tracing::try_init_simple();
+	let Opt { uri, at, solvers, suri, dry_run } = Opt::parse();
+	let signer = sr25519::Pair::from_string(&suri, None)
+		.map_err(|e| format!("invalid secret URI: {:?}", e))?;
+
+	let mut ext = election_miner::remote_ext::<Runtime, Block>(uri.clone(), at)
+		.await
+		.map_err(|e| format!("{:?}", e))?;
+	let input = ext.execute_with(RoundInput::<Runtime>::get).map_err(|e| format!("{:?}", e))?;
+
+	let best = election_miner::best(election_miner::mine(&input, &solvers))
+		.map_err(|e| format!("{:?}", e))?;
+	log::info!("{} mined the best solution, with score {:?}", best.solver, best.raw_solution.score);
+	ext.execute_with(|| election_miner::check(&best))
+		.map_err(|e| format!("{:?}", e))?;
+
+	if dry_run {
+		return Ok(())
+	}
+
+	let rpc = ws_client(&uri).await?;
+	let extrinsic =
+		create_extrinsic(&rpc, signer, election_miner::submit_call::<Runtime>(best).into()).await?;
+	let hash = AuthorApi::<Hash, Hash>::submit_extrinsic(&rpc, extrinsic.encode().into())
+		.await
+		.map_err(|e| format!("failed to submit the solution: {:?}", e))?;
+	log::info!("submitted the solution in extrinsic {:?}", hash);
+
+	Ok(())
+}
@@ -0,0 +1,386 @@
+// This file is part of a fork of Substrate which has had various changes.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// Copyright (C) 2022-2023 Luke Parker
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support code to mine signed solutions for `pallet-election-provider-multi-phase` off-chain.
+//!
+//! The offchain worker of the pallet mines unsigned solutions with the single
+//! `MinerConfig::Solver` of the runtime. This crate instead mines with several [`Solver`]s in
+//! parallel, outside of the runtime, and keeps the solution with the best [`ElectionScore`], which
+//! can then be submitted during the signed phase.
+//!
+//! Mining a solution takes four steps:
+//!
+//! 1. Fetch the state of the pallet from a node with [`remote_ext`].
+//! 2. Read the [`RoundInput`] from within these externalities.
+//! 3. [`mine`] a solution with each solver, and pick the [`best`] of them.
+//! 4. [`check`] the best solution, again within the externalities, and submit the [`submit_call`]
+//!    of it as a signed extrinsic.
+//!
+//! Take a look at _utils/frame/election-miner/node-runtime_ for an example.
+
+use frame_election_provider_support::{PhragMMS, SequentialPhragmen};
+use frame_remote_externalities::{Builder, Mode, OnlineConfig, RemoteExternalities};
+use frame_support::{parameter_types, traits::PalletInfoAccess};
+use pallet_election_provider_multi_phase::{
+	unsigned::{Miner, MinerError, VoterOf},
+	Call, Config, ElectionCompute, FeasibilityError, Pallet, RawSolution, RoundSnapshot,
+	SolutionAccuracyOf, SolutionOf, SolutionOrSnapshotSize,
+};
+use serde::de::DeserializeOwned;
+use sp_npos_elections::{BalancingConfig, ElectionScore};
+use sp_runtime::traits::{Block as BlockT, Get};
+use std::{
+	fmt,
+	str::FromStr,
+	time::{Duration, Instant},
+};
+
+#[cfg(test)]
+mod mock;
+
+const LOG_TARGET: &str = "election-miner";
+
+parameter_types! {
+	/// The balancing iterations of the solver running on the current thread.
+	pub static BalancingIterations: usize = 0;
+	pub Balancing: Option<BalancingConfig> =
+		Some(BalancingConfig { iterations: BalancingIterations::get(), tolerance: 0 });
+}
+
+/// Errors of the miner.
+#[derive(Debug)]
+pub enum Error {
+	/// Fetching the state of the pallet failed.
+	RemoteExternalities(&'static str),
+	/// There is no snapshot to mine a solution for.
+	SnapshotUnavailable,
+	/// Mining a solution failed.
+	Miner(MinerError),
+	/// The mined solution is not feasible.
+	Feasibility(FeasibilityError),
+	/// The election is not in its signed phase.
+	NotSignedPhase,
+	/// The mined solution is heavier than the maximum weight of signed solutions.
+	TooMuchWeight,
+	/// The mined solution is not better than the best signed solution already submitted.
+	ScoreNotBetter,
+	/// No solver was given.
+	NoSolver,
+}
+
+impl From<MinerError> for Error {
+	fn from(e: MinerError) -> Self {
+		Error::Miner(e)
+	}
+}
+
+impl From<FeasibilityError> for Error {
+	fn from(e: FeasibilityError) -> Self {
+		Error::Feasibility(e)
+	}
+}
+
+/// A solver to mine solutions with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solver {
+	/// Sequential Phragmén, followed by the given number of balancing iterations.
+	SeqPhragmen { iterations: usize },
+	/// PhragMMS, followed by the given number of balancing iterations.
+	PhragMMS { iterations: usize },
+}
+
+impl FromStr for Solver {
+	type Err = String;
+
+	/// Parses `seq-phragmen` or `phragmms`, optionally followed by `:<balancing iterations>`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (name, iterations) = match s.split_once(':') {
+			Some((name, iterations)) => (
+				name,
+				iterations
+					.parse()
+					.map_err(|e| format!("invalid balancing iterations {:?}: {}", iterations, e))?,
+			),
+			None => (s, 0),
+		};
+
+		match name {
+			"seq-phragmen" => Ok(Solver::SeqPhragmen { iterations }),
+			"phragmms" => Ok(Solver::PhragMMS { iterations }),
+			_ => Err(format!("unknown solver {:?}, expected seq-phragmen or phragmms", name)),
+		}
+	}
+}
+
+impl fmt::Display for Solver {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Solver::SeqPhragmen { iterations } => write!(f, "seq-phragmen:{}", iterations),
+			Solver::PhragMMS { iterations } => write!(f, "phragmms:{}", iterations),
+		}
+	}
+}
+
+/// The input of the miner for the current round of the election.
+pub struct RoundInput<T: Config> {
+	/// The snapshot of the voters and targets.
+	pub snapshot: RoundSnapshot<T::AccountId, VoterOf<T>>,
+	/// The number of targets to elect.
+	pub desired_targets: u32,
+	/// The round of the election.
+	pub round: u32,
+}
+
+impl<T: Config> RoundInput<T> {
+	/// Read the input of the current round.
+	///
+	/// This must be called within the externalities of the chain, such as those of [`remote_ext`].
+	pub fn get() -> Result<Self, Error> {
+		Ok(Self {
+			snapshot: Pallet::<T>::snapshot().ok_or(Error::SnapshotUnavailable)?,
+			desired_targets: Pallet::<T>::desired_targets().ok_or(Error::SnapshotUnavailable)?,
+			round: Pallet::<T>::round(),
+		})
+	}
+}
+
+/// A solution mined by a [`Solver`].
+pub struct MinedSolution<T: Config> {
+	/// The solver which mined the solution.
+	pub solver: Solver,
+	/// The solution, ready to be submitted.
+	pub raw_solution: RawSolution<SolutionOf<T::MinerConfig>>,
+	/// The size of the snapshot the solution was mined for.
+	pub size: SolutionOrSnapshotSize,
+	/// The time it took to mine the solution.
+	pub elapsed: Duration,
+}
+
+/// Fetch the state of the election pallet of a node at `uri`, at the block `at` or the latest
+/// finalized block.
+pub async fn remote_ext<T: Config, B: BlockT + DeserializeOwned>(
+	uri: String,
+	at: Option<B::Hash>,
+) -> Result<RemoteExternalities<B>, Error>
+where
+	B::Hash: DeserializeOwned,
+	B::Header: DeserializeOwned,
+{
+	Builder::<B>::new()
+		.mode(Mode::Online(OnlineConfig {
+			transport: uri.into(),
+			at,
+			pallets: vec![<Pallet<T> as PalletInfoAccess>::name().to_string()],
+			child_trie: false,
+			..Default::default()
+		}))
+		.build()
+		.await
+		.map_err(Error::RemoteExternalities)
+}
+
+/// Mine a solution for `input` with each of the `solvers`, in parallel.
+///
+/// The results are returned in the order of `solvers`.
+pub fn mine<T: Config>(
+	input: &RoundInput<T>,
+	solvers: &[Solver],
+) -> Vec<Result<MinedSolution<T>, Error>>
+where
+	VoterOf<T>: Sync,
+	SolutionOf<T::MinerConfig>: Send,
+{
+	std::thread::scope(|scope| {
+		let handles = solvers
+			.iter()
+			.map(|solver| scope.spawn(move || mine_with(input, *solver)))
+			.collect::<Vec<_>>();
+		handles
+			.into_iter()
+			.map(|handle| handle.join().expect("the solvers do not panic; qed"))
+			.collect()
+	})
+}
+
+fn mine_with<T: Config>(input: &RoundInput<T>, solver: Solver) -> Result<MinedSolution<T>, Error> {
+	let RoundSnapshot { voters, targets } = input.snapshot.clone();
+	let start = Instant::now();
+	let (solution, score, size) = match solver {
+		Solver::SeqPhragmen { iterations } => {
+			BalancingIterations::set(iterations);
+			Miner::<T::MinerConfig>::mine_solution_with_snapshot::<
+				SequentialPhragmen<T::AccountId, SolutionAccuracyOf<T>, Balancing>,
+			>(voters, targets, input.desired_targets)
+		},
+		Solver::PhragMMS { iterations } => {
+			BalancingIterations::set(iterations);
+			Miner::<T::MinerConfig>::mine_solution_with_snapshot::<
+				PhragMMS<T::AccountId, SolutionAccuracyOf<T>, Balancing>,
+			>(voters, targets, input.desired_targets)
+		},
+	}?;
+	let elapsed = start.elapsed();
+
+	log::info!(
+		target: LOG_TARGET,
+		"{} mined a solution with score {:?} in {:?}",
+		solver,
+		score,
+		elapsed,
+	);
+	Ok(MinedSolution {
+		solver,
+		raw_solution: RawSolution { solution, score, round: input.round },
+		size,
+		elapsed,
+	})
+}
+
+/// Pick the solution with the best score out of `mined`, preferring the fastest solver on ties.
+///
+/// Solvers which failed are logged and skipped.
+pub fn best<T: Config>(
+	mined: Vec<Result<MinedSolution<T>, Error>>,
+) -> Result<MinedSolution<T>, Error> {
+	let mut best: Option<MinedSolution<T>> = None;
+	for result in mined {
+		let candidate = match result {
+			Ok(candidate) => candidate,
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "solver failed: {:?}", e);
+				continue
+			},
+		};
+
+		best = match best {
+			Some(best)
+				if (best.raw_solution.score, std::cmp::Reverse(best.elapsed)) >=
+					(candidate.raw_solution.score, std::cmp::Reverse(candidate.elapsed)) =>
+				Some(best),
+			_ => Some(candidate),
+		};
+	}
+
+	best.ok_or(Error::NoSolver)
+}
+
+/// Check that `mined` would be accepted as a signed solution.
+///
+/// This must be called within the externalities of the chain, such as those of [`remote_ext`].
+pub fn check<T: Config>(mined: &MinedSolution<T>) -> Result<(), Error> {
+	if !Pallet::<T>::current_phase().is_signed() {
+		return Err(Error::NotSignedPhase)
+	}
+
+	Pallet::<T>::feasibility_check(mined.raw_solution.clone(), ElectionCompute::Signed)?;
+
+	if !Pallet::<T>::solution_weight_of(&mined.raw_solution, mined.size)
+		.all_lt(T::SignedMaxWeight::get())
+	{
+		return Err(Error::TooMuchWeight)
+	}
+
+	let best_signed_score: Option<ElectionScore> =
+		Pallet::<T>::signed_submissions().iter().map(|s| s.raw_solution.score).max();
+	if best_signed_score.map_or(false, |best| mined.raw_solution.score <= best) {
+		return Err(Error::ScoreNotBetter)
+	}
+
+	Ok(())
+}
+
+/// The call submitting `mined` as a signed solution.
+pub fn submit_call<T: Config>(mined: MinedSolution<T>) -> Call<T> {
+	Call::submit { raw_solution: Box::new(mined.raw_solution) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+
+	#[test]
+	fn solver_parsing_works() {
+		assert_eq!("seq-phragmen".parse(), Ok(Solver::SeqPhragmen { iterations: 0 }));
+		assert_eq!("phragmms:10".parse(), Ok(Solver::PhragMMS { iterations: 10 }));
+		assert!("phragmms:ten".parse::<Solver>().is_err());
+		assert!("approval-voting".parse::<Solver>().is_err());
+
+		let solver = Solver::SeqPhragmen { iterations: 5 };
+		assert_eq!(solver.to_string().parse(), Ok(solver));
+	}
+
+	#[test]
+	fn best_solution_of_both_solvers_is_kept() {
+		new_test_ext().execute_with(|| {
+			roll_to_signed();
+			let input = RoundInput::<Runtime>::get().unwrap();
+
+			let mined = mine(
+				&input,
+				&[Solver::SeqPhragmen { iterations: 0 }, Solver::PhragMMS { iterations: 2 }],
+			);
+			let scores = mined
+				.iter()
+				.map(|mined| mined.as_ref().unwrap().raw_solution.score)
+				.collect::<Vec<_>>();
+			let best = best(mined).unwrap();
+			assert_eq!(Some(best.raw_solution.score), scores.into_iter().max());
+			assert!(matches!(check(&best), Ok(())));
+		})
+	}
+
+	#[test]
+	fn better_score_wins_and_ties_go_to_the_fastest_solver() {
+		new_test_ext().execute_with(|| {
+			roll_to_signed();
+			let input = RoundInput::<Runtime>::get().unwrap();
+			let template =
+				mine(&input, &[Solver::SeqPhragmen { iterations: 0 }]).pop().unwrap().unwrap();
+			let candidate = |solver, millis, score_bump| -> Result<MinedSolution<Runtime>, Error> {
+				let mut raw_solution = template.raw_solution.clone();
+				raw_solution.score.minimal_stake += score_bump;
+				Ok(MinedSolution {
+					solver,
+					raw_solution,
+					size: template.size,
+					elapsed: Duration::from_millis(millis),
+				})
+			};
+			let seq_phragmen = Solver::SeqPhragmen { iterations: 0 };
+			let phragmms = Solver::PhragMMS { iterations: 0 };
+
+			// a better score wins, however long it took to mine
+			let mined = vec![candidate(seq_phragmen, 10, 0), candidate(phragmms, 20, 1)];
+			assert_eq!(best(mined).unwrap().solver, phragmms);
+			let mined = vec![candidate(seq_phragmen, 20, 1), candidate(phragmms, 10, 0)];
+			assert_eq!(best(mined).unwrap().solver, seq_phragmen);
+
+			// ties go to the fastest solver, whatever the order of the solvers
+			let mined = vec![candidate(seq_phragmen, 20, 0), candidate(phragmms, 10, 0)];
+			assert_eq!(best(mined).unwrap().solver, phragmms);
+			let mined = vec![candidate(seq_phragmen, 10, 0), candidate(phragmms, 20, 0)];
+			assert_eq!(best(mined).unwrap().solver, seq_phragmen);
+
+			// failed solvers are skipped
+			let mined = vec![Err(Error::SnapshotUnavailable), candidate(phragmms, 20, 0)];
+			assert_eq!(best(mined).unwrap().solver, phragmms);
+			assert!(matches!(best::<Runtime>(vec![]), Err(Error::NoSolver)));
+		})
+	}
+}
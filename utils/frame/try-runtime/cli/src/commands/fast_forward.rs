@@ -93,7 +93,7 @@ where
 }
 
 /// Call `method` with `data` and return the result. `externalities` will not change.
-async fn dry_run<T: Decode, Block: BlockT, HostFns: HostFunctions>(
+pub(crate) async fn dry_run<T: Decode, Block: BlockT, HostFns: HostFunctions>(
 	externalities: &TestExternalities,
 	executor: &WasmExecutor<HostFns>,
 	method: &'static str,
@@ -111,7 +111,7 @@ async fn dry_run<T: Decode, Block: BlockT, HostFns: HostFunctions>(
 }
 
 /// Call `method` with `data` and actually save storage changes to `externalities`.
-async fn run<Block: BlockT, HostFns: HostFunctions>(
+pub(crate) async fn run<Block: BlockT, HostFns: HostFunctions>(
 	externalities: &mut TestExternalities,
 	executor: &WasmExecutor<HostFns>,
 	method: &'static str,
@@ -139,7 +139,7 @@ async fn run<Block: BlockT, HostFns: HostFunctions>(
 }
 
 /// Produce next empty block.
-async fn next_empty_block<
+pub(crate) async fn next_empty_block<
 	Block: BlockT,
 	HostFns: HostFunctions,
 	BBIP: BlockBuildingInfoProvider<Block, Option<(InherentData, Digest)>>,
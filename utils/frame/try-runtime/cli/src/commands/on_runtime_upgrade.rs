@@ -16,11 +16,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{build_executor, state_machine_call_with_proof, SharedParams, State, LOG_TARGET};
+use crate::{
+	block_building_info::BlockBuildingInfoProvider,
+	build_executor,
+	commands::fast_forward::{dry_run, next_empty_block},
+	state_machine_call_with_proof, SharedParams, State, LOG_TARGET,
+};
 use frame_try_runtime::UpgradeCheckSelect;
 use parity_scale_codec::{Decode, Encode};
 use sc_executor::sp_wasm_interface::HostFunctions;
-use sp_runtime::traits::{Block as BlockT, NumberFor};
+use sp_core::hashing::twox_128;
+use sp_inherents::InherentData;
+use sp_runtime::{
+	traits::{Block as BlockT, Header, NumberFor},
+	Digest,
+};
 use sp_weights::Weight;
 use std::{fmt::Debug, str::FromStr};
 
@@ -48,11 +58,20 @@ pub struct OnRuntimeUpgradeCmd {
 		require_equals = true,
 		verbatim_doc_comment)]
 	pub checks: UpgradeCheckSelect,
+
+	/// Simulate the multi-block migrations started by the upgrade, for at most this many blocks.
+	///
+	/// Empty blocks are produced on top of the state until
+	/// `TryRuntime_multi_block_migrations_ongoing` returns `false`. The command fails if the
+	/// migrations are still ongoing after this many blocks.
+	#[arg(long)]
+	pub mbm_max_blocks: Option<u32>,
 }
 
-pub(crate) async fn on_runtime_upgrade<Block, HostFns>(
+pub(crate) async fn on_runtime_upgrade<Block, HostFns, BBIP>(
 	shared: SharedParams,
 	command: OnRuntimeUpgradeCmd,
+	block_building_info_provider: Option<BBIP>,
 ) -> sc_cli::Result<()>
 where
 	Block: BlockT + serde::de::DeserializeOwned,
@@ -61,9 +80,10 @@ where
 	NumberFor<Block>: FromStr,
 	<NumberFor<Block> as FromStr>::Err: Debug,
 	HostFns: HostFunctions,
+	BBIP: BlockBuildingInfoProvider<Block, Option<(InherentData, Digest)>>,
 {
 	let executor = build_executor(&shared);
-	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+	let mut ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
 
 	let (_, encoded_result) = state_machine_call_with_proof::<Block, HostFns>(
 		&ext,
@@ -86,5 +106,57 @@ where
 		(weight.proof_size() as f64 / total_weight.proof_size().max(1) as f64) * 100.0,
 	);
 
-	Ok(())
+	let Some(max_blocks) = command.mbm_max_blocks else { return Ok(()) };
+
+	// The changes of the call above were not persisted, so the upgrade runs again, this time as
+	// part of the first block.
+	let mut parent_hash = ext.block_hash;
+	let mut parent_height = ext
+		.execute_with(|| {
+			let key = [twox_128(b"System"), twox_128(b"Number")].concat();
+			sp_io::storage::get(&key).map(|n| NumberFor::<Block>::decode(&mut &n[..]))
+		})
+		.ok_or("the block number is not in the state")?
+		.map_err(|e| format!("failed to decode the block number: {:?}", e))?;
+	let mut ext = ext.inner_ext;
+	let mut prev_block_building_info = None;
+
+	for produced in 1..=max_blocks {
+		let (block, block_building_info) = next_empty_block::<Block, HostFns, BBIP>(
+			&mut ext,
+			&executor,
+			parent_height,
+			parent_hash,
+			&block_building_info_provider,
+			prev_block_building_info,
+		)
+		.await?;
+		prev_block_building_info = block_building_info;
+		parent_hash = block.hash();
+		parent_height = *block.header().number();
+
+		let ongoing = dry_run::<bool, Block, _>(
+			&ext,
+			&executor,
+			"TryRuntime_multi_block_migrations_ongoing",
+			&[],
+		)
+		.await?;
+		if !ongoing {
+			log::info!(
+				target: LOG_TARGET,
+				"the multi-block migrations completed after {} blocks, at block {:?}",
+				produced,
+				parent_height,
+			);
+			return Ok(())
+		}
+		log::info!(
+			target: LOG_TARGET,
+			"the multi-block migrations are ongoing after block {:?}",
+			parent_height,
+		);
+	}
+
+	Err(format!("the multi-block migrations are still ongoing after {} blocks", max_blocks).into())
 }
@@ -740,9 +740,10 @@ impl TryRuntimeCmd {
 	{
 		match &self.command {
 			Command::OnRuntimeUpgrade(ref cmd) =>
-				commands::on_runtime_upgrade::on_runtime_upgrade::<Block, HostFns>(
+				commands::on_runtime_upgrade::on_runtime_upgrade::<Block, HostFns, BBIP>(
 					self.shared.clone(),
 					cmd.clone(),
+					block_building_info_provider,
 				)
 				.await,
 			Command::OffchainWorker(cmd) =>